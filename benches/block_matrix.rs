@@ -0,0 +1,30 @@
+//! Timing of block CMTM assembly against the derivative order.
+//!
+//! Run with `cargo bench --bench block_matrix`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use mathroborust::{RustCmtm, RustSe3};
+
+const ITERATIONS: u32 = 2_000;
+
+fn main() {
+    let transform = RustSe3::from_matrix(RustSe3::exp([0.3, -0.2, 0.5, 1.0, 0.4, -0.7], None));
+    for order in [1, 2, 4, 6, 8, 10, 12] {
+        let derivatives = (1..order)
+            .map(|k| {
+                let k = k as f64;
+                [0.1 * k, -0.2, 0.3 / k, 0.5, -0.1 * k, 0.2]
+            })
+            .collect();
+        let cmtm = RustCmtm::from_se3_with_derivatives(&transform, derivatives);
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(cmtm.to_block_matrix(black_box(None)));
+        }
+        let per_call = start.elapsed() / ITERATIONS;
+        println!("order {order:>2}: {per_call:?} per block matrix");
+    }
+}
@@ -0,0 +1,44 @@
+//! Order-1 CMTMs (no derivatives) against raw 6×6 adjoint arithmetic.
+//!
+//! Run with `cargo bench --bench zero_order`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use mathroborust::{RustCmtm, RustSe3};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn time(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let a = RustSe3::from_matrix(RustSe3::exp([0.3, -0.2, 0.5, 1.0, 0.4, -0.7], None));
+    let b = RustSe3::from_matrix(RustSe3::exp([-0.1, 0.6, 0.2, -0.3, 0.8, 0.1], None));
+    let (ad_a, ad_b) = (a.adjoint(), b.adjoint());
+    let (cmtm_a, cmtm_b) = (RustCmtm::from_se3(&a), RustCmtm::from_se3(&b));
+    let twist = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+
+    let raw_compose = time(|| {
+        black_box(black_box(&ad_a) * black_box(&ad_b));
+    });
+    let cmtm_compose = time(|| {
+        black_box(black_box(&cmtm_a).compose(black_box(&cmtm_b)));
+    });
+    let raw_apply = time(|| {
+        let applied: [f64; 6] =
+            (black_box(&ad_a) * nalgebra::Vector6::from(black_box(twist))).into();
+        black_box(applied);
+    });
+    let cmtm_apply = time(|| {
+        black_box(black_box(&cmtm_a).apply_twist(black_box(twist)));
+    });
+
+    println!("compose: adjoint {raw_compose:?}, cmtm {cmtm_compose:?}");
+    println!("apply:   adjoint {raw_apply:?}, cmtm {cmtm_apply:?}");
+}
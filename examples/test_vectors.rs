@@ -0,0 +1,97 @@
+//! Emit reference test vectors as JSON for regression and cross-language checks.
+//!
+//! Regenerate the checked-in golden file with:
+//! `cargo run --example test_vectors > tests/data/test_vectors.json`
+
+use mathroborust::{RustSe3, RustSo3};
+use serde_json::{Value, json};
+
+const SEED: u64 = 0x5EED_2192;
+const RANDOM_CASES: usize = 16;
+
+/// SplitMix64, kept local so the vectors never depend on an external RNG's
+/// version-specific output.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in `[-1, 1)`.
+    fn symmetric(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+    }
+
+    fn vector<const N: usize>(&mut self, scale: f64) -> [f64; N] {
+        std::array::from_fn(|_| self.symmetric() * scale)
+    }
+}
+
+fn so3_case(rotation_vector: [f64; 3]) -> Value {
+    let rotation = RustSo3::from_rotation_vector(rotation_vector);
+    let (roll, pitch, yaw) = rotation.to_euler_angles();
+    json!({
+        "rotation_vector": rotation_vector,
+        "matrix": rotation.to_matrix(),
+        "quaternion_wxyz": rotation.to_quaternion(),
+        "euler_rpy": [roll, pitch, yaw],
+        "log": rotation.to_rotation_vector(),
+    })
+}
+
+fn se3_case(twist: [f64; 6]) -> Value {
+    let matrix = RustSe3::exp(twist, None);
+    let transform = RustSe3::from_matrix(matrix);
+    let adjoint = transform.adjoint();
+    let adjoint_rows: Vec<Vec<f64>> = adjoint
+        .row_iter()
+        .map(|row| row.iter().copied().collect())
+        .collect();
+    json!({
+        "twist": twist,
+        "exp": matrix,
+        "inverse": transform.inverse().to_matrix(),
+        "adjoint": adjoint_rows,
+    })
+}
+
+fn main() {
+    let mut rng = SplitMix64(SEED);
+
+    let mut so3 = vec![
+        so3_case([0.0, 0.0, 0.0]),
+        so3_case([1e-9, -2e-9, 5e-10]),
+        so3_case([0.0, 0.0, std::f64::consts::FRAC_PI_2]),
+    ];
+    so3.extend((0..RANDOM_CASES).map(|_| so3_case(rng.vector(3.0))));
+
+    let mut se3 = vec![
+        se3_case([0.0; 6]),
+        se3_case([0.0, 0.0, 0.0, 1.0, -2.0, 3.0]),
+        se3_case([1e-9, 0.0, -1e-9, 0.5, 0.25, -0.75]),
+    ];
+    se3.extend((0..RANDOM_CASES).map(|_| {
+        let rotation: [f64; 3] = rng.vector(3.0);
+        let translation: [f64; 3] = rng.vector(2.0);
+        se3_case([
+            rotation[0],
+            rotation[1],
+            rotation[2],
+            translation[0],
+            translation[1],
+            translation[2],
+        ])
+    }));
+
+    let document = json!({
+        "seed": SEED,
+        "so3": so3,
+        "se3": se3,
+    });
+    println!("{}", serde_json::to_string_pretty(&document).unwrap());
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mathroborust::SensorRig;
+
+// Rig files are the main text format read by the crate; malformed or
+// cyclic descriptions must surface as errors.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(rig) = serde_json::from_slice::<SensorRig>(data) {
+        for sensor in rig.sensors() {
+            let _ = rig.base_from_sensor(&sensor.name);
+            let _ = rig.relative(&sensor.name, rig.base_frame());
+        }
+    }
+});
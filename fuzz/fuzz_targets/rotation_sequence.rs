@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mathroborust::RustSo3;
+
+fuzz_target!(|input: (&str, Vec<f64>)| {
+    let (sequence, angles) = input;
+    let _ = RustSo3::from_sequence(sequence, &angles);
+});
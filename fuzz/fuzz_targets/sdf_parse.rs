@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mathroborust::{sdf, urdf};
+
+// Robot descriptions come from arbitrary files; malformed XML, numbers and
+// kinematic trees must surface as errors.
+fuzz_target!(|text: &str| {
+    if let Ok(robot) = sdf::parse(text) {
+        let _ = urdf::to_urdf(&robot);
+    }
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mathroborust::{RustCmtm, RustSe3};
+
+fuzz_target!(|input: ([[f64; 4]; 4], [f64; 6], Vec<[f64; 6]>, Option<u8>)| {
+    let (matrix, twist, derivatives, order) = input;
+
+    let transform = RustSe3::from_matrix(matrix);
+    let _ = transform.inverse().compose(&transform);
+    let _ = transform.adjoint();
+    let _ = transform.health_check();
+    let _ = transform.transform_wrench(twist);
+    let _ = RustSe3::vee(matrix);
+    let _ = RustSe3::from_matrix(RustSe3::exp(twist, None));
+
+    // Bound the order so block matrices stay small; invalid orders must be
+    // reported as errors.
+    let derivatives: Vec<_> = derivatives.into_iter().take(8).collect();
+    let cmtm = RustCmtm::from_se3_with_derivatives(&transform, derivatives);
+    let _ = cmtm.try_to_block_matrix(order.map(usize::from));
+    let _ = cmtm.health_check();
+});
@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mathroborust::RustSo3;
+
+// Arbitrary (including non-finite and non-orthogonal) inputs must never
+// panic, whatever garbage the conversions produce.
+fuzz_target!(|input: ([[f64; 3]; 3], [f64; 4], [f64; 3])| {
+    let (matrix, quaternion, vector) = input;
+
+    for rotation in [
+        RustSo3::from_matrix(matrix),
+        RustSo3::from_quaternion(quaternion),
+        RustSo3::from_quaternion_xyzw(quaternion),
+        RustSo3::from_rotation_vector(vector),
+    ] {
+        let _ = rotation.to_quaternion();
+        let _ = rotation.to_rotation_vector();
+        let _ = rotation.to_euler_angles();
+        let _ = rotation.health_check();
+        let _ = rotation.inverse().compose(&rotation).apply(vector);
+    }
+    let _ = RustSo3::try_from_quaternion(quaternion);
+    let _ = RustSo3::vee(matrix);
+    let _ = RustSo3::hat(vector);
+});
@@ -1,17 +1,273 @@
+use mathroborust::jacobian;
 use mathroborust::lie::LieGroup;
-use mathroborust::{Cmtm, Se3, So3};
-use nalgebra::SMatrix;
+use mathroborust::{Cmtm, Error, Se3, So3};
+use nalgebra::{DMatrix, Dim, Matrix, Matrix3, RawStorage, SMatrix};
+use pyo3::buffer::PyBuffer;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-
-#[pymodule]
+use pyo3::types::{PyByteArray, PyModule};
+
+create_exception!(
+    mathrobors,
+    MathRoboError,
+    PyValueError,
+    "Raised when an operation rejects its input. The `code` attribute holds the stable numeric error code."
+);
+
+/// Classes stay importable from the top level; the submodules group each
+/// group's class with free functions under canonical names, e.g.
+/// `mathrobors.so3.exp` or `mathrobors.kinematics.euler_rate_matrix`.
+#[pymodule(gil_used = false)]
 pub fn mathrobors(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<PySo3>()?;
     module.add_class::<PySe3>()?;
     module.add_class::<PyCmtm>()?;
+    module.add("MathRoboError", module.py().get_type::<MathRoboError>())?;
+    add_submodule(module, so3_module(module.py())?)?;
+    add_submodule(module, se3_module(module.py())?)?;
+    add_submodule(module, cmtm_module(module.py())?)?;
+    add_submodule(module, kinematics_module(module.py())?)?;
     Ok(())
 }
 
-#[pyclass(name = "SO3")]
+/// Attach `child` to `parent` and register it in `sys.modules`, so that
+/// `import mathrobors.<name>` works as for a Python package.
+fn add_submodule(parent: &Bound<'_, PyModule>, child: Bound<'_, PyModule>) -> PyResult<()> {
+    let name = format!("{}.{}", parent.name()?, child.name()?);
+    parent.add_submodule(&child)?;
+    parent
+        .py()
+        .import("sys")?
+        .getattr("modules")?
+        .set_item(name, child)
+}
+
+fn so3_module(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let module = PyModule::new(py, "so3")?;
+    module.add_class::<PySo3>()?;
+    module.add_function(wrap_pyfunction!(so3_exp, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_log, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_hat, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_vee, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_exp_batch, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_log_batch, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_right_jacobian, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_right_jacobian_inverse, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_left_jacobian, &module)?)?;
+    module.add_function(wrap_pyfunction!(so3_left_jacobian_inverse, &module)?)?;
+    Ok(module)
+}
+
+fn se3_module(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let module = PyModule::new(py, "se3")?;
+    module.add_class::<PySe3>()?;
+    module.add_function(wrap_pyfunction!(se3_exp, &module)?)?;
+    module.add_function(wrap_pyfunction!(se3_log, &module)?)?;
+    module.add_function(wrap_pyfunction!(se3_hat, &module)?)?;
+    module.add_function(wrap_pyfunction!(se3_vee, &module)?)?;
+    module.add_function(wrap_pyfunction!(se3_exp_batch, &module)?)?;
+    module.add_function(wrap_pyfunction!(se3_log_batch, &module)?)?;
+    Ok(module)
+}
+
+fn cmtm_module(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let module = PyModule::new(py, "cmtm")?;
+    module.add_class::<PyCmtm>()?;
+    Ok(module)
+}
+
+fn kinematics_module(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let module = PyModule::new(py, "kinematics")?;
+    module.add_function(wrap_pyfunction!(euler_rate_matrix, &module)?)?;
+    module.add_function(wrap_pyfunction!(quaternion_rate_matrix, &module)?)?;
+    Ok(module)
+}
+
+/// Rotation matrix of a rotation vector.
+#[pyfunction]
+#[pyo3(name = "exp", signature = (vector, a=None))]
+fn so3_exp(vector: [f64; 3], a: Option<f64>) -> [[f64; 3]; 3] {
+    PySo3::exp(vector, a)
+}
+
+/// Rotation vector of a rotation matrix.
+#[pyfunction]
+#[pyo3(name = "log")]
+fn so3_log(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+    So3::from_matrix(matrix).to_rotation_vector()
+}
+
+#[pyfunction]
+#[pyo3(name = "hat")]
+fn so3_hat(vector: [f64; 3]) -> [[f64; 3]; 3] {
+    So3::hat(vector)
+}
+
+#[pyfunction]
+#[pyo3(name = "vee")]
+fn so3_vee(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+    So3::vee(matrix)
+}
+
+#[pyfunction]
+#[pyo3(name = "exp_batch")]
+fn so3_exp_batch<'py>(py: Python<'py>, vectors: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    PySo3::exp_batch(py, vectors)
+}
+
+#[pyfunction]
+#[pyo3(name = "log_batch")]
+fn so3_log_batch<'py>(
+    py: Python<'py>,
+    matrices: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    PySo3::log_batch(py, matrices)
+}
+
+#[pyfunction]
+#[pyo3(name = "right_jacobian")]
+fn so3_right_jacobian(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+    PySo3::right_jacobian(py, vector)
+}
+
+#[pyfunction]
+#[pyo3(name = "right_jacobian_inverse")]
+fn so3_right_jacobian_inverse(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+    PySo3::right_jacobian_inverse(py, vector)
+}
+
+#[pyfunction]
+#[pyo3(name = "left_jacobian")]
+fn so3_left_jacobian(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+    PySo3::left_jacobian(py, vector)
+}
+
+#[pyfunction]
+#[pyo3(name = "left_jacobian_inverse")]
+fn so3_left_jacobian_inverse(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+    PySo3::left_jacobian_inverse(py, vector)
+}
+
+/// Homogeneous matrix of a twist `[omega, v]`.
+#[pyfunction]
+#[pyo3(name = "exp", signature = (twist, a=None))]
+fn se3_exp(twist: [f64; 6], a: Option<f64>) -> [[f64; 4]; 4] {
+    Se3::exp(twist, a)
+}
+
+/// Twist `[omega, v]` of a homogeneous matrix.
+#[pyfunction]
+#[pyo3(name = "log")]
+fn se3_log(matrix: [[f64; 4]; 4]) -> [f64; 6] {
+    Se3::log_matrix(matrix)
+}
+
+#[pyfunction]
+#[pyo3(name = "hat")]
+fn se3_hat(twist: [f64; 6]) -> [[f64; 4]; 4] {
+    Se3::hat(twist)
+}
+
+#[pyfunction]
+#[pyo3(name = "vee")]
+fn se3_vee(matrix: [[f64; 4]; 4]) -> [f64; 6] {
+    Se3::vee(matrix)
+}
+
+#[pyfunction]
+#[pyo3(name = "exp_batch")]
+fn se3_exp_batch<'py>(py: Python<'py>, twists: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    PySe3::exp_batch(py, twists)
+}
+
+#[pyfunction]
+#[pyo3(name = "log_batch")]
+fn se3_log_batch<'py>(
+    py: Python<'py>,
+    matrices: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    PySe3::log_batch(py, matrices)
+}
+
+/// Convert a core [`Error`] into a `MathRoboError`, attaching its numeric code.
+fn to_py_err(err: Error) -> PyErr {
+    Python::with_gil(|py| {
+        let py_err = MathRoboError::new_err(err.to_string());
+        // Setting an attribute on a freshly created exception cannot fail in
+        // practice; if it does, the message alone is still informative.
+        let _ = py_err.value(py).setattr("code", err.code());
+        py_err
+    })
+}
+
+/// Copy a matrix into a float64 `numpy.ndarray` of the same shape.
+fn to_numpy<'py, R: Dim, C: Dim, S: RawStorage<f64, R, C>>(
+    py: Python<'py>,
+    matrix: &Matrix<f64, R, C, S>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let rows: Vec<Vec<f64>> = matrix
+        .row_iter()
+        .map(|row| row.iter().copied().collect())
+        .collect();
+    py.import("numpy")?.call_method1("array", (rows, "float64"))
+}
+
+/// Apply `f` to every item of an array of shape `(N, *input_shape)`, writing
+/// items of `output_shape` into a new float64 `numpy.ndarray`. The loop runs
+/// in Rust without the GIL.
+fn map_batch<'py>(
+    py: Python<'py>,
+    array: &Bound<'py, PyAny>,
+    input_shape: &[usize],
+    output_shape: &[usize],
+    f: impl Fn(&[f64], &mut [f64]) + Send + Sync,
+) -> PyResult<Bound<'py, PyAny>> {
+    let numpy = py.import("numpy")?;
+    let contiguous = numpy.call_method1("ascontiguousarray", (array, "float64"))?;
+    let buffer = PyBuffer::<f64>::get(&contiguous)?;
+    let shape = buffer.shape();
+    if shape.len() != input_shape.len() + 1 || shape[1..] != *input_shape {
+        return Err(PyValueError::new_err(format!(
+            "expected an array of shape (N, {input_shape:?}), got {shape:?}"
+        )));
+    }
+    let count = shape[0];
+    let input = buffer.to_vec(py)?;
+    let input_len: usize = input_shape.iter().product();
+    let output_len: usize = output_shape.iter().product();
+    let output = py.allow_threads(|| {
+        let mut output = vec![0.0; count * output_len];
+        for (item, out) in input
+            .chunks_exact(input_len)
+            .zip(output.chunks_exact_mut(output_len))
+        {
+            f(item, out);
+        }
+        output
+    });
+    let bytes: Vec<u8> = output.iter().flat_map(|x| x.to_ne_bytes()).collect();
+    let mut result_shape = vec![count];
+    result_shape.extend_from_slice(output_shape);
+    numpy
+        .call_method1("frombuffer", (PyByteArray::new(py, &bytes), "float64"))?
+        .call_method1("reshape", (result_shape,))
+}
+
+/// Matrix mapping roll–pitch–yaw rates to the world-frame angular velocity.
+#[pyfunction]
+fn euler_rate_matrix(py: Python<'_>, angles: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+    to_numpy(py, &jacobian::euler_rate_matrix(angles))
+}
+
+/// Matrix mapping the world-frame angular velocity to `[w, x, y, z]`
+/// quaternion rates.
+#[pyfunction]
+fn quaternion_rate_matrix(py: Python<'_>, quaternion: [f64; 4]) -> PyResult<Bound<'_, PyAny>> {
+    to_numpy(py, &jacobian::quaternion_rate_matrix(quaternion))
+}
+
+#[pyclass(name = "SO3", frozen)]
 pub struct PySo3 {
     inner: So3,
 }
@@ -33,22 +289,31 @@ impl PySo3 {
     }
 
     #[staticmethod]
-    pub fn from_quaternion(quaternion: [f64; 4]) -> Self {
-        Self {
-            inner: So3::from_quaternion(quaternion),
-        }
+    pub fn from_quaternion(quaternion: [f64; 4]) -> PyResult<Self> {
+        Ok(Self {
+            inner: So3::try_from_quaternion(quaternion).map_err(to_py_err)?,
+        })
     }
 
     #[staticmethod]
-    pub fn quaternion_to_mat(quaternion: [f64; 4]) -> [[f64; 3]; 3] {
-        So3::from_quaternion(quaternion).to_matrix()
+    pub fn from_quaternion_wxyz(quaternion: [f64; 4]) -> PyResult<Self> {
+        PySo3::from_quaternion(quaternion)
     }
 
     #[staticmethod]
-    pub fn set_quaternion(quaternion: [f64; 4]) -> Self {
-        Self {
-            inner: So3::from_quaternion(quaternion),
-        }
+    pub fn from_quaternion_xyzw(quaternion: [f64; 4]) -> PyResult<Self> {
+        let [x, y, z, w] = quaternion;
+        PySo3::from_quaternion([w, x, y, z])
+    }
+
+    #[staticmethod]
+    pub fn quaternion_to_mat(quaternion: [f64; 4]) -> PyResult<[[f64; 3]; 3]> {
+        Ok(PySo3::from_quaternion(quaternion)?.inner.to_matrix())
+    }
+
+    #[staticmethod]
+    pub fn set_quaternion(quaternion: [f64; 4]) -> PyResult<Self> {
+        PySo3::from_quaternion(quaternion)
     }
 
     #[staticmethod]
@@ -58,6 +323,13 @@ impl PySo3 {
         }
     }
 
+    #[staticmethod]
+    pub fn from_sequence(sequence: &str, angles: Vec<f64>) -> PyResult<Self> {
+        Ok(Self {
+            inner: So3::from_sequence(sequence, &angles).map_err(to_py_err)?,
+        })
+    }
+
     #[staticmethod]
     pub fn set_euler(euler: (f64, f64, f64)) -> Self {
         Self {
@@ -98,9 +370,9 @@ impl PySo3 {
     #[staticmethod]
     pub fn hat_commute(vector: [f64; 3]) -> [[f64; 3]; 3] {
         let mut hat = So3::hat(vector);
-        for r in 0..3 {
-            for c in 0..3 {
-                hat[r][c] = -hat[r][c];
+        for row in hat.iter_mut() {
+            for value in row.iter_mut() {
+                *value = -*value;
             }
         }
         hat
@@ -192,6 +464,14 @@ impl PySo3 {
         self.inner.to_quaternion()
     }
 
+    pub fn quaternion_wxyz(&self) -> [f64; 4] {
+        self.inner.to_quaternion_wxyz()
+    }
+
+    pub fn quaternion_xyzw(&self) -> [f64; 4] {
+        self.inner.to_quaternion_xyzw()
+    }
+
     #[staticmethod]
     pub fn mat_to_quaternion(matrix: [[f64; 3]; 3]) -> [f64; 4] {
         So3::from_matrix(matrix).to_quaternion()
@@ -204,9 +484,83 @@ impl PySo3 {
     pub fn rotation_vector(&self) -> [f64; 3] {
         self.inner.to_rotation_vector()
     }
+
+    /// Rotation matrices `(N, 3, 3)` of rotation vectors `(N, 3)`.
+    #[staticmethod]
+    pub fn exp_batch<'py>(
+        py: Python<'py>,
+        vectors: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_batch(py, vectors, &[3], &[3, 3], |vector, out| {
+            let matrix = So3::from_rotation_vector([vector[0], vector[1], vector[2]]).to_matrix();
+            out.copy_from_slice(matrix.as_flattened());
+        })
+    }
+
+    /// Rotation vectors `(N, 3)` of rotation matrices `(N, 3, 3)`.
+    #[staticmethod]
+    pub fn log_batch<'py>(
+        py: Python<'py>,
+        matrices: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_batch(py, matrices, &[3, 3], &[3], |matrix, out| {
+            let rows = [0, 3, 6].map(|r| [matrix[r], matrix[r + 1], matrix[r + 2]]);
+            out.copy_from_slice(&So3::from_matrix(rows).to_rotation_vector());
+        })
+    }
+
+    /// Right Jacobian of `exp` at `vector`, as a 3×3 `numpy.ndarray`.
+    #[staticmethod]
+    pub fn right_jacobian(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+        to_numpy(py, &So3::right_jacobian(vector))
+    }
+
+    /// Inverse right Jacobian, the Jacobian of the logarithm at `exp(vector)`.
+    #[staticmethod]
+    pub fn right_jacobian_inverse(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+        to_numpy(py, &So3::right_jacobian_inverse(vector))
+    }
+
+    /// Left Jacobian of `exp` at `vector`, as a 3×3 `numpy.ndarray`.
+    #[staticmethod]
+    pub fn left_jacobian(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+        to_numpy(py, &So3::left_jacobian(vector))
+    }
+
+    /// Inverse left Jacobian.
+    #[staticmethod]
+    pub fn left_jacobian_inverse(py: Python<'_>, vector: [f64; 3]) -> PyResult<Bound<'_, PyAny>> {
+        to_numpy(py, &So3::left_jacobian_inverse(vector))
+    }
+
+    /// Jacobians of `self.compose(other)` with respect to right perturbations
+    /// of `self` and of `other`.
+    pub fn compose_jacobians<'py>(
+        &self,
+        py: Python<'py>,
+        other: &PySo3,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+        let other_inverse = other.inner.inverse();
+        Ok((
+            to_numpy(py, other_inverse.rotation().matrix())?,
+            to_numpy(py, &Matrix3::<f64>::identity())?,
+        ))
+    }
+
+    /// Jacobian of `self.inverse()` with respect to a right perturbation of
+    /// `self`.
+    pub fn inverse_jacobian<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_numpy(py, &-self.inner.rotation().matrix())
+    }
+}
+
+impl Default for PySo3 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[pyclass(name = "SE3")]
+#[pyclass(name = "SE3", frozen)]
 pub struct PySe3 {
     inner: Se3,
 }
@@ -327,6 +681,60 @@ impl PySe3 {
             inner: self.inner.rotation().clone(),
         }
     }
+
+    /// Homogeneous matrices `(N, 4, 4)` of twists `(N, 6)` `[omega, v]`.
+    #[staticmethod]
+    pub fn exp_batch<'py>(
+        py: Python<'py>,
+        twists: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_batch(py, twists, &[6], &[4, 4], |twist, out| {
+            let twist = std::array::from_fn(|i| twist[i]);
+            out.copy_from_slice(Se3::exp(twist, None).as_flattened());
+        })
+    }
+
+    /// Twists `(N, 6)` of homogeneous matrices `(N, 4, 4)`.
+    #[staticmethod]
+    pub fn log_batch<'py>(
+        py: Python<'py>,
+        matrices: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        map_batch(py, matrices, &[4, 4], &[6], |matrix, out| {
+            let rows = [0, 4, 8, 12].map(|r| std::array::from_fn(|c| matrix[r + c]));
+            out.copy_from_slice(&Se3::log_matrix(rows));
+        })
+    }
+
+    /// Adjoint as a 6×6 `numpy.ndarray`.
+    pub fn adjoint<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_numpy(py, &self.inner.adjoint())
+    }
+
+    /// Jacobians of `self.compose(other)` with respect to right perturbations
+    /// `[omega, v]` of `self` and of `other`.
+    pub fn compose_jacobians<'py>(
+        &self,
+        py: Python<'py>,
+        other: &PySe3,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+        Ok((
+            to_numpy(py, &other.inner.inverse().adjoint())?,
+            to_numpy(py, &SMatrix::<f64, 6, 6>::identity())?,
+        ))
+    }
+
+    /// Jacobian of `self.inverse()` with respect to a right perturbation of
+    /// `self`.
+    pub fn inverse_jacobian<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_numpy(py, &-self.inner.adjoint())
+    }
+}
+
+impl Default for PySe3 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 fn s_matrix6_to_array(matrix: &SMatrix<f64, 6, 6>) -> [[f64; 6]; 6] {
@@ -339,7 +747,14 @@ fn s_matrix6_to_array(matrix: &SMatrix<f64, 6, 6>) -> [[f64; 6]; 6] {
     out
 }
 
-#[pyclass(name = "CMTM")]
+fn d_matrix_to_rows(matrix: &DMatrix<f64>) -> Vec<Vec<f64>> {
+    matrix
+        .row_iter()
+        .map(|row| row.iter().copied().collect())
+        .collect()
+}
+
+#[pyclass(name = "CMTM", frozen)]
 pub struct PyCmtm {
     inner: Cmtm,
 }
@@ -360,6 +775,37 @@ impl PyCmtm {
         }
     }
 
+    /// CMTM of `transform` carrying twist derivatives \(V, \dot V, \dots\),
+    /// supporting block orders up to `len(derivatives) + 1`.
+    #[staticmethod]
+    pub fn from_se3_with_derivatives(transform: &PySe3, derivatives: Vec<[f64; 6]>) -> Self {
+        Self {
+            inner: Cmtm::from_se3_with_derivatives(&transform.inner, derivatives),
+        }
+    }
+
+    /// Highest block order available (1 means only the adjoint matrix).
+    pub fn order(&self) -> usize {
+        self.inner.order()
+    }
+
+    /// Stored derivative vectors, one per order above the first.
+    pub fn derivatives(&self) -> Vec<[f64; 6]> {
+        self.inner
+            .derivatives()
+            .iter()
+            .map(|d| (*d).into())
+            .collect()
+    }
+
+    /// Lower-order view of this CMTM; raises `MathRoboError` if `order` is
+    /// zero or exceeds [`order`](Self::order).
+    pub fn truncated(&self, order: usize) -> PyResult<PyCmtm> {
+        Ok(PyCmtm {
+            inner: self.inner.truncated(order).map_err(to_py_err)?,
+        })
+    }
+
     pub fn apply_twist(&self, twist: [f64; 6]) -> [f64; 6] {
         self.inner.apply_twist(twist)
     }
@@ -368,6 +814,12 @@ impl PyCmtm {
         self.inner.to_matrix()
     }
 
+    #[pyo3(signature = (order=None))]
+    pub fn to_block_matrix(&self, order: Option<usize>) -> PyResult<Vec<Vec<f64>>> {
+        let block = self.inner.try_to_block_matrix(order).map_err(to_py_err)?;
+        Ok(d_matrix_to_rows(&block))
+    }
+
     pub fn compose(&self, other: &PyCmtm) -> PyCmtm {
         PyCmtm {
             inner: self.inner.compose(&other.inner),
@@ -379,3 +831,9 @@ impl PyCmtm {
         Ok(self.compose(other))
     }
 }
+
+impl Default for PyCmtm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
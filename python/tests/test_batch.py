@@ -0,0 +1,35 @@
+import numpy as np
+import pytest
+import mathrobors
+
+
+def test_so3_batch_matches_single_calls():
+    rng = np.random.default_rng(7)
+    vectors = rng.uniform(-2.0, 2.0, size=(16, 3))
+    matrices = mathrobors.SO3.exp_batch(vectors)
+    assert matrices.shape == (16, 3, 3)
+    for vector, matrix in zip(vectors, matrices):
+        assert np.allclose(matrix, mathrobors.SO3.exp(tuple(vector)), atol=1e-12)
+    assert np.allclose(mathrobors.SO3.log_batch(matrices), vectors, atol=1e-10)
+
+
+def test_se3_batch_matches_single_calls():
+    rng = np.random.default_rng(11)
+    twists = rng.uniform(-1.0, 1.0, size=(16, 6))
+    matrices = mathrobors.SE3.exp_batch(twists)
+    assert matrices.shape == (16, 4, 4)
+    for twist, matrix in zip(twists, matrices):
+        assert np.allclose(matrix, mathrobors.SE3.exp(tuple(twist)), atol=1e-12)
+    assert np.allclose(mathrobors.SE3.log_batch(matrices), twists, atol=1e-10)
+
+
+def test_batch_accepts_lists_and_empty_batches():
+    assert mathrobors.SO3.exp_batch([[0.0, 0.0, 0.0]]).shape == (1, 3, 3)
+    assert mathrobors.SE3.log_batch(np.zeros((0, 4, 4))).shape == (0, 6)
+
+
+def test_batch_rejects_wrong_shapes():
+    with pytest.raises(ValueError):
+        mathrobors.SO3.exp_batch(np.zeros((4, 6)))
+    with pytest.raises(ValueError):
+        mathrobors.SE3.log_batch(np.zeros((4, 3, 3)))
@@ -0,0 +1,46 @@
+import json
+from pathlib import Path
+
+import mathrobors
+
+GOLDEN = Path(__file__).resolve().parents[2] / "tests" / "data" / "test_vectors.json"
+TOL = 1e-12
+
+
+def load_golden():
+    with GOLDEN.open() as handle:
+        return json.load(handle)
+
+
+def approx_eq(a, b, tol=TOL):
+    assert len(a) == len(b)
+    for x, y in zip(a, b):
+        assert abs(x - y) < tol, f"expected {y}, got {x}"
+
+
+def approx_eq_rows(a, b, tol=TOL):
+    assert len(a) == len(b)
+    for row_a, row_b in zip(a, b):
+        approx_eq(row_a, row_b, tol)
+
+
+def test_so3_matches_golden_vectors():
+    for case in load_golden()["so3"]:
+        rotation = mathrobors.SO3.from_rotation_vector(case["rotation_vector"])
+        approx_eq_rows(rotation.matrix(), case["matrix"])
+        approx_eq(rotation.quaternion(), case["quaternion_wxyz"])
+        approx_eq(rotation.euler_angles(), case["euler_rpy"])
+        approx_eq(rotation.rotation_vector(), case["log"])
+
+        approx_eq_rows(mathrobors.SO3.from_quaternion(case["quaternion_wxyz"]).matrix(), case["matrix"])
+        approx_eq_rows(mathrobors.SO3.set_euler(tuple(case["euler_rpy"])).matrix(), case["matrix"])
+
+
+def test_se3_matches_golden_vectors():
+    for case in load_golden()["se3"]:
+        exp = mathrobors.SE3.exp(case["twist"], None)
+        approx_eq_rows(exp, case["exp"])
+
+        transform = mathrobors.SE3.from_matrix(exp)
+        approx_eq_rows(transform.inverse().matrix(), case["inverse"])
+        approx_eq_rows(transform.mat_adj(), case["adjoint"])
@@ -0,0 +1,78 @@
+import numpy as np
+import mathrobors
+
+
+STEP = 1e-6
+
+
+def so3_plus(rotation, delta):
+    return rotation.compose(mathrobors.SO3.from_rotation_vector(tuple(delta)))
+
+
+def se3_plus(transform, delta):
+    return transform.compose(mathrobors.SE3.from_matrix(mathrobors.SE3.exp(tuple(delta))))
+
+
+def so3_minus(a, b):
+    return np.array(b.inverse().compose(a).rotation_vector())
+
+
+def se3_minus(a, b):
+    relative = np.array(b.inverse().compose(a).matrix())
+    # First-order twist of a near-identity transform.
+    return np.concatenate([mathrobors.SO3.vee(relative[:3, :3].tolist()), relative[:3, 3]])
+
+
+def numeric_jacobian(f, x, plus, minus, dim):
+    columns = []
+    for k in range(dim):
+        delta = np.zeros(dim)
+        delta[k] = STEP
+        forward = minus(f(plus(x, delta)), f(x))
+        backward = minus(f(plus(x, -delta)), f(x))
+        columns.append((forward - backward) / (2.0 * STEP))
+    return np.stack(columns, axis=1)
+
+
+def test_jacobians_are_numpy_arrays():
+    jacobian = mathrobors.SO3.right_jacobian((0.1, 0.2, 0.3))
+    assert isinstance(jacobian, np.ndarray)
+    assert jacobian.shape == (3, 3)
+    assert jacobian.dtype == np.float64
+    assert mathrobors.SE3().adjoint().shape == (6, 6)
+    assert mathrobors.kinematics.quaternion_rate_matrix((1.0, 0.0, 0.0, 0.0)).shape == (4, 3)
+    assert mathrobors.kinematics.euler_rate_matrix((0.1, 0.2, 0.3)).shape == (3, 3)
+
+
+def test_right_jacobian_inverts():
+    vector = (0.4, -0.3, 0.9)
+    product = mathrobors.SO3.right_jacobian(vector) @ mathrobors.SO3.right_jacobian_inverse(vector)
+    assert np.allclose(product, np.eye(3), atol=1e-12)
+
+
+def test_so3_compose_and_inverse_jacobians_match_finite_differences():
+    a = mathrobors.SO3.from_axis_angle((0.0, 0.0, 1.0), 0.7)
+    b = mathrobors.SO3.from_axis_angle((1.0, 1.0, 0.0), -0.4)
+    d_a, d_b = a.compose_jacobians(b)
+    assert np.allclose(d_a, numeric_jacobian(lambda x: x.compose(b), a, so3_plus, so3_minus, 3), atol=1e-8)
+    assert np.allclose(d_b, numeric_jacobian(lambda x: a.compose(x), b, so3_plus, so3_minus, 3), atol=1e-8)
+    numeric = numeric_jacobian(lambda x: x.inverse(), a, so3_plus, so3_minus, 3)
+    assert np.allclose(a.inverse_jacobian(), numeric, atol=1e-8)
+
+
+def test_se3_compose_and_inverse_jacobians_match_finite_differences():
+    a = mathrobors.SE3.from_axis_angle_translation((0.0, 0.0, 1.0), 0.7, (1.0, -0.5, 0.2))
+    b = mathrobors.SE3.from_axis_angle_translation((0.0, 1.0, 0.0), -0.3, (0.1, 0.4, -0.6))
+    d_a, d_b = a.compose_jacobians(b)
+    assert np.allclose(d_a, numeric_jacobian(lambda x: x.compose(b), a, se3_plus, se3_minus, 6), atol=1e-6)
+    assert np.allclose(d_b, numeric_jacobian(lambda x: a.compose(x), b, se3_plus, se3_minus, 6), atol=1e-6)
+    numeric = numeric_jacobian(lambda x: x.inverse(), a, se3_plus, se3_minus, 6)
+    assert np.allclose(a.inverse_jacobian(), numeric, atol=1e-6)
+
+
+def test_left_jacobian_relates_to_right_jacobian():
+    vector = (0.4, -0.7, 0.2)
+    rotation = np.array(mathrobors.SO3.from_rotation_vector(vector).matrix())
+    left = mathrobors.SO3.left_jacobian(vector)
+    assert np.allclose(left, rotation @ mathrobors.SO3.right_jacobian(vector), atol=1e-12)
+    assert np.allclose(left @ mathrobors.SO3.left_jacobian_inverse(vector), np.eye(3), atol=1e-12)
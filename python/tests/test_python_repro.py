@@ -115,6 +115,71 @@ def test_python_functions_match_original_names():
     exp_matrix = mathrobors.SO3.exp((0.1, -0.2, 0.3), None)
     approx_eq_matrix(exp_matrix, mathrobors.SO3.from_rotation_vector((0.1, -0.2, 0.3)).matrix(), 1e-12)
 
+def test_cmtm_block_matrix_raises_on_invalid_order():
+    cmtm = mathrobors.CMTM.from_se3(mathrobors.SE3.eye())
+    block = cmtm.to_block_matrix()
+    assert len(block) == 6 and len(block[0]) == 6
+
+    with pytest.raises(mathrobors.MathRoboError) as excinfo:
+        cmtm.to_block_matrix(0)
+    assert excinfo.value.code == 1
+
+    with pytest.raises(ValueError, match="exceeds available derivatives") as excinfo:
+        cmtm.to_block_matrix(2)
+    assert excinfo.value.code == 2
+
+def test_cmtm_exposes_order_and_lower_order_views():
+    twist = (0.1, -0.2, 0.3, 1.0, 0.0, -0.5)
+    cmtm = mathrobors.CMTM.from_se3_with_derivatives(
+        mathrobors.SE3.eye(), [twist, (0.0,) * 6])
+    assert cmtm.order() == 3
+    assert mathrobors.CMTM().order() == 1
+    approx_eq(cmtm.derivatives()[0], twist)
+
+    view = cmtm.truncated(2)
+    assert view.order() == 2
+    full = cmtm.to_block_matrix()
+    assert view.to_block_matrix() == [row[:12] for row in full[:12]]
+    assert cmtm.to_block_matrix(2) == view.to_block_matrix(None)
+
+    for order in (0, 4):
+        with pytest.raises(mathrobors.MathRoboError):
+            cmtm.truncated(order)
+
+def test_quaternion_constructors_raise_on_zero_norm():
+    for constructor in (
+        mathrobors.SO3.from_quaternion,
+        mathrobors.SO3.set_quaternion,
+        mathrobors.SO3.quaternion_to_mat,
+    ):
+        with pytest.raises(mathrobors.MathRoboError, match="zero norm") as excinfo:
+            constructor((0.0, 0.0, 0.0, 0.0))
+        assert excinfo.value.code == 3
+
+    with pytest.raises(ValueError) as excinfo:
+        mathrobors.SO3.from_quaternion((float("nan"), 0.0, 0.0, 1.0))
+    assert excinfo.value.code == 4
+
+def test_quaternion_layout_adapters_match_scipy_convention():
+    rotation = mathrobors.SO3.from_axis_angle((0.0, 0.0, 1.0), math.pi / 2.0)
+    w, x, y, z = rotation.quaternion_wxyz()
+    approx_eq(rotation.quaternion(), (w, x, y, z), 1e-12)
+    approx_eq(rotation.quaternion_xyzw(), (x, y, z, w), 1e-12)
+
+    approx_eq_matrix(mathrobors.SO3.from_quaternion_xyzw((x, y, z, w)).matrix(), rotation.matrix(), 1e-12)
+    approx_eq_matrix(mathrobors.SO3.from_quaternion_wxyz((w, x, y, z)).matrix(), rotation.matrix(), 1e-12)
+
+    with pytest.raises(mathrobors.MathRoboError):
+        mathrobors.SO3.from_quaternion_xyzw((0.0, 0.0, 0.0, 0.0))
+
+def test_from_sequence_matches_euler_and_raises_on_bad_spec():
+    rotation = mathrobors.SO3.from_sequence("xyz", (0.1, -0.2, 0.3))
+    approx_eq_matrix(rotation.matrix(), mathrobors.SO3.set_euler((0.1, -0.2, 0.3)).matrix(), 1e-12)
+
+    with pytest.raises(mathrobors.MathRoboError, match="mixes intrinsic") as excinfo:
+        mathrobors.SO3.from_sequence("Xyz", (0.1, -0.2, 0.3))
+    assert excinfo.value.code == 8
+
 @pytest.mark.dev
 def test_compare_mathrobo():
     import mathrobo as mr
@@ -224,3 +289,4 @@ def test_so3_eye_benchmark(benchmark, impl):
 
     benchmark(fn)
 
+
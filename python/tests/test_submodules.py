@@ -0,0 +1,34 @@
+import math
+
+import mathrobors
+import mathrobors.so3
+from mathrobors import cmtm, kinematics, se3
+
+
+def approx_eq(a, b, tol=1e-12):
+    assert len(a) == len(b)
+    for x, y in zip(a, b):
+        assert abs(x - y) < tol, f"expected {y}, got {x}"
+
+
+def test_submodules_share_the_top_level_classes():
+    assert mathrobors.so3.SO3 is mathrobors.SO3
+    assert se3.SE3 is mathrobors.SE3
+    assert cmtm.CMTM is mathrobors.CMTM
+    assert callable(kinematics.euler_rate_matrix)
+
+
+def test_so3_free_functions_round_trip():
+    vector = (0.2, -0.4, 0.3)
+    matrix = mathrobors.so3.exp(vector)
+    approx_eq(mathrobors.so3.log(matrix), vector)
+    approx_eq(mathrobors.so3.vee(mathrobors.so3.hat(vector)), vector)
+    expected = mathrobors.SO3.from_rotation_vector(vector).matrix()
+    for row, expected_row in zip(matrix, expected):
+        approx_eq(row, expected_row)
+
+
+def test_se3_free_functions_round_trip():
+    twist = (0.0, 0.0, math.pi / 4.0, 1.0, -2.0, 0.5)
+    approx_eq(se3.log(se3.exp(twist)), twist)
+    approx_eq(se3.vee(se3.hat(twist)), twist)
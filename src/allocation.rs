@@ -0,0 +1,173 @@
+use nalgebra::{DMatrix, DVector, Vector3};
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+
+/// A force-producing actuator (rotor, thruster) rigidly mounted on a body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Actuator {
+    /// Pose of the actuator frame in the body frame.
+    pub pose: Se3,
+    /// Unit thrust direction in the actuator frame.
+    pub axis: [f64; 3],
+    /// Reaction torque about `axis` per unit thrust, e.g. \(\pm k_m / k_f\)
+    /// for a propeller depending on its spin direction.
+    pub torque_coefficient: f64,
+}
+
+impl Actuator {
+    /// The `axis` is normalized. Returns [`Error::NonFinite`] or
+    /// [`Error::ZeroNorm`] if it has no direction.
+    pub fn new(pose: Se3, axis: [f64; 3], torque_coefficient: f64) -> Result<Self> {
+        let axis = Vector3::from(axis);
+        if axis.iter().any(|a| !a.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+        if axis.norm() == 0.0 {
+            return Err(Error::ZeroNorm);
+        }
+        Ok(Self {
+            pose,
+            axis: axis.normalize().into(),
+            torque_coefficient,
+        })
+    }
+
+    /// Body-frame wrench \([\tau, f]\) produced by unit thrust, mapped from the
+    /// actuator frame with the co-adjoint (see [`Se3::transform_wrench`]).
+    pub fn unit_wrench(&self) -> [f64; 6] {
+        let [x, y, z] = self.axis;
+        let k = self.torque_coefficient;
+        self.pose.transform_wrench([k * x, k * y, k * z, x, y, z])
+    }
+}
+
+/// The 6×n wrench-allocation matrix \(B\) mapping actuator thrusts \(u\) to
+/// the body wrench \(F = B u\) in twist order \([\tau, f]\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allocation {
+    matrix: DMatrix<f64>,
+}
+
+/// Iteration limits for [`Allocation::solve_bounded`].
+const BOUNDED_MAX_SWEEPS: usize = 10_000;
+const BOUNDED_TOLERANCE: f64 = 1e-12;
+
+impl Allocation {
+    /// Stack the unit wrenches of `actuators` as columns.
+    pub fn new(actuators: &[Actuator]) -> Self {
+        let mut matrix = DMatrix::zeros(6, actuators.len());
+        for (column, actuator) in actuators.iter().enumerate() {
+            for (row, value) in actuator.unit_wrench().into_iter().enumerate() {
+                matrix[(row, column)] = value;
+            }
+        }
+        Self { matrix }
+    }
+
+    pub fn matrix(&self) -> &DMatrix<f64> {
+        &self.matrix
+    }
+
+    /// Number of actuators (columns).
+    pub fn len(&self) -> usize {
+        self.matrix.ncols()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Body wrench produced by the thrusts `thrusts`. Returns
+    /// [`Error::DimensionMismatch`] unless there is one thrust per actuator.
+    pub fn wrench(&self, thrusts: &[f64]) -> Result<[f64; 6]> {
+        self.check_len(thrusts)?;
+        let wrench = &self.matrix * DVector::from_column_slice(thrusts);
+        Ok(std::array::from_fn(|i| wrench[i]))
+    }
+
+    fn check_len(&self, values: &[f64]) -> Result<()> {
+        if values.len() != self.len() {
+            return Err(Error::DimensionMismatch {
+                expected: self.len(),
+                provided: values.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Unconstrained allocation \(u = B^+ F\): the minimum-norm thrusts
+    /// reproducing `wrench`, or its least-squares approximation for
+    /// unreachable wrench directions.
+    pub fn solve_least_squares(&self, wrench: [f64; 6]) -> Vec<f64> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let target = DVector::from_column_slice(&wrench);
+        self.matrix
+            .clone()
+            .svd(true, true)
+            .solve(&target, 1e-12)
+            .expect("SVD computed with both singular vector sets")
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Allocation with per-actuator thrust limits `min[i] <= u[i] <= max[i]`,
+    /// minimizing \(\|B u - F\|^2 + \epsilon \|u\|^2\) with a small
+    /// regularization \(\epsilon\) that picks the lowest-effort solution among
+    /// equally good ones. Solved by cyclic coordinate descent, which converges
+    /// for this convex problem; when `wrench` is unreachable within the
+    /// limits the closest achievable wrench is produced.
+    ///
+    /// Returns [`Error::DimensionMismatch`] unless there is one limit of each
+    /// kind per actuator, and [`Error::InvalidDescription`] if a lower limit
+    /// exceeds its upper limit or either is NaN.
+    pub fn solve_bounded(&self, wrench: [f64; 6], min: &[f64], max: &[f64]) -> Result<Vec<f64>> {
+        self.check_len(min)?;
+        self.check_len(max)?;
+        if let Some(i) =
+            (0..self.len()).find(|&i| min[i].is_nan() || max[i].is_nan() || min[i] > max[i])
+        {
+            return Err(Error::InvalidDescription(format!(
+                "thrust limits [{}, {}] of actuator {i} are empty",
+                min[i], max[i]
+            )));
+        }
+        let epsilon = 1e-9;
+        let target = DVector::from_column_slice(&wrench);
+        let norms: Vec<f64> = self
+            .matrix
+            .column_iter()
+            .map(|column| column.norm_squared() + epsilon)
+            .collect();
+
+        let mut thrusts: Vec<f64> = self
+            .solve_least_squares(wrench)
+            .iter()
+            .enumerate()
+            .map(|(i, u)| u.clamp(min[i], max[i]))
+            .collect();
+        let mut residual = &self.matrix * DVector::from_column_slice(&thrusts) - &target;
+
+        for _ in 0..BOUNDED_MAX_SWEEPS {
+            let mut largest_change: f64 = 0.0;
+            for (i, column) in self.matrix.column_iter().enumerate() {
+                let gradient = column.dot(&residual) + epsilon * thrusts[i];
+                let updated = (thrusts[i] - gradient / norms[i]).clamp(min[i], max[i]);
+                let change = updated - thrusts[i];
+                if change != 0.0 {
+                    residual += column * change;
+                    thrusts[i] = updated;
+                    largest_change = largest_change.max(change.abs());
+                }
+            }
+            trace_event!(trace, largest_change, "bounded allocation sweep");
+            if largest_change <= BOUNDED_TOLERANCE {
+                break;
+            }
+        }
+        Ok(thrusts)
+    }
+}
@@ -0,0 +1,103 @@
+use nalgebra::Vector3;
+
+use crate::error::{Error, Result};
+use crate::so3::So3;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-12;
+
+/// Karcher (Fréchet L2) mean: the rotation minimizing
+/// \(\sum_i d(R, R_i)^2\) with the geodesic distance \(d\), found by the
+/// fixed-point iteration \(R \leftarrow R\,\mathrm{Exp}\big(\tfrac{1}{n}\sum_i
+/// \mathrm{Log}(R^{-1} R_i)\big)\) starting from the first rotation.
+///
+/// Returns [`Error::InsufficientData`] for an empty slice and
+/// [`Error::NotConverged`] if the iteration does not settle, which can happen
+/// when the rotations are spread over more than a hemisphere.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn karcher_mean(rotations: &[So3]) -> Result<So3> {
+    let first = rotations.first().ok_or(Error::InsufficientData {
+        required: 1,
+        provided: 0,
+    })?;
+    let mut mean = first.clone();
+    for _ in 0..MAX_ITERATIONS {
+        let step = rotations
+            .iter()
+            .map(|rotation| Vector3::from(tangent(&mean, rotation)))
+            .sum::<Vector3<f64>>()
+            / rotations.len() as f64;
+        mean = mean.compose(&So3::from_rotation_vector(step.into()));
+        trace_event!(debug, step = step.norm(), "karcher mean step");
+        if step.norm() < TOLERANCE {
+            return Ok(mean);
+        }
+    }
+    Err(Error::NotConverged {
+        iterations: MAX_ITERATIONS,
+    })
+}
+
+/// Geodesic L1 median: the rotation minimizing \(\sum_i d(R, R_i)\), which
+/// tolerates a minority of arbitrarily wrong measurements that would drag
+/// [`karcher_mean`] away.
+///
+/// Uses the Weiszfeld iteration on SO(3) of Hartley et al., stepping by the
+/// inverse-distance-weighted mean of \(\mathrm{Log}(R^{-1} R_i)\) and skipping
+/// measurements that coincide with the estimate. It starts from the input
+/// rotation with the smallest total distance to the others.
+///
+/// Returns [`Error::InsufficientData`] for an empty slice and
+/// [`Error::NotConverged`] if the iteration does not settle.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn geodesic_median(rotations: &[So3]) -> Result<So3> {
+    if rotations.is_empty() {
+        return Err(Error::InsufficientData {
+            required: 1,
+            provided: 0,
+        });
+    }
+    let total_distance = |candidate: &So3| -> f64 {
+        rotations
+            .iter()
+            .map(|rotation| Vector3::from(tangent(candidate, rotation)).norm())
+            .sum()
+    };
+    let mut median = rotations
+        .iter()
+        .map(|candidate| (total_distance(candidate), candidate))
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate.clone())
+        .expect("rotations is not empty");
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut weighted = Vector3::zeros();
+        let mut weight = 0.0;
+        for rotation in rotations {
+            let offset = Vector3::from(tangent(&median, rotation));
+            let distance = offset.norm();
+            if distance > TOLERANCE {
+                weighted += offset / distance;
+                weight += 1.0 / distance;
+            }
+        }
+        if weight == 0.0 {
+            return Ok(median);
+        }
+        let step = weighted / weight;
+        median = median.compose(&So3::from_rotation_vector(step.into()));
+        trace_event!(debug, step = step.norm(), weight, "weiszfeld step");
+        if step.norm() < TOLERANCE {
+            return Ok(median);
+        }
+    }
+    Err(Error::NotConverged {
+        iterations: MAX_ITERATIONS,
+    })
+}
+
+/// \(\mathrm{Log}(R^{-1} R_i)\), the body-frame offset of `rotation` from
+/// `base`.
+fn tangent(base: &So3, rotation: &So3) -> [f64; 3] {
+    base.inverse().compose(rotation).to_rotation_vector()
+}
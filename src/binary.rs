@@ -0,0 +1,172 @@
+//! Compact, deterministic binary encoding for telemetry streams.
+//!
+//! Every message starts with a five-byte header: the magic bytes `MRB`, the
+//! format version ([`BINARY_VERSION`]) and a kind byte naming the encoded
+//! type. Payloads use little-endian numbers:
+//!
+//! - pose ([`Se3`], kind 1): unit quaternion \([w, x, y, z]\) with
+//!   \(w \ge 0\) followed by the translation, seven `f64` (56 bytes);
+//! - [`TimedPose`] (kind 2): time `f64`, then a pose;
+//! - [`Trajectory`] (kind 3): sample count `u32`, then that many timed poses;
+//! - [`FrameTree`](crate::frame::FrameTree) snapshot (kind 4): translation and
+//!   rotation tolerances `f64`, frame count `u32` and names (`u32` length and
+//!   UTF-8), edge count `u32` and per edge parent and child indices `u32` and
+//!   a pose.
+//!
+//! Equal values encode to equal bytes, with `-0.0` written as `0.0`; NaN
+//! payloads are written as they are. Rotations pass through a quaternion, so
+//! decoding reproduces them to within a few ulps.
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::trajectory::{TimedPose, Trajectory};
+use crate::util::{ByteReader, put_f64s};
+
+const MAGIC: &[u8; 3] = b"MRB";
+/// Format version written in every header.
+pub const BINARY_VERSION: u8 = 1;
+
+pub(crate) const KIND_SE3: u8 = 1;
+pub(crate) const KIND_TIMED_POSE: u8 = 2;
+pub(crate) const KIND_TRAJECTORY: u8 = 3;
+pub(crate) const KIND_FRAME_TREE: u8 = 4;
+
+/// Types with a compact binary encoding described in the [module
+/// documentation](self).
+pub trait BinaryCodec: Sized {
+    /// Header plus payload.
+    ///
+    /// # Panics
+    /// Panics if a count or string length does not fit in a `u32`.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Decode a complete message, rejecting wrong headers, unsupported
+    /// versions and trailing bytes with [`Error::InvalidEncoding`].
+    fn from_bytes(bytes: &[u8]) -> Result<Self>;
+}
+
+impl BinaryCodec for Se3 {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_SE3, |buffer| put_pose(buffer, self))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode(bytes, KIND_SE3, read_pose)
+    }
+}
+
+impl BinaryCodec for TimedPose {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_TIMED_POSE, |buffer| put_timed_pose(buffer, self))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode(bytes, KIND_TIMED_POSE, read_timed_pose)
+    }
+}
+
+impl BinaryCodec for Trajectory {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_TRAJECTORY, |buffer| {
+            put_count(buffer, self.len());
+            for sample in self.samples() {
+                put_timed_pose(buffer, sample);
+            }
+        })
+    }
+
+    /// Also returns [`Error::InvalidTrajectory`] if the decoded timestamps
+    /// do not increase.
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode(bytes, KIND_TRAJECTORY, |reader| {
+            let count = reader.u32()?;
+            let samples = (0..count)
+                .map(|_| read_timed_pose(reader))
+                .collect::<Result<Vec<_>>>()?;
+            Trajectory::new(samples)
+        })
+    }
+}
+
+/// Header followed by the payload written by `payload`.
+pub(crate) fn encode(kind: u8, payload: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(64);
+    buffer.extend_from_slice(MAGIC);
+    buffer.push(BINARY_VERSION);
+    buffer.push(kind);
+    payload(&mut buffer);
+    buffer
+}
+
+/// Check the header of `bytes` and decode the payload with `payload`, which
+/// must consume all of it.
+pub(crate) fn decode<T>(
+    bytes: &[u8],
+    kind: u8,
+    payload: impl FnOnce(&mut ByteReader<'_>) -> Result<T>,
+) -> Result<T> {
+    let mut reader = ByteReader::new(bytes);
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(Error::InvalidEncoding("missing MRB header".to_string()));
+    }
+    let version = reader.u8()?;
+    if version != BINARY_VERSION {
+        return Err(Error::InvalidEncoding(format!(
+            "unsupported version {version}"
+        )));
+    }
+    let found = reader.u8()?;
+    if found != kind {
+        return Err(Error::InvalidEncoding(format!(
+            "expected kind {kind}, found {found}"
+        )));
+    }
+    let value = payload(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(Error::InvalidEncoding("trailing bytes".to_string()));
+    }
+    Ok(value)
+}
+
+/// Append a collection length as `u32`.
+///
+/// # Panics
+/// Panics if `count` does not fit in a `u32`.
+pub(crate) fn put_count(buffer: &mut Vec<u8>, count: usize) {
+    let count = u32::try_from(count).expect("more than u32::MAX items");
+    buffer.extend_from_slice(&count.to_le_bytes());
+}
+
+/// Append the quaternion (with \(w \ge 0\)) and translation of `pose`.
+pub(crate) fn put_pose(buffer: &mut Vec<u8>, pose: &Se3) {
+    let mut quaternion = pose.rotation().to_quaternion();
+    if quaternion[0] < 0.0 {
+        quaternion = quaternion.map(|q| -q);
+    }
+    put_f64s(buffer, &quaternion);
+    put_f64s(buffer, &pose.translation());
+}
+
+pub(crate) fn read_pose(reader: &mut ByteReader<'_>) -> Result<Se3> {
+    let mut values = [0.0; 7];
+    for value in &mut values {
+        *value = reader.f64()?;
+    }
+    if values.iter().any(|v| !v.is_finite()) {
+        return Err(Error::InvalidEncoding("non-finite pose".to_string()));
+    }
+    let rotation = So3::try_from_quaternion([values[0], values[1], values[2], values[3]])
+        .map_err(|err| Error::InvalidEncoding(err.to_string()))?;
+    Ok(Se3::from_parts(rotation, [values[4], values[5], values[6]]))
+}
+
+fn put_timed_pose(buffer: &mut Vec<u8>, sample: &TimedPose) {
+    put_f64s(buffer, &[sample.time]);
+    put_pose(buffer, &sample.pose);
+}
+
+fn read_timed_pose(reader: &mut ByteReader<'_>) -> Result<TimedPose> {
+    let time = reader.f64()?;
+    Ok(TimedPose::new(time, read_pose(reader)?))
+}
@@ -0,0 +1,44 @@
+use nalgebra::{Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Pinhole camera intrinsics in pixels, without distortion. The camera looks
+/// along \(+z\) with \(x\) right and \(y\) down.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraIntrinsics {
+    pub fx: f64,
+    pub fy: f64,
+    pub cx: f64,
+    pub cy: f64,
+}
+
+impl CameraIntrinsics {
+    pub fn new(fx: f64, fy: f64, cx: f64, cy: f64) -> Self {
+        Self { fx, fy, cx, cy }
+    }
+
+    /// The calibration matrix \(K\).
+    pub fn matrix(&self) -> Matrix3<f64> {
+        Matrix3::new(self.fx, 0.0, self.cx, 0.0, self.fy, self.cy, 0.0, 0.0, 1.0)
+    }
+
+    /// Pixel of a point in camera coordinates, or `None` if it is not in
+    /// front of the camera.
+    pub fn project(&self, point: [f64; 3]) -> Option<[f64; 2]> {
+        let [x, y, z] = point;
+        (z > 0.0).then(|| [self.fx * x / z + self.cx, self.fy * y / z + self.cy])
+    }
+
+    /// Normalized image coordinates \((x/z, y/z)\) of a pixel.
+    pub fn normalize(&self, pixel: [f64; 2]) -> [f64; 2] {
+        [
+            (pixel[0] - self.cx) / self.fx,
+            (pixel[1] - self.cy) / self.fy,
+        ]
+    }
+
+    /// Unit bearing vector of the ray through a pixel, in camera coordinates.
+    pub fn bearing(&self, pixel: [f64; 2]) -> [f64; 3] {
+        let [x, y] = self.normalize(pixel);
+        Vector3::new(x, y, 1.0).normalize().into()
+    }
+}
@@ -1,7 +1,12 @@
-use nalgebra::{DMatrix, SMatrix, SVector};
+use nalgebra::{DMatrix, Matrix3, SMatrix, SVector, Vector3};
 use std::ops::Mul;
+use std::sync::Arc;
 
-use crate::lie::{apply_linear, matrix_to_array, HasAdjoint};
+use crate::error::{Error, Result};
+use crate::health::{CmtmHealth, TransformHealth};
+use crate::lie::{
+    HasAdjoint, apply_linear, matrix_to_array, matrix_to_col_major, matrix_to_row_major,
+};
 use crate::se3::Se3;
 use crate::so3::So3;
 use crate::util::{skew_symmetric, vector3_from_array};
@@ -24,6 +29,8 @@ pub type RotationalCmtm = GenericCmtm<3>;
 /// Backwards-compatible alias that keeps the original 6×6 CMTM name.
 pub type Cmtm6 = SpatialCmtm;
 pub type Cmtm = SpatialCmtm;
+/// Cheaply clonable handle for sharing an immutable CMTM between threads.
+pub type ArcCmtm = Arc<Cmtm>;
 
 impl<const DIM: usize> GenericCmtm<DIM> {
     /// The identity transformation, which leaves tangent vectors unchanged.
@@ -37,10 +44,7 @@ impl<const DIM: usize> GenericCmtm<DIM> {
     /// Construct a CMTM from an adjoint matrix and a list of derivative vectors
     /// (up to the \(n-1\)-th derivative). The resulting block matrix supports
     /// orders up to `derivatives.len() + 1`.
-    pub fn with_derivatives(
-        matrix: SMatrix<f64, DIM, DIM>,
-        derivatives: Vec<[f64; DIM]>,
-    ) -> Self {
+    pub fn with_derivatives(matrix: SMatrix<f64, DIM, DIM>, derivatives: Vec<[f64; DIM]>) -> Self {
         Self {
             matrix,
             derivatives: derivatives
@@ -60,20 +64,45 @@ impl<const DIM: usize> GenericCmtm<DIM> {
         &self.matrix
     }
 
+    /// The stored derivative vectors, the \(i\)-th entry being the \(i\)-th
+    /// time derivative of the tangent vector.
+    pub fn derivatives(&self) -> &[SVector<f64, DIM>] {
+        &self.derivatives
+    }
+
     /// Highest derivative order supported by this CMTM (1 means only the base matrix).
     pub fn order(&self) -> usize {
         self.derivatives.len() + 1
     }
 
-    fn check_output_order(&self, output_order: Option<usize>) -> usize {
+    /// Lower-order view keeping the base matrix and the first `order - 1`
+    /// derivatives, so its block matrix is the top-left corner of this one.
+    pub fn truncated(&self, order: usize) -> Result<Self> {
+        let order = self.check_output_order(Some(order))?;
+        Ok(Self {
+            matrix: self.matrix,
+            derivatives: self.derivatives[..order - 1].to_vec(),
+        })
+    }
+
+    fn check_output_order(&self, output_order: Option<usize>) -> Result<usize> {
         match output_order {
-            Some(o) if o == 0 => panic!("Output order must be positive"),
-            Some(o) if o > self.order() => panic!("Output order exceeds available derivatives"),
-            Some(o) => o,
-            None => self.order(),
+            Some(0) => Err(Error::ZeroOrder),
+            Some(o) if o > self.order() => Err(Error::OrderOutOfRange {
+                requested: o,
+                available: self.order(),
+            }),
+            Some(o) => Ok(o),
+            None => Ok(self.order()),
         }
     }
 
+    fn derivatives_finite(&self) -> bool {
+        self.derivatives
+            .iter()
+            .all(|d| d.iter().all(|v| v.is_finite()))
+    }
+
     fn factorial(n: usize) -> f64 {
         (1..=n).fold(1.0, |acc, v| acc * v as f64)
     }
@@ -98,14 +127,46 @@ impl GenericCmtm<3> {
     }
 
     /// Apply the 3×3 transformation to an angular velocity vector.
+    #[inline]
     pub fn apply_omega(&self, omega: [f64; 3]) -> [f64; 3] {
         apply_linear(&self.matrix, omega)
     }
 
     /// Build the block CMTM up to the requested derivative order.
+    ///
+    /// # Panics
+    /// Panics if `output_order` is zero or exceeds [`GenericCmtm::order`];
+    /// use [`GenericCmtm::try_to_block_matrix`] to handle those cases.
     pub fn to_block_matrix(&self, output_order: Option<usize>) -> DMatrix<f64> {
+        self.build_block_matrix(output_order)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Fallible variant of [`GenericCmtm::to_block_matrix`] that reports an
+    /// invalid `output_order` as an [`Error`] instead of panicking.
+    pub fn try_to_block_matrix(&self, output_order: Option<usize>) -> Result<DMatrix<f64>> {
         self.build_block_matrix(output_order)
     }
+
+    /// Check that the base matrix is still a proper rotation and that the
+    /// stored derivatives are finite.
+    pub fn health_check(&self) -> CmtmHealth {
+        CmtmHealth {
+            base: TransformHealth::from_rotation(&self.matrix, 0.0),
+            block_structure_error: 0.0,
+            derivatives_finite: self.derivatives_finite(),
+        }
+    }
+
+    /// Export the 3×3 base matrix in row-major order (`(r, c)` at `3 * r + c`).
+    pub fn to_row_major_flat(&self) -> [f64; 9] {
+        matrix_to_row_major(&self.matrix)
+    }
+
+    /// Export the 3×3 base matrix in column-major order (`(r, c)` at `3 * c + r`).
+    pub fn to_col_major_flat(&self) -> [f64; 9] {
+        matrix_to_col_major(&self.matrix)
+    }
 }
 
 impl GenericCmtm<6> {
@@ -125,51 +186,117 @@ impl GenericCmtm<6> {
         Self::with_derivatives(matrix, derivatives)
     }
 
+    /// Recover the transform from the adjoint blocks: \(R\) from the upper-left
+    /// block and \(t\) from \([t]_\times = (\text{lower-left}) R^T\).
+    pub fn to_se3(&self) -> Se3 {
+        let rotation: Matrix3<f64> = self.matrix.fixed_view::<3, 3>(0, 0).into_owned();
+        let coupling = self.matrix.fixed_view::<3, 3>(3, 0) * rotation.transpose();
+        let translation = [
+            0.5 * (coupling[(2, 1)] - coupling[(1, 2)]),
+            0.5 * (coupling[(0, 2)] - coupling[(2, 0)]),
+            0.5 * (coupling[(1, 0)] - coupling[(0, 1)]),
+        ];
+        Se3::from_parts(So3::from_matrix(matrix_to_array(&rotation)), translation)
+    }
+
     /// Apply the 6×6 transformation to a twist vector \([\omega, v]\), returning
     /// the transformed angular and linear velocity components.
+    #[inline]
     pub fn apply_twist(&self, twist: [f64; 6]) -> [f64; 6] {
         apply_linear(&self.matrix, twist)
     }
 
     /// Build the block CMTM up to the requested derivative order.
+    ///
+    /// # Panics
+    /// Panics if `output_order` is zero or exceeds [`GenericCmtm::order`];
+    /// use [`GenericCmtm::try_to_block_matrix`] to handle those cases.
     pub fn to_block_matrix(&self, output_order: Option<usize>) -> DMatrix<f64> {
+        self.build_block_matrix(output_order)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Fallible variant of [`GenericCmtm::to_block_matrix`] that reports an
+    /// invalid `output_order` as an [`Error`] instead of panicking.
+    pub fn try_to_block_matrix(&self, output_order: Option<usize>) -> Result<DMatrix<f64>> {
         self.build_block_matrix(output_order)
     }
+
+    /// Check that the base matrix still has the adjoint block structure
+    /// \(\begin{bmatrix} R & 0 \\ [t]_\times R & R \end{bmatrix}\) with a proper
+    /// rotation \(R\), and that the stored derivatives are finite.
+    pub fn health_check(&self) -> CmtmHealth {
+        let rotation: Matrix3<f64> = self.matrix.fixed_view::<3, 3>(0, 0).into_owned();
+        let lower_rotation = self.matrix.fixed_view::<3, 3>(3, 3);
+        let upper_right = self.matrix.fixed_view::<3, 3>(0, 3);
+        let coupling = self.matrix.fixed_view::<3, 3>(3, 0) * rotation.transpose();
+
+        let translation = Vector3::new(
+            0.5 * (coupling[(2, 1)] - coupling[(1, 2)]),
+            0.5 * (coupling[(0, 2)] - coupling[(2, 0)]),
+            0.5 * (coupling[(1, 0)] - coupling[(0, 1)]),
+        );
+        let block_structure_error = ((lower_rotation - rotation).norm_squared()
+            + upper_right.norm_squared()
+            + (coupling + coupling.transpose()).norm_squared())
+        .sqrt();
+
+        CmtmHealth {
+            base: TransformHealth::from_rotation(&rotation, translation.norm()),
+            block_structure_error,
+            derivatives_finite: self.derivatives_finite(),
+        }
+    }
+
+    /// Export the 6×6 base matrix in row-major order (`(r, c)` at `6 * r + c`).
+    pub fn to_row_major_flat(&self) -> [f64; 36] {
+        matrix_to_row_major(&self.matrix)
+    }
+
+    /// Export the 6×6 base matrix in column-major order (`(r, c)` at `6 * c + r`).
+    pub fn to_col_major_flat(&self) -> [f64; 36] {
+        matrix_to_col_major(&self.matrix)
+    }
 }
 
 impl<const DIM: usize> GenericCmtm<DIM> {
-    fn hat_adj(&self, vec: &SVector<f64, DIM>) -> SMatrix<f64, DIM, DIM> {
+    fn hat_adj(vec: &SVector<f64, DIM>) -> SMatrix<f64, DIM, DIM> {
+        const { assert!(DIM == 3 || DIM == 6, "CMTM blocks need DIM 3 or 6") };
+        let mut mat = SMatrix::<f64, DIM, DIM>::zeros();
         match DIM {
             3 => {
                 let omega_hat = skew_symmetric(&vector3_from_array([vec[0], vec[1], vec[2]]));
-                let mut data = vec![0.0_f64; DIM * DIM];
-                for r in 0..3 {
-                    for c in 0..3 {
-                        data[r * DIM + c] = omega_hat[(r, c)];
-                    }
-                }
-
-                SMatrix::<f64, DIM, DIM>::from_row_slice(&data)
+                mat.fixed_view_mut::<3, 3>(0, 0).copy_from(&omega_hat);
             }
             6 => {
                 let omega = vector3_from_array([vec[0], vec[1], vec[2]]);
                 let v = vector3_from_array([vec[3], vec[4], vec[5]]);
                 let omega_hat = skew_symmetric(&omega);
-                let v_hat = skew_symmetric(&v);
-
-                let mut data = vec![0.0_f64; DIM * DIM];
-                for r in 0..3 {
-                    for c in 0..3 {
-                        data[r * DIM + c] = omega_hat[(r, c)];
-                        data[(r + 3) * DIM + (c + 3)] = omega_hat[(r, c)];
-                        data[(r + 3) * DIM + c] = v_hat[(r, c)];
-                    }
-                }
-
-                SMatrix::<f64, DIM, DIM>::from_row_slice(&data)
+                mat.fixed_view_mut::<3, 3>(0, 0).copy_from(&omega_hat);
+                mat.fixed_view_mut::<3, 3>(3, 3).copy_from(&omega_hat);
+                mat.fixed_view_mut::<3, 3>(3, 0)
+                    .copy_from(&skew_symmetric(&v));
             }
-            _ => panic!("hat_adj not implemented for dimension {DIM}"),
+            _ => unreachable!(),
         }
+        mat
+    }
+
+    /// The scaled adjoint \(\mathrm{ad}(\xi_i / i!)\) of the `i`-th derivative.
+    fn scaled_hat(derivative: &SVector<f64, DIM>, i: usize) -> SMatrix<f64, DIM, DIM> {
+        Self::hat_adj(&(derivative / Self::factorial(i)))
+    }
+
+    /// Block \(M_p = \tfrac{1}{p} \sum_{i<p} M_{p-i-1}\,\mathrm{ad}(\xi_i / i!)\)
+    /// of the recurrence, given the lower blocks and the scaled adjoints.
+    fn recurrence_block(
+        p: usize,
+        block: impl Fn(usize) -> SMatrix<f64, DIM, DIM>,
+        scaled_hat: impl Fn(usize) -> SMatrix<f64, DIM, DIM>,
+    ) -> SMatrix<f64, DIM, DIM> {
+        (0..p).fold(SMatrix::zeros(), |mat, i| {
+            mat + block(p - i - 1) * scaled_hat(i)
+        }) / p as f64
     }
 
     /// Compose two CMTMs by multiplying their base matrices and pairing the
@@ -177,6 +304,13 @@ impl<const DIM: usize> GenericCmtm<DIM> {
     /// are treated as zero, so the resulting order matches the larger operand.
     pub fn compose(&self, other: &Self) -> Self {
         let matrix = self.matrix * other.matrix;
+        if self.derivatives.is_empty() && other.derivatives.is_empty() {
+            // Order-1 fast path: a plain adjoint product.
+            return Self {
+                matrix,
+                derivatives: Vec::new(),
+            };
+        }
         let max_order = usize::max(self.derivatives.len(), other.derivatives.len());
 
         let derivatives = (0..max_order)
@@ -185,55 +319,222 @@ impl<const DIM: usize> GenericCmtm<DIM> {
                     .derivatives
                     .get(i)
                     .cloned()
-                    .unwrap_or_else(|| SVector::<f64, DIM>::zeros());
+                    .unwrap_or_else(SVector::<f64, DIM>::zeros);
                 let right = other
                     .derivatives
                     .get(i)
                     .cloned()
-                    .unwrap_or_else(|| SVector::<f64, DIM>::zeros());
+                    .unwrap_or_else(SVector::<f64, DIM>::zeros);
                 left + right
             })
             .collect();
 
-        Self { matrix, derivatives }
+        Self {
+            matrix,
+            derivatives,
+        }
     }
 
-    fn mat_elem(&self, p: usize) -> SMatrix<f64, DIM, DIM> {
-        if p == 0 {
-            return self.matrix;
+    /// Write the block CMTM of `order` into `out`, reusing its storage when it
+    /// already has the right shape.
+    ///
+    /// The first block column holds \(M_0, \dots, M_{order-1}\) from the
+    /// recurrence \(M_p = \tfrac{1}{p} \sum_{i<p} M_{p-i-1}\,\mathrm{ad}(\xi_i / i!)\),
+    /// reading the lower blocks back from `out`. Each block and each scaled
+    /// \(\mathrm{ad}(\xi_i / i!)\) is computed once, the latter kept in the
+    /// upper triangle until the end, so no temporary heap storage is needed.
+    fn write_block_matrix(&self, order: usize, out: &mut DMatrix<f64>) {
+        let size = DIM * order;
+        if out.shape() == (size, size) {
+            out.fill(0.0);
+        } else {
+            *out = DMatrix::zeros(size, size);
         }
 
-        let mut mat = SMatrix::<f64, DIM, DIM>::zeros();
-        for i in 0..p {
-            let prev = self.mat_elem(p - i - 1);
-            let scaled = self.derivatives[i] / Self::factorial(i);
-            let hat = self.hat_adj(&scaled);
-            mat += prev * hat;
+        out.fixed_view_mut::<DIM, DIM>(0, 0).copy_from(&self.matrix);
+        // Stash \(\mathrm{ad}(\xi_i / i!)\) in block (0, i + 1) of the upper
+        // triangle, which is zero in the result.
+        for i in 0..order.saturating_sub(1) {
+            out.fixed_view_mut::<DIM, DIM>(0, (i + 1) * DIM)
+                .copy_from(&Self::scaled_hat(&self.derivatives[i], i));
+        }
+        for p in 1..order {
+            let block = Self::recurrence_block(
+                p,
+                |k| out.fixed_view::<DIM, DIM>(k * DIM, 0).into_owned(),
+                |i| out.fixed_view::<DIM, DIM>(0, (i + 1) * DIM).into_owned(),
+            );
+            out.fixed_view_mut::<DIM, DIM>(p * DIM, 0).copy_from(&block);
         }
+        out.view_mut((0, DIM), (DIM, size - DIM)).fill(0.0);
 
-        mat / p as f64
+        for i in 0..order {
+            let block: SMatrix<f64, DIM, DIM> = out.fixed_view::<DIM, DIM>(i * DIM, 0).into_owned();
+            for j in i + 1..order {
+                out.fixed_view_mut::<DIM, DIM>(j * DIM, (j - i) * DIM)
+                    .copy_from(&block);
+            }
+        }
     }
 
-    fn build_block_matrix(&self, output_order: Option<usize>) -> DMatrix<f64> {
-        let order = self.check_output_order(output_order);
-        let size = DIM * order;
+    fn build_block_matrix(&self, output_order: Option<usize>) -> Result<DMatrix<f64>> {
+        let order = self.check_output_order(output_order)?;
+        let mut mat = DMatrix::<f64>::zeros(0, 0);
+        self.write_block_matrix(order, &mut mat);
+        Ok(mat)
+    }
 
-        let mut mat = DMatrix::<f64>::zeros(size, size);
-        let tmp: Vec<SMatrix<f64, DIM, DIM>> = (0..order).map(|i| self.mat_elem(i)).collect();
+    /// Build the block CMTM into a caller-owned buffer. When `out` already has
+    /// the requested size (e.g. from the previous control cycle) no allocation
+    /// takes place; otherwise it is resized.
+    pub fn build_block_matrix_into(
+        &self,
+        output_order: Option<usize>,
+        out: &mut DMatrix<f64>,
+    ) -> Result<()> {
+        let order = self.check_output_order(output_order)?;
+        self.write_block_matrix(order, out);
+        Ok(())
+    }
+}
 
-        for i in 0..order {
-            for j in i..order {
-                let row_offset = j * DIM;
-                let col_offset = (j - i) * DIM;
-                for r in 0..DIM {
-                    for c in 0..DIM {
-                        mat[(row_offset + r, col_offset + c)] = tmp[i][(r, c)];
-                    }
-                }
+/// CMTM with the dimension and block order fixed at compile time.
+///
+/// Derivatives live in a fixed-size array and the block matrix is a statically
+/// sized [`SMatrix`], so no heap allocation takes place. Semantics match
+/// [`GenericCmtm`] of the same order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CmtmN<const DIM: usize, const ORDER: usize> {
+    matrix: SMatrix<f64, DIM, DIM>,
+    // Only the first ORDER - 1 entries are used; the last one stays zero so
+    // the array length can be written without const-generic arithmetic.
+    derivatives: [SVector<f64, DIM>; ORDER],
+}
+
+impl<const DIM: usize, const ORDER: usize> CmtmN<DIM, ORDER> {
+    /// The identity transformation with all derivatives zero.
+    pub fn identity() -> Self {
+        const { assert!(ORDER >= 1, "CMTM order must be at least 1") };
+        Self {
+            matrix: SMatrix::identity(),
+            derivatives: [SVector::zeros(); ORDER],
+        }
+    }
+
+    /// Construct from an adjoint matrix and exactly `ORDER - 1` derivative
+    /// vectors. Passing a different number fails to compile.
+    pub fn with_derivatives<const N: usize>(
+        matrix: SMatrix<f64, DIM, DIM>,
+        derivatives: [[f64; DIM]; N],
+    ) -> Self {
+        const { assert!(N + 1 == ORDER, "expected ORDER - 1 derivative vectors") };
+        Self {
+            matrix,
+            derivatives: std::array::from_fn(|i| {
+                derivatives
+                    .get(i)
+                    .map_or_else(SVector::zeros, |v| SVector::from_row_slice(v))
+            }),
+        }
+    }
+
+    /// Block order, i.e. the number of Taylor coefficients the block matrix
+    /// acts on.
+    pub const fn order(&self) -> usize {
+        ORDER
+    }
+
+    /// Borrow the base matrix.
+    pub fn matrix(&self) -> &SMatrix<f64, DIM, DIM> {
+        &self.matrix
+    }
+
+    /// The `ORDER - 1` stored derivative vectors.
+    pub fn derivatives(&self) -> &[SVector<f64, DIM>] {
+        &self.derivatives[..ORDER - 1]
+    }
+
+    /// Compose by multiplying base matrices and adding derivatives order-wise,
+    /// as [`GenericCmtm::compose`] does.
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            matrix: self.matrix * other.matrix,
+            derivatives: std::array::from_fn(|i| self.derivatives[i] + other.derivatives[i]),
+        }
+    }
+
+    /// Build the statically sized block matrix. `SIZE` must equal
+    /// `DIM * ORDER` and `DIM` must be 3 or 6; anything else fails to compile.
+    ///
+    /// ```compile_fail
+    /// use mathroborust::cmtm::CmtmN;
+    /// let _ = CmtmN::<4, 2>::identity().to_block_matrix::<8>();
+    /// ```
+    pub fn to_block_matrix<const SIZE: usize>(&self) -> SMatrix<f64, SIZE, SIZE> {
+        const { assert!(SIZE == DIM * ORDER, "block matrix size must be DIM * ORDER") };
+        let hats: [SMatrix<f64, DIM, DIM>; ORDER] =
+            std::array::from_fn(|i| GenericCmtm::<DIM>::scaled_hat(&self.derivatives[i], i));
+        let mut blocks = [SMatrix::<f64, DIM, DIM>::zeros(); ORDER];
+        blocks[0] = self.matrix;
+        for p in 1..ORDER {
+            blocks[p] = GenericCmtm::<DIM>::recurrence_block(p, |k| blocks[k], |i| hats[i]);
+        }
+
+        let mut out = SMatrix::<f64, SIZE, SIZE>::zeros();
+        for (i, block) in blocks.iter().enumerate() {
+            for j in i..ORDER {
+                out.fixed_view_mut::<DIM, DIM>(j * DIM, (j - i) * DIM)
+                    .copy_from(block);
             }
         }
+        out
+    }
 
-        mat
+    /// Convert into the heap-backed [`GenericCmtm`] of the same order.
+    pub fn to_generic(&self) -> GenericCmtm<DIM> {
+        GenericCmtm {
+            matrix: self.matrix,
+            derivatives: self.derivatives().to_vec(),
+        }
+    }
+
+    /// Convert from a [`GenericCmtm`], which must have exactly this order.
+    pub fn try_from_generic(cmtm: &GenericCmtm<DIM>) -> Result<Self> {
+        if cmtm.order() != ORDER {
+            return Err(Error::OrderOutOfRange {
+                requested: ORDER,
+                available: cmtm.order(),
+            });
+        }
+        Ok(Self {
+            matrix: cmtm.matrix,
+            derivatives: std::array::from_fn(|i| {
+                cmtm.derivatives
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(SVector::zeros)
+            }),
+        })
+    }
+}
+
+impl<const ORDER: usize> CmtmN<6, ORDER> {
+    /// Spatial CMTM of an SE(3) transform with `ORDER - 1` derivatives.
+    pub fn from_se3_with_derivatives<const N: usize>(
+        transform: &Se3,
+        derivatives: [[f64; 6]; N],
+    ) -> Self {
+        Self::with_derivatives(transform.adjoint_matrix(), derivatives)
+    }
+}
+
+impl<const ORDER: usize> CmtmN<3, ORDER> {
+    /// Rotational CMTM of an SO(3) rotation with `ORDER - 1` derivatives.
+    pub fn from_so3_with_derivatives<const N: usize>(
+        rotation: &So3,
+        derivatives: [[f64; 3]; N],
+    ) -> Self {
+        Self::with_derivatives(rotation.rotation().matrix().clone_owned(), derivatives)
     }
 }
 
@@ -253,7 +554,7 @@ impl<'a, const DIM: usize> Mul<&'a GenericCmtm<DIM>> for GenericCmtm<DIM> {
     }
 }
 
-impl<'a, const DIM: usize> Mul<GenericCmtm<DIM>> for &'a GenericCmtm<DIM> {
+impl<const DIM: usize> Mul<GenericCmtm<DIM>> for &GenericCmtm<DIM> {
     type Output = GenericCmtm<DIM>;
 
     fn mul(self, rhs: GenericCmtm<DIM>) -> Self::Output {
@@ -261,7 +562,7 @@ impl<'a, const DIM: usize> Mul<GenericCmtm<DIM>> for &'a GenericCmtm<DIM> {
     }
 }
 
-impl<'a, 'b, const DIM: usize> Mul<&'a GenericCmtm<DIM>> for &'b GenericCmtm<DIM> {
+impl<'a, const DIM: usize> Mul<&'a GenericCmtm<DIM>> for &GenericCmtm<DIM> {
     type Output = GenericCmtm<DIM>;
 
     fn mul(self, rhs: &'a GenericCmtm<DIM>) -> Self::Output {
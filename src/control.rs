@@ -0,0 +1,159 @@
+use nalgebra::Vector3;
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+
+/// Per-axis diagonal parameters of an [`Admittance`], ordered like twists
+/// \([\omega, v]\): rotational axes first, then translational axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdmittanceParams {
+    /// Virtual inertia \(M\); every entry must be positive.
+    pub mass: [f64; 6],
+    /// Virtual damping \(D\); every entry must be non-negative.
+    pub damping: [f64; 6],
+    /// Virtual stiffness \(K\) pulling the pose towards the reference; every
+    /// entry must be non-negative.
+    pub stiffness: [f64; 6],
+}
+
+impl AdmittanceParams {
+    /// The same inertia, damping and stiffness on every rotational axis and
+    /// on every translational axis.
+    pub fn uniform(rotational: [f64; 3], translational: [f64; 3]) -> Self {
+        let [m_r, d_r, k_r] = rotational;
+        let [m_t, d_t, k_t] = translational;
+        Self {
+            mass: [m_r, m_r, m_r, m_t, m_t, m_t],
+            damping: [d_r, d_r, d_r, d_t, d_t, d_t],
+            stiffness: [k_r, k_r, k_r, k_t, k_t, k_t],
+        }
+    }
+
+    /// Check that every mass is positive and finite and every damping and
+    /// stiffness non-negative and finite, returning
+    /// [`Error::InvalidParameter`] otherwise.
+    pub fn validate(&self) -> Result<()> {
+        for (kind, values, zero_allowed) in [
+            ("mass", &self.mass, false),
+            ("damping", &self.damping, true),
+            ("stiffness", &self.stiffness, true),
+        ] {
+            let valid = |v: f64| v.is_finite() && (v > 0.0 || (zero_allowed && v == 0.0));
+            if let Some(axis) = values.iter().position(|v| !valid(*v)) {
+                return Err(Error::InvalidParameter(format!(
+                    "admittance {kind} {} on axis {axis} is out of range",
+                    values[axis]
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Task-space admittance \(M \dot{V} + D V + K x = F\) on \(\mathrm{SE}(3)\).
+///
+/// The state is the pose \(T\) and its body twist \(V\). The displacement
+/// \(x = [\log(R_r^T R), R^T (p - p_r)]\) from the reference \(T_r\) and the
+/// external wrench \(F = [\tau, f]\) are both expressed in the body frame.
+/// Each step advances the twist explicitly and then the pose on the group,
+/// \(T \leftarrow T \exp(V\,\Delta t)\), so the pose never leaves
+/// \(\mathrm{SE}(3)\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Admittance {
+    params: AdmittanceParams,
+    reference: Se3,
+    pose: Se3,
+    twist: [f64; 6],
+}
+
+impl Admittance {
+    /// Create an admittance at rest at `reference`. Returns the errors of
+    /// [`AdmittanceParams::validate`].
+    pub fn new(params: AdmittanceParams, reference: Se3) -> Result<Self> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            pose: reference.clone(),
+            reference,
+            twist: [0.0; 6],
+        })
+    }
+
+    pub fn params(&self) -> &AdmittanceParams {
+        &self.params
+    }
+
+    /// Replace the parameters, keeping the old ones if `params` fail
+    /// [`AdmittanceParams::validate`].
+    pub fn set_params(&mut self, params: AdmittanceParams) -> Result<()> {
+        params.validate()?;
+        self.params = params;
+        Ok(())
+    }
+
+    /// Equilibrium pose the stiffness pulls towards.
+    pub fn reference(&self) -> &Se3 {
+        &self.reference
+    }
+
+    pub fn set_reference(&mut self, reference: Se3) {
+        self.reference = reference;
+    }
+
+    /// Current pose \(T\).
+    pub fn pose(&self) -> &Se3 {
+        &self.pose
+    }
+
+    /// Current body twist \(V = [\omega, v]\).
+    pub fn twist(&self) -> [f64; 6] {
+        self.twist
+    }
+
+    /// Overwrite the pose and body twist, e.g. to sync with the measured robot
+    /// state.
+    pub fn set_state(&mut self, pose: Se3, twist: [f64; 6]) {
+        self.pose = pose;
+        self.twist = twist;
+    }
+
+    /// Body-frame displacement \(x\) of the pose from the reference.
+    pub fn displacement(&self) -> [f64; 6] {
+        let rotation_error = self
+            .reference
+            .rotation()
+            .inverse()
+            .compose(self.pose.rotation())
+            .to_rotation_vector();
+        let offset =
+            Vector3::from(self.pose.translation()) - Vector3::from(self.reference.translation());
+        let [x, y, z] = self.pose.rotation().inverse().apply(offset.into());
+        let [rx, ry, rz] = rotation_error;
+        [rx, ry, rz, x, y, z]
+    }
+
+    /// Advance the admittance by `dt` seconds under the body-frame external
+    /// wrench `wrench` and return the new pose.
+    pub fn step(&mut self, wrench: [f64; 6], dt: f64) -> &Se3 {
+        let displacement = self.displacement();
+        let AdmittanceParams {
+            mass,
+            damping,
+            stiffness,
+        } = self.params;
+        for i in 0..6 {
+            let acceleration =
+                (wrench[i] - damping[i] * self.twist[i] - stiffness[i] * displacement[i]) / mass[i];
+            self.twist[i] += acceleration * dt;
+        }
+        let increment = Se3::from_matrix(Se3::exp(self.twist, Some(dt)));
+        self.pose = self.pose.compose(&increment);
+        &self.pose
+    }
+
+    /// Return to rest at the reference pose.
+    pub fn reset(&mut self) {
+        self.pose = self.reference.clone();
+        self.twist = [0.0; 6];
+    }
+}
@@ -0,0 +1,143 @@
+//! Viewer-independent JSON dumps of a [`FrameTree`] for post-mortem
+//! inspection, e.g. on headless robots.
+//!
+//! A scene is one JSON object with three arrays:
+//!
+//! - `frames`: per frame its `name`, the `root` of its connected component
+//!   and its pose in that root as `translation` \([x, y, z]\), unit
+//!   quaternion `rotation` \([w, x, y, z]\) with \(w \ge 0\) and `axes`, the
+//!   root-frame directions of its x, y and z axes;
+//! - `edges`: per transform edge its `parent`, `child`, and the child pose in
+//!   the parent as `translation` and `rotation`;
+//! - `trajectories`: per named trajectory its `name` and `samples`, each with
+//!   `time`, `translation` and `rotation`.
+//!
+//! Numbers are written with enough digits to round-trip; non-finite values
+//! become `null`.
+
+use std::fmt::Write;
+
+use crate::frame::FrameTree;
+use crate::se3::Se3;
+use crate::trajectory::Trajectory;
+
+/// JSON scene of the frames and edges of `frames`.
+pub fn dump_scene(frames: &FrameTree) -> String {
+    dump_scene_with_trajectories(frames, &[])
+}
+
+/// [`dump_scene`] plus named trajectories, e.g. planned or recorded paths,
+/// expressed in whichever frame the caller chose.
+pub fn dump_scene_with_trajectories(
+    frames: &FrameTree,
+    trajectories: &[(&str, &Trajectory)],
+) -> String {
+    let mut out = String::from("{\"frames\":[");
+    for (i, name) in frames.frames().enumerate() {
+        let (root, pose) = frames
+            .root_transform(name)
+            .expect("listed frames are registered");
+        separator(&mut out, i);
+        out.push_str("{\"name\":");
+        push_string(&mut out, name);
+        out.push_str(",\"root\":");
+        push_string(&mut out, root);
+        push_pose(&mut out, pose);
+        out.push_str(",\"axes\":");
+        let rotation = pose.rotation().to_matrix();
+        push_array(
+            &mut out,
+            (0..3).map(|axis| std::array::from_fn::<f64, 3, _>(|row| rotation[row][axis])),
+            |out, direction| push_numbers(out, &direction),
+        );
+        out.push('}');
+    }
+    out.push_str("],\"edges\":[");
+    for (i, (parent, child, pose)) in frames.edges().enumerate() {
+        separator(&mut out, i);
+        out.push_str("{\"parent\":");
+        push_string(&mut out, parent);
+        out.push_str(",\"child\":");
+        push_string(&mut out, child);
+        push_pose(&mut out, pose);
+        out.push('}');
+    }
+    out.push_str("],\"trajectories\":[");
+    for (i, (name, trajectory)) in trajectories.iter().enumerate() {
+        separator(&mut out, i);
+        out.push_str("{\"name\":");
+        push_string(&mut out, name);
+        out.push_str(",\"samples\":");
+        push_array(&mut out, trajectory.samples().iter(), |out, sample| {
+            out.push_str("{\"time\":");
+            push_number(out, sample.time);
+            push_pose(out, &sample.pose);
+            out.push('}');
+        });
+        out.push('}');
+    }
+    out.push_str("]}");
+    out
+}
+
+fn separator(out: &mut String, index: usize) {
+    if index > 0 {
+        out.push(',');
+    }
+}
+
+fn push_array<T>(
+    out: &mut String,
+    items: impl Iterator<Item = T>,
+    mut push: impl FnMut(&mut String, T),
+) {
+    out.push('[');
+    for (i, item) in items.enumerate() {
+        separator(out, i);
+        push(out, item);
+    }
+    out.push(']');
+}
+
+/// `,"translation":[...],"rotation":[w,x,y,z]` with \(w \ge 0\).
+fn push_pose(out: &mut String, pose: &Se3) {
+    let mut quaternion = pose.rotation().to_quaternion();
+    if quaternion[0] < 0.0 {
+        quaternion = quaternion.map(|q| -q);
+    }
+    out.push_str(",\"translation\":");
+    push_numbers(out, &pose.translation());
+    out.push_str(",\"rotation\":");
+    push_numbers(out, &quaternion);
+}
+
+fn push_numbers(out: &mut String, values: &[f64]) {
+    push_array(out, values.iter(), |out, value| push_number(out, *value));
+}
+
+fn push_number(out: &mut String, value: f64) {
+    if value.is_finite() {
+        // `Display` prints the shortest representation that round-trips.
+        write!(out, "{value}").expect("writing to a String cannot fail");
+    } else {
+        out.push_str("null");
+    }
+}
+
+fn push_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                write!(out, "\\u{:04x}", u32::from(c)).expect("writing to a String cannot fail")
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
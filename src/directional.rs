@@ -0,0 +1,407 @@
+use nalgebra::{Matrix3, Vector3};
+
+use crate::error::{Error, Result};
+use crate::lie::matrix_to_array;
+use crate::so3::So3;
+use crate::util::SplitMix64;
+
+/// Iteration limit of the concentration solve in [`MatrixFisher::fit`].
+const MAX_ITERATIONS: usize = 100;
+/// Convergence threshold on the gap between fitted and sample moments.
+const MOMENT_TOLERANCE: f64 = 1e-10;
+/// Sample means this close to the boundary of the attainable moments (all
+/// rotations equal) have no finite maximum-likelihood concentration.
+const BOUNDARY_TOLERANCE: f64 = 1e-12;
+/// Relative tolerance of the adaptive quadrature of the normalizing constant.
+const QUADRATURE_TOLERANCE: f64 = 1e-13;
+const MAX_QUADRATURE_DEPTH: usize = 40;
+
+/// Matrix-Fisher distribution on \(\mathrm{SO}(3)\) with density
+/// \(p(R) = \exp(\operatorname{tr}(F^T R)) / c(F)\) relative to the normalized
+/// Haar measure.
+///
+/// With the proper singular value decomposition \(F = U S V^T\)
+/// (\(\det U = \det V = 1\), \(s_1 \ge s_2 \ge |s_3|\)) the mode is \(U V^T\)
+/// and \(S\) sets the concentration about it; unlike a Gaussian in the
+/// tangent space, the distribution stays well defined for arbitrarily large
+/// spreads, down to the uniform distribution at \(F = 0\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixFisher {
+    parameter: Matrix3<f64>,
+    u: Matrix3<f64>,
+    v: Matrix3<f64>,
+    concentration: Vector3<f64>,
+}
+
+impl MatrixFisher {
+    pub fn new(parameter: Matrix3<f64>) -> Self {
+        let (u, concentration, v) = proper_svd(&parameter);
+        Self {
+            parameter,
+            u,
+            v,
+            concentration,
+        }
+    }
+
+    /// The uniform distribution.
+    pub fn uniform() -> Self {
+        Self::new(Matrix3::zeros())
+    }
+
+    /// Maximum-likelihood fit to `rotations`.
+    ///
+    /// The sample mean \(U D V^T\) fixes \(U\) and \(V\), and the concentration
+    /// solves \(\partial \log c / \partial s_i = d_i\) by Newton's method.
+    /// Returns [`Error::InsufficientData`] for fewer than two rotations,
+    /// [`Error::Singular`] if the rotations (nearly) coincide, so that the
+    /// concentration is unbounded, and [`Error::NotConverged`] if the solve
+    /// stalls.
+    pub fn fit(rotations: &[So3]) -> Result<Self> {
+        if rotations.len() < 2 {
+            return Err(Error::InsufficientData {
+                required: 2,
+                provided: rotations.len(),
+            });
+        }
+        let mean = rotations
+            .iter()
+            .map(|r| r.rotation().matrix())
+            .sum::<Matrix3<f64>>()
+            / rotations.len() as f64;
+        let (u, moments, v) = proper_svd(&mean);
+        if moments[0] + moments[1] - moments[2] >= 1.0 - BOUNDARY_TOLERANCE {
+            return Err(Error::Singular);
+        }
+        let concentration = solve_concentration(&moments)?;
+        Ok(Self::new(
+            u * Matrix3::from_diagonal(&concentration) * v.transpose(),
+        ))
+    }
+
+    /// The matrix-Fisher distribution matching a Gaussian
+    /// \(R = M \exp(\xi^\wedge)\), \(\xi \sim \mathcal{N}(0, \Sigma)\), to second
+    /// order about its mode: with \(\Sigma = V \operatorname{diag}(\sigma_i^2) V^T\),
+    /// \(s_j + s_k = 1 / \sigma_i^2\) and \(U = M V\).
+    ///
+    /// Returns [`Error::Singular`] unless `covariance` is positive definite.
+    pub fn from_tangent_gaussian(mean: &So3, covariance: &Matrix3<f64>) -> Result<Self> {
+        let eigen = covariance.symmetric_eigen();
+        if !eigen.eigenvalues.iter().all(|&e| e > 0.0 && e.is_finite()) {
+            return Err(Error::Singular);
+        }
+        let mut v = eigen.eigenvectors;
+        if v.determinant() < 0.0 {
+            v.column_mut(2).neg_mut();
+        }
+        let precision = eigen.eigenvalues.map(|e| 1.0 / e);
+        let total = precision.sum();
+        let concentration = precision.map(|p| 0.5 * (total - 2.0 * p));
+        let u = mean.rotation().matrix() * v;
+        Ok(Self::new(
+            u * Matrix3::from_diagonal(&concentration) * v.transpose(),
+        ))
+    }
+
+    /// Mode and right-perturbation covariance of the Gaussian approximation,
+    /// the inverse of [`MatrixFisher::from_tangent_gaussian`]:
+    /// \(\Sigma = V \operatorname{diag}(1 / (s_j + s_k)) V^T\).
+    ///
+    /// Returns [`Error::Singular`] if the distribution is not concentrated
+    /// about a unique mode (\(s_2 + s_3 \le 0\)).
+    pub fn to_tangent_gaussian(&self) -> Result<(So3, Matrix3<f64>)> {
+        let s = &self.concentration;
+        let precision = Vector3::new(s[1] + s[2], s[0] + s[2], s[0] + s[1]);
+        if precision.iter().any(|&p| p <= 0.0) {
+            return Err(Error::Singular);
+        }
+        let covariance =
+            self.v * Matrix3::from_diagonal(&precision.map(|p| 1.0 / p)) * self.v.transpose();
+        Ok((self.mode(), covariance))
+    }
+
+    pub fn parameter(&self) -> &Matrix3<f64> {
+        &self.parameter
+    }
+
+    /// The most likely rotation \(U V^T\).
+    pub fn mode(&self) -> So3 {
+        So3::from_matrix(matrix_to_array(&(self.u * self.v.transpose())))
+    }
+
+    /// Proper singular values \(s_1 \ge s_2 \ge |s_3|\) of the parameter.
+    pub fn concentration(&self) -> [f64; 3] {
+        self.concentration.into()
+    }
+
+    /// \(\log c(F)\), evaluated through the one-dimensional integral
+    /// \(c(S) = \int_{-1}^{1} \tfrac{1}{2} I_0(\tfrac{1}{2}(s_1 - s_2)(1 - u))
+    /// I_0(\tfrac{1}{2}(s_1 + s_2)(1 + u)) e^{s_3 u}\,du\) of Lee (2018).
+    pub fn log_normalizing_constant(&self) -> f64 {
+        let (scale, integrals) = normalizing_integrals(&self.concentration);
+        scale + integrals[0].ln()
+    }
+
+    /// Log density relative to the normalized Haar measure.
+    pub fn log_density(&self, rotation: &So3) -> f64 {
+        self.parameter.dot(rotation.rotation().matrix()) - self.log_normalizing_constant()
+    }
+
+    /// Expected rotation matrix \(E[R] = U \operatorname{diag}(\partial \log c / \partial s_i) V^T\).
+    pub fn mean(&self) -> Matrix3<f64> {
+        self.u * Matrix3::from_diagonal(&moments(&self.concentration)) * self.v.transpose()
+    }
+
+    /// Draw `count` rotations with a generator seeded by `seed`.
+    ///
+    /// Rotations \(U Q V^T\) are sampled through the quaternion of \(Q\), which
+    /// follows a Bingham distribution, by rejection from an angular central
+    /// Gaussian envelope (Kent, Ganeiber and Mardia, 2013).
+    pub fn sample(&self, count: usize, seed: u64) -> Vec<So3> {
+        let s = &self.concentration;
+        let exponents = [
+            s[0] + s[1] + s[2],
+            s[0] - s[1] - s[2],
+            -s[0] + s[1] - s[2],
+            -s[0] - s[1] + s[2],
+        ];
+        let top = exponents.iter().copied().fold(f64::MIN, f64::max);
+        let lambda = exponents.map(|e| top - e);
+        let b = envelope_parameter(&lambda);
+        let omega = lambda.map(|l| 1.0 + 2.0 * l / b);
+        let log_bound = -0.5 * (4.0 - b) + 2.0 * (4.0 / b).ln();
+
+        let mut rng = SplitMix64(seed);
+        let mut rotations = Vec::with_capacity(count);
+        while rotations.len() < count {
+            let y: [f64; 4] = std::array::from_fn(|i| rng.gaussian() / omega[i].sqrt());
+            let norm = y.iter().map(|v| v * v).sum::<f64>().sqrt();
+            let q = y.map(|v| v / norm);
+            let bingham: f64 = (0..4).map(|i| lambda[i] * q[i] * q[i]).sum();
+            let envelope: f64 = (0..4).map(|i| omega[i] * q[i] * q[i]).sum();
+            if rng.uniform().ln() < -bingham + 2.0 * envelope.ln() - log_bound {
+                let local = So3::from_quaternion_wxyz(q);
+                let rotation = self.u * local.rotation().matrix() * self.v.transpose();
+                rotations.push(So3::from_matrix(matrix_to_array(&rotation)));
+            }
+        }
+        rotations
+    }
+}
+
+/// SVD with \(\det U = \det V = 1\), the sign of the smallest singular value
+/// absorbing any reflection.
+fn proper_svd(matrix: &Matrix3<f64>) -> (Matrix3<f64>, Vector3<f64>, Matrix3<f64>) {
+    let svd = matrix.svd(true, true);
+    let (mut u, mut v) = (
+        svd.u.unwrap_or_else(Matrix3::identity),
+        svd.v_t
+            .map_or_else(Matrix3::identity, |v_t| v_t.transpose()),
+    );
+    let mut singular = svd.singular_values;
+    for basis in [&mut u, &mut v] {
+        if basis.determinant() < 0.0 {
+            basis.column_mut(2).neg_mut();
+            singular[2] = -singular[2];
+        }
+    }
+    (u, singular, v)
+}
+
+/// \(\log c\) splits as `scale + ln(integrals[0])`; `integrals[1..]` are the
+/// matching \(\partial c / \partial s_i\), scaled by the same \(e^{-\text{scale}}\).
+fn normalizing_integrals(s: &Vector3<f64>) -> (f64, [f64; 4]) {
+    // The exponent |a| + |b| + s₃u of the integrand is linear in u, so its
+    // maximum over [-1, 1] sits at an end point.
+    let scale = f64::max((s[0] + s[1]).abs() + s[2], (s[0] - s[1]).abs() - s[2]);
+    let integrand = |u: f64| -> [f64; 4] {
+        let a = 0.5 * (s[0] - s[1]) * (1.0 - u);
+        let b = 0.5 * (s[0] + s[1]) * (1.0 + u);
+        let weight = 0.5 * (a.abs() + b.abs() + s[2] * u - scale).exp();
+        let (i0a, i1a) = (
+            scaled_bessel_i0(a.abs()),
+            scaled_bessel_i1(a.abs()) * a.signum(),
+        );
+        let (i0b, i1b) = (
+            scaled_bessel_i0(b.abs()),
+            scaled_bessel_i1(b.abs()) * b.signum(),
+        );
+        let along_a = 0.5 * (1.0 - u) * i1a * i0b;
+        let along_b = 0.5 * (1.0 + u) * i0a * i1b;
+        [
+            i0a * i0b * weight,
+            (along_a + along_b) * weight,
+            (along_b - along_a) * weight,
+            i0a * i0b * u * weight,
+        ]
+    };
+    (scale, adaptive_simpson(&integrand, -1.0, 1.0))
+}
+
+/// \(\partial \log c / \partial s_i\), the diagonal of \(E[U^T R V]\).
+fn moments(s: &Vector3<f64>) -> Vector3<f64> {
+    let (_, integrals) = normalizing_integrals(s);
+    Vector3::new(integrals[1], integrals[2], integrals[3]) / integrals[0]
+}
+
+/// Newton's method on the concave log-likelihood \(d \cdot s - \log c(s)\),
+/// started from the Gaussian approximation \(1 - d_i \approx
+/// \tfrac{1}{2}(\sigma_j^2 + \sigma_k^2)\).
+fn solve_concentration(target: &Vector3<f64>) -> Result<Vector3<f64>> {
+    let gap = target.map(|d| 1.0 - d);
+    let variance = Vector3::new(
+        gap[1] + gap[2] - gap[0],
+        gap[0] + gap[2] - gap[1],
+        gap[0] + gap[1] - gap[2],
+    )
+    .map(|v| v.max(1e-3));
+    let precision_total = variance.map(|v| 1.0 / v).sum();
+    let mut s = variance.map(|v| 0.5 * (precision_total - 2.0 / v));
+
+    let likelihood = |s: &Vector3<f64>| {
+        let (scale, integrals) = normalizing_integrals(s);
+        target.dot(s) - scale - integrals[0].ln()
+    };
+    for _ in 0..MAX_ITERATIONS {
+        let residual = target - moments(&s);
+        if residual.amax() < MOMENT_TOLERANCE {
+            return Ok(s);
+        }
+        // The Hessian of log c, the covariance of diag(Q), by central
+        // differences of the moments.
+        let h = 1e-5 * s.amax().max(1.0);
+        let mut hessian = Matrix3::zeros();
+        for j in 0..3 {
+            let step = Vector3::ith(j, h);
+            hessian.set_column(
+                j,
+                &((moments(&(s + step)) - moments(&(s - step))) / (2.0 * h)),
+            );
+        }
+        let Some(direction) = (hessian + hessian.transpose())
+            .scale(0.5)
+            .lu()
+            .solve(&residual)
+        else {
+            return Err(Error::Singular);
+        };
+        let current = likelihood(&s);
+        let mut length = 1.0;
+        while likelihood(&(s + direction * length)) < current && length > 1e-8 {
+            length *= 0.5;
+        }
+        s += direction * length;
+        trace_event!(
+            debug,
+            residual = residual.amax(),
+            step = length,
+            "matrix-Fisher concentration step"
+        );
+    }
+    Err(Error::NotConverged {
+        iterations: MAX_ITERATIONS,
+    })
+}
+
+/// Root \(b \in (0, 4]\) of \(\sum_i 1 / (b + 2\lambda_i) = 1\), which makes
+/// the angular central Gaussian envelope tight.
+fn envelope_parameter(lambda: &[f64; 4]) -> f64 {
+    let excess = |b: f64| lambda.iter().map(|l| 1.0 / (b + 2.0 * l)).sum::<f64>() - 1.0;
+    let (mut low, mut high) = (0.0, 4.0);
+    for _ in 0..100 {
+        let mid = 0.5 * (low + high);
+        if excess(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    high
+}
+
+/// Adaptive Simpson quadrature of a vector-valued integrand, refining until
+/// every component meets [`QUADRATURE_TOLERANCE`] relative to the first.
+fn adaptive_simpson(f: &impl Fn(f64) -> [f64; 4], a: f64, b: f64) -> [f64; 4] {
+    let (fa, fm, fb) = (f(a), f(0.5 * (a + b)), f(b));
+    let whole = simpson(a, b, &fa, &fm, &fb);
+    let tolerance = QUADRATURE_TOLERANCE * whole[0].abs().max(f64::MIN_POSITIVE);
+    refine(
+        f,
+        a,
+        b,
+        [fa, fm, fb],
+        whole,
+        tolerance,
+        MAX_QUADRATURE_DEPTH,
+    )
+}
+
+fn refine(
+    f: &impl Fn(f64) -> [f64; 4],
+    a: f64,
+    b: f64,
+    [fa, fm, fb]: [[f64; 4]; 3],
+    whole: [f64; 4],
+    tolerance: f64,
+    depth: usize,
+) -> [f64; 4] {
+    let m = 0.5 * (a + b);
+    let (flm, frm) = (f(0.5 * (a + m)), f(0.5 * (m + b)));
+    let left = simpson(a, m, &fa, &flm, &fm);
+    let right = simpson(m, b, &fm, &frm, &fb);
+    let error = (0..4)
+        .map(|i| (left[i] + right[i] - whole[i]).abs())
+        .fold(0.0, f64::max);
+    if depth == 0 || error <= 15.0 * tolerance {
+        return std::array::from_fn(|i| {
+            left[i] + right[i] + (left[i] + right[i] - whole[i]) / 15.0
+        });
+    }
+    let left = refine(f, a, m, [fa, flm, fm], left, 0.5 * tolerance, depth - 1);
+    let right = refine(f, m, b, [fm, frm, fb], right, 0.5 * tolerance, depth - 1);
+    std::array::from_fn(|i| left[i] + right[i])
+}
+
+fn simpson(a: f64, b: f64, fa: &[f64; 4], fm: &[f64; 4], fb: &[f64; 4]) -> [f64; 4] {
+    std::array::from_fn(|i| (b - a) / 6.0 * (fa[i] + 4.0 * fm[i] + fb[i]))
+}
+
+/// \(e^{-x} I_0(x)\) for \(x \ge 0\).
+fn scaled_bessel_i0(x: f64) -> f64 {
+    scaled_bessel(0, x)
+}
+
+/// \(e^{-x} I_1(x)\) for \(x \ge 0\).
+fn scaled_bessel_i1(x: f64) -> f64 {
+    scaled_bessel(1, x)
+}
+
+/// Exponentially scaled modified Bessel function of the first kind: the
+/// positive power series for small arguments, the asymptotic expansion
+/// \(\frac{1}{\sqrt{2\pi x}} \sum_k (-1)^k \prod_{j \le k} \frac{4\nu^2 - (2j - 1)^2}{8 j x}\)
+/// beyond.
+fn scaled_bessel(order: u32, x: f64) -> f64 {
+    let nu = order as f64;
+    if x < 30.0 {
+        let quarter = 0.25 * x * x;
+        let mut term = if order == 0 { 1.0 } else { 0.5 * x };
+        let mut sum = term;
+        let mut k = 1.0;
+        while term > sum * f64::EPSILON {
+            term *= quarter / (k * (k + nu));
+            sum += term;
+            k += 1.0;
+        }
+        sum * (-x).exp()
+    } else {
+        let mu = 4.0 * nu * nu;
+        let mut term = 1.0;
+        let mut sum = 1.0;
+        for j in 1..=12 {
+            let odd = (2 * j - 1) as f64;
+            term *= -(mu - odd * odd) / (8.0 * j as f64 * x);
+            sum += term;
+        }
+        sum / (std::f64::consts::TAU * x).sqrt()
+    }
+}
@@ -0,0 +1,502 @@
+use nalgebra::{DMatrix, Matrix3, SMatrix, Vector3};
+
+use crate::cmtm::{Matrix6, Vector6};
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::util::{skew_symmetric, vector3_from_array, vector3_to_array};
+
+/// Mass properties of a rigid body expressed in its body frame: the mass, the
+/// centre of mass \(c\) and the rotational inertia \(I_c\) about the centre of
+/// mass in body axes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpatialInertia {
+    mass: f64,
+    center_of_mass: Vector3<f64>,
+    inertia: Matrix3<f64>,
+}
+
+impl SpatialInertia {
+    /// `mass` must be positive and `inertia` (about the centre of mass)
+    /// symmetric positive definite.
+    pub fn new(mass: f64, center_of_mass: [f64; 3], inertia: [[f64; 3]; 3]) -> Self {
+        Self {
+            mass,
+            center_of_mass: vector3_from_array(center_of_mass),
+            inertia: Matrix3::from_fn(|r, c| inertia[r][c]),
+        }
+    }
+
+    /// A body with principal moments `moments` about axes aligned with the
+    /// body frame, centred on the body origin.
+    pub fn from_principal(mass: f64, moments: [f64; 3]) -> Self {
+        Self {
+            mass,
+            center_of_mass: Vector3::zeros(),
+            inertia: Matrix3::from_diagonal(&Vector3::from(moments)),
+        }
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn center_of_mass(&self) -> [f64; 3] {
+        vector3_to_array(&self.center_of_mass)
+    }
+
+    /// Rotational inertia about the centre of mass, in body axes.
+    pub fn rotational_inertia(&self) -> [[f64; 3]; 3] {
+        std::array::from_fn(|r| std::array::from_fn(|c| self.inertia[(r, c)]))
+    }
+
+    /// The 6×6 spatial inertia \(G\) about the body origin acting on twists
+    /// \([\omega, v]\):
+    /// \(G = \begin{bmatrix} I_c - m[c]_\times^2 & m[c]_\times \\ -m[c]_\times & m I \end{bmatrix}\).
+    pub fn matrix(&self) -> Matrix6 {
+        let c = skew_symmetric(&self.center_of_mass);
+        let m = self.mass;
+        let mut matrix = Matrix6::zeros();
+        matrix
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&(self.inertia - c * c * m));
+        matrix.fixed_view_mut::<3, 3>(0, 3).copy_from(&(c * m));
+        matrix.fixed_view_mut::<3, 3>(3, 0).copy_from(&(-c * m));
+        matrix
+            .fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&(Matrix3::identity() * m));
+        matrix
+    }
+
+    /// Body momentum \([L, p] = G\,[\omega, v]\) about the body origin.
+    pub fn momentum(&self, twist: [f64; 6]) -> [f64; 6] {
+        (self.matrix() * Vector6::from(twist)).into()
+    }
+
+    /// Kinetic energy \(\tfrac{1}{2} V^T G V\).
+    pub fn kinetic_energy(&self, twist: [f64; 6]) -> f64 {
+        let twist = Vector6::from(twist);
+        0.5 * twist.dot(&(self.matrix() * twist))
+    }
+
+    /// Standard inertial parameters about the body origin,
+    /// \(\pi = [m, mc_x, mc_y, mc_z, I_{xx}, I_{xy}, I_{xz}, I_{yy}, I_{yz}, I_{zz}]\),
+    /// where \(I = I_c - m[c]_\times^2\) is the rotational inertia about the
+    /// origin. The body wrench is linear in \(\pi\), see [`body_regressor`].
+    pub fn to_parameters(&self) -> [f64; 10] {
+        let m = self.mass;
+        let c = skew_symmetric(&self.center_of_mass);
+        let i = self.inertia - c * c * m;
+        let mc = self.center_of_mass * m;
+        [
+            m,
+            mc.x,
+            mc.y,
+            mc.z,
+            i[(0, 0)],
+            i[(0, 1)],
+            i[(0, 2)],
+            i[(1, 1)],
+            i[(1, 2)],
+            i[(2, 2)],
+        ]
+    }
+
+    /// Inverse of [`SpatialInertia::to_parameters`]; `parameters[0]` must be
+    /// positive.
+    pub fn from_parameters(parameters: [f64; 10]) -> Self {
+        let [m, mcx, mcy, mcz, ixx, ixy, ixz, iyy, iyz, izz] = parameters;
+        let center_of_mass = Vector3::new(mcx, mcy, mcz) / m;
+        let c = skew_symmetric(&center_of_mass);
+        let origin = Matrix3::new(ixx, ixy, ixz, ixy, iyy, iyz, ixz, iyz, izz);
+        Self {
+            mass: m,
+            center_of_mass,
+            inertia: origin + c * c * m,
+        }
+    }
+
+    /// Rigid-body Coriolis–centripetal matrix \(C_{RB}(V)\), see
+    /// [`coriolis_matrix`].
+    pub fn coriolis_matrix(&self, twist: [f64; 6]) -> Matrix6 {
+        coriolis_matrix(&self.matrix(), twist)
+    }
+}
+
+/// Hydrodynamic added mass \(M_A\) of a body moving through a fluid, acting
+/// on body twists \([\omega, v]\) like [`SpatialInertia::matrix`].
+///
+/// Fossen's models order the velocity as \([v, \omega]\); use
+/// [`AddedMass::from_fossen`] to reorder such a matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddedMass {
+    matrix: Matrix6,
+}
+
+impl AddedMass {
+    /// Added mass in twist order \([\omega, v]\). Only the symmetric part is
+    /// kept, as for an ideal fluid.
+    pub fn new(matrix: [[f64; 6]; 6]) -> Self {
+        let matrix = Matrix6::from_fn(|r, c| matrix[r][c]);
+        Self {
+            matrix: (matrix + matrix.transpose()) * 0.5,
+        }
+    }
+
+    /// Added mass given in Fossen's \([v, \omega]\) ordering (e.g. from
+    /// hydrodynamic derivatives \(-X_{\dot{u}}, \dots, -N_{\dot{r}}\)).
+    pub fn from_fossen(matrix: [[f64; 6]; 6]) -> Self {
+        let reorder = |i: usize| (i + 3) % 6;
+        Self::new(std::array::from_fn(|r| {
+            std::array::from_fn(|c| matrix[reorder(r)][reorder(c)])
+        }))
+    }
+
+    /// Diagonal added mass with rotational terms `rotational` and
+    /// translational terms `translational`.
+    pub fn from_diagonal(rotational: [f64; 3], translational: [f64; 3]) -> Self {
+        let [a, b, c] = rotational;
+        let [d, e, f] = translational;
+        Self {
+            matrix: Matrix6::from_diagonal(&Vector6::from([a, b, c, d, e, f])),
+        }
+    }
+
+    pub fn matrix(&self) -> Matrix6 {
+        self.matrix
+    }
+
+    /// Added-mass Coriolis–centripetal matrix \(C_A(V)\), see
+    /// [`coriolis_matrix`].
+    pub fn coriolis_matrix(&self, twist: [f64; 6]) -> Matrix6 {
+        coriolis_matrix(&self.matrix, twist)
+    }
+}
+
+/// Coriolis–centripetal matrix for a symmetric generalized mass \(M\) in the
+/// skew-symmetric form of Fossen. With body momentum
+/// \([h_\omega, h_v] = M V\),
+/// \(C(V) = \begin{bmatrix} -[h_\omega]_\times & -[h_v]_\times \\ -[h_v]_\times & 0 \end{bmatrix}\),
+/// so \(C(V) V = [\omega \times h_\omega + v \times h_v, \omega \times h_v]\) and
+/// \(V^T C(V) V = 0\).
+pub fn coriolis_matrix(mass: &Matrix6, twist: [f64; 6]) -> Matrix6 {
+    let momentum = mass * Vector6::from(twist);
+    let angular = skew_symmetric(&momentum.fixed_rows::<3>(0).into_owned());
+    let linear = skew_symmetric(&momentum.fixed_rows::<3>(3).into_owned());
+    let mut matrix = Matrix6::zeros();
+    matrix.fixed_view_mut::<3, 3>(0, 0).copy_from(&(-angular));
+    matrix.fixed_view_mut::<3, 3>(0, 3).copy_from(&(-linear));
+    matrix.fixed_view_mut::<3, 3>(3, 0).copy_from(&(-linear));
+    matrix
+}
+
+/// Body acceleration \(\dot{V}\) of a (possibly submerged) rigid body from
+/// \((M_{RB} + M_A)\dot{V} + (C_{RB}(V) + C_A(V))V = F\), where `wrench` is the
+/// total applied body-frame wrench \([\tau, f]\) (thrust, damping, restoring
+/// forces). Without added mass this is the Newton–Euler equation.
+///
+/// Returns [`Error::Singular`] unless the generalized mass
+/// \(M_{RB} + M_A\) is positive definite.
+pub fn body_acceleration(
+    inertia: &SpatialInertia,
+    added_mass: Option<&AddedMass>,
+    twist: [f64; 6],
+    wrench: [f64; 6],
+) -> Result<[f64; 6]> {
+    let mass = match added_mass {
+        Some(added) => inertia.matrix() + added.matrix,
+        None => inertia.matrix(),
+    };
+    let twist_vector = Vector6::from(twist);
+    let bias = coriolis_matrix(&mass, twist) * twist_vector;
+    let acceleration = mass
+        .cholesky()
+        .ok_or(Error::Singular)?
+        .solve(&(Vector6::from(wrench) - bias));
+    Ok(acceleration.into())
+}
+
+/// Pose of the body frame in the world together with its body twist
+/// \([\omega, v]\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RigidBodyState {
+    pub pose: Se3,
+    pub twist: [f64; 6],
+}
+
+impl RigidBodyState {
+    pub fn new(pose: Se3, twist: [f64; 6]) -> Self {
+        Self { pose, twist }
+    }
+
+    /// Angular and linear momentum of the body in the world frame, about the
+    /// world origin. Constant for a free body.
+    pub fn spatial_momentum(&self, inertia: &SpatialInertia) -> [f64; 6] {
+        let [lx, ly, lz, px, py, pz] = inertia.momentum(self.twist);
+        let rotation = self.pose.rotation();
+        let angular = vector3_from_array(rotation.apply([lx, ly, lz]));
+        let linear = vector3_from_array(rotation.apply([px, py, pz]));
+        let origin = vector3_from_array(self.pose.translation());
+        let angular = angular + origin.cross(&linear);
+        [
+            angular.x, angular.y, angular.z, linear.x, linear.y, linear.z,
+        ]
+    }
+}
+
+/// Inertial-parameter regressor \(Y(V, \dot{V})\) of a single rigid body, such
+/// that the body wrench needed for body twist `twist` and body acceleration
+/// `acceleration` is \(F = G\dot{V} + C(V)V = Y\pi\) with \(\pi\) from
+/// [`SpatialInertia::to_parameters`].
+///
+/// Stacking the regressors of several samples gives a linear least-squares
+/// problem for \(\pi\); see [`base_parameter_columns`] for the identifiable
+/// subset.
+pub fn body_regressor(twist: [f64; 6], acceleration: [f64; 6]) -> SMatrix<f64, 6, 10> {
+    let omega = Vector3::new(twist[0], twist[1], twist[2]);
+    let v = Vector3::new(twist[3], twist[4], twist[5]);
+    // -ad_V^T maps the body momentum to the Coriolis wrench.
+    let mut coriolis = Matrix6::zeros();
+    coriolis
+        .fixed_view_mut::<3, 3>(0, 0)
+        .copy_from(&skew_symmetric(&omega));
+    coriolis
+        .fixed_view_mut::<3, 3>(0, 3)
+        .copy_from(&skew_symmetric(&v));
+    coriolis
+        .fixed_view_mut::<3, 3>(3, 3)
+        .copy_from(&skew_symmetric(&omega));
+    momentum_regressor(&Vector6::from(acceleration))
+        + coriolis * momentum_regressor(&Vector6::from(twist))
+}
+
+/// Indices of a maximal set of linearly independent columns of a stacked
+/// regressor (rows from many samples), chosen greedily in parameter order.
+/// Only these parameters (with the others fixed or absorbed) are identifiable
+/// from the excitation in the data. A column counts as independent when its
+/// component orthogonal to the previously chosen columns exceeds `tolerance`
+/// relative to its norm.
+pub fn base_parameter_columns(stacked: &DMatrix<f64>, tolerance: f64) -> Vec<usize> {
+    let mut basis: Vec<nalgebra::DVector<f64>> = Vec::new();
+    let mut columns = Vec::new();
+    for (index, column) in stacked.column_iter().enumerate() {
+        let norm = column.norm();
+        if norm == 0.0 {
+            continue;
+        }
+        let mut residual = column.clone_owned();
+        for direction in &basis {
+            residual -= direction * direction.dot(&residual);
+        }
+        let remaining = residual.norm();
+        if remaining > tolerance * norm {
+            basis.push(residual / remaining);
+            columns.push(index);
+        }
+    }
+    columns
+}
+
+/// \(\Phi(X)\) with \(G X = \Phi(X)\pi\) for a twist-ordered vector \(X\).
+fn momentum_regressor(x: &Vector6) -> SMatrix<f64, 6, 10> {
+    let (wx, wy, wz) = (x[0], x[1], x[2]);
+    let angular = Vector3::new(wx, wy, wz);
+    let linear = Vector3::new(x[3], x[4], x[5]);
+    let mut phi = SMatrix::<f64, 6, 10>::zeros();
+    phi.fixed_view_mut::<3, 3>(0, 1)
+        .copy_from(&(-skew_symmetric(&linear)));
+    phi.fixed_view_mut::<3, 6>(0, 4)
+        .copy_from(&SMatrix::<f64, 3, 6>::new(
+            wx, wy, wz, 0.0, 0.0, 0.0, //
+            0.0, wx, 0.0, wy, wz, 0.0, //
+            0.0, 0.0, wx, 0.0, wy, wz,
+        ));
+    phi.fixed_view_mut::<3, 1>(3, 0).copy_from(&linear);
+    phi.fixed_view_mut::<3, 3>(3, 1)
+        .copy_from(&skew_symmetric(&angular));
+    phi
+}
+
+/// Generalized-momentum observer estimating the external body wrench acting
+/// on a rigid body (e.g. contacts or collisions on a drone or AUV hull).
+///
+/// With \(p = M V\) and the body dynamics \(\dot{p} = F_{cmd} + F_{ext} - C(V)V\),
+/// the residual
+/// \(r = K\bigl(p - p_0 - \int (F_{cmd} - C(V)V + r)\,dt\bigr)\)
+/// follows \(\dot{r} = K(F_{ext} - r)\), a per-axis first-order low-pass of
+/// the external wrench that needs no acceleration measurement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MomentumObserver {
+    mass: Matrix6,
+    gain: Vector6,
+    initial_momentum: Option<Vector6>,
+    integral: Vector6,
+    residual: Vector6,
+}
+
+impl MomentumObserver {
+    /// Create an observer for a body with the given inertia (and optional
+    /// added mass) and per-axis observer gains `gain` (1/s) in twist order.
+    pub fn new(inertia: &SpatialInertia, added_mass: Option<&AddedMass>, gain: [f64; 6]) -> Self {
+        let mass = match added_mass {
+            Some(added) => inertia.matrix() + added.matrix,
+            None => inertia.matrix(),
+        };
+        Self {
+            mass,
+            gain: Vector6::from(gain),
+            initial_momentum: None,
+            integral: Vector6::zeros(),
+            residual: Vector6::zeros(),
+        }
+    }
+
+    /// Feed the measured body twist and the commanded body wrench applied
+    /// over the last `dt` seconds, and return the updated estimate of the
+    /// external wrench \([\tau, f]\). The first call only latches the initial
+    /// momentum.
+    pub fn update(&mut self, twist: [f64; 6], commanded: [f64; 6], dt: f64) -> [f64; 6] {
+        let momentum = self.mass * Vector6::from(twist);
+        let Some(initial) = self.initial_momentum else {
+            self.initial_momentum = Some(momentum);
+            return self.residual.into();
+        };
+        let bias = coriolis_matrix(&self.mass, twist) * Vector6::from(twist);
+        self.integral += (Vector6::from(commanded) - bias + self.residual) * dt;
+        self.residual = self
+            .gain
+            .component_mul(&(momentum - initial - self.integral));
+        self.residual.into()
+    }
+
+    /// Latest external wrench estimate.
+    pub fn estimate(&self) -> [f64; 6] {
+        self.residual.into()
+    }
+
+    /// Forget the history; the next update latches a new initial momentum.
+    pub fn reset(&mut self) {
+        self.initial_momentum = None;
+        self.integral = Vector6::zeros();
+        self.residual = Vector6::zeros();
+    }
+}
+
+/// Relative tolerance for the implicit rotation update of
+/// [`variational_step`].
+const NEWTON_TOLERANCE: f64 = 1e-15;
+const NEWTON_MAX_ITERATIONS: usize = 50;
+
+/// Advance a rigid body by `h` seconds with a Lie group variational
+/// integrator on \(\mathrm{SO}(3) \times \mathbb{R}^3\), stepping the
+/// attitude and the centre of mass.
+///
+/// `wrench` returns the applied body-frame wrench \([\tau, f]\) at the body
+/// origin for a given time and pose; it may depend on the configuration
+/// (gravity, springs) but not on the velocity. The discrete flow is
+/// symplectic and preserves the spatial momentum up to round-off when no
+/// wrench is applied, so energy errors stay bounded over long horizons
+/// instead of drifting.
+///
+/// Returns [`Error::Singular`] if the mass is zero or the rotational inertia
+/// is not invertible.
+pub fn variational_step(
+    inertia: &SpatialInertia,
+    state: &RigidBodyState,
+    t: f64,
+    h: f64,
+    mut wrench: impl FnMut(f64, &Se3) -> [f64; 6],
+) -> Result<RigidBodyState> {
+    let c = inertia.center_of_mass;
+    let m = inertia.mass;
+    if m == 0.0 {
+        return Err(Error::Singular);
+    }
+    let inverse_inertia = inertia.inertia.try_inverse().ok_or(Error::Singular)?;
+    let rotation = state.pose.rotation().rotation().into_inner();
+    let omega = Vector3::new(state.twist[0], state.twist[1], state.twist[2]);
+    let v = Vector3::new(state.twist[3], state.twist[4], state.twist[5]);
+
+    // Centre-of-mass coordinates decouple rotation from translation.
+    let x = vector3_from_array(state.pose.translation()) + rotation * c;
+    let momentum = rotation * (v + omega.cross(&c)) * m;
+    let pi = inertia.inertia * omega;
+    let (torque, force) = com_wrench(inertia, &rotation, &wrench(t, &state.pose));
+
+    let f = solve_cayley(
+        &inertia.inertia,
+        &inverse_inertia,
+        &((pi + torque * (0.5 * h)) * h),
+    );
+    // So3::cayley halves its argument, so F is its value at 2f.
+    let step = So3::cayley(vector3_to_array(&(f * 2.0)))
+        .rotation()
+        .into_inner();
+    let next_rotation = rotation * step;
+    let next_x = x + momentum * (h / m) + force * (h * h / (2.0 * m));
+    let next_pose = Se3::from_parts(
+        So3::from_matrix(std::array::from_fn(|r| {
+            std::array::from_fn(|col| next_rotation[(r, col)])
+        })),
+        vector3_to_array(&(next_x - next_rotation * c)),
+    );
+
+    let (next_torque, next_force) = com_wrench(inertia, &next_rotation, &wrench(t + h, &next_pose));
+    let next_pi = step.transpose() * (pi + torque * (0.5 * h)) + next_torque * (0.5 * h);
+    let next_momentum = momentum + (force + next_force) * (0.5 * h);
+
+    let next_omega = inverse_inertia * next_pi;
+    let next_v = next_rotation.transpose() * next_momentum / m - next_omega.cross(&c);
+    Ok(RigidBodyState::new(
+        next_pose,
+        [
+            next_omega.x,
+            next_omega.y,
+            next_omega.z,
+            next_v.x,
+            next_v.y,
+            next_v.z,
+        ],
+    ))
+}
+
+/// Split a body-origin wrench into the torque about the centre of mass (body
+/// axes) and the force in world axes.
+fn com_wrench(
+    inertia: &SpatialInertia,
+    rotation: &Matrix3<f64>,
+    wrench: &[f64; 6],
+) -> (Vector3<f64>, Vector3<f64>) {
+    let torque = Vector3::new(wrench[0], wrench[1], wrench[2]);
+    let force = Vector3::new(wrench[3], wrench[4], wrench[5]);
+    (
+        torque - inertia.center_of_mass.cross(&force),
+        rotation * force,
+    )
+}
+
+/// Solve the discrete Euler–Poincaré equation
+/// \(h\Pi = F J_d - J_d F^T\) with \(J_d = \tfrac{1}{2}\operatorname{tr}(J) I - J\)
+/// for the Cayley parameter \(f\) of \(F\). In these coordinates it reads
+/// \(h\Pi (1 + f^T f) = 2(Jf + f \times Jf)\).
+fn solve_cayley(
+    inertia: &Matrix3<f64>,
+    inverse_inertia: &Matrix3<f64>,
+    impulse: &Vector3<f64>,
+) -> Vector3<f64> {
+    let mut f = inverse_inertia * impulse * 0.5;
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let jf = inertia * f;
+        let residual = impulse * (1.0 + f.norm_squared()) - (jf + f.cross(&jf)) * 2.0;
+        if residual.norm() <= NEWTON_TOLERANCE * impulse.norm().max(1.0) {
+            break;
+        }
+        let jacobian = impulse * f.transpose() * 2.0 - inertia * 2.0 + skew_symmetric(&jf) * 2.0
+            - skew_symmetric(&f) * inertia * 2.0;
+        match jacobian.lu().solve(&residual) {
+            Some(delta) => f -= delta,
+            None => break,
+        }
+    }
+    f
+}
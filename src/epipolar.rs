@@ -0,0 +1,145 @@
+use nalgebra::{Matrix3, Matrix4, RowVector4, Vector3};
+
+use crate::camera::CameraIntrinsics;
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::util::skew_symmetric;
+
+/// Essential matrix \(E = [t]_\times R\) of the relative pose
+/// `second_from_first` \(= (R, t)\), so that \(x_2^T E x_1 = 0\) for
+/// normalized homogeneous image points \(x_1, x_2\) of the same scene point.
+pub fn essential_from_pose(second_from_first: &Se3) -> Matrix3<f64> {
+    let translation = Vector3::from(second_from_first.translation());
+    skew_symmetric(&translation) * second_from_first.rotation().rotation().matrix()
+}
+
+/// Essential matrix between two cameras given their poses \(T_{w,c_1}\) and
+/// \(T_{w,c_2}\) in a common world frame, via the relative pose
+/// \(T_{c_2,c_1} = T_{w,c_2}^{-1} T_{w,c_1}\).
+pub fn essential_from_poses(world_from_first: &Se3, world_from_second: &Se3) -> Matrix3<f64> {
+    essential_from_pose(&world_from_second.inverse().compose(world_from_first))
+}
+
+/// Fundamental matrix \(F = K_2^{-T} E K_1^{-1}\) between pixels of two
+/// cameras, so that \(p_2^T F p_1 = 0\). Returns [`Error::Singular`] if
+/// either calibration matrix is not invertible.
+pub fn fundamental_from_poses(
+    world_from_first: &Se3,
+    world_from_second: &Se3,
+    first: &CameraIntrinsics,
+    second: &CameraIntrinsics,
+) -> Result<Matrix3<f64>> {
+    fundamental_from_essential(
+        &essential_from_poses(world_from_first, world_from_second),
+        first,
+        second,
+    )
+}
+
+/// Fundamental matrix \(F = K_2^{-T} E K_1^{-1}\) of an essential matrix.
+/// Returns [`Error::Singular`] if either calibration matrix is not
+/// invertible.
+pub fn fundamental_from_essential(
+    essential: &Matrix3<f64>,
+    first: &CameraIntrinsics,
+    second: &CameraIntrinsics,
+) -> Result<Matrix3<f64>> {
+    let first_inverse = first.matrix().try_inverse().ok_or(Error::Singular)?;
+    let second_inverse = second.matrix().try_inverse().ok_or(Error::Singular)?;
+    Ok(second_inverse.transpose() * essential * first_inverse)
+}
+
+/// The four relative poses \((R, t)\) compatible with an essential matrix,
+/// with unit translation: \(R \in \{U W V^T, U W^T V^T\}\) and
+/// \(t = \pm u_3\). Only one places triangulated points in front of both
+/// cameras; see [`relative_pose_from_essential`].
+pub fn decompose_essential(essential: &Matrix3<f64>) -> [Se3; 4] {
+    let svd = essential.svd(true, true);
+    let mut u = svd.u.unwrap_or_else(Matrix3::identity);
+    let mut v_t = svd.v_t.unwrap_or_else(Matrix3::identity);
+    // Order the null direction last, as SVD does not sort singular values.
+    let smallest = svd.singular_values.imin();
+    if smallest != 2 {
+        u.swap_columns(smallest, 2);
+        v_t.swap_rows(smallest, 2);
+    }
+    if u.determinant() < 0.0 {
+        u = -u;
+    }
+    if v_t.determinant() < 0.0 {
+        v_t = -v_t;
+    }
+    let w = Matrix3::new(0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+    let rotation = |m: Matrix3<f64>| {
+        So3::from_matrix(std::array::from_fn(|r| std::array::from_fn(|c| m[(r, c)])))
+    };
+    let first = rotation(u * w * v_t);
+    let second = rotation(u * w.transpose() * v_t);
+    let t: [f64; 3] = u.column(2).into_owned().into();
+    let negated = t.map(|x| -x);
+    [
+        Se3::from_parts(first.clone(), t),
+        Se3::from_parts(first, negated),
+        Se3::from_parts(second.clone(), t),
+        Se3::from_parts(second, negated),
+    ]
+}
+
+/// Linear (DLT) triangulation of normalized image points `first` and
+/// `second` seen from cameras related by `second_from_first`. The point is
+/// returned in the first camera frame, or `None` if it lies at infinity
+/// (e.g. parallel rays).
+pub fn triangulate(second_from_first: &Se3, first: [f64; 2], second: [f64; 2]) -> Option<[f64; 3]> {
+    let r = second_from_first.rotation().rotation().matrix();
+    let t = second_from_first.translation();
+    // Rows of the projections [I | 0] and [R | t].
+    let row = |k: usize| RowVector4::new(r[(k, 0)], r[(k, 1)], r[(k, 2)], t[k]);
+    let system = Matrix4::from_rows(&[
+        RowVector4::new(-1.0, 0.0, first[0], 0.0),
+        RowVector4::new(0.0, -1.0, first[1], 0.0),
+        row(2) * second[0] - row(0),
+        row(2) * second[1] - row(1),
+    ]);
+    let svd = system.svd(false, true);
+    let v_t = svd.v_t?;
+    let homogeneous = v_t.row(svd.singular_values.imin());
+    let w = homogeneous[3];
+    let scale = homogeneous.norm();
+    (w.abs() > 1e-12 * scale).then(|| [homogeneous[0] / w, homogeneous[1] / w, homogeneous[2] / w])
+}
+
+/// Select from [`decompose_essential`] the pose that places the most
+/// triangulated correspondences in front of both cameras (cheirality).
+/// Points are normalized image coordinates; the translation has unit length.
+///
+/// Returns [`Error::InsufficientData`] without correspondences and
+/// [`Error::Singular`] if no candidate puts any point in front of both
+/// cameras.
+pub fn relative_pose_from_essential(
+    essential: &Matrix3<f64>,
+    first: &[[f64; 2]],
+    second: &[[f64; 2]],
+) -> Result<Se3> {
+    if first.len() != second.len() || first.is_empty() {
+        return Err(Error::InsufficientData {
+            required: 1,
+            provided: first.len().min(second.len()),
+        });
+    }
+    let mut best: Option<(usize, Se3)> = None;
+    for candidate in decompose_essential(essential) {
+        let in_front = first
+            .iter()
+            .zip(second)
+            .filter(|&(&a, &b)| {
+                triangulate(&candidate, a, b)
+                    .is_some_and(|point| point[2] > 0.0 && candidate.apply(point)[2] > 0.0)
+            })
+            .count();
+        if in_front > best.as_ref().map_or(0, |(count, _)| *count) {
+            best = Some((in_front, candidate));
+        }
+    }
+    best.map(|(_, pose)| pose).ok_or(Error::Singular)
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// Errors reported by the fallible entry points of the crate.
+///
+/// Every variant carries a stable numeric code (see [`Error::code`]) so that
+/// foreign-language bindings can map errors without parsing messages. Codes
+/// are never reused or renumbered; new variants receive new codes.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A derivative output order of zero was requested.
+    ZeroOrder,
+    /// The requested derivative output order exceeds the stored derivatives.
+    OrderOutOfRange { requested: usize, available: usize },
+    /// A vector that must define a direction (e.g. a quaternion) has zero norm.
+    ZeroNorm,
+    /// An input contained NaN or infinite values.
+    NonFinite,
+    /// A frame name was registered more than once.
+    DuplicateFrame(String),
+    /// A frame name was referenced but never registered.
+    UnknownFrame(String),
+    /// A frame cannot be connected to the root frame (e.g. its parents form a cycle).
+    DisconnectedFrame(String),
+    /// A rotation axis sequence string could not be parsed.
+    InvalidSequence(String),
+    /// Trajectory samples are missing or their timestamps do not increase.
+    InvalidTrajectory(String),
+    /// An iterative solver stopped after the given number of iterations
+    /// without meeting its tolerance.
+    NotConverged { iterations: usize },
+    /// A target lies outside the workspace of a mechanism.
+    Unreachable,
+    /// A configuration is singular for the requested representation (e.g.
+    /// gimbal lock of Euler angles).
+    Singular,
+    /// A robot description file is malformed or uses unsupported features.
+    InvalidDescription(String),
+    /// A transform disagrees with the one implied by an existing path between
+    /// the same frames by more than the tolerance. `translation` is the
+    /// distance and `rotation` the angle (radians) of the discrepancy.
+    InconsistentFrames {
+        parent: String,
+        child: String,
+        translation: f64,
+        rotation: f64,
+    },
+    /// An estimator received fewer samples than it needs.
+    InsufficientData { required: usize, provided: usize },
+    /// Binary data is truncated, malformed or of an unsupported version.
+    InvalidEncoding(String),
+    /// A numeric setting (limit, step size, gain) is out of its valid range.
+    InvalidParameter(String),
+    /// A slice or vector argument has the wrong number of entries.
+    DimensionMismatch { expected: usize, provided: usize },
+}
+
+impl Error {
+    /// Stable numeric code identifying the error kind across FFI boundaries.
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::ZeroOrder => 1,
+            Error::OrderOutOfRange { .. } => 2,
+            Error::ZeroNorm => 3,
+            Error::NonFinite => 4,
+            Error::DuplicateFrame(_) => 5,
+            Error::UnknownFrame(_) => 6,
+            Error::DisconnectedFrame(_) => 7,
+            Error::InvalidSequence(_) => 8,
+            Error::InvalidTrajectory(_) => 9,
+            Error::NotConverged { .. } => 10,
+            Error::Unreachable => 11,
+            Error::Singular => 12,
+            Error::InvalidDescription(_) => 13,
+            Error::InconsistentFrames { .. } => 14,
+            Error::InsufficientData { .. } => 15,
+            Error::InvalidEncoding(_) => 16,
+            Error::InvalidParameter(_) => 17,
+            Error::DimensionMismatch { .. } => 18,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroOrder => write!(f, "Output order must be positive"),
+            Error::OrderOutOfRange {
+                requested,
+                available,
+            } => write!(
+                f,
+                "Output order {requested} exceeds available derivatives (order {available})"
+            ),
+            Error::ZeroNorm => write!(f, "Input has zero norm and does not define a direction"),
+            Error::NonFinite => write!(f, "Input contains NaN or infinite values"),
+            Error::DuplicateFrame(name) => write!(f, "Frame '{name}' is defined more than once"),
+            Error::UnknownFrame(name) => write!(f, "Frame '{name}' is not defined"),
+            Error::DisconnectedFrame(name) => {
+                write!(f, "Frame '{name}' is not connected to the root frame")
+            }
+            Error::InvalidSequence(reason) => write!(f, "Invalid rotation sequence: {reason}"),
+            Error::InvalidTrajectory(reason) => write!(f, "Invalid trajectory: {reason}"),
+            Error::NotConverged { iterations } => {
+                write!(f, "Solver did not converge within {iterations} iterations")
+            }
+            Error::Unreachable => write!(f, "Target is outside the reachable workspace"),
+            Error::Singular => write!(f, "Configuration is singular for this representation"),
+            Error::InvalidDescription(reason) => write!(f, "Invalid robot description: {reason}"),
+            Error::InconsistentFrames {
+                parent,
+                child,
+                translation,
+                rotation,
+            } => write!(
+                f,
+                "Transform from '{child}' to '{parent}' disagrees with an existing path by \
+                 {translation} m and {rotation} rad"
+            ),
+            Error::InsufficientData { required, provided } => {
+                write!(f, "Estimator needs {required} samples but got {provided}")
+            }
+            Error::InvalidEncoding(reason) => write!(f, "Invalid binary encoding: {reason}"),
+            Error::InvalidParameter(reason) => write!(f, "Invalid parameter: {reason}"),
+            Error::DimensionMismatch { expected, provided } => {
+                write!(f, "Expected {expected} entries but got {provided}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Convenience alias used by the fallible APIs.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,481 @@
+use nalgebra::{Matrix3, Matrix6, SMatrix, SVector, Vector6};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::lie::HasExp;
+use crate::motion::{MotionCovariance, MotionState, constant_acceleration_noise};
+use crate::se3::Se3;
+use crate::so3::So3;
+
+/// Process model of a [`RigidBodyTracker`], with the spectral density of the
+/// white noise driving the highest tracked derivative.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MotionModel {
+    /// The twist is a random walk under white acceleration noise; the
+    /// acceleration of the state is held at zero.
+    ConstantTwist { acceleration_density: [f64; 6] },
+    /// The acceleration is a random walk under white jerk noise.
+    ConstantAcceleration { jerk_density: [f64; 6] },
+}
+
+impl MotionModel {
+    /// Process noise accumulated over `dt`.
+    pub fn process_noise(&self, dt: f64) -> MotionCovariance {
+        match *self {
+            MotionModel::ConstantTwist {
+                acceleration_density,
+            } => {
+                // The constant-acceleration blocks one derivative lower.
+                let mut noise = MotionCovariance::zeros();
+                let blocks = [[dt.powi(3) / 3.0, dt * dt / 2.0], [dt * dt / 2.0, dt]];
+                for (axis, density) in acceleration_density.into_iter().enumerate() {
+                    for (r, row) in blocks.iter().enumerate() {
+                        for (c, value) in row.iter().enumerate() {
+                            noise[(6 * r + axis, 6 * c + axis)] = density * value;
+                        }
+                    }
+                }
+                noise
+            }
+            MotionModel::ConstantAcceleration { jerk_density } => {
+                constant_acceleration_noise(dt, jerk_density)
+            }
+        }
+    }
+}
+
+/// Kalman filter tracking the pose, twist and acceleration of a rigid object
+/// from pose measurements.
+///
+/// The state is a [`MotionState`] with a [`MotionCovariance`] over the error
+/// \([\delta\xi, \delta V, \delta\dot{V}]\), the pose error being a right
+/// perturbation \(T \exp(\delta\xi)\). Prediction uses
+/// [`MotionState::propagate_with_covariance`]; a pose measurement \(Z\) is
+/// compared through the innovation \(\log(T^{-1} Z)\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RigidBodyTracker {
+    state: MotionState,
+    covariance: MotionCovariance,
+    model: MotionModel,
+}
+
+impl RigidBodyTracker {
+    /// Start tracking from `state` with error covariance `covariance`. Under
+    /// [`MotionModel::ConstantTwist`] the acceleration and its covariance
+    /// entries are zeroed.
+    pub fn new(state: MotionState, covariance: MotionCovariance, model: MotionModel) -> Self {
+        let mut tracker = Self {
+            state,
+            covariance,
+            model,
+        };
+        if let MotionModel::ConstantTwist { .. } = model {
+            tracker.state.acceleration = [0.0; 6];
+            tracker.covariance.rows_mut(12, 6).fill(0.0);
+            tracker.covariance.columns_mut(12, 6).fill(0.0);
+        }
+        tracker
+    }
+
+    pub fn state(&self) -> &MotionState {
+        &self.state
+    }
+
+    pub fn covariance(&self) -> &MotionCovariance {
+        &self.covariance
+    }
+
+    pub fn model(&self) -> &MotionModel {
+        &self.model
+    }
+
+    /// Advance the estimate by `dt` seconds.
+    pub fn predict(&mut self, dt: f64) {
+        let (state, covariance) = self.state.propagate_with_covariance(
+            dt,
+            &self.covariance,
+            &self.model.process_noise(dt),
+        );
+        self.state = state;
+        self.covariance = covariance;
+    }
+
+    /// Gate a pose measurement against the predicted pose: the innovation
+    /// \(\log(T^{-1} Z)\) is tested with covariance \(P_{\xi\xi} + R\).
+    pub fn gate(
+        &self,
+        measurement: &Se3,
+        measurement_covariance: &Matrix6<f64>,
+        probability: f64,
+    ) -> Result<Gate> {
+        let covariance =
+            self.covariance.fixed_view::<6, 6>(0, 0).into_owned() + measurement_covariance;
+        gate_pose(&self.state.pose, &covariance, measurement, probability)
+    }
+
+    /// Fuse a pose measurement whose error \(\log(T^{-1} Z)\) has covariance
+    /// `measurement_covariance`, and return the innovation.
+    ///
+    /// The covariance is updated in Joseph form. Returns
+    /// [`Error::NonFinite`] for a non-finite measurement and
+    /// [`Error::Singular`] if the innovation covariance cannot be inverted.
+    pub fn update(
+        &mut self,
+        measurement: &Se3,
+        measurement_covariance: &Matrix6<f64>,
+    ) -> Result<[f64; 6]> {
+        let innovation = Vector6::from(self.state.pose.inverse().compose(measurement).log());
+        if !innovation.iter().all(|v| v.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+
+        // H = [I 0 0] selects the pose error.
+        let cross: SMatrix<f64, 18, 6> = self.covariance.fixed_columns::<6>(0).into_owned();
+        let innovation_covariance = cross.fixed_rows::<6>(0) + measurement_covariance;
+        let inverse = innovation_covariance.try_inverse().ok_or(Error::Singular)?;
+        let gain = cross * inverse;
+
+        let correction = gain * innovation;
+        let pose_step = Se3::exp_map(&correction.fixed_rows::<6>(0).into_owned());
+        self.state.pose = self.state.pose.compose(&pose_step);
+        for i in 0..6 {
+            self.state.twist[i] += correction[6 + i];
+            self.state.acceleration[i] += correction[12 + i];
+        }
+
+        let mut reduction = MotionCovariance::identity();
+        let mut pose_columns = reduction.fixed_columns_mut::<6>(0);
+        pose_columns -= gain;
+        self.covariance = reduction * self.covariance * reduction.transpose()
+            + gain * measurement_covariance * gain.transpose();
+        Ok(innovation.into())
+    }
+}
+
+/// One weighted hypothesis of a [`PoseMixture`]: a pose with the covariance
+/// of its right-perturbation error \(T \exp(\delta\xi)\).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoseHypothesis {
+    pub weight: f64,
+    pub pose: Se3,
+    pub covariance: Matrix6<f64>,
+}
+
+impl PoseHypothesis {
+    pub fn new(weight: f64, pose: Se3, covariance: Matrix6<f64>) -> Self {
+        Self {
+            weight,
+            pose,
+            covariance,
+        }
+    }
+}
+
+/// Weighted set of Gaussian pose hypotheses, e.g. the candidate poses of a
+/// symmetric object.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PoseMixture {
+    hypotheses: Vec<PoseHypothesis>,
+}
+
+impl PoseMixture {
+    pub fn new(hypotheses: Vec<PoseHypothesis>) -> Self {
+        Self { hypotheses }
+    }
+
+    pub fn push(&mut self, hypothesis: PoseHypothesis) {
+        self.hypotheses.push(hypothesis);
+    }
+
+    pub fn hypotheses(&self) -> &[PoseHypothesis] {
+        &self.hypotheses
+    }
+
+    pub fn len(&self) -> usize {
+        self.hypotheses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hypotheses.is_empty()
+    }
+
+    /// Scale the weights to sum to one.
+    ///
+    /// Returns [`Error::InsufficientData`] for an empty mixture,
+    /// [`Error::NonFinite`] if a weight is not finite and [`Error::ZeroNorm`]
+    /// if the weights sum to zero or less.
+    pub fn normalize(&mut self) -> Result<()> {
+        if self.hypotheses.is_empty() {
+            return Err(Error::InsufficientData {
+                required: 1,
+                provided: 0,
+            });
+        }
+        if !self.hypotheses.iter().all(|h| h.weight.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+        let total: f64 = self.hypotheses.iter().map(|h| h.weight).sum();
+        if total <= 0.0 {
+            return Err(Error::ZeroNorm);
+        }
+        for hypothesis in &mut self.hypotheses {
+            hypothesis.weight /= total;
+        }
+        Ok(())
+    }
+
+    /// Drop hypotheses weighing less than `min_weight` and keep at most the
+    /// `max_count` heaviest, ordered by decreasing weight. Weights are not
+    /// renormalized; call [`PoseMixture::normalize`] afterwards if needed.
+    pub fn prune(&mut self, min_weight: f64, max_count: usize) {
+        self.hypotheses.retain(|h| h.weight >= min_weight);
+        self.sort_by_weight();
+        self.hypotheses.truncate(max_count);
+    }
+
+    /// Greedily merge hypotheses closer than `threshold` in squared
+    /// Mahalanobis distance to the heaviest remaining one, measured in its
+    /// tangent space.
+    ///
+    /// Each group is replaced by its moment-matched Gaussian: with
+    /// \(\delta_i = \log(T_0^{-1} T_i)\) about the heaviest pose \(T_0\), the
+    /// merged pose is \(T_0 \exp(\bar{\delta})\) and the covariance
+    /// \(\sum_i w_i (P_i + (\delta_i - \bar{\delta})(\delta_i - \bar{\delta})^T) / \sum_i w_i\),
+    /// to first order in the spread of the group.
+    pub fn merge(&mut self, threshold: f64) {
+        self.sort_by_weight();
+        let mut remaining = std::mem::take(&mut self.hypotheses);
+        while !remaining.is_empty() {
+            let lead = remaining.remove(0);
+            let inverse = lead.pose.inverse();
+            let offset = |h: &PoseHypothesis| Vector6::from(inverse.compose(&h.pose).log());
+            let cholesky = lead.covariance.cholesky();
+            let mut group = vec![(lead.weight, Vector6::zeros(), lead.covariance)];
+            remaining.retain(|h| {
+                let delta = offset(h);
+                let close = cholesky
+                    .as_ref()
+                    .is_some_and(|c| delta.dot(&c.solve(&delta)) < threshold);
+                if close {
+                    group.push((h.weight, delta, h.covariance));
+                }
+                !close
+            });
+            self.hypotheses.push(moment_match(&lead.pose, &group));
+        }
+    }
+
+    /// The hypothesis with the largest weight.
+    pub fn most_likely(&self) -> Option<&PoseHypothesis> {
+        self.hypotheses
+            .iter()
+            .max_by(|a, b| a.weight.total_cmp(&b.weight))
+    }
+
+    fn sort_by_weight(&mut self) {
+        self.hypotheses
+            .sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    }
+}
+
+/// Gaussian matching the weight, mean and covariance of `group`, whose
+/// entries are `(weight, offset, covariance)` in the tangent space at `base`.
+/// A group without positive total weight is averaged with equal weights.
+fn moment_match(base: &Se3, group: &[(f64, Vector6<f64>, Matrix6<f64>)]) -> PoseHypothesis {
+    let weight: f64 = group.iter().map(|(w, _, _)| w).sum();
+    let share = |w: f64| {
+        if weight > 0.0 {
+            w / weight
+        } else {
+            1.0 / group.len() as f64
+        }
+    };
+    let mean = group.iter().fold(Vector6::zeros(), |sum, (w, delta, _)| {
+        sum + delta * share(*w)
+    });
+    let covariance = group
+        .iter()
+        .fold(Matrix6::zeros(), |sum, (w, delta, covariance)| {
+            let spread = delta - mean;
+            sum + (covariance + spread * spread.transpose()) * share(*w)
+        });
+    PoseHypothesis::new(weight, base.compose(&Se3::exp_map(&mean)), covariance)
+}
+
+/// Outcome of a chi-square gate on a tangent-space residual.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gate {
+    /// Squared Mahalanobis distance \(r^T \Sigma^{-1} r\).
+    pub distance_squared: f64,
+    /// Chi-square quantile the distance is compared against.
+    pub threshold: f64,
+}
+
+impl Gate {
+    /// Whether the residual lies inside the gate.
+    pub fn accepts(&self) -> bool {
+        self.distance_squared <= self.threshold
+    }
+}
+
+/// Squared Mahalanobis distance \(r^T \Sigma^{-1} r\), through a Cholesky
+/// factorization of `covariance`.
+///
+/// Returns [`Error::Singular`] unless `covariance` is positive definite.
+pub fn mahalanobis_squared<const N: usize>(
+    residual: &SVector<f64, N>,
+    covariance: &SMatrix<f64, N, N>,
+) -> Result<f64> {
+    let cholesky = covariance.cholesky().ok_or(Error::Singular)?;
+    Ok(residual.dot(&cholesky.solve(residual)))
+}
+
+/// Gate `residual` at the `probability` quantile of the chi-square
+/// distribution with `N` degrees of freedom, e.g. `0.99` to keep 99 % of
+/// consistent measurements.
+///
+/// # Panics
+/// Panics unless `probability` lies in \((0, 1)\).
+pub fn gate<const N: usize>(
+    residual: &SVector<f64, N>,
+    covariance: &SMatrix<f64, N, N>,
+    probability: f64,
+) -> Result<Gate> {
+    Ok(Gate {
+        distance_squared: mahalanobis_squared(residual, covariance)?,
+        threshold: chi_square_threshold(N, probability),
+    })
+}
+
+/// Gate a measured pose \(Z\) against a prediction \(T\) whose
+/// right-perturbation error has covariance `covariance`, on the 6-DOF
+/// residual \(\log(T^{-1} Z)\) used by [`RigidBodyTracker::update`].
+pub fn gate_pose(
+    predicted: &Se3,
+    covariance: &Matrix6<f64>,
+    measured: &Se3,
+    probability: f64,
+) -> Result<Gate> {
+    let residual = Vector6::from(predicted.inverse().compose(measured).log());
+    gate(&residual, covariance, probability)
+}
+
+/// Gate a measured rotation against a prediction on the 3-DOF residual
+/// \(\log(R^{-1} R')\).
+pub fn gate_rotation(
+    predicted: &So3,
+    covariance: &Matrix3<f64>,
+    measured: &So3,
+    probability: f64,
+) -> Result<Gate> {
+    let residual = predicted.inverse().compose(measured).to_rotation_vector();
+    gate(&residual.into(), covariance, probability)
+}
+
+/// Quantile of the chi-square distribution with `dof` degrees of freedom:
+/// the \(x\) with \(P(\tfrac{\text{dof}}{2}, \tfrac{x}{2}) = \) `probability`,
+/// \(P\) being the regularized lower incomplete gamma function. For
+/// example, the 95 % gates of 3 and 6 DOF residuals are about 7.815 and
+/// 12.592.
+///
+/// # Panics
+/// Panics if `dof` is zero or `probability` is outside \((0, 1)\).
+pub fn chi_square_threshold(dof: usize, probability: f64) -> f64 {
+    assert!(dof > 0, "chi-square needs at least one degree of freedom");
+    assert!(
+        probability > 0.0 && probability < 1.0,
+        "gate probability must lie in (0, 1)"
+    );
+    let a = 0.5 * dof as f64;
+    let cdf = |x: f64| lower_regularized_gamma(a, 0.5 * x);
+    let mut high = dof as f64;
+    while cdf(high) < probability {
+        high *= 2.0;
+    }
+    let mut low = 0.0;
+    while high - low > 1e-13 * high {
+        let mid = 0.5 * (low + high);
+        if cdf(mid) < probability {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Regularized lower incomplete gamma function \(P(a, x)\), by its power
+/// series below \(x = a + 1\) and the continued fraction of \(1 - P\)
+/// above (Numerical Recipes, section 6.2).
+fn lower_regularized_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let prefactor = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        while term.abs() > sum.abs() * f64::EPSILON {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+        }
+        sum * prefactor
+    } else {
+        // Modified Lentz evaluation of the continued fraction.
+        let tiny = f64::MIN_POSITIVE / f64::EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.0).abs() < f64::EPSILON {
+                break;
+            }
+        }
+        1.0 - prefactor * fraction
+    }
+}
+
+/// \(\ln \Gamma(x)\) for \(x > 0\) by the Lanczos approximation (g = 7).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection keeps the series in its accurate range.
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5 * std::f64::consts::TAU.ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
@@ -0,0 +1,839 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::ops::Mul;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::binary::{BinaryCodec, KIND_FRAME_TREE, decode, encode, put_count, put_pose, read_pose};
+use crate::error::{Error, Result};
+use crate::lie::LieGroup;
+use crate::recording::{FrameEvent, FrameRecord, FrameRecorder};
+use crate::se3::Se3;
+use crate::util::{put_f64s, put_string};
+
+/// A rigid transform tagged with its frames at the type level:
+/// `Transform<A, B>` is \(T_{a,b}\), the pose of frame `B` in frame `A`,
+/// mapping `B` coordinates to `A` coordinates.
+///
+/// Frames are arbitrary marker types. [`Transform::compose`] only accepts
+/// \(T_{a,b} T_{b,c}\) and [`Transform::inverse`] swaps the tags, so mixing up
+/// \(T_{a,b}\) and \(T_{b,a}\) is a compile error:
+///
+/// ```compile_fail
+/// use mathroborust::frame::Transform;
+/// use mathroborust::se3::Se3;
+/// struct World;
+/// struct Body;
+/// struct Camera;
+/// let pose = Se3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.5, [1.0, 0.0, 0.0]);
+/// let world_from_body = Transform::<World, Body>::from_se3(pose.clone());
+/// let camera_from_body = Transform::<Camera, Body>::from_se3(pose);
+/// // Needs T_body_camera, not T_camera_body.
+/// let _ = world_from_body.compose(&camera_from_body);
+/// ```
+///
+/// Serializes exactly like the underlying [`Se3`].
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+pub struct Transform<A, B> {
+    pose: Se3,
+    #[serde(skip)]
+    frames: PhantomData<fn() -> (A, B)>,
+}
+
+impl<A, B> Transform<A, B> {
+    /// Tag `pose` as \(T_{a,b}\). This is the only place frames are asserted
+    /// rather than checked.
+    pub fn from_se3(pose: Se3) -> Self {
+        Self {
+            pose,
+            frames: PhantomData,
+        }
+    }
+
+    pub fn as_se3(&self) -> &Se3 {
+        &self.pose
+    }
+
+    pub fn to_se3(&self) -> Se3 {
+        self.pose.clone()
+    }
+
+    /// \(T_{a,b} T_{b,c} = T_{a,c}\).
+    pub fn compose<C>(&self, other: &Transform<B, C>) -> Transform<A, C> {
+        Transform::from_se3(self.pose.compose(&other.pose))
+    }
+
+    /// \(T_{a,b}^{-1} = T_{b,a}\).
+    pub fn inverse(&self) -> Transform<B, A> {
+        Transform::from_se3(self.pose.inverse())
+    }
+
+    /// Map a point from `B` coordinates to `A` coordinates.
+    pub fn apply(&self, point: [f64; 3]) -> [f64; 3] {
+        self.pose.apply(point)
+    }
+
+    /// Map a twist \([\omega, v]\) from `B` coordinates to `A` coordinates.
+    pub fn transform_twist(&self, twist: [f64; 6]) -> [f64; 6] {
+        self.pose.transform_twist(twist)
+    }
+}
+
+impl<A> Transform<A, A> {
+    pub fn identity() -> Self {
+        Self::from_se3(Se3::identity())
+    }
+}
+
+impl<A, B> Clone for Transform<A, B> {
+    fn clone(&self) -> Self {
+        Self::from_se3(self.pose.clone())
+    }
+}
+
+impl<A, B> PartialEq for Transform<A, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pose == other.pose
+    }
+}
+
+impl<A, B> fmt::Debug for Transform<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transform")
+            .field("to", &std::any::type_name::<A>())
+            .field("from", &std::any::type_name::<B>())
+            .field("pose", &self.pose)
+            .finish()
+    }
+}
+
+impl<A, B> From<Transform<A, B>> for Se3 {
+    fn from(transform: Transform<A, B>) -> Self {
+        transform.pose
+    }
+}
+
+impl<A, B, C> Mul<Transform<B, C>> for Transform<A, B> {
+    type Output = Transform<A, C>;
+
+    fn mul(self, rhs: Transform<B, C>) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl<'a, A, B, C> Mul<&'a Transform<B, C>> for &Transform<A, B> {
+    type Output = Transform<A, C>;
+
+    fn mul(self, rhs: &'a Transform<B, C>) -> Self::Output {
+        self.compose(rhs)
+    }
+}
+
+/// Default translation (metres) and rotation (radians) tolerance of a
+/// [`FrameTree`].
+pub const DEFAULT_FRAME_TOLERANCE: f64 = 1e-6;
+
+/// Disagreement between a transform edge and the path through the rest of a
+/// [`FrameTree`] connecting the same frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrepancy {
+    pub parent: String,
+    pub child: String,
+    /// Distance between the two estimates of the child origin.
+    pub translation: f64,
+    /// Angle in radians of the relative rotation.
+    pub rotation: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    parent: usize,
+    child: usize,
+    parent_from_child: Se3,
+}
+
+/// A mutable graph of named frames connected by rigid transforms.
+///
+/// Lookups chain transforms along any path and never loop on cycles. Edges
+/// that close a loop are allowed only if they agree with the existing path
+/// within the tolerance, so the graph stays consistent whichever path a
+/// lookup takes.
+///
+/// Every edit pre-multiplies the chain from the root of each connected
+/// component to every frame, so a lookup is a single composition instead of
+/// a graph search, however deep the frames are.
+///
+/// Consumers can [`FrameTree::subscribe`] to a lookup instead of polling it,
+/// and [`FrameTree::start_recording`] logs every accepted edit. Clones start
+/// without subscriptions or recording.
+#[derive(Debug)]
+pub struct FrameTree {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    edges: Vec<Edge>,
+    adjacency: Vec<Vec<usize>>,
+    translation_tolerance: f64,
+    rotation_tolerance: f64,
+    /// Component root and \(T_{\text{root},f}\) of every frame `f`.
+    root_from: Vec<(usize, Se3)>,
+    subscriptions: Vec<Subscription>,
+    recording: Option<Recording>,
+}
+
+/// A watched lookup and the last value sent for it.
+#[derive(Debug)]
+struct Subscription {
+    target: String,
+    source: String,
+    last: Option<Se3>,
+    sender: Sender<Se3>,
+}
+
+/// Log of accepted edits, timestamped in seconds since `start`. Writing stops
+/// at the first error, which is kept for [`FrameTree::stop_recording`].
+struct Recording {
+    recorder: Mutex<FrameRecorder<Box<dyn Write + Send>>>,
+    start: Instant,
+    error: Option<io::Error>,
+}
+
+impl Recording {
+    fn record(&mut self, event: FrameEvent) {
+        if self.error.is_some() {
+            return;
+        }
+        let record = FrameRecord {
+            time: self.start.elapsed().as_secs_f64(),
+            event,
+        };
+        let recorder = self
+            .recorder
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Err(error) = recorder.record(&record) {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl fmt::Debug for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recording")
+            .field("start", &self.start)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for FrameTree {
+    fn clone(&self) -> Self {
+        Self {
+            names: self.names.clone(),
+            index: self.index.clone(),
+            edges: self.edges.clone(),
+            adjacency: self.adjacency.clone(),
+            translation_tolerance: self.translation_tolerance,
+            rotation_tolerance: self.rotation_tolerance,
+            root_from: self.root_from.clone(),
+            subscriptions: Vec::new(),
+            recording: None,
+        }
+    }
+}
+
+impl Default for FrameTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameTree {
+    /// An empty tree with [`DEFAULT_FRAME_TOLERANCE`] for both translation and
+    /// rotation.
+    pub fn new() -> Self {
+        Self::with_tolerance(DEFAULT_FRAME_TOLERANCE, DEFAULT_FRAME_TOLERANCE)
+    }
+
+    pub fn with_tolerance(translation_tolerance: f64, rotation_tolerance: f64) -> Self {
+        Self {
+            names: Vec::new(),
+            index: HashMap::new(),
+            edges: Vec::new(),
+            adjacency: Vec::new(),
+            translation_tolerance,
+            rotation_tolerance,
+            root_from: Vec::new(),
+            subscriptions: Vec::new(),
+            recording: None,
+        }
+    }
+
+    pub fn contains(&self, frame: &str) -> bool {
+        self.index.contains_key(frame)
+    }
+
+    /// Frame names in registration order.
+    pub fn frames(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Every edge as `(parent, child, T_parent_child)`.
+    pub fn edges(&self) -> impl Iterator<Item = (&str, &str, &Se3)> {
+        self.edges.iter().map(|edge| {
+            (
+                self.names[edge.parent].as_str(),
+                self.names[edge.child].as_str(),
+                &edge.parent_from_child,
+            )
+        })
+    }
+
+    /// The root of the component containing `frame` (its lowest-registered
+    /// frame) and \(T_{\text{root},\text{frame}}\), from the cached chains.
+    pub fn root_transform(&self, frame: &str) -> Result<(&str, &Se3)> {
+        let (root, root_from_frame) = &self.root_from[self.frame_index(frame)?];
+        Ok((&self.names[*root], root_from_frame))
+    }
+
+    /// Set \(T_{\text{parent},\text{child}}\), registering unknown frames and
+    /// replacing any existing edge between the two frames.
+    ///
+    /// If the frames are already connected through other edges, the new
+    /// transform is compared with the chained one and rejected with
+    /// [`Error::InconsistentFrames`] when they differ by more than the
+    /// tolerance; the tree is then left unchanged. Setting a frame relative to
+    /// itself is checked against the identity.
+    pub fn set_transform(
+        &mut self,
+        parent: &str,
+        child: &str,
+        parent_from_child: Se3,
+    ) -> Result<()> {
+        let known = self
+            .index
+            .get(parent)
+            .copied()
+            .zip(self.index.get(child).copied());
+        let previous = known.and_then(|(p, c)| self.detach(p, c));
+
+        let existing = if parent == child {
+            Some(Se3::identity())
+        } else {
+            known.and_then(|(p, c)| self.chain(p, c))
+        };
+        if let Some(existing) = existing {
+            let discrepancy = Self::discrepancy(parent, child, &existing, &parent_from_child);
+            if discrepancy.translation > self.translation_tolerance
+                || discrepancy.rotation > self.rotation_tolerance
+            {
+                // The graph is as before, so the cached chains still hold.
+                if let Some((e, edge)) = previous {
+                    self.reattach(e, edge);
+                }
+                return Err(Error::InconsistentFrames {
+                    parent: discrepancy.parent,
+                    child: discrepancy.child,
+                    translation: discrepancy.translation,
+                    rotation: discrepancy.rotation,
+                });
+            }
+        }
+        let (p, c) = (self.register(parent), self.register(child));
+        if let Some(recording) = &mut self.recording {
+            recording.record(FrameEvent::Set {
+                parent: parent.to_string(),
+                child: child.to_string(),
+                parent_from_child: parent_from_child.clone(),
+            });
+        }
+        self.attach(Edge {
+            parent: p,
+            child: c,
+            parent_from_child,
+        });
+        self.rebuild_chains();
+        self.notify();
+        Ok(())
+    }
+
+    /// Remove the edge between `parent` and `child` in either direction,
+    /// returning \(T_{\text{parent},\text{child}}\) if it existed.
+    pub fn remove_transform(&mut self, parent: &str, child: &str) -> Option<Se3> {
+        let (&p, &c) = (self.index.get(parent)?, self.index.get(child)?);
+        let (_, edge) = self.detach(p, c)?;
+        if let Some(recording) = &mut self.recording {
+            recording.record(FrameEvent::Remove {
+                parent: parent.to_string(),
+                child: child.to_string(),
+            });
+        }
+        self.rebuild_chains();
+        self.notify();
+        Some(if edge.parent == p {
+            edge.parent_from_child
+        } else {
+            edge.parent_from_child.inverse()
+        })
+    }
+
+    /// \(T_{\text{target},\text{source}}\), composed from the cached chains
+    /// of both frames.
+    ///
+    /// Returns [`Error::UnknownFrame`] for unregistered names and
+    /// [`Error::DisconnectedFrame`] (naming `source`) when no path exists.
+    pub fn transform(&self, target: &str, source: &str) -> Result<Se3> {
+        let (target_root, root_from_target) = &self.root_from[self.frame_index(target)?];
+        let (source_root, root_from_source) = &self.root_from[self.frame_index(source)?];
+        if target_root != source_root {
+            return Err(Error::DisconnectedFrame(source.to_string()));
+        }
+        Ok(root_from_target.inverse().compose(root_from_source))
+    }
+
+    /// Watch \(T_{\text{target},\text{source}}\).
+    ///
+    /// The receiver gets the current transform if the frames are already
+    /// connected, then a new value after every [`FrameTree::set_transform`] or
+    /// [`FrameTree::remove_transform`] that changes it. Nothing is sent while
+    /// the frames are disconnected or unknown. The subscription ends when the
+    /// receiver is dropped.
+    pub fn subscribe(&mut self, target: &str, source: &str) -> Receiver<Se3> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscriptions.push(Subscription {
+            target: target.to_string(),
+            source: source.to_string(),
+            last: None,
+            sender,
+        });
+        self.notify();
+        receiver
+    }
+
+    /// Compare every loop-closing edge with the path through a spanning tree
+    /// of the graph and report the size of each disagreement.
+    pub fn check_consistency(&self) -> Vec<Discrepancy> {
+        let (root_from, tree_edge) = self.spanning_forest();
+        self.edges
+            .iter()
+            .zip(&tree_edge)
+            .filter(|(_, in_tree)| !**in_tree)
+            .map(|(edge, _)| {
+                let root_from_parent = &root_from[edge.parent].1;
+                let root_from_child = &root_from[edge.child].1;
+                let chained = root_from_parent.inverse().compose(root_from_child);
+                Self::discrepancy(
+                    &self.names[edge.parent],
+                    &self.names[edge.child],
+                    &chained,
+                    &edge.parent_from_child,
+                )
+            })
+            .collect()
+    }
+
+    /// Log every accepted edit to `writer` in the format of
+    /// [`FrameLog`](crate::recording::FrameLog), starting with the current
+    /// edges at time 0. Later edits are timestamped in seconds since this
+    /// call. Records are written while editing, so a slow `writer` should be
+    /// buffered. Any previous recording is stopped first and its result
+    /// dropped.
+    pub fn start_recording(&mut self, writer: impl Write + Send + 'static) -> io::Result<()> {
+        let writer: Box<dyn Write + Send> = Box::new(writer);
+        let mut recorder = FrameRecorder::new(writer)?;
+        for edge in &self.edges {
+            recorder.record_set(
+                0.0,
+                &self.names[edge.parent],
+                &self.names[edge.child],
+                &edge.parent_from_child,
+            )?;
+        }
+        self.recording = Some(Recording {
+            recorder: Mutex::new(recorder),
+            start: Instant::now(),
+            error: None,
+        });
+        Ok(())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Stop recording and flush the writer, returning the first write error
+    /// since [`FrameTree::start_recording`]. Does nothing if not recording.
+    pub fn stop_recording(&mut self) -> io::Result<()> {
+        let Some(recording) = self.recording.take() else {
+            return Ok(());
+        };
+        if let Some(error) = recording.error {
+            return Err(error);
+        }
+        recording
+            .recorder
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .flush()
+    }
+
+    /// \(T_{\text{root},f}\) and the root of every frame `f` along a spanning
+    /// forest grown from the lowest-numbered frame of each component, and the
+    /// edges used.
+    fn spanning_forest(&self) -> (Vec<(usize, Se3)>, Vec<bool>) {
+        let mut root_from = vec![None; self.names.len()];
+        let mut roots = vec![None; self.names.len()];
+        let mut tree_edge = vec![false; self.edges.len()];
+        for root in 0..self.names.len() {
+            if root_from[root].is_some() {
+                continue;
+            }
+            for frame in self.search(root, None, &mut root_from, &mut tree_edge) {
+                roots[frame] = Some(root);
+            }
+        }
+        let chains = roots
+            .into_iter()
+            .zip(root_from)
+            .map(|(root, chain)| (root.unwrap(), chain.unwrap()))
+            .collect();
+        (chains, tree_edge)
+    }
+
+    /// Refresh the cached chains after the graph changed.
+    fn rebuild_chains(&mut self) {
+        self.root_from = self.spanning_forest().0;
+    }
+
+    /// Send changed lookups to subscribers, dropping closed subscriptions.
+    fn notify(&mut self) {
+        let mut subscriptions = std::mem::take(&mut self.subscriptions);
+        subscriptions.retain_mut(|subscription| {
+            let Ok(current) = self.transform(&subscription.target, &subscription.source) else {
+                return true;
+            };
+            if subscription.last.as_ref() == Some(&current) {
+                return true;
+            }
+            subscription.last = Some(current.clone());
+            subscription.sender.send(current).is_ok()
+        });
+        self.subscriptions = subscriptions;
+    }
+
+    fn frame_index(&self, frame: &str) -> Result<usize> {
+        self.index
+            .get(frame)
+            .copied()
+            .ok_or_else(|| Error::UnknownFrame(frame.to_string()))
+    }
+
+    fn register(&mut self, frame: &str) -> usize {
+        if let Some(&i) = self.index.get(frame) {
+            return i;
+        }
+        self.names.push(frame.to_string());
+        self.adjacency.push(Vec::new());
+        self.index.insert(frame.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    fn attach(&mut self, edge: Edge) {
+        let e = self.edges.len();
+        self.adjacency[edge.parent].push(e);
+        if edge.child != edge.parent {
+            self.adjacency[edge.child].push(e);
+        }
+        self.edges.push(edge);
+    }
+
+    /// Remove the edge between `a` and `b`, returning it with its index.
+    fn detach(&mut self, a: usize, b: usize) -> Option<(usize, Edge)> {
+        let e = self.adjacency[a].iter().copied().find(|&e| {
+            let edge = &self.edges[e];
+            (edge.parent, edge.child) == (a, b) || (edge.parent, edge.child) == (b, a)
+        })?;
+        let edge = self.edges.swap_remove(e);
+        // swap_remove renumbered the last edge.
+        self.rebuild_adjacency();
+        Some((e, edge))
+    }
+
+    /// Undo [`FrameTree::detach`], putting `edge` back at index `e`.
+    fn reattach(&mut self, e: usize, edge: Edge) {
+        self.edges.push(edge);
+        let last = self.edges.len() - 1;
+        self.edges.swap(e, last);
+        self.rebuild_adjacency();
+    }
+
+    fn rebuild_adjacency(&mut self) {
+        for list in &mut self.adjacency {
+            list.clear();
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            self.adjacency[edge.parent].push(i);
+            if edge.child != edge.parent {
+                self.adjacency[edge.child].push(i);
+            }
+        }
+    }
+
+    /// \(T_{\text{target},\text{source}}\) if the frames are connected.
+    fn chain(&self, target: usize, source: usize) -> Option<Se3> {
+        let mut target_from = vec![None; self.names.len()];
+        let mut tree_edge = vec![false; self.edges.len()];
+        self.search(target, Some(source), &mut target_from, &mut tree_edge);
+        target_from[source].take()
+    }
+
+    /// Breadth-first search from `root` filling `root_from[f]` with
+    /// \(T_{\text{root},f}\) and marking the edges used, stopping early once
+    /// `stop` is reached. Returns the frames reached, in the order found.
+    fn search(
+        &self,
+        root: usize,
+        stop: Option<usize>,
+        root_from: &mut [Option<Se3>],
+        tree_edge: &mut [bool],
+    ) -> Vec<usize> {
+        let mut queue = vec![root];
+        root_from[root] = Some(Se3::identity());
+        let mut next_index = 0;
+        while let Some(&frame) = queue.get(next_index) {
+            next_index += 1;
+            if Some(frame) == stop {
+                break;
+            }
+            let root_from_frame = root_from[frame].clone().unwrap();
+            for &e in &self.adjacency[frame] {
+                let edge = &self.edges[e];
+                let (next, frame_from_next) = if edge.parent == frame {
+                    (edge.child, edge.parent_from_child.clone())
+                } else {
+                    (edge.parent, edge.parent_from_child.inverse())
+                };
+                if root_from[next].is_none() {
+                    root_from[next] = Some(root_from_frame.compose(&frame_from_next));
+                    tree_edge[e] = true;
+                    queue.push(next);
+                }
+            }
+        }
+        queue
+    }
+
+    fn discrepancy(parent: &str, child: &str, chained: &Se3, direct: &Se3) -> Discrepancy {
+        let difference = chained.inverse().compose(direct);
+        let [x, y, z] = difference.translation();
+        let [rx, ry, rz] = difference.rotation().to_rotation_vector();
+        Discrepancy {
+            parent: parent.to_string(),
+            child: child.to_string(),
+            translation: (x * x + y * y + z * z).sqrt(),
+            rotation: (rx * rx + ry * ry + rz * rz).sqrt(),
+        }
+    }
+}
+
+/// Snapshot of the frames, edges and tolerances; subscriptions are not
+/// encoded.
+impl BinaryCodec for FrameTree {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_FRAME_TREE, |buffer| {
+            put_f64s(
+                buffer,
+                &[self.translation_tolerance, self.rotation_tolerance],
+            );
+            put_count(buffer, self.names.len());
+            for name in &self.names {
+                put_string(buffer, name);
+            }
+            put_count(buffer, self.edges.len());
+            for edge in &self.edges {
+                put_count(buffer, edge.parent);
+                put_count(buffer, edge.child);
+                put_pose(buffer, &edge.parent_from_child);
+            }
+        })
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode(bytes, KIND_FRAME_TREE, |reader| {
+            let translation_tolerance = reader.f64()?;
+            let rotation_tolerance = reader.f64()?;
+            let mut tree = FrameTree::with_tolerance(translation_tolerance, rotation_tolerance);
+            for _ in 0..reader.u32()? {
+                let name = reader.string()?;
+                if tree.contains(&name) {
+                    return Err(Error::InvalidEncoding(format!("duplicate frame '{name}'")));
+                }
+                tree.register(&name);
+            }
+            for _ in 0..reader.u32()? {
+                let (parent, child) = (reader.u32()? as usize, reader.u32()? as usize);
+                if parent >= tree.names.len() || child >= tree.names.len() {
+                    return Err(Error::InvalidEncoding(
+                        "edge to an unknown frame".to_string(),
+                    ));
+                }
+                let parent_from_child = read_pose(reader)?;
+                tree.attach(Edge {
+                    parent,
+                    child,
+                    parent_from_child,
+                });
+            }
+            tree.rebuild_chains();
+            let inconsistent = tree.check_consistency().iter().any(|d| {
+                d.translation > tree.translation_tolerance || d.rotation > tree.rotation_tolerance
+            });
+            if inconsistent {
+                return Err(Error::InvalidEncoding(
+                    "edges disagree beyond the tolerance".to_string(),
+                ));
+            }
+            Ok(tree)
+        })
+    }
+}
+
+/// A [`FrameTree`] shared between writers and real-time readers with a
+/// read-copy-update scheme.
+///
+/// Writers edit a private tree under a mutex and then publish an immutable
+/// snapshot. Readers go through a [`FrameReader`], which keeps its own
+/// snapshot and only checks an atomic version per lookup, so a reader never
+/// waits on a writer: while a snapshot is being swapped in it simply serves
+/// the previous one. Cloning the handle shares the same tree.
+#[derive(Debug, Clone)]
+pub struct SharedFrameTree {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    writer: Mutex<FrameTree>,
+    published: Mutex<Arc<FrameTree>>,
+    version: AtomicU64,
+}
+
+impl SharedFrameTree {
+    pub fn new(tree: FrameTree) -> Self {
+        let snapshot = Arc::new(tree.clone());
+        Self {
+            shared: Arc::new(Shared {
+                writer: Mutex::new(tree),
+                published: Mutex::new(snapshot),
+                version: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Edit the tree and publish the result to readers. Subscriptions made
+    /// through [`FrameTree::subscribe`] here are notified as usual.
+    pub fn update<R>(&self, edit: impl FnOnce(&mut FrameTree) -> R) -> R {
+        let mut tree = self
+            .shared
+            .writer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let result = edit(&mut tree);
+        let snapshot = Arc::new(tree.clone());
+        // Free the old snapshot only after unlocking, so readers polling with
+        // `try_lock` never wait on the deallocation.
+        let previous = std::mem::replace(
+            &mut *self
+                .shared
+                .published
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+            snapshot,
+        );
+        self.shared.version.fetch_add(1, Ordering::Release);
+        drop(previous);
+        result
+    }
+
+    /// [`FrameTree::set_transform`] followed by publishing.
+    pub fn set_transform(&self, parent: &str, child: &str, parent_from_child: Se3) -> Result<()> {
+        self.update(|tree| tree.set_transform(parent, child, parent_from_child))
+    }
+
+    /// [`FrameTree::remove_transform`] followed by publishing.
+    pub fn remove_transform(&self, parent: &str, child: &str) -> Option<Se3> {
+        self.update(|tree| tree.remove_transform(parent, child))
+    }
+
+    /// [`FrameTree::start_recording`] on the shared tree, so every accepted
+    /// edit through this handle or its clones is logged.
+    pub fn start_recording(&self, writer: impl Write + Send + 'static) -> io::Result<()> {
+        self.update(|tree| tree.start_recording(writer))
+    }
+
+    /// [`FrameTree::stop_recording`] on the shared tree.
+    pub fn stop_recording(&self) -> io::Result<()> {
+        self.update(FrameTree::stop_recording)
+    }
+
+    /// A read handle for one thread, starting at the latest snapshot.
+    pub fn reader(&self) -> FrameReader {
+        let version = self.shared.version.load(Ordering::Acquire);
+        let snapshot = self
+            .shared
+            .published
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        FrameReader {
+            shared: Arc::clone(&self.shared),
+            snapshot,
+            version,
+        }
+    }
+}
+
+/// Per-thread read handle of a [`SharedFrameTree`].
+///
+/// Lookups cost an atomic load plus a cached [`FrameTree::transform`]; a
+/// newer snapshot is picked up with a non-blocking `try_lock`, so the read
+/// path never blocks and is free of priority inversion. Dropping the last
+/// handle to an outdated snapshot frees it on the reading thread.
+#[derive(Debug)]
+pub struct FrameReader {
+    shared: Arc<Shared>,
+    snapshot: Arc<FrameTree>,
+    version: u64,
+}
+
+impl FrameReader {
+    /// \(T_{\text{target},\text{source}}\) in the newest snapshot available
+    /// without waiting.
+    pub fn transform(&mut self, target: &str, source: &str) -> Result<Se3> {
+        self.snapshot().transform(target, source)
+    }
+
+    /// The newest snapshot available without waiting, for several lookups
+    /// that must see the same tree.
+    pub fn snapshot(&mut self) -> &FrameTree {
+        let version = self.shared.version.load(Ordering::Acquire);
+        if version != self.version
+            && let Ok(published) = self.shared.published.try_lock()
+        {
+            self.snapshot = Arc::clone(&published);
+            self.version = version;
+        }
+        &self.snapshot
+    }
+
+    /// Number of publications the current snapshot reflects.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
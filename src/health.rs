@@ -0,0 +1,58 @@
+use nalgebra::Matrix3;
+
+/// Numerical conditioning diagnostics for a rotation or rigid transform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformHealth {
+    /// Frobenius norm of \(R^T R - I\).
+    pub orthogonality_error: f64,
+    /// Absolute deviation \(|\det R - 1|\).
+    pub determinant_deviation: f64,
+    /// Euclidean norm of the translation; zero for pure rotations.
+    pub translation_norm: f64,
+    /// Whether every stored entry is finite.
+    pub finite: bool,
+}
+
+impl TransformHealth {
+    /// Whether all entries are finite and the rotation deviates from
+    /// \(\mathrm{SO}(3)\) by at most `tolerance`. The translation magnitude is
+    /// left to the caller, since its acceptable range is application specific.
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.finite
+            && self.orthogonality_error <= tolerance
+            && self.determinant_deviation <= tolerance
+    }
+
+    pub(crate) fn from_rotation(rotation: &Matrix3<f64>, translation_norm: f64) -> Self {
+        Self {
+            orthogonality_error: (rotation.transpose() * rotation - Matrix3::identity()).norm(),
+            determinant_deviation: (rotation.determinant() - 1.0).abs(),
+            translation_norm,
+            finite: rotation.iter().all(|v| v.is_finite()) && translation_norm.is_finite(),
+        }
+    }
+}
+
+/// Consistency diagnostics for a CMTM.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CmtmHealth {
+    /// Conditioning of the rotation block(s) of the base matrix. For the
+    /// spatial CMTM the translation norm is recovered from the coupling block.
+    pub base: TransformHealth,
+    /// Frobenius norm of the deviation from the block structure required of an
+    /// adjoint matrix (equal diagonal rotation blocks, zero upper-right block,
+    /// skew-symmetric \(B R^T\) for the coupling block \(B\)). Always zero for
+    /// the 3×3 rotational CMTM.
+    pub block_structure_error: f64,
+    /// Whether every stored derivative vector is finite.
+    pub derivatives_finite: bool,
+}
+
+impl CmtmHealth {
+    /// Whether the CMTM is finite and consistent up to `tolerance`.
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.base.is_within(tolerance)
+            && self.block_structure_error <= tolerance
+            && self.derivatives_finite
+    }
+}
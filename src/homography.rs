@@ -0,0 +1,165 @@
+use nalgebra::{Matrix3, Rotation3, Vector3};
+
+use crate::se3::Se3;
+use crate::so3::So3;
+
+/// Below this norm of \(H^T H - I\) the homography is treated as a pure
+/// rotation.
+const PURE_ROTATION_TOLERANCE: f64 = 1e-9;
+
+/// One motion and plane compatible with a homography.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HomographyDecomposition {
+    /// Relative pose of the first camera in the second, with the translation
+    /// divided by the plane distance \(d\) from the first camera.
+    pub second_from_first: Se3,
+    /// Unit plane normal \(n\) in the first camera frame, with
+    /// \(n^T X = d > 0\) for plane points \(X\). Zero for a pure rotation.
+    pub normal: [f64; 3],
+}
+
+/// Homography \(H = R + t n^T / d\) between normalized image points,
+/// \(x_2 \sim H x_1\), induced by the plane \(n^T X = d\) in the first
+/// camera frame.
+pub fn homography_from_pose(
+    second_from_first: &Se3,
+    normal: [f64; 3],
+    distance: f64,
+) -> Matrix3<f64> {
+    let translation = Vector3::from(second_from_first.translation());
+    second_from_first.rotation().rotation().matrix()
+        + translation * Vector3::from(normal).transpose() / distance
+}
+
+/// Analytic decomposition of a homography between normalized image points
+/// (Malis & Vargas, 2007). Pixel homographies must first be normalized as
+/// \(K_2^{-1} H K_1\).
+///
+/// The input scale and sign are arbitrary. Four solutions are returned in
+/// general, two of which are removed by [`visible_decompositions`]; a single
+/// one with zero translation and normal is returned for a pure rotation.
+pub fn decompose_homography(homography: &Matrix3<f64>) -> Vec<HomographyDecomposition> {
+    let mut singular = homography.singular_values().as_slice().to_vec();
+    singular.sort_by(|a, b| b.total_cmp(a));
+    if singular[1] <= 0.0 {
+        return Vec::new();
+    }
+    // Fix the scale to σ₂ = 1 and the sign to det H = 1 + nᵀRᵀt/d > 0.
+    let h = homography / singular[1] * homography.determinant().signum();
+    let s = h.transpose() * h - Matrix3::identity();
+    if s.abs().max() < PURE_ROTATION_TOLERANCE {
+        return vec![HomographyDecomposition {
+            second_from_first: Se3::from_parts(nearest_rotation(&h), [0.0; 3]),
+            normal: [0.0; 3],
+        }];
+    }
+
+    let m00 = opposite_of_minor(&s, 0, 0).max(0.0);
+    let m11 = opposite_of_minor(&s, 1, 1).max(0.0);
+    let m22 = opposite_of_minor(&s, 2, 2).max(0.0);
+    let (rt00, rt11, rt22) = (m00.sqrt(), m11.sqrt(), m22.sqrt());
+    let e01 = sign(opposite_of_minor(&s, 0, 1));
+    let e02 = sign(opposite_of_minor(&s, 0, 2));
+    let e12 = sign(opposite_of_minor(&s, 1, 2));
+
+    // Build the normals from the row of S with the largest diagonal entry,
+    // which is the best conditioned.
+    let diagonal = s.diagonal().abs();
+    let index = diagonal.imax();
+    let (first, second) = match index {
+        0 => (
+            Vector3::new(s[(0, 0)], s[(0, 1)] + rt22, s[(0, 2)] + e12 * rt11),
+            Vector3::new(s[(0, 0)], s[(0, 1)] - rt22, s[(0, 2)] - e12 * rt11),
+        ),
+        1 => (
+            Vector3::new(s[(0, 1)] + rt22, s[(1, 1)], s[(1, 2)] - e02 * rt00),
+            Vector3::new(s[(0, 1)] - rt22, s[(1, 1)], s[(1, 2)] + e02 * rt00),
+        ),
+        _ => (
+            Vector3::new(s[(0, 2)] + e01 * rt11, s[(1, 2)] + rt00, s[(2, 2)]),
+            Vector3::new(s[(0, 2)] - e01 * rt11, s[(1, 2)] - rt00, s[(2, 2)]),
+        ),
+    };
+    let (na, nb) = (first.normalize(), second.normalize());
+
+    let trace = s.trace();
+    let v = 2.0 * (1.0 + trace - m00 - m11 - m22).max(0.0).sqrt();
+    let r = (2.0 + trace + v).max(0.0).sqrt();
+    let n_t = (2.0 + trace - v).max(0.0).sqrt();
+    let esii_r = sign(s[(index, index)]) * r;
+    let ta_star = (nb * esii_r - na * n_t) * (0.5 * n_t);
+    let tb_star = (na * esii_r - nb * n_t) * (0.5 * n_t);
+
+    let mut solutions = Vec::with_capacity(4);
+    for (t_star, n) in [(ta_star, na), (tb_star, nb)] {
+        // R = H (I - 2 t* nᵀ / v), and t = R t*.
+        let rotation =
+            nearest_rotation(&(h * (Matrix3::identity() - t_star * n.transpose() * (2.0 / v))));
+        let t = rotation.rotation() * t_star;
+        for side in [1.0, -1.0] {
+            solutions.push(HomographyDecomposition {
+                second_from_first: Se3::from_parts(rotation.clone(), (t * side).into()),
+                normal: (n * side).into(),
+            });
+        }
+    }
+    solutions
+}
+
+/// Keep the decompositions that place every reference point in front of
+/// both cameras, given normalized image points of the plane in the first
+/// and second view. At most two of four remain.
+pub fn visible_decompositions(
+    decompositions: &[HomographyDecomposition],
+    first: &[[f64; 2]],
+    second: &[[f64; 2]],
+) -> Vec<HomographyDecomposition> {
+    decompositions
+        .iter()
+        .filter(|solution| {
+            let normal = Vector3::from(solution.normal);
+            let rotated = solution.second_from_first.rotation().rotation() * normal;
+            // Plane distance from the second camera, for unit first distance.
+            let distance =
+                1.0 + rotated.dot(&Vector3::from(solution.second_from_first.translation()));
+            first
+                .iter()
+                .all(|&[x, y]| normal.dot(&Vector3::new(x, y, 1.0)) > 0.0)
+                && second
+                    .iter()
+                    .all(|&[x, y]| distance * rotated.dot(&Vector3::new(x, y, 1.0)) > 0.0)
+        })
+        .cloned()
+        .collect()
+}
+
+/// The decomposition whose plane normal is closest to `expected` (e.g. the
+/// ground normal from a known camera mounting), or `None` if empty.
+pub fn closest_to_normal(
+    decompositions: &[HomographyDecomposition],
+    expected: [f64; 3],
+) -> Option<&HomographyDecomposition> {
+    let expected = Vector3::from(expected);
+    decompositions.iter().max_by(|a, b| {
+        Vector3::from(a.normal)
+            .dot(&expected)
+            .total_cmp(&Vector3::from(b.normal).dot(&expected))
+    })
+}
+
+/// Negated minor of `s` without `row` and `col`.
+fn opposite_of_minor(s: &Matrix3<f64>, row: usize, col: usize) -> f64 {
+    let (x1, x2) = (usize::from(col == 0), if col == 2 { 1 } else { 2 });
+    let (y1, y2) = (usize::from(row == 0), if row == 2 { 1 } else { 2 });
+    s[(y1, x2)] * s[(y2, x1)] - s[(y1, x1)] * s[(y2, x2)]
+}
+
+fn sign(value: f64) -> f64 {
+    if value < 0.0 { -1.0 } else { 1.0 }
+}
+
+fn nearest_rotation(matrix: &Matrix3<f64>) -> So3 {
+    let rotation = Rotation3::from_matrix(matrix);
+    let m = rotation.matrix();
+    So3::from_matrix(std::array::from_fn(|r| std::array::from_fn(|c| m[(r, c)])))
+}
@@ -0,0 +1,326 @@
+use nalgebra::{DMatrix, DVector, Matrix3, SMatrix};
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+
+/// Below this \(|\cos(\text{pitch})|\) the roll–pitch–yaw rate matrix is
+/// treated as singular (gimbal lock).
+const GIMBAL_LOCK_TOLERANCE: f64 = 1e-9;
+/// Relative singular value below which [`task_to_joint`] treats a direction
+/// as lost when undamped.
+const PSEUDO_INVERSE_TOLERANCE: f64 = 1e-12;
+
+/// Orientation coordinates used by the rows of an analytic Jacobian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrientationCoordinates {
+    /// Roll–pitch–yaw angles in ZYX order, as in [`So3::from_euler_angles`].
+    EulerRpy,
+    /// Unit quaternion \([w, x, y, z]\), as in [`So3::to_quaternion`].
+    Quaternion,
+}
+
+impl OrientationCoordinates {
+    /// Number of orientation rows, three for Euler angles and four for
+    /// quaternions.
+    pub fn dimension(self) -> usize {
+        match self {
+            OrientationCoordinates::EulerRpy => 3,
+            OrientationCoordinates::Quaternion => 4,
+        }
+    }
+}
+
+/// Matrix \(E(\phi)\) mapping roll–pitch–yaw rates to the world-frame angular
+/// velocity, \(\omega = E(\phi)\dot{\phi}\), for `angles`
+/// \([r, p, y]\). Singular when \(\cos p = 0\).
+pub fn euler_rate_matrix(angles: [f64; 3]) -> Matrix3<f64> {
+    let [_, pitch, yaw] = angles;
+    let (sp, cp) = pitch.sin_cos();
+    let (sy, cy) = yaw.sin_cos();
+    Matrix3::new(cy * cp, -sy, 0.0, sy * cp, cy, 0.0, -sp, 0.0, 1.0)
+}
+
+/// Time derivative \(\dot{E}\) of [`euler_rate_matrix`] for angles moving with
+/// `rates` \([\dot{r}, \dot{p}, \dot{y}]\).
+pub fn euler_rate_matrix_derivative(angles: [f64; 3], rates: [f64; 3]) -> Matrix3<f64> {
+    let [_, pitch, yaw] = angles;
+    let [_, dp, dy] = rates;
+    let (sp, cp) = pitch.sin_cos();
+    let (sy, cy) = yaw.sin_cos();
+    Matrix3::new(
+        -sy * cp * dy - cy * sp * dp,
+        -cy * dy,
+        0.0,
+        cy * cp * dy - sy * sp * dp,
+        -sy * dy,
+        0.0,
+        -cp * dp,
+        0.0,
+        0.0,
+    )
+}
+
+/// Matrix \(E(q)\) mapping the world-frame angular velocity to quaternion
+/// rates, \(\dot{q} = E(q)\omega = \tfrac{1}{2}\,\omega \otimes q\), for
+/// \(q = [w, x, y, z]\). For unit \(q\), \(E^T E = \tfrac{1}{4} I\), so
+/// \(\omega = 4 E^T \dot{q}\).
+pub fn quaternion_rate_matrix(quaternion: [f64; 4]) -> SMatrix<f64, 4, 3> {
+    let [w, x, y, z] = quaternion;
+    SMatrix::<f64, 4, 3>::new(-x, -y, -z, w, z, -y, -z, w, x, y, -x, w) * 0.5
+}
+
+/// Time derivative of [`quaternion_rate_matrix`]. The matrix is linear in
+/// \(q\), so this is the same map evaluated at \(\dot{q}\).
+pub fn quaternion_rate_matrix_derivative(quaternion_rate: [f64; 4]) -> SMatrix<f64, 4, 3> {
+    quaternion_rate_matrix(quaternion_rate)
+}
+
+/// Convert a geometric Jacobian, whose rows are the world-frame angular
+/// velocity \(\omega\) followed by the linear velocity \(\dot{p}\), into the
+/// analytic Jacobian with rows \([\dot{\phi}; \dot{p}]\) for the orientation
+/// coordinates \(\phi\) of `rotation`.
+///
+/// Returns [`Error::Singular`] for Euler angles at gimbal lock.
+pub fn geometric_to_analytic(
+    jacobian: &DMatrix<f64>,
+    rotation: &So3,
+    coordinates: OrientationCoordinates,
+) -> Result<DMatrix<f64>> {
+    let mut analytic = DMatrix::zeros(0, 0);
+    geometric_to_analytic_into(jacobian, rotation, coordinates, &mut analytic)?;
+    Ok(analytic)
+}
+
+/// [`geometric_to_analytic`] writing into a caller-owned buffer, which is
+/// only reallocated when its shape does not match.
+pub fn geometric_to_analytic_into(
+    jacobian: &DMatrix<f64>,
+    rotation: &So3,
+    coordinates: OrientationCoordinates,
+    analytic: &mut DMatrix<f64>,
+) -> Result<()> {
+    assert_eq!(jacobian.nrows(), 6, "geometric Jacobian must have 6 rows");
+    let angular = jacobian.rows(0, 3);
+    reshape(analytic, coordinates.dimension() + 3, jacobian.ncols());
+    match coordinates {
+        OrientationCoordinates::EulerRpy => {
+            let (roll, pitch, yaw) = rotation.to_euler_angles();
+            if pitch.cos().abs() < GIMBAL_LOCK_TOLERANCE {
+                return Err(Error::Singular);
+            }
+            let inverse = euler_rate_matrix([roll, pitch, yaw])
+                .try_inverse()
+                .ok_or(Error::Singular)?;
+            analytic.rows_mut(0, 3).gemm(1.0, &inverse, &angular, 0.0);
+        }
+        OrientationCoordinates::Quaternion => {
+            let rate = quaternion_rate_matrix(rotation.to_quaternion());
+            analytic.rows_mut(0, 4).gemm(1.0, &rate, &angular, 0.0);
+        }
+    }
+    analytic
+        .rows_mut(coordinates.dimension(), 3)
+        .copy_from(&jacobian.rows(3, 3));
+    Ok(())
+}
+
+/// Convert an analytic Jacobian with rows \([\dot{\phi}; \dot{p}]\) back into
+/// the geometric Jacobian with rows \([\omega; \dot{p}]\).
+pub fn analytic_to_geometric(
+    jacobian: &DMatrix<f64>,
+    rotation: &So3,
+    coordinates: OrientationCoordinates,
+) -> DMatrix<f64> {
+    let mut geometric = DMatrix::zeros(0, 0);
+    analytic_to_geometric_into(jacobian, rotation, coordinates, &mut geometric);
+    geometric
+}
+
+/// [`analytic_to_geometric`] writing into a caller-owned buffer, which is
+/// only reallocated when its shape does not match.
+pub fn analytic_to_geometric_into(
+    jacobian: &DMatrix<f64>,
+    rotation: &So3,
+    coordinates: OrientationCoordinates,
+    geometric: &mut DMatrix<f64>,
+) {
+    let dimension = coordinates.dimension();
+    assert_eq!(
+        jacobian.nrows(),
+        dimension + 3,
+        "analytic Jacobian must have {} rows",
+        dimension + 3
+    );
+    let orientation = jacobian.rows(0, dimension);
+    reshape(geometric, 6, jacobian.ncols());
+    match coordinates {
+        OrientationCoordinates::EulerRpy => {
+            let (roll, pitch, yaw) = rotation.to_euler_angles();
+            let rate = euler_rate_matrix([roll, pitch, yaw]);
+            geometric.rows_mut(0, 3).gemm(1.0, &rate, &orientation, 0.0);
+        }
+        OrientationCoordinates::Quaternion => {
+            let rate = quaternion_rate_matrix(rotation.to_quaternion());
+            geometric
+                .rows_mut(0, 3)
+                .gemm(4.0, &rate.transpose(), &orientation, 0.0);
+        }
+    }
+    geometric
+        .rows_mut(3, 3)
+        .copy_from(&jacobian.rows(dimension, 3));
+}
+
+/// Dexterity measures of a Jacobian from its singular values
+/// \(\sigma_1 \ge \dots \ge \sigma_k\), \(k = \min(m, n)\).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Manipulability {
+    /// Yoshikawa measure \(\prod_i \sigma_i\), equal to
+    /// \(\sqrt{\det(J J^T)}\) for a full-row-rank redundant arm.
+    pub yoshikawa: f64,
+    /// \(\sigma_1 / \sigma_k\), infinite at a singularity.
+    pub condition_number: f64,
+    /// \(\sigma_k\), the smallest gain from joint to task velocity.
+    pub min_singular_value: f64,
+}
+
+impl Manipulability {
+    /// Whether the smallest singular value has dropped below `threshold`,
+    /// e.g. to switch a controller to damped least squares.
+    pub fn is_near_singularity(&self, threshold: f64) -> bool {
+        self.min_singular_value < threshold
+    }
+}
+
+/// [`Manipulability`] of a geometric (or any) Jacobian. An empty Jacobian
+/// has no dexterity: all measures are zero except an infinite condition
+/// number.
+pub fn manipulability(jacobian: &DMatrix<f64>) -> Manipulability {
+    let singular = jacobian.singular_values();
+    if singular.is_empty() {
+        return Manipulability {
+            yoshikawa: 0.0,
+            condition_number: f64::INFINITY,
+            min_singular_value: 0.0,
+        };
+    }
+    let (largest, smallest) = (singular.max(), singular.min());
+    Manipulability {
+        yoshikawa: singular.product(),
+        condition_number: if smallest > 0.0 {
+            largest / smallest
+        } else {
+            f64::INFINITY
+        },
+        min_singular_value: smallest,
+    }
+}
+
+/// Whether `jacobian` is within `threshold` of losing rank, judged by its
+/// smallest singular value.
+pub fn is_near_singularity(jacobian: &DMatrix<f64>, threshold: f64) -> bool {
+    manipulability(jacobian).is_near_singularity(threshold)
+}
+
+/// First-order task-space change \(J \delta q = [\delta\phi; \delta p]\) of a
+/// small joint perturbation under a geometric Jacobian.
+pub fn joint_to_task(jacobian: &DMatrix<f64>, joint_delta: &[f64]) -> [f64; 6] {
+    assert_eq!(jacobian.nrows(), 6, "geometric Jacobian must have 6 rows");
+    let task = jacobian * DVector::from_column_slice(joint_delta);
+    std::array::from_fn(|i| task[i])
+}
+
+/// Joint perturbation producing the task-space change `task_delta`, by
+/// damped least squares \(\sum_i \frac{\sigma_i}{\sigma_i^2 + \lambda^2} v_i u_i^T \delta x\)
+/// over the SVD of \(J\). With zero `damping` this is the minimum-norm
+/// pseudo-inverse solution, ignoring directions lost at a singularity.
+pub fn task_to_joint(jacobian: &DMatrix<f64>, task_delta: [f64; 6], damping: f64) -> Vec<f64> {
+    assert_eq!(jacobian.nrows(), 6, "geometric Jacobian must have 6 rows");
+    let svd = jacobian.clone().svd(true, true);
+    let (Some(u), Some(v_t)) = (svd.u, svd.v_t) else {
+        return vec![0.0; jacobian.ncols()];
+    };
+    let cutoff = PSEUDO_INVERSE_TOLERANCE * svd.singular_values.max();
+    let task = DVector::from_column_slice(&task_delta);
+    let mut joints = DVector::zeros(jacobian.ncols());
+    for (i, &sigma) in svd.singular_values.iter().enumerate() {
+        let gain = if damping > 0.0 {
+            sigma / (sigma * sigma + damping * damping)
+        } else if sigma > cutoff {
+            1.0 / sigma
+        } else {
+            0.0
+        };
+        joints += v_t.row(i).transpose() * (gain * u.column(i).dot(&task));
+    }
+    joints.as_slice().to_vec()
+}
+
+/// Apply a task-space change \([\delta\phi; \delta p]\) to a pose, rotating by
+/// \(\mathrm{Exp}(\delta\phi)\) in the world frame and shifting the origin by
+/// \(\delta p\).
+pub fn apply_task_delta(pose: &Se3, task_delta: [f64; 6]) -> Se3 {
+    let [wx, wy, wz, dx, dy, dz] = task_delta;
+    let rotation = So3::from_rotation_vector([wx, wy, wz]).compose(pose.rotation());
+    let [x, y, z] = pose.translation();
+    Se3::from_parts(rotation, [x + dx, y + dy, z + dz])
+}
+
+/// Task-space change \([\log(R_b R_a^T); p_b - p_a]\) taking pose `a` to
+/// `b`, the inverse of [`apply_task_delta`].
+pub fn task_delta(a: &Se3, b: &Se3) -> [f64; 6] {
+    let [rx, ry, rz] = b
+        .rotation()
+        .compose(&a.rotation().inverse())
+        .to_rotation_vector();
+    let [ax, ay, az] = a.translation();
+    let [bx, by, bz] = b.translation();
+    [rx, ry, rz, bx - ax, by - ay, bz - az]
+}
+
+/// Geometric Jacobian of `forward_kinematics` at `joints` by central
+/// differences of [`task_delta`] with the given joint `step`.
+pub fn finite_difference_jacobian(
+    forward_kinematics: impl Fn(&[f64]) -> Se3,
+    joints: &[f64],
+    step: f64,
+) -> DMatrix<f64> {
+    let mut jacobian = DMatrix::zeros(6, joints.len());
+    let mut perturbed = joints.to_vec();
+    for j in 0..joints.len() {
+        perturbed[j] = joints[j] + step;
+        let forward = forward_kinematics(&perturbed);
+        perturbed[j] = joints[j] - step;
+        let backward = forward_kinematics(&perturbed);
+        perturbed[j] = joints[j];
+        let column = task_delta(&backward, &forward);
+        for i in 0..6 {
+            jacobian[(i, j)] = column[i] / (2.0 * step);
+        }
+    }
+    jacobian
+}
+
+/// Largest entry-wise deviation of `jacobian` from the central-difference
+/// Jacobian of `forward_kinematics`, for checking a hand-derived Jacobian.
+pub fn jacobian_error(
+    forward_kinematics: impl Fn(&[f64]) -> Se3,
+    jacobian: &DMatrix<f64>,
+    joints: &[f64],
+    step: f64,
+) -> f64 {
+    let numeric = finite_difference_jacobian(forward_kinematics, joints, step);
+    assert_eq!(
+        jacobian.shape(),
+        numeric.shape(),
+        "Jacobian must be 6 x joints"
+    );
+    (jacobian - numeric).amax()
+}
+
+fn reshape(matrix: &mut DMatrix<f64>, rows: usize, cols: usize) {
+    if matrix.shape() != (rows, cols) {
+        *matrix = DMatrix::zeros(rows, cols);
+    }
+}
@@ -0,0 +1,442 @@
+use std::ops::ControlFlow;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::solver::{Budget, IterationInfo, SolveReport, SolveStatus, WarmStart, no_callback};
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-12;
+const INITIAL_DAMPING: f64 = 1e-3;
+const MIN_DAMPING: f64 = 1e-12;
+const MAX_DAMPING: f64 = 1e12;
+/// Tangent step of the central-difference Jacobian.
+const DIFFERENCE_STEP: f64 = 1e-6;
+/// Relative eigenvalue cutoff separating constrained from free directions.
+const ELIMINATION_TOLERANCE: f64 = 1e-12;
+
+/// A state updated by tangent increments, \(x \boxplus \delta\).
+///
+/// Groups are updated on the right in the body frame, as in
+/// [`crate::parameterization::Parameterization`]; vectors are updated by
+/// addition. Pairs of manifolds stack their increments.
+pub trait Manifold: Clone {
+    /// Dimension of the tangent space.
+    fn dimension(&self) -> usize;
+    /// \(x \boxplus \delta\) for an increment of length [`Manifold::dimension`].
+    fn plus(&self, delta: &[f64]) -> Self;
+}
+
+impl<const N: usize> Manifold for [f64; N] {
+    fn dimension(&self) -> usize {
+        N
+    }
+
+    fn plus(&self, delta: &[f64]) -> Self {
+        std::array::from_fn(|i| self[i] + delta[i])
+    }
+}
+
+impl Manifold for Vec<f64> {
+    fn dimension(&self) -> usize {
+        self.len()
+    }
+
+    fn plus(&self, delta: &[f64]) -> Self {
+        self.iter().zip(delta).map(|(x, d)| x + d).collect()
+    }
+}
+
+impl Manifold for So3 {
+    fn dimension(&self) -> usize {
+        3
+    }
+
+    /// \(R \,\mathrm{Exp}(\delta)\).
+    fn plus(&self, delta: &[f64]) -> Self {
+        self.compose(&So3::from_rotation_vector([delta[0], delta[1], delta[2]]))
+    }
+}
+
+impl Manifold for Se3 {
+    fn dimension(&self) -> usize {
+        6
+    }
+
+    /// \(T \,\mathrm{Exp}(\delta)\) with \(\delta = [\omega, v]\).
+    fn plus(&self, delta: &[f64]) -> Self {
+        let twist = std::array::from_fn(|i| delta[i]);
+        self.compose(&Se3::from_matrix(Se3::exp(twist, None)))
+    }
+}
+
+impl<A: Manifold, B: Manifold> Manifold for (A, B) {
+    fn dimension(&self) -> usize {
+        self.0.dimension() + self.1.dimension()
+    }
+
+    fn plus(&self, delta: &[f64]) -> Self {
+        let (first, second) = delta.split_at(self.0.dimension());
+        (self.0.plus(first), self.1.plus(second))
+    }
+}
+
+/// A nonlinear least-squares problem \(\min_x \lVert r(x) \rVert^2\) over a
+/// [`Manifold`].
+pub trait LeastSquaresProblem {
+    type State: Manifold;
+
+    /// Residual vector \(r(x)\); its length must not depend on the state.
+    fn residuals(&self, state: &Self::State) -> DVector<f64>;
+
+    /// \(\partial r(x \boxplus \delta) / \partial \delta\) at \(\delta = 0\).
+    /// Defaults to central differences; override with an analytic Jacobian
+    /// where one is available.
+    fn jacobian(&self, state: &Self::State) -> DMatrix<f64> {
+        let dimension = state.dimension();
+        let mut delta = vec![0.0; dimension];
+        let columns: Vec<DVector<f64>> = (0..dimension)
+            .map(|j| {
+                delta[j] = DIFFERENCE_STEP;
+                let forward = self.residuals(&state.plus(&delta));
+                delta[j] = -DIFFERENCE_STEP;
+                let backward = self.residuals(&state.plus(&delta));
+                delta[j] = 0.0;
+                (forward - backward) / (2.0 * DIFFERENCE_STEP)
+            })
+            .collect();
+        DMatrix::from_columns(&columns)
+    }
+}
+
+/// Minimize a small [`LeastSquaresProblem`] by Levenberg–Marquardt from
+/// `initial`, solving the dense damped normal equations each iteration.
+/// Intended for problems with up to around a hundred variables, such as
+/// calibration or pose refinement.
+///
+/// Returns [`Error::InsufficientData`] for an empty residual vector,
+/// [`Error::Singular`] for an unsolvable step and [`Error::NotConverged`]
+/// if the residuals are not finite.
+pub fn levenberg_marquardt<P: LeastSquaresProblem>(
+    problem: &P,
+    initial: &P::State,
+) -> Result<P::State> {
+    levenberg_marquardt_with(
+        problem,
+        initial,
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        no_callback,
+    )
+    .and_then(|report| match report.status {
+        SolveStatus::Singular => Err(Error::Singular),
+        _ => Ok(report.solution),
+    })
+}
+
+/// Bound and linear equality constraints on the tangent offset \(x\) of the
+/// state from the initial guess, \(x_{k+1} = x_k + \delta_k\) as the solver
+/// takes steps \(\delta_k\).
+///
+/// For vector states \(x\) is the coordinate change from the initial guess;
+/// for groups the accumulated steps are a first-order approximation of
+/// \(\mathrm{Log}(x_0^{-1} x)\). Bounds are enforced by projection with an
+/// active set, equalities \(C x = d\) by eliminating the constrained
+/// directions from each step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraints {
+    dimension: usize,
+    bounds: Vec<(usize, f64, f64)>,
+    equality_rows: Vec<Vec<f64>>,
+    equality_values: Vec<f64>,
+}
+
+impl Constraints {
+    /// No constraints on a tangent space of the given dimension.
+    pub fn new(dimension: usize) -> Self {
+        Self {
+            dimension,
+            bounds: Vec::new(),
+            equality_rows: Vec::new(),
+            equality_values: Vec::new(),
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Keep \(x_i\) in `[lower, upper]`; infinite bounds leave a side open.
+    /// A later bound on the same index replaces the earlier one.
+    pub fn with_bounds(mut self, index: usize, lower: f64, upper: f64) -> Self {
+        self.bounds.push((index, lower, upper));
+        self
+    }
+
+    /// Require \(c^\top x = d\) for a row `c` of length [`Constraints::dimension`].
+    pub fn with_equality(mut self, row: &[f64], value: f64) -> Self {
+        self.equality_rows.push(row.to_vec());
+        self.equality_values.push(value);
+        self
+    }
+
+    /// Check that every bound names a variable and is a non-empty interval,
+    /// returning [`Error::InvalidParameter`] otherwise, and that every
+    /// equality row has one coefficient per variable, returning
+    /// [`Error::DimensionMismatch`] otherwise.
+    pub fn validate(&self) -> Result<()> {
+        for &(index, lower, upper) in &self.bounds {
+            if index >= self.dimension {
+                return Err(Error::InvalidParameter(format!(
+                    "bound on variable {index} of {}",
+                    self.dimension
+                )));
+            }
+            if lower.is_nan() || upper.is_nan() || lower > upper {
+                return Err(Error::InvalidParameter(format!(
+                    "bounds [{lower}, {upper}] of variable {index} are empty"
+                )));
+            }
+        }
+        match self
+            .equality_rows
+            .iter()
+            .find(|row| row.len() != self.dimension)
+        {
+            Some(row) => Err(Error::DimensionMismatch {
+                expected: self.dimension,
+                provided: row.len(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn is_unconstrained(&self) -> bool {
+        self.equality_rows.is_empty()
+            && self
+                .bounds
+                .iter()
+                .all(|&(_, lower, upper)| lower == f64::NEG_INFINITY && upper == f64::INFINITY)
+    }
+
+    /// Minimizer of \(\tfrac{1}{2} \delta^\top H \delta + g^\top \delta\) with
+    /// \(C (x + \delta) = d\) and \(x + \delta\) within the bounds. Variables
+    /// whose step leaves the bounds are pinned to them and the step is solved
+    /// again until none does.
+    fn step(
+        &self,
+        offset: &DVector<f64>,
+        hessian: &DMatrix<f64>,
+        gradient: &DVector<f64>,
+    ) -> Result<DVector<f64>> {
+        let mut lower = vec![f64::NEG_INFINITY; self.dimension];
+        let mut upper = vec![f64::INFINITY; self.dimension];
+        for &(i, low, high) in &self.bounds {
+            lower[i] = low;
+            upper[i] = high;
+        }
+        let clamped = |i: usize, value: f64| value.clamp(lower[i], upper[i]);
+        let mut pinned: Vec<(usize, f64)> = Vec::new();
+        let step = loop {
+            let mut rows = Vec::new();
+            let mut targets = Vec::new();
+            for (row, value) in self.equality_rows.iter().zip(&self.equality_values) {
+                let current: f64 = row.iter().zip(offset.iter()).map(|(c, x)| c * x).sum();
+                rows.push(row.clone());
+                targets.push(value - current);
+            }
+            for &(i, target) in &pinned {
+                let mut row = vec![0.0; self.dimension()];
+                row[i] = 1.0;
+                rows.push(row);
+                targets.push(target);
+            }
+            let step = solve_eliminated(hessian, gradient, &rows, &targets)?;
+            let violated: Vec<(usize, f64)> = (0..self.dimension())
+                .filter(|i| pinned.iter().all(|(j, _)| j != i))
+                .filter_map(|i| {
+                    let value = offset[i] + step[i];
+                    let bounded = clamped(i, value);
+                    (bounded != value).then_some((i, bounded - offset[i]))
+                })
+                .collect();
+            if violated.is_empty() {
+                break step;
+            }
+            pinned.extend(violated);
+        };
+        Ok(DVector::from_fn(step.len(), |i, _| {
+            clamped(i, offset[i] + step[i]) - offset[i]
+        }))
+    }
+}
+
+/// Minimizer of \(\tfrac{1}{2} \delta^\top H \delta + g^\top \delta\) subject
+/// to \(R \delta = t\), by elimination: \(\delta = R^+ t + N z\) with \(N\) a
+/// basis of the null space of \(R\). Dependent rows (e.g. an equality on a
+/// pinned variable) are resolved in the least-squares sense.
+fn solve_eliminated(
+    hessian: &DMatrix<f64>,
+    gradient: &DVector<f64>,
+    rows: &[Vec<f64>],
+    targets: &[f64],
+) -> Result<DVector<f64>> {
+    if rows.is_empty() {
+        return hessian
+            .clone()
+            .lu()
+            .solve(&-gradient)
+            .ok_or(Error::Singular);
+    }
+    let n = hessian.nrows();
+    let matrix = DMatrix::from_fn(rows.len(), n, |k, j| rows[k][j]);
+    let gram = matrix.transpose() * &matrix;
+    let tolerance = ELIMINATION_TOLERANCE * gram.amax();
+    let particular = matrix
+        .pseudo_inverse(tolerance.sqrt())
+        .map_err(|_| Error::Singular)?
+        * DVector::from_column_slice(targets);
+    let eigen = gram.symmetric_eigen();
+    let free: Vec<_> = (0..n)
+        .filter(|&k| eigen.eigenvalues[k] <= tolerance)
+        .map(|k| eigen.eigenvectors.column(k).into_owned())
+        .collect();
+    if free.is_empty() {
+        return Ok(particular);
+    }
+    let basis = DMatrix::from_columns(&free);
+    let reduced = basis.transpose() * hessian * &basis;
+    let reduced_gradient = basis.transpose() * (gradient + hessian * &particular);
+    let z = reduced
+        .lu()
+        .solve(&-reduced_gradient)
+        .ok_or(Error::Singular)?;
+    Ok(particular + basis * z)
+}
+
+/// [`levenberg_marquardt`] within `budget`, resuming from the damping in
+/// `warm_start` and reporting every iteration to `callback`. When the budget
+/// runs out, the callback stops or a step cannot be solved, the best state so
+/// far is returned with the corresponding [`SolveStatus`]; the report cost is
+/// \(\lVert r \rVert^2\) at the returned state.
+pub fn levenberg_marquardt_with<P: LeastSquaresProblem>(
+    problem: &P,
+    initial: &P::State,
+    budget: &Budget,
+    warm_start: &WarmStart,
+    callback: impl FnMut(&P::State, &IterationInfo) -> ControlFlow<()>,
+) -> Result<SolveReport<P::State>> {
+    let constraints = Constraints::new(initial.dimension());
+    levenberg_marquardt_constrained(problem, initial, &constraints, budget, warm_start, callback)
+}
+
+/// [`levenberg_marquardt_with`] subject to `constraints`. The initial guess
+/// is first moved by the smallest tangent step that satisfies them, so it
+/// need not be feasible; every later step keeps the state feasible. Returns
+/// [`Error::Singular`] if that first step cannot be solved, since there is
+/// no feasible estimate yet, [`Error::DimensionMismatch`] if `constraints`
+/// are for another tangent dimension, and the errors of
+/// [`Constraints::validate`].
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn levenberg_marquardt_constrained<P: LeastSquaresProblem>(
+    problem: &P,
+    initial: &P::State,
+    constraints: &Constraints,
+    budget: &Budget,
+    warm_start: &WarmStart,
+    mut callback: impl FnMut(&P::State, &IterationInfo) -> ControlFlow<()>,
+) -> Result<SolveReport<P::State>> {
+    let dimension = initial.dimension();
+    if constraints.dimension() != dimension {
+        return Err(Error::DimensionMismatch {
+            expected: dimension,
+            provided: constraints.dimension(),
+        });
+    }
+    constraints.validate()?;
+    let clock = budget.start(MAX_ITERATIONS);
+    let mut offset = DVector::zeros(dimension);
+    let mut state = initial.clone();
+    if !constraints.is_unconstrained() {
+        offset = constraints.step(
+            &offset,
+            &DMatrix::identity(dimension, dimension),
+            &DVector::zeros(dimension),
+        )?;
+        state = state.plus(offset.as_slice());
+    }
+    let mut residuals = problem.residuals(&state);
+    if residuals.is_empty() {
+        return Err(Error::InsufficientData {
+            required: 1,
+            provided: 0,
+        });
+    }
+    let mut cost = residuals.norm_squared();
+    let mut damping = warm_start.damping.unwrap_or(INITIAL_DAMPING);
+    let mut iterations = 0;
+    let status = loop {
+        if let Some(status) = clock.exhausted(iterations) {
+            break status;
+        }
+        let jacobian = problem.jacobian(&state);
+        let normal = jacobian.transpose() * &jacobian;
+        let gradient = jacobian.transpose() * &residuals;
+        let mut damped = normal.clone();
+        for k in 0..damped.nrows() {
+            damped[(k, k)] += damping * normal[(k, k)].max(MIN_DAMPING);
+        }
+        // The step only fails on singular systems.
+        let Ok(step) = constraints.step(&offset, &damped, &gradient) else {
+            break SolveStatus::Singular;
+        };
+        let candidate = state.plus(step.as_slice());
+        let candidate_residuals = problem.residuals(&candidate);
+        let candidate_cost = candidate_residuals.norm_squared();
+        trace_event!(
+            debug,
+            cost,
+            candidate_cost,
+            damping,
+            step_norm = step.norm(),
+            "levenberg-marquardt step"
+        );
+        let accepted = candidate_cost <= cost;
+        if accepted {
+            state = candidate;
+            offset += &step;
+            residuals = candidate_residuals;
+            cost = candidate_cost;
+            damping = (damping * 0.1).max(MIN_DAMPING);
+        } else {
+            damping *= 10.0;
+        }
+        let info = IterationInfo {
+            iteration: iterations,
+            cost,
+            step_norm: step.norm(),
+        };
+        iterations += 1;
+        if callback(&state, &info).is_break() {
+            break SolveStatus::Stopped;
+        }
+        if (accepted && info.step_norm < TOLERANCE) || damping > MAX_DAMPING {
+            break SolveStatus::Converged;
+        }
+    };
+    if !cost.is_finite() {
+        return Err(Error::NotConverged { iterations });
+    }
+    Ok(SolveReport {
+        solution: state,
+        status,
+        iterations,
+        cost,
+        warm_start: WarmStart {
+            damping: Some(damping.clamp(MIN_DAMPING, INITIAL_DAMPING)),
+            inverse_jacobian: None,
+        },
+    })
+}
@@ -1,12 +1,70 @@
+/// Emit a `tracing` event when the `tracing` feature is enabled; expands to
+/// nothing otherwise, so instrumented loops cost nothing by default.
+macro_rules! trace_event {
+    ($level:ident, $($field:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($field)+);
+    };
+}
+
+pub mod allocation;
+pub mod averaging;
+pub mod binary;
+pub mod camera;
 pub mod cmtm;
+pub mod control;
+pub mod debug;
+pub mod directional;
+pub mod dynamics;
+pub mod epipolar;
+pub mod error;
+pub mod estimation;
+pub mod frame;
+pub mod health;
+pub mod homography;
+pub mod integrate;
+pub mod jacobian;
+pub mod least_squares;
 pub mod lie;
+pub mod mjcf;
+pub mod mobile;
+pub mod motion;
+pub mod parallel;
+pub mod parameterization;
+pub mod pnp;
+pub mod quat;
+pub mod ransac;
+pub mod recording;
+pub mod registration;
+pub mod rig;
+pub mod robot;
+pub mod screw;
+pub mod sdf;
+pub mod se2;
+pub mod se23;
 pub mod se3;
+pub mod signal;
+pub mod sim;
+pub mod so2;
 pub mod so3;
+pub mod solver;
+pub mod stereo;
+pub mod timesync;
+pub mod trajectory;
+pub mod urdf;
 pub mod util;
+mod xml;
 
-pub use cmtm::{Cmtm, RotationalCmtm, SpatialCmtm};
-pub use se3::Se3;
-pub use so3::So3;
+pub use cmtm::{ArcCmtm, Cmtm, CmtmN, RotationalCmtm, SpatialCmtm};
+pub use error::Error;
+pub use health::{CmtmHealth, TransformHealth};
+pub use quat::Quat;
+pub use rig::{SensorExtrinsic, SensorRig};
+pub use se2::Se2;
+pub use se3::{ArcSe3, Se3};
+pub use se23::Se23;
+pub use so2::So2;
+pub use so3::{ArcSo3, EulerOrder, LookAtConvention, So3};
 
 pub use cmtm::Cmtm as RustCmtm;
 pub use se3::Se3 as RustSe3;
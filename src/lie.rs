@@ -13,14 +13,22 @@ pub trait HasAdjoint<const ADJ_DIM: usize> {
     fn adjoint_matrix(&self) -> SMatrix<f64, ADJ_DIM, ADJ_DIM>;
 }
 
+/// Exponential map from the Lie algebra, identified with
+/// \(\mathbb{R}^{\text{ALG\_DIM}}\) through the same ordering as `vee`.
+pub trait HasExp<const ALG_DIM: usize>: Sized {
+    /// Group element \(\exp(\xi^\wedge)\).
+    fn exp_map(xi: &SVector<f64, ALG_DIM>) -> Self;
+    /// Lie bracket \([a, b] = (a^\wedge b^\wedge - b^\wedge a^\wedge)^\vee\).
+    fn bracket(a: &SVector<f64, ALG_DIM>, b: &SVector<f64, ALG_DIM>) -> SVector<f64, ALG_DIM>;
+}
+
 /// Apply a matrix-valued group action to a vector using static dimensions.
+#[inline]
 pub fn apply_linear<const DIM: usize>(
     matrix: &SMatrix<f64, DIM, DIM>,
     vector: [f64; DIM],
 ) -> [f64; DIM] {
-    let vec = SVector::<f64, DIM>::from_row_slice(&vector);
-    let result = matrix * vec;
-    result.into()
+    (matrix * SVector::<f64, DIM>::from(vector)).into()
 }
 
 /// Convert a statically sized matrix into a nested array for FFI-friendly use.
@@ -33,3 +41,32 @@ pub fn matrix_to_array<const DIM: usize>(matrix: &SMatrix<f64, DIM, DIM>) -> [[f
     }
     array
 }
+
+/// Flatten a statically sized matrix in row-major order, so element `(r, c)`
+/// lands at index `r * DIM + c`. `LEN` must equal `DIM * DIM`.
+pub fn matrix_to_row_major<const DIM: usize, const LEN: usize>(
+    matrix: &SMatrix<f64, DIM, DIM>,
+) -> [f64; LEN] {
+    const { assert!(LEN == DIM * DIM, "LEN must equal DIM * DIM") };
+    let mut flat = [0.0_f64; LEN];
+    for r in 0..DIM {
+        for c in 0..DIM {
+            flat[r * DIM + c] = matrix[(r, c)];
+        }
+    }
+    flat
+}
+
+/// Flatten a statically sized matrix in column-major order, so element
+/// `(r, c)` lands at index `c * DIM + r`. This is the layout expected by
+/// OpenGL, Eigen's default storage and BLAS. `LEN` must equal `DIM * DIM`.
+pub fn matrix_to_col_major<const DIM: usize, const LEN: usize>(
+    matrix: &SMatrix<f64, DIM, DIM>,
+) -> [f64; LEN] {
+    const { assert!(LEN == DIM * DIM, "LEN must equal DIM * DIM") };
+    let mut flat = [0.0_f64; LEN];
+    // nalgebra stores matrices column-major, so the backing slice already
+    // has the requested layout.
+    flat.copy_from_slice(matrix.as_slice());
+    flat
+}
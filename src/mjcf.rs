@@ -0,0 +1,295 @@
+//! Import of MuJoCo MJCF models into a [`RobotDescription`].
+//!
+//! The supported subset covers the kinematic tree and mass properties:
+//! nested `<body>` elements with `pos` and one of `quat`, `axisangle` or
+//! `euler`; `hinge` and `slide` joints with `pos`, `axis` and `range`;
+//! `<inertial>` with `diaginertia` or `fullinertia`; and the `angle` and
+//! `eulerseq` settings of `<compiler>`. A `free` joint is accepted on a single
+//! top-level body, which then becomes the floating root. Default classes are
+//! not expanded and mass is not inferred from geoms.
+
+use nalgebra::{Matrix3, Vector3};
+use roxmltree::Node;
+
+use crate::error::Result;
+use crate::lie::LieGroup;
+use crate::robot::{
+    DescriptionFormat, Inertial, Joint, JointKind, JointLimits, Link, RobotDescription,
+};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::xml::{self, attribute, child, children, invalid};
+
+/// Name of the link standing for MJCF's `<worldbody>`.
+pub const WORLD_LINK: &str = "world";
+
+/// The MuJoCo MJCF format, for use with [`RobotDescription::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mjcf;
+
+impl DescriptionFormat for Mjcf {
+    fn parse(text: &str) -> Result<RobotDescription> {
+        parse(text)
+    }
+}
+
+/// Parse an MJCF document into a validated [`RobotDescription`].
+///
+/// Each body becomes a link. A body with several joints is split into a
+/// chain of massless intermediate links named `"<body>::<joint>"`, and a
+/// body without joints is attached by a fixed joint named `"<body>_fixed"`.
+/// Link frames coincide with the MJCF body frames except that they are
+/// shifted to the anchor of the body's last joint, so that each joint acts at
+/// its child link's origin.
+pub fn parse(text: &str) -> Result<RobotDescription> {
+    let document = xml::parse(text)?;
+    let root = document.root_element();
+    if !root.has_tag_name("mujoco") {
+        return Err(invalid("root element must be <mujoco>"));
+    }
+    let name = root.attribute("model").unwrap_or("mujoco");
+
+    let compiler = child(root, "compiler");
+    let degrees = match compiler.and_then(|c| c.attribute("angle")) {
+        None | Some("degree") => true,
+        Some("radian") => false,
+        Some(other) => return Err(invalid(format!("unknown compiler angle '{other}'"))),
+    };
+    let euler_sequence = compiler
+        .and_then(|c| c.attribute("eulerseq"))
+        .unwrap_or("xyz");
+    let worldbody =
+        child(root, "worldbody").ok_or_else(|| invalid("missing <worldbody> element"))?;
+
+    let mut builder = Builder {
+        links: Vec::new(),
+        joints: Vec::new(),
+        degrees,
+        euler_sequence,
+        unnamed: 0,
+    };
+
+    let top: Vec<Node> = children(worldbody, "body").collect();
+    match top.as_slice() {
+        [body] if is_floating(*body) => builder.body(*body, None)?,
+        _ => {
+            builder.links.push(Link::new(WORLD_LINK, None));
+            for body in top {
+                builder.body(body, Some((WORLD_LINK.to_string(), Vector3::zeros())))?;
+            }
+        }
+    }
+
+    RobotDescription::new(name, builder.links, builder.joints)
+}
+
+fn is_floating(body: Node) -> bool {
+    child(body, "freejoint").is_some()
+        || children(body, "joint").any(|joint| joint.attribute("type") == Some("free"))
+}
+
+struct Builder<'a> {
+    links: Vec<Link>,
+    joints: Vec<Joint>,
+    degrees: bool,
+    euler_sequence: &'a str,
+    unnamed: usize,
+}
+
+impl Builder<'_> {
+    fn angle(&self, value: f64) -> f64 {
+        if self.degrees {
+            value.to_radians()
+        } else {
+            value
+        }
+    }
+
+    /// Add `body` and its subtree. `parent` is the parent link and the offset
+    /// of that link's origin from the parent body origin, in body axes; `None`
+    /// makes `body` the floating root.
+    fn body(&mut self, body: Node, parent: Option<(String, Vector3<f64>)>) -> Result<()> {
+        let name = match body.attribute("name") {
+            Some(name) => name.to_string(),
+            None => {
+                self.unnamed += 1;
+                format!("body{}", self.unnamed)
+            }
+        };
+
+        let mut offset = Vector3::zeros();
+        if let Some((parent_link, parent_offset)) = parent {
+            let position = Vector3::from(attribute::<3>(body, "pos")?.unwrap_or([0.0; 3]));
+            let pose = Se3::from_parts(self.orientation(body)?, (position - parent_offset).into());
+
+            let joints: Vec<Node> = children(body, "joint").collect();
+            if joints.is_empty() {
+                self.joints.push(Joint::new(
+                    format!("{name}_fixed"),
+                    parent_link,
+                    name.clone(),
+                    pose,
+                    JointKind::Fixed,
+                ));
+            } else {
+                let mut previous_link = parent_link;
+                let mut previous_anchor = Vector3::zeros();
+                for (i, joint) in joints.iter().enumerate() {
+                    let joint_name = match joint.attribute("name") {
+                        Some(joint_name) => joint_name.to_string(),
+                        None => format!("{name}_joint{i}"),
+                    };
+                    let anchor = Vector3::from(attribute::<3>(*joint, "pos")?.unwrap_or([0.0; 3]));
+                    let step = Se3::from_parts(So3::identity(), (anchor - previous_anchor).into());
+                    let origin = if i == 0 { pose.compose(&step) } else { step };
+                    let child_link = if i + 1 == joints.len() {
+                        name.clone()
+                    } else {
+                        let intermediate = format!("{name}::{joint_name}");
+                        self.links.push(Link::new(intermediate.clone(), None));
+                        intermediate
+                    };
+                    let mut description = Joint::new(
+                        joint_name,
+                        previous_link,
+                        child_link.clone(),
+                        origin,
+                        self.joint_kind(*joint)?,
+                    );
+                    description.limits = self.joint_limits(*joint)?;
+                    self.joints.push(description);
+                    previous_link = child_link;
+                    previous_anchor = anchor;
+                }
+                offset = previous_anchor;
+            }
+        } else if children(body, "joint").any(|joint| joint.attribute("type") != Some("free")) {
+            return Err(invalid(format!(
+                "floating body '{name}' cannot carry further joints"
+            )));
+        }
+
+        self.links
+            .push(Link::new(name.clone(), self.inertial(body, offset)?));
+        for nested in children(body, "body") {
+            self.body(nested, Some((name.clone(), offset)))?;
+        }
+        Ok(())
+    }
+
+    /// Orientation of a body or inertial frame from `quat`, `axisangle` or
+    /// `euler`.
+    fn orientation(&self, node: Node) -> Result<So3> {
+        for unsupported in ["xyaxes", "zaxis"] {
+            if node.attribute(unsupported).is_some() {
+                return Err(invalid(format!(
+                    "orientation attribute '{unsupported}' is not supported"
+                )));
+            }
+        }
+        let quat = attribute::<4>(node, "quat")?;
+        let axis_angle = attribute::<4>(node, "axisangle")?;
+        let euler = attribute::<3>(node, "euler")?;
+        match (quat, axis_angle, euler) {
+            (None, None, None) => Ok(So3::identity()),
+            (Some(quat), None, None) => So3::try_from_quaternion(quat),
+            (None, Some([x, y, z, angle]), None) => {
+                Ok(So3::from_axis_angle([x, y, z], self.angle(angle)))
+            }
+            (None, None, Some(angles)) => {
+                // MJCF uses lower case for axes that move with the frame, the
+                // opposite of the convention taken by `So3::from_sequence`.
+                let sequence: String = self
+                    .euler_sequence
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_lowercase() {
+                            c.to_ascii_uppercase()
+                        } else {
+                            c.to_ascii_lowercase()
+                        }
+                    })
+                    .collect();
+                So3::from_sequence(&sequence, &angles.map(|a| self.angle(a)))
+            }
+            _ => Err(invalid(format!(
+                "<{}> specifies more than one orientation",
+                node.tag_name().name()
+            ))),
+        }
+    }
+
+    fn joint_kind(&self, joint: Node) -> Result<JointKind> {
+        let axis = Vector3::from(attribute::<3>(joint, "axis")?.unwrap_or([0.0, 0.0, 1.0]));
+        if axis.norm() == 0.0 {
+            return Err(invalid("joint axis has zero length"));
+        }
+        let axis = axis.normalize().into();
+        let limited = self.is_limited(joint)?;
+        match joint.attribute("type").unwrap_or("hinge") {
+            "hinge" if limited => Ok(JointKind::Revolute { axis }),
+            "hinge" => Ok(JointKind::Continuous { axis }),
+            "slide" => Ok(JointKind::Prismatic { axis }),
+            other => Err(invalid(format!(
+                "joint type '{other}' is not supported here"
+            ))),
+        }
+    }
+
+    fn is_limited(&self, joint: Node) -> Result<bool> {
+        match joint.attribute("limited").unwrap_or("auto") {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            "auto" => Ok(joint.attribute("range").is_some()),
+            other => Err(invalid(format!("unknown joint limited value '{other}'"))),
+        }
+    }
+
+    fn joint_limits(&self, joint: Node) -> Result<Option<JointLimits>> {
+        if !self.is_limited(joint)? {
+            return Ok(None);
+        }
+        let [lower, upper] =
+            attribute::<2>(joint, "range")?.ok_or_else(|| invalid("limited joint has no range"))?;
+        let angular = joint.attribute("type").unwrap_or("hinge") == "hinge";
+        let convert = |value: f64| if angular { self.angle(value) } else { value };
+        Ok(Some(JointLimits {
+            lower: convert(lower),
+            upper: convert(upper),
+            velocity: None,
+            effort: None,
+        }))
+    }
+
+    /// Mass properties of `body` in its link frame, whose origin sits at
+    /// `offset` in body coordinates.
+    fn inertial(&self, body: Node, offset: Vector3<f64>) -> Result<Option<Inertial>> {
+        let Some(inertial) = child(body, "inertial") else {
+            return Ok(None);
+        };
+        let mass = attribute::<1>(inertial, "mass")?
+            .ok_or_else(|| invalid("<inertial> requires a mass"))?[0];
+        let position = Vector3::from(attribute::<3>(inertial, "pos")?.unwrap_or([0.0; 3]));
+        let principal = match (
+            attribute::<3>(inertial, "diaginertia")?,
+            attribute::<6>(inertial, "fullinertia")?,
+        ) {
+            (Some(diagonal), None) => Matrix3::from_diagonal(&Vector3::from(diagonal)),
+            (None, Some([xx, yy, zz, xy, xz, yz])) => {
+                Matrix3::new(xx, xy, xz, xy, yy, yz, xz, yz, zz)
+            }
+            _ => {
+                return Err(invalid(
+                    "<inertial> requires exactly one of diaginertia and fullinertia",
+                ));
+            }
+        };
+        let rotation = *self.orientation(inertial)?.rotation().matrix();
+        let inertia = rotation * principal * rotation.transpose();
+        Ok(Some(Inertial {
+            mass,
+            center_of_mass: (position - offset).into(),
+            inertia: std::array::from_fn(|r| std::array::from_fn(|c| inertia[(r, c)])),
+        }))
+    }
+}
@@ -0,0 +1,236 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::error::{Error, Result};
+
+/// Velocity of a point at `position` on a planar body moving with body twist
+/// `twist` \([\omega, v_x, v_y]\): \(v + \omega \times r\).
+pub fn point_velocity(twist: [f64; 3], position: [f64; 2]) -> [f64; 2] {
+    let [omega, vx, vy] = twist;
+    let [x, y] = position;
+    [vx - omega * y, vy + omega * x]
+}
+
+/// Steering angle (rad, from the body X axis) and signed wheel speed of one
+/// swerve module.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModuleState {
+    pub angle: f64,
+    pub speed: f64,
+}
+
+impl ModuleState {
+    /// The equivalent state (same ground velocity) whose steering angle is
+    /// closest to `current_angle`, flipping the wheel by 180° and reversing
+    /// its speed when that is shorter.
+    pub fn optimized(self, current_angle: f64) -> Self {
+        let difference = wrap_angle(self.angle - current_angle);
+        if difference.abs() > std::f64::consts::FRAC_PI_2 {
+            Self {
+                angle: wrap_angle(self.angle + std::f64::consts::PI),
+                speed: -self.speed,
+            }
+        } else {
+            self
+        }
+    }
+}
+
+/// A swerve drive with independently steered and driven modules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwerveDrive {
+    modules: Vec<[f64; 2]>,
+    max_speed: f64,
+}
+
+impl SwerveDrive {
+    /// Modules at `positions` in the body frame, each limited to `max_speed`.
+    pub fn new(positions: Vec<[f64; 2]>, max_speed: f64) -> Self {
+        Self {
+            modules: positions,
+            max_speed,
+        }
+    }
+
+    pub fn positions(&self) -> &[[f64; 2]] {
+        &self.modules
+    }
+
+    /// Module states realizing the body twist \([\omega, v_x, v_y]\). Modules
+    /// that do not need to move report angle zero and speed zero.
+    pub fn module_states(&self, twist: [f64; 3]) -> Vec<ModuleState> {
+        self.modules
+            .iter()
+            .map(|&position| {
+                let [vx, vy] = point_velocity(twist, position);
+                let speed = vx.hypot(vy);
+                let angle = if speed > 0.0 { vy.atan2(vx) } else { 0.0 };
+                ModuleState { angle, speed }
+            })
+            .collect()
+    }
+
+    /// Least-squares body twist from measured module states (one per module),
+    /// e.g. for odometry. Returns [`Error::InsufficientData`] unless there is
+    /// one state per module, and [`Error::Singular`] with fewer than two
+    /// modules, which cannot observe the rotation rate.
+    pub fn body_twist(&self, states: &[ModuleState]) -> Result<[f64; 3]> {
+        check_measurements(self.modules.len(), states.len())?;
+        let rows = self
+            .modules
+            .iter()
+            .flat_map(|&[x, y]| [[-y, 1.0, 0.0], [x, 0.0, 1.0]]);
+        let velocities = states.iter().flat_map(|s| {
+            let (sin, cos) = s.angle.sin_cos();
+            [s.speed * cos, s.speed * sin]
+        });
+        solve_planar(rows.collect(), velocities.collect())
+    }
+
+    /// Whether every module stays within the speed limit.
+    pub fn is_feasible(&self, twist: [f64; 3]) -> bool {
+        self.module_states(twist)
+            .iter()
+            .all(|s| s.speed <= self.max_speed)
+    }
+
+    /// Uniformly scale `twist` down (keeping its direction and the path
+    /// curvature) so that no module exceeds the speed limit.
+    pub fn desaturate(&self, twist: [f64; 3]) -> [f64; 3] {
+        let fastest = self
+            .module_states(twist)
+            .iter()
+            .map(|s| s.speed)
+            .fold(0.0, f64::max);
+        scale_to_limit(twist, fastest, self.max_speed)
+    }
+}
+
+/// A wheel with passive rollers (omni or mecanum) that drives along its
+/// heading.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OmniWheel {
+    /// Contact point in the body frame.
+    pub position: [f64; 2],
+    /// Drive direction angle from the body X axis.
+    pub heading: f64,
+    /// Angle of the free roller direction from the wheel axle: 0 for omni
+    /// wheels, ±45° for mecanum wheels.
+    pub roller_angle: f64,
+}
+
+impl OmniWheel {
+    pub fn new(position: [f64; 2], heading: f64, roller_angle: f64) -> Self {
+        Self {
+            position,
+            heading,
+            roller_angle,
+        }
+    }
+
+    /// Row of the kinematic matrix mapping the body twist to this wheel's
+    /// rim speed: the contact velocity along the heading minus the part the
+    /// rollers absorb, \(s = v \cdot d - \tan\gamma\, v \cdot a\).
+    fn row(&self) -> [f64; 3] {
+        let (sin, cos) = self.heading.sin_cos();
+        let tan = self.roller_angle.tan();
+        // Drive direction d = (cos, sin), axle a = (-sin, cos).
+        let direction = [cos + tan * sin, sin - tan * cos];
+        let [x, y] = self.position;
+        [
+            direction[1] * x - direction[0] * y,
+            direction[0],
+            direction[1],
+        ]
+    }
+}
+
+/// A holonomic base driven by omni or mecanum wheels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OmniDrive {
+    wheels: Vec<OmniWheel>,
+    max_speed: f64,
+}
+
+impl OmniDrive {
+    pub fn new(wheels: Vec<OmniWheel>, max_speed: f64) -> Self {
+        Self { wheels, max_speed }
+    }
+
+    pub fn wheels(&self) -> &[OmniWheel] {
+        &self.wheels
+    }
+
+    /// Rim speed of each wheel for the body twist \([\omega, v_x, v_y]\).
+    pub fn wheel_speeds(&self, twist: [f64; 3]) -> Vec<f64> {
+        self.wheels
+            .iter()
+            .map(|wheel| {
+                let row = wheel.row();
+                row[0] * twist[0] + row[1] * twist[1] + row[2] * twist[2]
+            })
+            .collect()
+    }
+
+    /// Least-squares body twist from measured wheel speeds (one per wheel).
+    /// Returns [`Error::InsufficientData`] unless there is one speed per
+    /// wheel, and [`Error::Singular`] if the wheel layout cannot observe every
+    /// component of the twist.
+    pub fn body_twist(&self, speeds: &[f64]) -> Result<[f64; 3]> {
+        check_measurements(self.wheels.len(), speeds.len())?;
+        solve_planar(
+            self.wheels.iter().map(OmniWheel::row).collect(),
+            speeds.to_vec(),
+        )
+    }
+
+    pub fn is_feasible(&self, twist: [f64; 3]) -> bool {
+        self.wheel_speeds(twist)
+            .iter()
+            .all(|s| s.abs() <= self.max_speed)
+    }
+
+    /// Uniformly scale `twist` down so that no wheel exceeds the speed limit.
+    pub fn desaturate(&self, twist: [f64; 3]) -> [f64; 3] {
+        let fastest = self
+            .wheel_speeds(twist)
+            .iter()
+            .map(|s| s.abs())
+            .fold(0.0, f64::max);
+        scale_to_limit(twist, fastest, self.max_speed)
+    }
+}
+
+fn scale_to_limit(twist: [f64; 3], fastest: f64, limit: f64) -> [f64; 3] {
+    if fastest > limit {
+        twist.map(|v| v * limit / fastest)
+    } else {
+        twist
+    }
+}
+
+fn check_measurements(required: usize, provided: usize) -> Result<()> {
+    if provided == required {
+        Ok(())
+    } else {
+        Err(Error::InsufficientData { required, provided })
+    }
+}
+
+/// Solve the overdetermined system `rows · twist = values`, which have equal
+/// lengths, in the least-squares sense.
+fn solve_planar(rows: Vec<[f64; 3]>, values: Vec<f64>) -> Result<[f64; 3]> {
+    let matrix = DMatrix::from_fn(rows.len(), 3, |r, c| rows[r][c]);
+    let svd = matrix.svd(true, true);
+    if svd.rank(1e-12) < 3 {
+        return Err(Error::Singular);
+    }
+    let twist = svd
+        .solve(&DVector::from_vec(values), 1e-12)
+        .map_err(|_| Error::Singular)?;
+    Ok([twist[0], twist[1], twist[2]])
+}
+
+fn wrap_angle(angle: f64) -> f64 {
+    use std::f64::consts::PI;
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
@@ -0,0 +1,289 @@
+use nalgebra::{DVector, Matrix6, SMatrix, Vector3, Vector6};
+use serde::{Deserialize, Serialize};
+
+use crate::cmtm::Cmtm;
+use crate::error::{Error, Result};
+use crate::lie::{HasAdjoint, HasExp};
+use crate::se3::Se3;
+
+/// Order of the CMTM equivalent to a [`MotionState`].
+const MOTION_STATE_ORDER: usize = 3;
+
+/// Covariance of a [`MotionState`] error \([\delta\xi, \delta V, \delta\dot{V}]\),
+/// where the pose error is a right perturbation \(T \exp(\delta\xi)\).
+pub type MotionCovariance = SMatrix<f64, 18, 18>;
+
+/// Motion of a rigidly mounted sensor, expressed in the sensor frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensorMotion {
+    /// Angular velocity, as measured by a gyroscope.
+    pub angular_velocity: [f64; 3],
+    /// Angular acceleration.
+    pub angular_acceleration: [f64; 3],
+    /// Velocity of the sensor origin.
+    pub velocity: [f64; 3],
+    /// Classical (non-spatial) acceleration of the sensor origin, as
+    /// measured by an accelerometer before gravity is added.
+    pub acceleration: [f64; 3],
+}
+
+/// Pose of a rigid body together with its body twist \([\omega, v]\) and the
+/// time derivative of that twist.
+///
+/// This is the user-facing form of an order-3 SE(3) [`Cmtm`], which stores
+/// the same quantities as the adjoint of `pose` and its derivative vectors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MotionState {
+    pub pose: Se3,
+    /// Body twist \([\omega, v]\).
+    pub twist: [f64; 6],
+    /// Time derivative \(\dot{V}\) of the body twist; see
+    /// [`transform_acceleration`] for the relation to the classical
+    /// acceleration.
+    pub acceleration: [f64; 6],
+}
+
+impl MotionState {
+    pub fn new(pose: Se3, twist: [f64; 6], acceleration: [f64; 6]) -> Self {
+        Self {
+            pose,
+            twist,
+            acceleration,
+        }
+    }
+
+    /// A body at rest at `pose`.
+    pub fn at_rest(pose: Se3) -> Self {
+        Self::new(pose, [0.0; 6], [0.0; 6])
+    }
+
+    /// The order-3 CMTM carrying the twist and its rate as derivatives.
+    pub fn to_cmtm(&self) -> Cmtm {
+        Cmtm::from_se3_with_derivatives(&self.pose, vec![self.twist, self.acceleration])
+    }
+
+    /// Read a motion state back from an SE(3) CMTM. Derivatives the CMTM does
+    /// not carry are zero; a CMTM of order above 3 would lose its higher
+    /// derivatives and is rejected with [`Error::OrderOutOfRange`].
+    pub fn from_cmtm(cmtm: &Cmtm) -> Result<Self> {
+        if cmtm.order() > MOTION_STATE_ORDER {
+            return Err(Error::OrderOutOfRange {
+                requested: cmtm.order(),
+                available: MOTION_STATE_ORDER,
+            });
+        }
+        let derivative = |i: usize| {
+            cmtm.derivatives()
+                .get(i)
+                .map_or([0.0; 6], |vector| (*vector).into())
+        };
+        Ok(Self::new(cmtm.to_se3(), derivative(0), derivative(1)))
+    }
+
+    /// Advance the state by `dt` with the acceleration held constant, so the
+    /// twist grows to \(V + \dot{V}\,dt\) and the pose moves to
+    /// \(T \exp(\Theta)\) with the Magnus expansion truncated after the
+    /// commutator term,
+    /// \(\Theta = V\,dt + \tfrac{1}{2}\dot{V}\,dt^2 + \tfrac{1}{12}[V, \dot{V}]\,dt^3\).
+    pub fn propagate(&self, dt: f64) -> Self {
+        let step = Se3::exp_map(&self.increment(dt));
+        self.advance(&step, dt)
+    }
+
+    /// [`MotionState::propagate`] together with the covariance prediction
+    /// \(F P F^T + Q\), where `process_noise` is \(Q\) (see
+    /// [`constant_acceleration_noise`]) and \(F\) linearizes the step with
+    /// \(J_r(\Theta) \approx I - \tfrac{1}{2}\mathrm{ad}_\Theta\).
+    pub fn propagate_with_covariance(
+        &self,
+        dt: f64,
+        covariance: &MotionCovariance,
+        process_noise: &MotionCovariance,
+    ) -> (Self, MotionCovariance) {
+        let theta = self.increment(dt);
+        let step = Se3::exp_map(&theta);
+        let jacobian = Matrix6::identity() - ad_matrix(&theta) * 0.5;
+
+        let mut transition = MotionCovariance::identity();
+        transition
+            .fixed_view_mut::<6, 6>(0, 0)
+            .copy_from(&step.inverse().adjoint_matrix());
+        transition
+            .fixed_view_mut::<6, 6>(0, 6)
+            .copy_from(&(jacobian * dt));
+        transition
+            .fixed_view_mut::<6, 6>(0, 12)
+            .copy_from(&(jacobian * (0.5 * dt * dt)));
+        transition
+            .fixed_view_mut::<6, 6>(6, 12)
+            .copy_from(&(Matrix6::identity() * dt));
+
+        let predicted = transition * covariance * transition.transpose() + process_noise;
+        (self.advance(&step, dt), predicted)
+    }
+
+    fn increment(&self, dt: f64) -> Vector6<f64> {
+        let twist = Vector6::from(self.twist);
+        let acceleration = Vector6::from(self.acceleration);
+        twist * dt
+            + acceleration * (0.5 * dt * dt)
+            + Se3::bracket(&twist, &acceleration) * (dt * dt * dt / 12.0)
+    }
+
+    fn advance(&self, step: &Se3, dt: f64) -> Self {
+        Self::new(
+            self.pose.compose(step),
+            std::array::from_fn(|i| self.twist[i] + self.acceleration[i] * dt),
+            self.acceleration,
+        )
+    }
+}
+
+/// Process noise of [`MotionState::propagate_with_covariance`] for a white
+/// jerk with spectral density `jerk_density` on each twist component, the
+/// per-axis \(q\,[dt^5/20, dt^4/8, dt^3/6; dt^4/8, dt^3/3, dt^2/2; dt^3/6, dt^2/2, dt]\)
+/// of the continuous constant-acceleration model.
+pub fn constant_acceleration_noise(dt: f64, jerk_density: [f64; 6]) -> MotionCovariance {
+    let blocks = [
+        [dt.powi(5) / 20.0, dt.powi(4) / 8.0, dt.powi(3) / 6.0],
+        [dt.powi(4) / 8.0, dt.powi(3) / 3.0, dt * dt / 2.0],
+        [dt.powi(3) / 6.0, dt * dt / 2.0, dt],
+    ];
+    let mut noise = MotionCovariance::zeros();
+    for (axis, density) in jerk_density.into_iter().enumerate() {
+        for (r, row) in blocks.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                noise[(6 * r + axis, 6 * c + axis)] = density * value;
+            }
+        }
+    }
+    noise
+}
+
+/// Matrix of \(\mathrm{ad}_\xi\), built column by column from the bracket.
+fn ad_matrix(xi: &Vector6<f64>) -> Matrix6<f64> {
+    let mut ad = Matrix6::zeros();
+    for i in 0..6 {
+        ad.set_column(i, &Se3::bracket(xi, &Vector6::ith(i, 1.0)));
+    }
+    ad
+}
+
+impl From<&MotionState> for Cmtm {
+    fn from(state: &MotionState) -> Self {
+        state.to_cmtm()
+    }
+}
+
+impl TryFrom<&Cmtm> for MotionState {
+    type Error = Error;
+
+    fn try_from(cmtm: &Cmtm) -> Result<Self> {
+        Self::from_cmtm(cmtm)
+    }
+}
+
+/// Motion observed by a sensor at `body_from_sensor` on a body moving with
+/// body twist `twist` \([\omega, v]\) and its time derivative `twist_rate`.
+///
+/// The sensor twist and its rate are obtained with the order-2 CMTM of the
+/// constant mounting transform; the classical acceleration then adds the
+/// \(\omega \times v\) term, which expands to the familiar lever-arm terms
+/// \(\dot{\omega} \times r + \omega \times (\omega \times r)\).
+pub fn lever_arm(body_from_sensor: &Se3, twist: [f64; 6], twist_rate: [f64; 6]) -> SensorMotion {
+    let sensor_from_body = body_from_sensor.inverse();
+    let (sensor_twist, sensor_rate) =
+        transform_twist_rate(&sensor_from_body, [0.0; 6], twist, twist_rate);
+    let [wx, wy, wz, vx, vy, vz] = sensor_twist;
+    let [ax, ay, az, lx, ly, lz] = classical_from_spatial(sensor_twist, sensor_rate);
+    SensorMotion {
+        angular_velocity: [wx, wy, wz],
+        angular_acceleration: [ax, ay, az],
+        velocity: [vx, vy, vz],
+        acceleration: [lx, ly, lz],
+    }
+}
+
+/// Re-express a twist and its time derivative through a possibly moving
+/// transform \(T_{ab}(t)\) whose own body twist is `frame_twist` \(\xi\).
+///
+/// With \(V_a = \mathrm{Ad}_T V_b\), the rate picks up a velocity-dependent
+/// term, \(\dot{V}_a = \mathrm{Ad}_T(\dot{V}_b + \mathrm{ad}_\xi V_b)\); both
+/// are read off the order-2 CMTM of \(T\) carrying \(\xi\) as its first
+/// derivative. For a constant transform pass a zero `frame_twist`.
+pub fn transform_twist_rate(
+    a_from_b: &Se3,
+    frame_twist: [f64; 6],
+    twist: [f64; 6],
+    twist_rate: [f64; 6],
+) -> ([f64; 6], [f64; 6]) {
+    let cmtm = Cmtm::from_se3_with_derivatives(a_from_b, vec![frame_twist]);
+    let stacked = cmtm.to_block_matrix(None)
+        * DVector::from_iterator(12, twist.into_iter().chain(twist_rate));
+    (
+        std::array::from_fn(|i| stacked[i]),
+        std::array::from_fn(|i| stacked[i + 6]),
+    )
+}
+
+/// Map the classical acceleration \([\alpha, a]\) of a rigid body, observed at
+/// the origin of body-fixed frame \(b\) and expressed in it, to the origin of
+/// another body-fixed frame \(a\) with \(T_{ab}\) = `a_from_b`. `twist` is
+/// the body twist \([\omega, v]\) in frame \(b\).
+///
+/// Classical accelerations are not adjoint-covariant: the centripetal and
+/// Coriolis parts \(\omega \times v\) are removed to obtain the spatial rate,
+/// which is transformed with the CMTM, and then re-added in frame \(a\).
+pub fn transform_acceleration(a_from_b: &Se3, twist: [f64; 6], acceleration: [f64; 6]) -> [f64; 6] {
+    let spatial = spatial_from_classical(twist, acceleration);
+    let (twist, rate) = transform_twist_rate(a_from_b, [0.0; 6], twist, spatial);
+    classical_from_spatial(twist, rate)
+}
+
+/// \([\dot{\omega}, \dot{v} + \omega \times v]\) from a body twist and its rate.
+fn classical_from_spatial(twist: [f64; 6], rate: [f64; 6]) -> [f64; 6] {
+    let [ax, ay, az, lx, ly, lz] = rate;
+    let coriolis = angular(&twist).cross(&linear(&twist));
+    [
+        ax,
+        ay,
+        az,
+        lx + coriolis.x,
+        ly + coriolis.y,
+        lz + coriolis.z,
+    ]
+}
+
+fn spatial_from_classical(twist: [f64; 6], acceleration: [f64; 6]) -> [f64; 6] {
+    let [ax, ay, az, lx, ly, lz] = acceleration;
+    let coriolis = angular(&twist).cross(&linear(&twist));
+    [
+        ax,
+        ay,
+        az,
+        lx - coriolis.x,
+        ly - coriolis.y,
+        lz - coriolis.z,
+    ]
+}
+
+fn angular(twist: &[f64; 6]) -> Vector3<f64> {
+    Vector3::new(twist[0], twist[1], twist[2])
+}
+
+fn linear(twist: &[f64; 6]) -> Vector3<f64> {
+    Vector3::new(twist[3], twist[4], twist[5])
+}
+
+/// Velocity of the body origin, in the body frame, from a velocity measured
+/// at the sensor (e.g. a GNSS antenna), expressed in the sensor frame, and
+/// the body angular velocity: \(v_b = R_{bs} v_s - \omega \times r\).
+pub fn compensate_velocity(
+    body_from_sensor: &Se3,
+    angular_velocity: [f64; 3],
+    sensor_velocity: [f64; 3],
+) -> [f64; 3] {
+    let rotated = Vector3::from(body_from_sensor.rotation().apply(sensor_velocity));
+    let arm = Vector3::from(body_from_sensor.translation());
+    (rotated - Vector3::from(angular_velocity).cross(&arm)).into()
+}
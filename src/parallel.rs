@@ -0,0 +1,312 @@
+use std::f64::consts::PI;
+use std::ops::ControlFlow;
+
+use nalgebra::{DMatrix, SMatrix, SVector, Vector3};
+
+use crate::error::{Error, Result};
+use crate::lie::HasExp;
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::solver::{Budget, IterationInfo, SolveReport, SolveStatus, WarmStart, no_callback};
+
+/// Iteration limit for [`StewartPlatform::forward_kinematics`].
+const FK_MAX_ITERATIONS: usize = 50;
+const FK_TOLERANCE: f64 = 1e-12;
+/// A reused inverse Jacobian is refreshed unless each step shrinks the
+/// residual by at least this factor.
+const CHORD_CONTRACTION: f64 = 0.5;
+/// Relative distance below which [`DeltaRobot::forward_kinematics`] treats
+/// sphere centres as coincident or collinear.
+const DELTA_DEGENERATE_TOLERANCE: f64 = 1e-9;
+
+/// A 6-UPS Stewart–Gough platform: six prismatic legs joining base anchors
+/// \(a_i\) (base frame) to platform anchors \(b_i\) (platform frame).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StewartPlatform {
+    base_anchors: [Vector3<f64>; 6],
+    platform_anchors: [Vector3<f64>; 6],
+}
+
+impl StewartPlatform {
+    pub fn new(base_anchors: [[f64; 3]; 6], platform_anchors: [[f64; 3]; 6]) -> Self {
+        Self {
+            base_anchors: base_anchors.map(Vector3::from),
+            platform_anchors: platform_anchors.map(Vector3::from),
+        }
+    }
+
+    /// The common symmetric layout: anchor pairs on circles of radius
+    /// `base_radius` and `platform_radius`, separated by `base_spread` and
+    /// `platform_spread` radians around each of three 120° positions, with
+    /// the platform pairs rotated by 60° relative to the base pairs.
+    pub fn symmetric(
+        base_radius: f64,
+        platform_radius: f64,
+        base_spread: f64,
+        platform_spread: f64,
+    ) -> Self {
+        let anchor = |radius: f64, angle: f64| [radius * angle.cos(), radius * angle.sin(), 0.0];
+        let base = std::array::from_fn(|i| {
+            let center = (i / 2) as f64 * 2.0 * PI / 3.0;
+            let side = if i % 2 == 0 { -0.5 } else { 0.5 };
+            anchor(base_radius, center + side * base_spread)
+        });
+        let platform = std::array::from_fn(|i| {
+            // Leg i pairs with the platform anchor of the neighbouring group.
+            let group = i.div_ceil(2) % 3;
+            let center = group as f64 * 2.0 * PI / 3.0 - PI / 3.0;
+            let side = if i % 2 == 0 { 0.5 } else { -0.5 };
+            anchor(platform_radius, center + side * platform_spread)
+        });
+        Self::new(base, platform)
+    }
+
+    /// Leg lengths \(l_i = \|p + R b_i - a_i\|\) for the platform pose `pose`
+    /// (platform frame in the base frame).
+    pub fn inverse_kinematics(&self, pose: &Se3) -> [f64; 6] {
+        std::array::from_fn(|i| self.leg(pose, i).norm())
+    }
+
+    /// Platform pose reaching the leg lengths `lengths`, found by Newton's
+    /// method from `initial_guess`. Returns [`Error::NotConverged`] if the
+    /// iteration does not settle (e.g. from a poor guess or near a
+    /// singularity).
+    pub fn forward_kinematics(&self, lengths: [f64; 6], initial_guess: &Se3) -> Result<Se3> {
+        let report = self.forward_kinematics_with(
+            lengths,
+            initial_guess,
+            &Budget::unlimited(),
+            &WarmStart::default(),
+            no_callback,
+        );
+        if report.status.is_converged() {
+            Ok(report.solution)
+        } else {
+            Err(Error::NotConverged {
+                iterations: report.iterations,
+            })
+        }
+    }
+
+    /// [`StewartPlatform::forward_kinematics`] within `budget`, reporting
+    /// every Newton step to `callback` with the largest leg-length residual
+    /// before the step as the cost. When the budget runs out, the callback
+    /// stops or the Jacobian is singular, the current pose is returned as a
+    /// best effort; the report cost is its largest leg-length residual.
+    ///
+    /// The inverse Jacobian is kept across steps (a chord method) while the
+    /// residual keeps contracting, and handed on through the report's
+    /// [`WarmStart`]: when tracking slowly moving leg lengths, starting from
+    /// the previous solution and its warm start avoids refactoring the
+    /// Jacobian on most frames.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn forward_kinematics_with(
+        &self,
+        lengths: [f64; 6],
+        initial_guess: &Se3,
+        budget: &Budget,
+        warm_start: &WarmStart,
+        mut callback: impl FnMut(&Se3, &IterationInfo) -> ControlFlow<()>,
+    ) -> SolveReport<Se3> {
+        let clock = budget.start(FK_MAX_ITERATIONS);
+        let mut pose = initial_guess.clone();
+        let mut inverse = warm_start
+            .inverse_jacobian
+            .as_ref()
+            .filter(|matrix| matrix.shape() == (6, 6))
+            .map(|matrix| SMatrix::<f64, 6, 6>::from_iterator(matrix.iter().copied()));
+        let mut previous_cost = f64::INFINITY;
+        let mut iterations = 0;
+        let mut stopped = false;
+        loop {
+            let current = self.inverse_kinematics(&pose);
+            let residual = SVector::<f64, 6>::from_fn(|i, _| current[i] - lengths[i]);
+            let cost = residual.amax();
+            trace_event!(debug, residual = cost, "stewart newton step");
+            let status = if cost < FK_TOLERANCE {
+                Some(SolveStatus::Converged)
+            } else if stopped {
+                Some(SolveStatus::Stopped)
+            } else {
+                clock.exhausted(iterations).or_else(|| {
+                    if inverse.is_none() || cost > CHORD_CONTRACTION * previous_cost {
+                        inverse = self.jacobian(&pose).try_inverse();
+                    }
+                    inverse.is_none().then_some(SolveStatus::Singular)
+                })
+            };
+            if let Some(status) = status {
+                return SolveReport {
+                    solution: pose,
+                    status,
+                    iterations,
+                    cost,
+                    warm_start: WarmStart {
+                        damping: None,
+                        inverse_jacobian: inverse
+                            .map(|matrix| DMatrix::from_iterator(6, 6, matrix.iter().copied())),
+                    },
+                };
+            }
+            previous_cost = cost;
+            let step = inverse.expect("a singular Jacobian ends the solve") * residual;
+            // Apply the world-frame correction R ← exp(-δω) R, p ← p - δp.
+            let rotation = So3::exp_map(&(-step.fixed_rows::<3>(0)).into_owned());
+            let translation = Vector3::from(pose.translation()) - step.fixed_rows::<3>(3);
+            pose = Se3::from_parts(rotation.compose(pose.rotation()), translation.into());
+            let info = IterationInfo {
+                iteration: iterations,
+                cost,
+                step_norm: step.norm(),
+            };
+            iterations += 1;
+            stopped = callback(&pose, &info).is_break();
+        }
+    }
+
+    /// Rows \([(R b_i \times u_i)^T, u_i^T]\) mapping a world-frame pose
+    /// perturbation \([\delta\omega, \delta p]\) to leg length rates, where
+    /// \(u_i\) is the unit leg direction.
+    pub fn jacobian(&self, pose: &Se3) -> SMatrix<f64, 6, 6> {
+        let mut jacobian = SMatrix::<f64, 6, 6>::zeros();
+        for i in 0..6 {
+            let direction = self.leg(pose, i).normalize();
+            let arm = Vector3::from(pose.rotation().apply(self.platform_anchors[i].into()));
+            let moment = arm.cross(&direction);
+            for k in 0..3 {
+                jacobian[(i, k)] = moment[k];
+                jacobian[(i, k + 3)] = direction[k];
+            }
+        }
+        jacobian
+    }
+
+    fn leg(&self, pose: &Se3, i: usize) -> Vector3<f64> {
+        Vector3::from(pose.apply(self.platform_anchors[i].into())) - self.base_anchors[i]
+    }
+}
+
+/// A three-arm delta robot with revolute actuators spaced 120° apart about
+/// the base Z axis (arm 0 along +X) and parallelogram forearms keeping the
+/// effector parallel to the base. Positions are in the base frame with Z
+/// pointing up, so the workspace lies at negative Z. Joint angles are
+/// measured downward from the base plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaRobot {
+    /// Distance from the base centre to each actuator axis.
+    pub base_radius: f64,
+    /// Distance from the effector centre to each forearm attachment.
+    pub effector_radius: f64,
+    /// Length of the actuated upper arms.
+    pub upper_arm: f64,
+    /// Length of the parallelogram forearms.
+    pub forearm: f64,
+}
+
+impl DeltaRobot {
+    pub fn new(base_radius: f64, effector_radius: f64, upper_arm: f64, forearm: f64) -> Self {
+        Self {
+            base_radius,
+            effector_radius,
+            upper_arm,
+            forearm,
+        }
+    }
+
+    /// Actuator angles placing the effector centre at `position`, choosing the
+    /// elbow-out branch. Returns [`Error::Unreachable`] outside the workspace.
+    pub fn inverse_kinematics(&self, position: [f64; 3]) -> Result<[f64; 3]> {
+        let [x, y, z] = position;
+        let mut angles = [0.0; 3];
+        for (arm, angle) in angles.iter_mut().enumerate() {
+            let (sin, cos) = arm_angle(arm).sin_cos();
+            // Express the target in the arm's vertical plane.
+            let radial = x * cos + y * sin;
+            let lateral = -x * sin + y * cos;
+            let d = self.base_radius - self.effector_radius - radial;
+            let a = 2.0 * d * self.upper_arm;
+            let b = 2.0 * z * self.upper_arm;
+            let c =
+                self.forearm.powi(2) - d * d - self.upper_arm.powi(2) - lateral * lateral - z * z;
+            let amplitude = a.hypot(b);
+            if amplitude == 0.0 || c.abs() > amplitude {
+                return Err(Error::Unreachable);
+            }
+            let phase = b.atan2(a);
+            let offset = (c / amplitude).acos();
+            // Both solutions satisfy a cos θ + b sin θ = c; the elbow-out one
+            // keeps the elbow farther from the centre.
+            let candidates = [phase + offset, phase - offset];
+            *angle = candidates
+                .into_iter()
+                .map(wrap_angle)
+                .max_by(|p, q| p.cos().total_cmp(&q.cos()))
+                .expect("two candidates");
+        }
+        Ok(angles)
+    }
+
+    /// Effector centre for actuator angles `angles`, from the intersection of
+    /// the three forearm spheres below the base. Returns
+    /// [`Error::Unreachable`] if the spheres do not meet,
+    /// [`Error::Singular`] if their centres coincide or are collinear, and
+    /// [`Error::NonFinite`] for non-finite angles.
+    pub fn forward_kinematics(&self, angles: [f64; 3]) -> Result<[f64; 3]> {
+        if angles.iter().any(|a| !a.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+        // Shift each elbow inward by the effector radius so the spheres are
+        // centred on candidate effector centres.
+        let centers: [Vector3<f64>; 3] = std::array::from_fn(|arm| {
+            let (sin, cos) = arm_angle(arm).sin_cos();
+            let reach =
+                self.base_radius + self.upper_arm * angles[arm].cos() - self.effector_radius;
+            Vector3::new(
+                reach * cos,
+                reach * sin,
+                -self.upper_arm * angles[arm].sin(),
+            )
+        });
+        let radius = self.forearm;
+
+        // Trilateration in the frame spanned by the three centres, which
+        // needs them to span a plane.
+        let tolerance = DELTA_DEGENERATE_TOLERANCE
+            * (self.base_radius.abs() + self.upper_arm.abs() + self.effector_radius.abs());
+        let d = (centers[1] - centers[0]).norm();
+        if d <= tolerance {
+            return Err(Error::Singular);
+        }
+        let ex = (centers[1] - centers[0]) / d;
+        let i = ex.dot(&(centers[2] - centers[0]));
+        let in_plane = centers[2] - centers[0] - ex * i;
+        let j = in_plane.norm();
+        if j <= tolerance {
+            return Err(Error::Singular);
+        }
+        let ey = in_plane / j;
+        let ez = ex.cross(&ey);
+        let px = d / 2.0;
+        let py = (i * i + j * j) / (2.0 * j) - i / j * px;
+        let height_sq = radius * radius - px * px - py * py;
+        if height_sq < 0.0 {
+            return Err(Error::Unreachable);
+        }
+        let base = centers[0] + ex * px + ey * py;
+        let candidates = [base + ez * height_sq.sqrt(), base - ez * height_sq.sqrt()];
+        let lowest = if candidates[0].z < candidates[1].z {
+            candidates[0]
+        } else {
+            candidates[1]
+        };
+        Ok(lowest.into())
+    }
+}
+
+fn arm_angle(arm: usize) -> f64 {
+    arm as f64 * 2.0 * PI / 3.0
+}
+
+fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
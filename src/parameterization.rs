@@ -0,0 +1,132 @@
+use nalgebra::{Matrix3, SMatrix, Vector4};
+
+use crate::jacobian::{euler_rate_matrix, quaternion_rate_matrix};
+use crate::so3::So3;
+
+/// A local (Ceres-style) parameterization of rotations with `N` ambient
+/// parameters and a three-dimensional tangent space.
+///
+/// Increments are applied on the right, in the body frame:
+/// \(x \boxplus \delta = x \cdot \mathrm{Exp}(\delta)\) and
+/// \(y \boxminus x = \mathrm{Log}(x^{-1} y)\). [`So3`] remains the storage type;
+/// implementations only translate to and from the solver's parameter block.
+pub trait Parameterization<const N: usize> {
+    /// Parameters describing `rotation`.
+    fn to_parameters(rotation: &So3) -> [f64; N];
+    /// Rotation described by `parameters`.
+    fn from_parameters(parameters: [f64; N]) -> So3;
+    /// \(x \boxplus \delta\).
+    fn plus(parameters: [f64; N], delta: [f64; 3]) -> [f64; N];
+    /// \(y \boxminus x\), the tangent increment taking `x` to `y`.
+    fn minus(y: [f64; N], x: [f64; N]) -> [f64; 3] {
+        let relative = Self::from_parameters(x)
+            .inverse()
+            .compose(&Self::from_parameters(y));
+        relative.to_rotation_vector()
+    }
+    /// \(\partial (x \boxplus \delta) / \partial \delta\) at \(\delta = 0\).
+    fn plus_jacobian(parameters: [f64; N]) -> SMatrix<f64, N, 3>;
+    /// \(\partial (y \boxminus x) / \partial y\) at \(y = x\); a left inverse of
+    /// [`Parameterization::plus_jacobian`].
+    fn minus_jacobian(parameters: [f64; N]) -> SMatrix<f64, 3, N>;
+}
+
+/// Rotation vector (exponential coordinates) \(\phi\) with \(R = \mathrm{Exp}(\phi)\).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationVectorManifold;
+
+impl Parameterization<3> for RotationVectorManifold {
+    fn to_parameters(rotation: &So3) -> [f64; 3] {
+        rotation.to_rotation_vector()
+    }
+
+    fn from_parameters(parameters: [f64; 3]) -> So3 {
+        So3::from_rotation_vector(parameters)
+    }
+
+    fn plus(parameters: [f64; 3], delta: [f64; 3]) -> [f64; 3] {
+        Self::from_parameters(parameters)
+            .compose(&So3::from_rotation_vector(delta))
+            .to_rotation_vector()
+    }
+
+    fn plus_jacobian(parameters: [f64; 3]) -> Matrix3<f64> {
+        So3::right_jacobian_inverse(parameters)
+    }
+
+    fn minus_jacobian(parameters: [f64; 3]) -> Matrix3<f64> {
+        So3::right_jacobian(parameters)
+    }
+}
+
+/// Unit quaternion \([w, x, y, z]\). The update multiplies by the increment
+/// quaternion and renormalizes, so the sign of the parameter block is
+/// preserved across updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuaternionManifold;
+
+impl Parameterization<4> for QuaternionManifold {
+    fn to_parameters(rotation: &So3) -> [f64; 4] {
+        rotation.to_quaternion()
+    }
+
+    fn from_parameters(parameters: [f64; 4]) -> So3 {
+        So3::from_quaternion(parameters)
+    }
+
+    fn plus(parameters: [f64; 4], delta: [f64; 3]) -> [f64; 4] {
+        let [w, x, y, z] = parameters;
+        let [dw, dx, dy, dz] = So3::from_rotation_vector(delta).to_quaternion();
+        let product = Vector4::new(
+            w * dw - x * dx - y * dy - z * dz,
+            w * dx + x * dw + y * dz - z * dy,
+            w * dy - x * dz + y * dw + z * dx,
+            w * dz + x * dy - y * dx + z * dw,
+        );
+        product.normalize().into()
+    }
+
+    fn plus_jacobian(parameters: [f64; 4]) -> SMatrix<f64, 4, 3> {
+        // q ⊗ [0, δ/2] = ½ (R δ) ⊗ q, i.e. the world-frame rate matrix
+        // applied to the increment rotated into the world frame.
+        let rotation = So3::from_quaternion(parameters);
+        quaternion_rate_matrix(parameters) * rotation.rotation().matrix()
+    }
+
+    fn minus_jacobian(parameters: [f64; 4]) -> SMatrix<f64, 3, 4> {
+        Self::plus_jacobian(parameters).transpose() * 4.0
+    }
+}
+
+/// Roll–pitch–yaw angles in ZYX order, as in [`So3::from_euler_angles`].
+/// Both Jacobians are singular at gimbal lock (\(\cos(\text{pitch}) = 0\)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EulerManifold;
+
+impl Parameterization<3> for EulerManifold {
+    fn to_parameters(rotation: &So3) -> [f64; 3] {
+        let (roll, pitch, yaw) = rotation.to_euler_angles();
+        [roll, pitch, yaw]
+    }
+
+    fn from_parameters(parameters: [f64; 3]) -> So3 {
+        let [roll, pitch, yaw] = parameters;
+        So3::from_euler_angles(roll, pitch, yaw)
+    }
+
+    fn plus(parameters: [f64; 3], delta: [f64; 3]) -> [f64; 3] {
+        let updated = Self::from_parameters(parameters).compose(&So3::from_rotation_vector(delta));
+        Self::to_parameters(&updated)
+    }
+
+    fn plus_jacobian(parameters: [f64; 3]) -> Matrix3<f64> {
+        Self::minus_jacobian(parameters)
+            .try_inverse()
+            .unwrap_or_else(|| Matrix3::from_element(f64::NAN))
+    }
+
+    fn minus_jacobian(parameters: [f64; 3]) -> Matrix3<f64> {
+        let rotation = Self::from_parameters(parameters);
+        rotation.rotation().matrix().transpose() * euler_rate_matrix(parameters)
+    }
+}
@@ -0,0 +1,320 @@
+use std::ops::ControlFlow;
+
+use nalgebra::{Matrix3, SMatrix, SVector, Vector2, Vector3};
+
+use crate::camera::CameraIntrinsics;
+use crate::error::{Error, Result};
+use crate::registration::fit_rigid;
+use crate::se3::Se3;
+use crate::solver::{Budget, IterationInfo, SolveReport, SolveStatus, WarmStart, no_callback};
+use crate::util::skew_symmetric;
+
+const REFINE_MAX_ITERATIONS: usize = 50;
+const REFINE_TOLERANCE: f64 = 1e-12;
+/// Levenberg–Marquardt damping of a cold start, and the largest handed on
+/// to the next refinement.
+const INITIAL_DAMPING: f64 = 1e-3;
+const MIN_DAMPING: f64 = 1e-12;
+/// Largest number of point triplets tried by [`solve_pnp`] for the
+/// initial guess.
+const MAX_TRIPLETS: usize = 8;
+
+/// Camera poses \(T_{c,w}\) consistent with three world points seen along
+/// the unit `bearings`, by Grunert's method (Haralick et al., 1994).
+///
+/// Up to four solutions are returned; a fourth correspondence is needed to
+/// pick one. Degenerate configurations (e.g. collinear points) yield fewer or
+/// none.
+pub fn p3p(world: &[[f64; 3]; 3], bearings: &[[f64; 3]; 3]) -> Vec<Se3> {
+    let p = world.map(Vector3::from);
+    let j = bearings.map(|b| Vector3::from(b).normalize());
+    let a2 = (p[1] - p[2]).norm_squared();
+    let b2 = (p[0] - p[2]).norm_squared();
+    let c2 = (p[0] - p[1]).norm_squared();
+    if b2 == 0.0 {
+        return Vec::new();
+    }
+    let cos_alpha = j[1].dot(&j[2]);
+    let cos_beta = j[0].dot(&j[2]);
+    let cos_gamma = j[0].dot(&j[1]);
+
+    let amc = (a2 - c2) / b2;
+    let apc = (a2 + c2) / b2;
+    let bmc = (b2 - c2) / b2;
+    let bma = (b2 - a2) / b2;
+    let (ca, cb, cg) = (cos_alpha, cos_beta, cos_gamma);
+    // Grunert's quartic in v = s3 / s1, highest power first.
+    let coefficients = [
+        (amc - 1.0).powi(2) - 4.0 * c2 / b2 * ca * ca,
+        4.0 * (amc * (1.0 - amc) * cb - (1.0 - apc) * ca * cg + 2.0 * c2 / b2 * ca * ca * cb),
+        2.0 * (amc * amc - 1.0 + 2.0 * amc * amc * cb * cb + 2.0 * bmc * ca * ca
+            - 4.0 * apc * ca * cb * cg
+            + 2.0 * bma * cg * cg),
+        4.0 * (-amc * (1.0 + amc) * cb + 2.0 * a2 / b2 * cg * cg * cb - (1.0 - apc) * ca * cg),
+        (1.0 + amc).powi(2) - 4.0 * a2 / b2 * cg * cg,
+    ];
+
+    let mut poses = Vec::new();
+    for v in real_quartic_roots(coefficients) {
+        let denominator = 2.0 * (cg - v * ca);
+        if denominator.abs() < 1e-12 {
+            continue;
+        }
+        let u = ((-1.0 + amc) * v * v - 2.0 * amc * cb * v + 1.0 + amc) / denominator;
+        let s1_squared = b2 / (1.0 + v * v - 2.0 * v * cb);
+        if s1_squared.is_nan() || s1_squared <= 0.0 || u <= 0.0 || v <= 0.0 {
+            continue;
+        }
+        let s1 = s1_squared.sqrt();
+        let camera = [j[0] * s1, j[1] * (u * s1), j[2] * (v * s1)].map(Into::into);
+        if let Ok(pose) = fit_rigid(world, &camera) {
+            poses.push(pose);
+        }
+    }
+    poses
+}
+
+/// Camera pose \(T_{c,w}\) from at least four world points and their pixels.
+///
+/// An initial guess is taken from [`p3p`] on a few point triplets, keeping
+/// the candidate with the smallest total reprojection error, and is then
+/// polished by [`refine_pnp`]. The correspondences must be free of outliers;
+/// wrap them in a [`crate::ransac::RansacProblem`] otherwise.
+///
+/// Returns [`Error::InsufficientData`] for fewer than four correspondences
+/// and [`Error::Singular`] if no triplet gives a pose.
+pub fn solve_pnp(
+    world: &[[f64; 3]],
+    pixels: &[[f64; 2]],
+    intrinsics: &CameraIntrinsics,
+) -> Result<Se3> {
+    if world.len() != pixels.len() || world.len() < 4 {
+        return Err(Error::InsufficientData {
+            required: 4,
+            provided: world.len().min(pixels.len()),
+        });
+    }
+    let bearings: Vec<[f64; 3]> = pixels.iter().map(|&px| intrinsics.bearing(px)).collect();
+    let n = world.len();
+    let mut best: Option<(f64, Se3)> = None;
+    for start in 0..n.min(MAX_TRIPLETS) {
+        let [i, k, l] = [start, (start + n / 3) % n, (start + 2 * n / 3) % n];
+        if i == k || k == l || i == l {
+            continue;
+        }
+        for pose in p3p(
+            &[world[i], world[k], world[l]],
+            &[bearings[i], bearings[k], bearings[l]],
+        ) {
+            let error = reprojection_error(&pose, world, pixels, intrinsics);
+            if best.as_ref().is_none_or(|(lowest, _)| error < *lowest) {
+                best = Some((error, pose));
+            }
+        }
+    }
+    let (_, initial) = best.ok_or(Error::Singular)?;
+    refine_pnp(&initial, world, pixels, intrinsics)
+}
+
+/// Minimize the squared pixel reprojection error over \(T_{c,w}\) by
+/// Levenberg–Marquardt, starting from `initial`. Updates are applied on the
+/// left, \(T \leftarrow \mathrm{Exp}(\delta) T\) with \(\delta = [\omega, v]\)
+/// in the camera frame.
+///
+/// Returns [`Error::InsufficientData`] for fewer than three correspondences,
+/// [`Error::Singular`] for a rank-deficient system and
+/// [`Error::NotConverged`] if points remain behind the camera.
+pub fn refine_pnp(
+    initial: &Se3,
+    world: &[[f64; 3]],
+    pixels: &[[f64; 2]],
+    intrinsics: &CameraIntrinsics,
+) -> Result<Se3> {
+    refine_pnp_with(
+        initial,
+        world,
+        pixels,
+        intrinsics,
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        no_callback,
+    )
+    .and_then(|report| match report.status {
+        SolveStatus::Singular => Err(Error::Singular),
+        _ => Ok(report.solution),
+    })
+}
+
+/// [`refine_pnp`] within `budget`, reporting every iteration to `callback`.
+/// When the budget runs out, the callback stops or the damped normal
+/// equations are singular, the best pose so far is returned with the
+/// corresponding [`SolveStatus`]; the report cost is its squared
+/// reprojection error. Returns [`Error::InsufficientData`] and
+/// [`Error::NotConverged`] like [`refine_pnp`].
+///
+/// `warm_start` resumes from the damping a previous refinement ended with,
+/// which saves the iterations spent re-adapting it when tracking a camera
+/// from frame to frame.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn refine_pnp_with(
+    initial: &Se3,
+    world: &[[f64; 3]],
+    pixels: &[[f64; 2]],
+    intrinsics: &CameraIntrinsics,
+    budget: &Budget,
+    warm_start: &WarmStart,
+    mut callback: impl FnMut(&Se3, &IterationInfo) -> ControlFlow<()>,
+) -> Result<SolveReport<Se3>> {
+    if world.len() != pixels.len() || world.len() < 3 {
+        return Err(Error::InsufficientData {
+            required: 3,
+            provided: world.len().min(pixels.len()),
+        });
+    }
+    let clock = budget.start(REFINE_MAX_ITERATIONS);
+    let mut pose = initial.clone();
+    let mut cost = reprojection_error(&pose, world, pixels, intrinsics);
+    let mut damping = warm_start.damping.unwrap_or(INITIAL_DAMPING);
+    let mut iterations = 0;
+    let status = loop {
+        if let Some(status) = clock.exhausted(iterations) {
+            break status;
+        }
+        let mut normal = SMatrix::<f64, 6, 6>::zeros();
+        let mut gradient = SVector::<f64, 6>::zeros();
+        for (&point, &pixel) in world.iter().zip(pixels) {
+            let [x, y, z] = pose.apply(point);
+            let Some([u, v]) = intrinsics.project([x, y, z]) else {
+                continue;
+            };
+            let residual = Vector2::new(u - pixel[0], v - pixel[1]);
+            // d(pixel)/d(point) followed by d(point)/d[ω, v] = [-[P]×, I].
+            let projection = SMatrix::<f64, 2, 3>::new(
+                intrinsics.fx / z,
+                0.0,
+                -intrinsics.fx * x / (z * z),
+                0.0,
+                intrinsics.fy / z,
+                -intrinsics.fy * y / (z * z),
+            );
+            let mut motion = SMatrix::<f64, 3, 6>::zeros();
+            motion
+                .fixed_view_mut::<3, 3>(0, 0)
+                .copy_from(&-skew_symmetric(&Vector3::new(x, y, z)));
+            motion
+                .fixed_view_mut::<3, 3>(0, 3)
+                .copy_from(&Matrix3::identity());
+            let jacobian = projection * motion;
+            normal += jacobian.transpose() * jacobian;
+            gradient += jacobian.transpose() * residual;
+        }
+
+        let mut damped = normal;
+        for k in 0..6 {
+            damped[(k, k)] += damping * normal[(k, k)].max(1e-12);
+        }
+        let Some(step) = damped.lu().solve(&-gradient) else {
+            break SolveStatus::Singular;
+        };
+        let candidate = Se3::from_matrix(Se3::exp(step.into(), None)).compose(&pose);
+        let candidate_cost = reprojection_error(&candidate, world, pixels, intrinsics);
+        trace_event!(
+            debug,
+            cost,
+            candidate_cost,
+            damping,
+            step_norm = step.norm(),
+            "levenberg-marquardt step"
+        );
+        let accepted = candidate_cost <= cost;
+        if accepted {
+            pose = candidate;
+            cost = candidate_cost;
+            damping = (damping * 0.1).max(MIN_DAMPING);
+        } else {
+            damping *= 10.0;
+        }
+        let info = IterationInfo {
+            iteration: iterations,
+            cost,
+            step_norm: step.norm(),
+        };
+        iterations += 1;
+        if callback(&pose, &info).is_break() {
+            break SolveStatus::Stopped;
+        }
+        // A tiny accepted step, or no decrease possible at any damping: a
+        // local minimum.
+        if (accepted && info.step_norm < REFINE_TOLERANCE) || damping > 1e12 {
+            break SolveStatus::Converged;
+        }
+    };
+    if !cost.is_finite() {
+        trace_event!(warn, cost, "refinement left points behind the camera");
+        return Err(Error::NotConverged { iterations });
+    }
+    Ok(SolveReport {
+        solution: pose,
+        status,
+        iterations,
+        cost,
+        warm_start: WarmStart {
+            damping: Some(damping.clamp(MIN_DAMPING, INITIAL_DAMPING)),
+            inverse_jacobian: None,
+        },
+    })
+}
+
+/// Sum of squared pixel errors of `camera_from_world`; points behind the
+/// camera count as infinitely wrong.
+pub fn reprojection_error(
+    camera_from_world: &Se3,
+    world: &[[f64; 3]],
+    pixels: &[[f64; 2]],
+    intrinsics: &CameraIntrinsics,
+) -> f64 {
+    world
+        .iter()
+        .zip(pixels)
+        .map(
+            |(&point, pixel)| match intrinsics.project(camera_from_world.apply(point)) {
+                Some([u, v]) => (u - pixel[0]).powi(2) + (v - pixel[1]).powi(2),
+                None => f64::INFINITY,
+            },
+        )
+        .sum()
+}
+
+/// Real roots of \(c_0 x^4 + c_1 x^3 + c_2 x^2 + c_3 x + c_4\), from the
+/// eigenvalues of the companion matrix, polished by Newton steps.
+fn real_quartic_roots(coefficients: [f64; 5]) -> Vec<f64> {
+    let [c0, c1, c2, c3, c4] = coefficients;
+    if c0.abs() < 1e-14 {
+        return Vec::new();
+    }
+    let mut companion = SMatrix::<f64, 4, 4>::zeros();
+    companion[(0, 0)] = -c1 / c0;
+    companion[(0, 1)] = -c2 / c0;
+    companion[(0, 2)] = -c3 / c0;
+    companion[(0, 3)] = -c4 / c0;
+    companion[(1, 0)] = 1.0;
+    companion[(2, 1)] = 1.0;
+    companion[(3, 2)] = 1.0;
+    let polynomial = |x: f64| (((c0 * x + c1) * x + c2) * x + c3) * x + c4;
+    let derivative = |x: f64| ((4.0 * c0 * x + 3.0 * c1) * x + 2.0 * c2) * x + c3;
+    companion
+        .complex_eigenvalues()
+        .iter()
+        .filter(|root| root.im.abs() <= 1e-6 * root.re.abs().max(1.0))
+        .map(|root| {
+            let mut x = root.re;
+            for _ in 0..3 {
+                let slope = derivative(x);
+                if slope != 0.0 {
+                    x -= polynomial(x) / slope;
+                }
+            }
+            x
+        })
+        .collect()
+}
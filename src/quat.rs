@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::error::{Error, Result};
+use crate::so3::So3;
+
+/// Cosine of the angle between quaternions above which [`Quat::slerp`] falls
+/// back to normalized linear interpolation.
+const SLERP_LINEAR_THRESHOLD: f64 = 1.0 - 1e-9;
+
+/// A quaternion \(w + x i + y j + z k\) with the Hamilton product, for
+/// pipelines that stay in quaternion space. Rotations are represented by unit
+/// quaternions; [`Quat::normalize`] projects back onto them after accumulated
+/// round-off.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quat {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quat {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// The unit quaternion of the identity rotation.
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Construct from the scalar-first layout \([w, x, y, z]\) used by
+    /// [`So3::from_quaternion`].
+    pub fn from_wxyz(wxyz: [f64; 4]) -> Self {
+        Self::new(wxyz[0], wxyz[1], wxyz[2], wxyz[3])
+    }
+
+    /// Components in the scalar-first layout \([w, x, y, z]\).
+    pub fn to_wxyz(&self) -> [f64; 4] {
+        [self.w, self.x, self.y, self.z]
+    }
+
+    /// Unit quaternion of a rotation by `angle` (rad) about `axis`, which is
+    /// normalized first.
+    ///
+    /// # Panics
+    /// Panics if `axis` is zero.
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Self {
+        let norm = axis.iter().map(|a| a * a).sum::<f64>().sqrt();
+        assert!(norm > 0.0, "rotation axis must be non-zero");
+        let (sin, cos) = (0.5 * angle).sin_cos();
+        let scale = sin / norm;
+        Self::new(cos, axis[0] * scale, axis[1] * scale, axis[2] * scale)
+    }
+
+    /// Unit quaternion of `rotation`, with non-negative scalar part.
+    pub fn from_so3(rotation: &So3) -> Self {
+        Self::from_wxyz(rotation.to_quaternion())
+    }
+
+    /// Rotation of the quaternion, normalizing it first.
+    pub fn to_so3(&self) -> So3 {
+        So3::from_quaternion(self.to_wxyz())
+    }
+
+    /// Vector part \([x, y, z]\).
+    pub fn vector(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Four-dimensional inner product, the cosine of half the angle between
+    /// two unit quaternions.
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Conjugate \(w - x i - y j - z k\), the inverse of a unit quaternion.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// The unit quaternion in the direction of `self`. Errors with
+    /// [`Error::NonFinite`] or [`Error::ZeroNorm`] like
+    /// [`So3::try_from_quaternion`].
+    pub fn normalize(&self) -> Result<Self> {
+        if self.to_wxyz().iter().any(|v| !v.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+        let norm = self.norm();
+        if norm == 0.0 {
+            return Err(Error::ZeroNorm);
+        }
+        Ok(self.scale(1.0 / norm))
+    }
+
+    /// Hamilton product \(p q\), composing rotations as \(R_p R_q\).
+    pub fn multiply(&self, other: &Self) -> Self {
+        let (p, q) = (self, other);
+        Self::new(
+            p.w * q.w - p.x * q.x - p.y * q.y - p.z * q.z,
+            p.w * q.x + p.x * q.w + p.y * q.z - p.z * q.y,
+            p.w * q.y - p.x * q.z + p.y * q.w + p.z * q.x,
+            p.w * q.z + p.x * q.y - p.y * q.x + p.z * q.w,
+        )
+    }
+
+    /// Rotate `vector` by the unit quaternion, \(q v q^*\).
+    pub fn rotate(&self, vector: [f64; 3]) -> [f64; 3] {
+        let pure = Self::new(0.0, vector[0], vector[1], vector[2]);
+        self.multiply(&pure).multiply(&self.conjugate()).vector()
+    }
+
+    /// Spherical linear interpolation between unit quaternions along the
+    /// shorter arc, so `other` and `-other` give the same rotations. Nearly
+    /// equal inputs are interpolated linearly and renormalized.
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut cos = self.dot(other);
+        let target = if cos < 0.0 {
+            cos = -cos;
+            other.scale(-1.0)
+        } else {
+            *other
+        };
+        if cos > SLERP_LINEAR_THRESHOLD {
+            let blend = self.scale(1.0 - t).add(&target.scale(t));
+            return blend.scale(1.0 / blend.norm());
+        }
+        let angle = cos.min(1.0).acos();
+        let sin = angle.sin();
+        self.scale(((1.0 - t) * angle).sin() / sin)
+            .add(&target.scale((t * angle).sin() / sin))
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        Self::new(
+            self.w * factor,
+            self.x * factor,
+            self.y * factor,
+            self.z * factor,
+        )
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self::new(
+            self.w + other.w,
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+        )
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for Quat {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.multiply(&rhs)
+    }
+}
+
+impl From<&So3> for Quat {
+    fn from(rotation: &So3) -> Self {
+        Self::from_so3(rotation)
+    }
+}
+
+impl From<Quat> for So3 {
+    fn from(quaternion: Quat) -> Self {
+        quaternion.to_so3()
+    }
+}
@@ -0,0 +1,165 @@
+use crate::error::{Error, Result};
+use crate::util::SplitMix64;
+
+/// A robust estimation problem over indexed correspondences, solved by
+/// [`ransac`].
+pub trait RansacProblem {
+    type Model;
+
+    /// Number of correspondences.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Minimal number of correspondences that determines a model.
+    fn sample_size(&self) -> usize;
+
+    /// Fit a model to the correspondences at `indices`, which holds at least
+    /// [`RansacProblem::sample_size`] entries. `None` for degenerate samples.
+    fn fit(&self, indices: &[usize]) -> Option<Self::Model>;
+
+    /// Error of correspondence `index` under `model`, compared against
+    /// [`RansacOptions::threshold`].
+    fn residual(&self, model: &Self::Model, index: usize) -> f64;
+}
+
+/// Iteration control of [`ransac`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RansacOptions {
+    /// Largest residual of an inlier.
+    pub threshold: f64,
+    /// Hard cap on the number of minimal samples drawn.
+    pub max_iterations: usize,
+    /// Stop once a sample free of outliers has been drawn with this
+    /// probability, judged from the best inlier ratio so far.
+    pub confidence: f64,
+    /// Seed of the internal generator; equal seeds give equal results.
+    pub seed: u64,
+}
+
+impl RansacOptions {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            max_iterations: 1000,
+            confidence: 0.999,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// Output of [`ransac`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RansacResult<M> {
+    /// Model refitted to all inliers of the best minimal sample.
+    pub model: M,
+    /// Inlier flag per correspondence under `model`.
+    pub inliers: Vec<bool>,
+    pub inlier_count: usize,
+    /// Minimal samples drawn.
+    pub iterations: usize,
+}
+
+/// Random sample consensus: draw minimal samples, keep the model with the
+/// most inliers, and refit it to those inliers.
+///
+/// The number of iterations adapts to the best inlier ratio \(w\) seen so far,
+/// stopping after \(\log(1 - p) / \log(1 - w^s)\) samples for confidence \(p\)
+/// and sample size \(s\), and never exceeding `max_iterations`.
+///
+/// Returns [`Error::InsufficientData`] if the problem has fewer
+/// correspondences than a minimal sample and [`Error::NotConverged`] if no
+/// non-degenerate model was found.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn ransac<P: RansacProblem>(
+    problem: &P,
+    options: &RansacOptions,
+) -> Result<RansacResult<P::Model>> {
+    let (count, size) = (problem.len(), problem.sample_size());
+    if count < size || size == 0 {
+        return Err(Error::InsufficientData {
+            required: size.max(1),
+            provided: count,
+        });
+    }
+
+    let mut rng = SplitMix64(options.seed);
+    let mut sample = vec![0; size];
+    let mut best: Option<(P::Model, usize)> = None;
+    let mut required = options.max_iterations;
+    let mut iterations = 0;
+    while iterations < required.min(options.max_iterations) {
+        iterations += 1;
+        draw(&mut rng, count, &mut sample);
+        let Some(model) = problem.fit(&sample) else {
+            continue;
+        };
+        let inliers = (0..count)
+            .filter(|&i| problem.residual(&model, i) <= options.threshold)
+            .count();
+        if best.as_ref().is_none_or(|(_, most)| inliers > *most) {
+            best = Some((model, inliers));
+            required = required_iterations(inliers as f64 / count as f64, size, options.confidence);
+            trace_event!(debug, iterations, inliers, required, "new best model");
+        }
+    }
+
+    let (model, _) = best.ok_or(Error::NotConverged { iterations })?;
+    let inliers = inlier_mask(problem, &model, options.threshold);
+    let indices: Vec<usize> = (0..count).filter(|&i| inliers[i]).collect();
+    // Keep the sample model if the refit is degenerate or loses inliers.
+    let (model, inliers) = match problem.fit(&indices) {
+        Some(refit) => {
+            let refit_inliers = inlier_mask(problem, &refit, options.threshold);
+            if refit_inliers.iter().filter(|&&inlier| inlier).count() >= indices.len() {
+                (refit, refit_inliers)
+            } else {
+                (model, inliers)
+            }
+        }
+        None => (model, inliers),
+    };
+    Ok(RansacResult {
+        inlier_count: inliers.iter().filter(|&&inlier| inlier).count(),
+        model,
+        inliers,
+        iterations,
+    })
+}
+
+fn inlier_mask<P: RansacProblem>(problem: &P, model: &P::Model, threshold: f64) -> Vec<bool> {
+    (0..problem.len())
+        .map(|i| problem.residual(model, i) <= threshold)
+        .collect()
+}
+
+fn required_iterations(inlier_ratio: f64, sample_size: usize, confidence: f64) -> usize {
+    let clean = inlier_ratio.powi(sample_size as i32);
+    if clean >= 1.0 {
+        return 1;
+    }
+    if clean <= 0.0 {
+        return usize::MAX;
+    }
+    let iterations = (1.0 - confidence).ln() / (1.0 - clean).ln();
+    if iterations.is_finite() {
+        iterations.ceil().max(1.0) as usize
+    } else {
+        usize::MAX
+    }
+}
+
+/// Fill `sample` with distinct indices below `count`, redrawing repeats.
+fn draw(rng: &mut SplitMix64, count: usize, sample: &mut [usize]) {
+    for i in 0..sample.len() {
+        loop {
+            let candidate = (rng.next() % count as u64) as usize;
+            if !sample[..i].contains(&candidate) {
+                sample[i] = candidate;
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Recording and playback of timestamped [`FrameTree`] updates.
+//!
+//! A log starts with the magic bytes `MRFL` and a little-endian `u16` format
+//! version, followed by records tagged with one byte:
+//!
+//! - `0`: frame name definition, a `u32` length and UTF-8 bytes; names are
+//!   numbered in order of definition and written once per log;
+//! - `1`: set, with time `f64`, parent and child ids `u32`, and the pose as a
+//!   pose encoded as in [`crate::binary`];
+//! - `2`: removal, with time `f64` and parent and child ids `u32`.
+//!
+//! All numbers are little-endian.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::binary::{put_pose, read_pose};
+use crate::error::{Error, Result};
+use crate::frame::FrameTree;
+use crate::se3::Se3;
+use crate::util::{ByteReader, put_f64s, put_string};
+
+const MAGIC: &[u8; 4] = b"MRFL";
+/// Current format version written by [`FrameRecorder`].
+pub const FRAME_LOG_VERSION: u16 = 1;
+
+const TAG_NAME: u8 = 0;
+const TAG_SET: u8 = 1;
+const TAG_REMOVE: u8 = 2;
+
+/// One change of a [`FrameTree`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameEvent {
+    /// [`FrameTree::set_transform`].
+    Set {
+        parent: String,
+        child: String,
+        parent_from_child: Se3,
+    },
+    /// [`FrameTree::remove_transform`].
+    Remove { parent: String, child: String },
+}
+
+/// A [`FrameEvent`] at a time in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameRecord {
+    pub time: f64,
+    pub event: FrameEvent,
+}
+
+impl FrameRecord {
+    /// Apply the event to `tree`. Removing a missing edge is not an error.
+    pub fn apply(&self, tree: &mut FrameTree) -> Result<()> {
+        match &self.event {
+            FrameEvent::Set {
+                parent,
+                child,
+                parent_from_child,
+            } => tree.set_transform(parent, child, parent_from_child.clone()),
+            FrameEvent::Remove { parent, child } => {
+                tree.remove_transform(parent, child);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Writes [`FrameRecord`]s to a binary log as they happen.
+#[derive(Debug)]
+pub struct FrameRecorder<W: Write> {
+    writer: W,
+    ids: HashMap<String, u32>,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> FrameRecorder<W> {
+    /// Start a log on `writer` by writing its header.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FRAME_LOG_VERSION.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            ids: HashMap::new(),
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Append `record`, defining its frame names on first use. Each record
+    /// is passed to the writer in a single `write_all`; names it defines only
+    /// count as written once that succeeds.
+    ///
+    /// # Panics
+    /// Panics if a frame name is longer than `u32::MAX` bytes.
+    pub fn record(&mut self, record: &FrameRecord) -> io::Result<()> {
+        self.buffer.clear();
+        let (tag, parent, child) = match &record.event {
+            FrameEvent::Set { parent, child, .. } => (TAG_SET, parent, child),
+            FrameEvent::Remove { parent, child } => (TAG_REMOVE, parent, child),
+        };
+        let mut defined = Vec::new();
+        let parent = self.id(parent, &mut defined);
+        let child = self.id(child, &mut defined);
+        self.buffer.push(tag);
+        put_f64s(&mut self.buffer, &[record.time]);
+        self.buffer.extend_from_slice(&parent.to_le_bytes());
+        self.buffer.extend_from_slice(&child.to_le_bytes());
+        if let FrameEvent::Set {
+            parent_from_child, ..
+        } = &record.event
+        {
+            put_pose(&mut self.buffer, parent_from_child);
+        }
+        self.writer.write_all(&self.buffer)?;
+        for name in defined {
+            let id = self.ids.len() as u32;
+            self.ids.insert(name, id);
+        }
+        Ok(())
+    }
+
+    /// Record a set at `time`.
+    pub fn record_set(
+        &mut self,
+        time: f64,
+        parent: &str,
+        child: &str,
+        parent_from_child: &Se3,
+    ) -> io::Result<()> {
+        self.record(&FrameRecord {
+            time,
+            event: FrameEvent::Set {
+                parent: parent.to_string(),
+                child: child.to_string(),
+                parent_from_child: parent_from_child.clone(),
+            },
+        })
+    }
+
+    /// Record a removal at `time`.
+    pub fn record_remove(&mut self, time: f64, parent: &str, child: &str) -> io::Result<()> {
+        self.record(&FrameRecord {
+            time,
+            event: FrameEvent::Remove {
+                parent: parent.to_string(),
+                child: child.to_string(),
+            },
+        })
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Id of `name`, writing its definition into the pending record and
+    /// onto `defined` if it is new to the log.
+    fn id(&mut self, name: &str, defined: &mut Vec<String>) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let pending = defined.iter().position(|n| n == name);
+        let index = pending.unwrap_or_else(|| {
+            self.buffer.push(TAG_NAME);
+            put_string(&mut self.buffer, name);
+            defined.push(name.to_string());
+            defined.len() - 1
+        });
+        (self.ids.len() + index) as u32
+    }
+}
+
+/// Iterator over the records of a binary log written by [`FrameRecorder`].
+///
+/// Yields [`Error::InvalidEncoding`] for malformed data, after which it
+/// stops. A log cut off mid-record, e.g. by a crash, yields every complete
+/// record before the error.
+#[derive(Debug)]
+pub struct FrameLog<'a> {
+    reader: ByteReader<'a>,
+    names: Vec<String>,
+    failed: bool,
+}
+
+impl<'a> FrameLog<'a> {
+    /// Check the header of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(Error::InvalidEncoding("not a frame log".to_string()));
+        }
+        let version = reader.u16()?;
+        if version != FRAME_LOG_VERSION {
+            return Err(Error::InvalidEncoding(format!(
+                "unsupported frame log version {version}"
+            )));
+        }
+        Ok(Self {
+            reader,
+            names: Vec::new(),
+            failed: false,
+        })
+    }
+
+    fn next_record(&mut self) -> Result<FrameRecord> {
+        loop {
+            let tag = self.reader.u8()?;
+            if tag == TAG_NAME {
+                self.names.push(self.reader.string()?);
+                continue;
+            }
+            let time = self.reader.f64()?;
+            let parent = self.name()?;
+            let child = self.name()?;
+            let event = match tag {
+                TAG_SET => FrameEvent::Set {
+                    parent,
+                    child,
+                    parent_from_child: read_pose(&mut self.reader)?,
+                },
+                TAG_REMOVE => FrameEvent::Remove { parent, child },
+                _ => return Err(Error::InvalidEncoding(format!("unknown record tag {tag}"))),
+            };
+            return Ok(FrameRecord { time, event });
+        }
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let id = self.reader.u32()? as usize;
+        self.names
+            .get(id)
+            .cloned()
+            .ok_or_else(|| Error::InvalidEncoding(format!("undefined frame id {id}")))
+    }
+}
+
+impl Iterator for FrameLog<'_> {
+    type Item = Result<FrameRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.reader.is_empty() {
+            return None;
+        }
+        let record = self.next_record();
+        self.failed = record.is_err();
+        Some(record)
+    }
+}
+
+/// Apply `records` to `tree` in order, pacing them by their timestamps
+/// divided by `speed` (2 replays twice as fast; infinity without waiting),
+/// and call `on_record` after each one. Returns the number of records
+/// applied, stopping at the first error. Timestamps that go backwards or lie
+/// too far ahead to wait for are reported as [`Error::InvalidEncoding`].
+///
+/// # Panics
+/// Panics unless `speed` is positive.
+pub fn replay<I>(
+    records: I,
+    tree: &mut FrameTree,
+    speed: f64,
+    mut on_record: impl FnMut(&FrameRecord, &FrameTree),
+) -> Result<usize>
+where
+    I: IntoIterator<Item = Result<FrameRecord>>,
+{
+    assert!(speed > 0.0, "replay speed must be positive");
+    let start = Instant::now();
+    let mut first_time = None;
+    let mut previous = f64::NEG_INFINITY;
+    let mut count = 0;
+    for record in records {
+        let record = record?;
+        if record.time.is_nan() || record.time < previous {
+            return Err(Error::InvalidEncoding(format!(
+                "timestamp {} goes back from {previous}",
+                record.time
+            )));
+        }
+        previous = record.time;
+        let first = *first_time.get_or_insert(record.time);
+        if speed.is_finite() {
+            let due = Duration::try_from_secs_f64((record.time - first) / speed)
+                .ok()
+                .and_then(|offset| start.checked_add(offset))
+                .ok_or_else(|| {
+                    Error::InvalidEncoding(format!(
+                        "timestamp {} cannot be replayed after {first}",
+                        record.time
+                    ))
+                })?;
+            let now = Instant::now();
+            if due > now {
+                std::thread::sleep(due - now);
+            }
+        }
+        record.apply(tree)?;
+        on_record(&record, tree);
+        count += 1;
+    }
+    Ok(count)
+}
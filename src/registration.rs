@@ -0,0 +1,184 @@
+use nalgebra::{Matrix3, Vector2, Vector3};
+
+use crate::error::{Error, Result};
+use crate::ransac::RansacProblem;
+use crate::se3::Se3;
+use crate::so3::So3;
+
+/// Below this ratio of singular values the point spread is treated as
+/// degenerate (collinear or coincident points).
+const DEGENERACY_TOLERANCE: f64 = 1e-9;
+
+/// Least-squares rigid transform \(T\) minimizing
+/// \(\sum_i \lVert T s_i - t_i \rVert^2\) for corresponding `source` and
+/// `target` points (Kabsch). The rotation is forced proper, so reflections
+/// are never returned.
+///
+/// Returns [`Error::InsufficientData`] for fewer than three pairs or
+/// mismatched lengths, and [`Error::Singular`] when the source points are
+/// collinear.
+pub fn fit_rigid(source: &[[f64; 3]], target: &[[f64; 3]]) -> Result<Se3> {
+    if source.len() != target.len() || source.len() < 3 {
+        return Err(Error::InsufficientData {
+            required: 3,
+            provided: source.len().min(target.len()),
+        });
+    }
+    let centroid = |points: &[[f64; 3]]| {
+        points
+            .iter()
+            .map(|&p| Vector3::from(p))
+            .sum::<Vector3<f64>>()
+            / points.len() as f64
+    };
+    let (source_centroid, target_centroid) = (centroid(source), centroid(target));
+    let covariance = source
+        .iter()
+        .zip(target)
+        .map(|(&s, &t)| {
+            (Vector3::from(s) - source_centroid) * (Vector3::from(t) - target_centroid).transpose()
+        })
+        .sum::<Matrix3<f64>>();
+
+    let svd = covariance.svd(true, true);
+    let (Some(u), Some(v_t)) = (svd.u, svd.v_t) else {
+        return Err(Error::Singular);
+    };
+    let mut singular = svd.singular_values.as_slice().to_vec();
+    singular.sort_by(|a, b| b.total_cmp(a));
+    if singular[0] <= 0.0 || singular[1] <= DEGENERACY_TOLERANCE * singular[0] {
+        return Err(Error::Singular);
+    }
+    let v = v_t.transpose();
+    let sign = (v * u.transpose()).determinant().signum();
+    let rotation = v * Matrix3::from_diagonal(&Vector3::new(1.0, 1.0, sign)) * u.transpose();
+    let rotation = So3::from_matrix(std::array::from_fn(|r| {
+        std::array::from_fn(|c| rotation[(r, c)])
+    }));
+    let translation = target_centroid - rotation.rotation() * source_centroid;
+    Ok(Se3::from_parts(rotation, translation.into()))
+}
+
+/// A planar rigid transform: rotation by `angle` (rad) followed by
+/// `translation`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanarTransform {
+    pub angle: f64,
+    pub translation: [f64; 2],
+}
+
+impl PlanarTransform {
+    pub fn apply(&self, point: [f64; 2]) -> [f64; 2] {
+        let (s, c) = self.angle.sin_cos();
+        let [x, y] = point;
+        [
+            c * x - s * y + self.translation[0],
+            s * x + c * y + self.translation[1],
+        ]
+    }
+}
+
+/// Least-squares planar rigid transform mapping `source` onto `target`.
+///
+/// Returns [`Error::InsufficientData`] for fewer than two pairs or mismatched
+/// lengths, and [`Error::Singular`] when the source points coincide.
+pub fn fit_planar(source: &[[f64; 2]], target: &[[f64; 2]]) -> Result<PlanarTransform> {
+    if source.len() != target.len() || source.len() < 2 {
+        return Err(Error::InsufficientData {
+            required: 2,
+            provided: source.len().min(target.len()),
+        });
+    }
+    let centroid = |points: &[[f64; 2]]| {
+        points
+            .iter()
+            .map(|&p| Vector2::from(p))
+            .sum::<Vector2<f64>>()
+            / points.len() as f64
+    };
+    let (source_centroid, target_centroid) = (centroid(source), centroid(target));
+    let (mut dot, mut cross, mut spread) = (0.0, 0.0, 0.0);
+    for (&s, &t) in source.iter().zip(target) {
+        let (s, t) = (
+            Vector2::from(s) - source_centroid,
+            Vector2::from(t) - target_centroid,
+        );
+        dot += s.dot(&t);
+        cross += s.x * t.y - s.y * t.x;
+        spread += s.norm_squared();
+    }
+    if spread <= DEGENERACY_TOLERANCE * DEGENERACY_TOLERANCE {
+        return Err(Error::Singular);
+    }
+    let angle = cross.atan2(dot);
+    let (sin, cos) = angle.sin_cos();
+    let rotated = Vector2::new(
+        cos * source_centroid.x - sin * source_centroid.y,
+        sin * source_centroid.x + cos * source_centroid.y,
+    );
+    Ok(PlanarTransform {
+        angle,
+        translation: (target_centroid - rotated).into(),
+    })
+}
+
+/// 3D point correspondences for [`crate::ransac::ransac`], fitted with
+/// [`fit_rigid`] from three-point samples. Residuals are the distances
+/// \(\lVert T s_i - t_i \rVert\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RigidRegistration<'a> {
+    pub source: &'a [[f64; 3]],
+    pub target: &'a [[f64; 3]],
+}
+
+impl RansacProblem for RigidRegistration<'_> {
+    type Model = Se3;
+
+    fn len(&self) -> usize {
+        self.source.len().min(self.target.len())
+    }
+
+    fn sample_size(&self) -> usize {
+        3
+    }
+
+    fn fit(&self, indices: &[usize]) -> Option<Se3> {
+        let source: Vec<_> = indices.iter().map(|&i| self.source[i]).collect();
+        let target: Vec<_> = indices.iter().map(|&i| self.target[i]).collect();
+        fit_rigid(&source, &target).ok()
+    }
+
+    fn residual(&self, model: &Se3, index: usize) -> f64 {
+        (Vector3::from(model.apply(self.source[index])) - Vector3::from(self.target[index])).norm()
+    }
+}
+
+/// 2D point correspondences for [`crate::ransac::ransac`], fitted with
+/// [`fit_planar`] from two-point samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanarRegistration<'a> {
+    pub source: &'a [[f64; 2]],
+    pub target: &'a [[f64; 2]],
+}
+
+impl RansacProblem for PlanarRegistration<'_> {
+    type Model = PlanarTransform;
+
+    fn len(&self) -> usize {
+        self.source.len().min(self.target.len())
+    }
+
+    fn sample_size(&self) -> usize {
+        2
+    }
+
+    fn fit(&self, indices: &[usize]) -> Option<PlanarTransform> {
+        let source: Vec<_> = indices.iter().map(|&i| self.source[i]).collect();
+        let target: Vec<_> = indices.iter().map(|&i| self.target[i]).collect();
+        fit_planar(&source, &target).ok()
+    }
+
+    fn residual(&self, model: &PlanarTransform, index: usize) -> f64 {
+        (Vector2::from(model.apply(self.source[index])) - Vector2::from(self.target[index])).norm()
+    }
+}
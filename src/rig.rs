@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::lie::LieGroup;
+use crate::se3::Se3;
+
+/// Extrinsic calibration of a single sensor: the pose of the sensor frame
+/// expressed in its parent frame, \(T_{\text{parent},\text{sensor}}\).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorExtrinsic {
+    pub name: String,
+    pub parent: String,
+    pub transform: Se3,
+}
+
+impl SensorExtrinsic {
+    pub fn new(name: impl Into<String>, parent: impl Into<String>, transform: Se3) -> Self {
+        Self {
+            name: name.into(),
+            parent: parent.into(),
+            transform,
+        }
+    }
+}
+
+/// An immutable set of named sensor extrinsics sharing a common base frame.
+///
+/// Sensors may be mounted on the base or on another sensor. Construction
+/// (including deserialization) validates that every name is unique and that
+/// each sensor connects back to the base frame, and caches the composed
+/// base-from-sensor transforms so lookups do not re-walk the mounting chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RigDescription", into = "RigDescription")]
+pub struct SensorRig {
+    base: String,
+    sensors: Vec<SensorExtrinsic>,
+    base_from_sensor: Vec<Se3>,
+    index: HashMap<String, usize>,
+}
+
+/// Serialized form of a [`SensorRig`]; only the calibration data is stored.
+#[derive(Serialize, Deserialize)]
+struct RigDescription {
+    base: String,
+    sensors: Vec<SensorExtrinsic>,
+}
+
+impl SensorRig {
+    /// Build a rig from a base frame name and the sensor extrinsics.
+    ///
+    /// Returns [`Error::DuplicateFrame`] if a sensor name repeats (or reuses
+    /// the base name), [`Error::UnknownFrame`] if a parent is not defined and
+    /// [`Error::DisconnectedFrame`] if the parents form a cycle.
+    pub fn new(base: impl Into<String>, sensors: Vec<SensorExtrinsic>) -> Result<Self> {
+        let base = base.into();
+
+        let mut index = HashMap::with_capacity(sensors.len());
+        for (i, sensor) in sensors.iter().enumerate() {
+            if sensor.name == base || index.insert(sensor.name.clone(), i).is_some() {
+                return Err(Error::DuplicateFrame(sensor.name.clone()));
+            }
+        }
+        for sensor in &sensors {
+            if sensor.parent != base && !index.contains_key(&sensor.parent) {
+                return Err(Error::UnknownFrame(sensor.parent.clone()));
+            }
+        }
+
+        let mut resolved: Vec<Option<Se3>> = vec![None; sensors.len()];
+        for start in 0..sensors.len() {
+            // Walk up the mounting chain until reaching the base or an already
+            // resolved sensor, then compose back down.
+            let mut chain = Vec::new();
+            let mut current = start;
+            let mut anchor = Se3::identity();
+            while resolved[current].is_none() {
+                if chain.contains(&current) {
+                    return Err(Error::DisconnectedFrame(sensors[start].name.clone()));
+                }
+                chain.push(current);
+                let parent = &sensors[current].parent;
+                if *parent == base {
+                    break;
+                }
+                current = index[parent];
+            }
+            if let Some(transform) = &resolved[current] {
+                anchor = transform.clone();
+            }
+            for &i in chain.iter().rev() {
+                anchor = anchor.compose(&sensors[i].transform);
+                resolved[i] = Some(anchor.clone());
+            }
+        }
+
+        Ok(Self {
+            base,
+            sensors,
+            base_from_sensor: resolved.into_iter().flatten().collect(),
+            index,
+        })
+    }
+
+    /// Name of the frame all sensors are ultimately expressed in.
+    pub fn base_frame(&self) -> &str {
+        &self.base
+    }
+
+    /// Number of sensors in the rig.
+    pub fn len(&self) -> usize {
+        self.sensors.len()
+    }
+
+    /// Whether the rig holds no sensors.
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty()
+    }
+
+    /// The sensor extrinsics in the order they were supplied.
+    pub fn sensors(&self) -> &[SensorExtrinsic] {
+        &self.sensors
+    }
+
+    /// Look up a sensor's extrinsic relative to its parent frame.
+    pub fn sensor(&self, name: &str) -> Option<&SensorExtrinsic> {
+        self.index.get(name).map(|&i| &self.sensors[i])
+    }
+
+    /// Pose of a frame in the base frame, \(T_{\text{base},\text{name}}\).
+    /// The base frame itself maps to the identity.
+    pub fn base_from_sensor(&self, name: &str) -> Option<Se3> {
+        if name == self.base {
+            return Some(Se3::identity());
+        }
+        self.index
+            .get(name)
+            .map(|&i| self.base_from_sensor[i].clone())
+    }
+
+    /// Pose of a frame in the world given the current base pose,
+    /// \(T_{\text{world},\text{name}} = T_{\text{world},\text{base}} T_{\text{base},\text{name}}\).
+    pub fn world_from_sensor(&self, world_from_base: &Se3, name: &str) -> Option<Se3> {
+        self.base_from_sensor(name)
+            .map(|base_from_sensor| world_from_base.compose(&base_from_sensor))
+    }
+
+    /// Relative pose \(T_{\text{from},\text{to}}\) between two frames of the rig.
+    pub fn relative(&self, from: &str, to: &str) -> Option<Se3> {
+        let base_from = self.base_from_sensor(from)?;
+        let base_to = self.base_from_sensor(to)?;
+        Some(base_from.inverse().compose(&base_to))
+    }
+}
+
+impl TryFrom<RigDescription> for SensorRig {
+    type Error = Error;
+
+    fn try_from(description: RigDescription) -> Result<Self> {
+        SensorRig::new(description.base, description.sensors)
+    }
+}
+
+impl From<SensorRig> for RigDescription {
+    fn from(rig: SensorRig) -> Self {
+        Self {
+            base: rig.base,
+            sensors: rig.sensors,
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dynamics::SpatialInertia;
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+
+/// Mass properties of a link, expressed in the link frame. The inertia is
+/// taken about the centre of mass, as in [`SpatialInertia::new`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inertial {
+    pub mass: f64,
+    pub center_of_mass: [f64; 3],
+    pub inertia: [[f64; 3]; 3],
+}
+
+impl Inertial {
+    pub fn to_spatial_inertia(&self) -> SpatialInertia {
+        SpatialInertia::new(self.mass, self.center_of_mass, self.inertia)
+    }
+}
+
+impl From<&SpatialInertia> for Inertial {
+    fn from(inertia: &SpatialInertia) -> Self {
+        Self {
+            mass: inertia.mass(),
+            center_of_mass: inertia.center_of_mass(),
+            inertia: inertia.rotational_inertia(),
+        }
+    }
+}
+
+/// A rigid link of the robot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    pub name: String,
+    /// Mass properties; `None` for massless frames such as tool tips.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inertial: Option<Inertial>,
+}
+
+impl Link {
+    pub fn new(name: impl Into<String>, inertial: Option<Inertial>) -> Self {
+        Self {
+            name: name.into(),
+            inertial,
+        }
+    }
+}
+
+/// Motion allowed by a joint. Axes are unit vectors in the joint frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JointKind {
+    Fixed,
+    Revolute { axis: [f64; 3] },
+    Continuous { axis: [f64; 3] },
+    Prismatic { axis: [f64; 3] },
+}
+
+impl JointKind {
+    /// Joint axis, or `None` for fixed joints.
+    pub fn axis(&self) -> Option<[f64; 3]> {
+        match self {
+            JointKind::Fixed => None,
+            JointKind::Revolute { axis }
+            | JointKind::Continuous { axis }
+            | JointKind::Prismatic { axis } => Some(*axis),
+        }
+    }
+}
+
+/// Position, velocity and effort limits of a joint, in radians or metres and
+/// the matching rate and force/torque units. Velocity and effort limits are
+/// `None` when the source does not specify them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JointLimits {
+    pub lower: f64,
+    pub upper: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub velocity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort: Option<f64>,
+}
+
+/// A joint connecting a parent link to a child link. `origin` is the pose of
+/// the joint frame in the parent link frame, \(T_{\text{parent},\text{joint}}\);
+/// at zero joint position the child frame coincides with the joint frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Joint {
+    pub name: String,
+    pub parent: String,
+    pub child: String,
+    pub origin: Se3,
+    pub kind: JointKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<JointLimits>,
+}
+
+impl Joint {
+    pub fn new(
+        name: impl Into<String>,
+        parent: impl Into<String>,
+        child: impl Into<String>,
+        origin: Se3,
+        kind: JointKind,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            parent: parent.into(),
+            child: child.into(),
+            origin,
+            kind,
+            limits: None,
+        }
+    }
+
+    pub fn with_limits(mut self, limits: JointLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+}
+
+/// A validated, serializable tree of links and joints.
+///
+/// Like [`crate::SensorRig`], construction (including deserialization)
+/// checks that link and joint names are unique, that every joint references
+/// defined links, and that the joints form a single tree rooted at the one
+/// link without a parent joint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RobotData", into = "RobotData")]
+pub struct RobotDescription {
+    name: String,
+    links: Vec<Link>,
+    joints: Vec<Joint>,
+    root: usize,
+    link_index: HashMap<String, usize>,
+    joint_index: HashMap<String, usize>,
+    parent_joint: Vec<Option<usize>>,
+}
+
+/// Serialized form of a [`RobotDescription`]; lookup tables are rebuilt on load.
+#[derive(Serialize, Deserialize)]
+struct RobotData {
+    name: String,
+    links: Vec<Link>,
+    joints: Vec<Joint>,
+}
+
+impl RobotDescription {
+    /// Build and validate a description.
+    ///
+    /// Returns [`Error::DuplicateFrame`] for a repeated link or joint name,
+    /// [`Error::UnknownFrame`] if a joint references an undefined link and
+    /// [`Error::DisconnectedFrame`] if a link has several parent joints, the
+    /// joints form a cycle or the links do not share a single root.
+    pub fn new(name: impl Into<String>, links: Vec<Link>, joints: Vec<Joint>) -> Result<Self> {
+        let mut link_index = HashMap::with_capacity(links.len());
+        for (i, link) in links.iter().enumerate() {
+            if link_index.insert(link.name.clone(), i).is_some() {
+                return Err(Error::DuplicateFrame(link.name.clone()));
+            }
+        }
+        let mut joint_index = HashMap::with_capacity(joints.len());
+        let mut parent_joint = vec![None; links.len()];
+        for (j, joint) in joints.iter().enumerate() {
+            if joint_index.insert(joint.name.clone(), j).is_some() {
+                return Err(Error::DuplicateFrame(joint.name.clone()));
+            }
+            if !link_index.contains_key(&joint.parent) {
+                return Err(Error::UnknownFrame(joint.parent.clone()));
+            }
+            let Some(&child) = link_index.get(&joint.child) else {
+                return Err(Error::UnknownFrame(joint.child.clone()));
+            };
+            if parent_joint[child].replace(j).is_some() {
+                return Err(Error::DisconnectedFrame(joint.child.clone()));
+            }
+        }
+
+        let mut roots = (0..links.len()).filter(|&i| parent_joint[i].is_none());
+        let root = match (roots.next(), roots.next()) {
+            (Some(root), None) => root,
+            (Some(_), Some(second)) => {
+                return Err(Error::DisconnectedFrame(links[second].name.clone()));
+            }
+            // Every link has a parent joint, so the joints contain a cycle.
+            (None, _) => {
+                let name = links.first().map_or_else(String::new, |l| l.name.clone());
+                return Err(Error::DisconnectedFrame(name));
+            }
+        };
+        // With a single root and one parent per link, any link that cannot
+        // walk up to the root sits on a cycle.
+        for start in 0..links.len() {
+            let mut current = start;
+            for _ in 0..links.len() {
+                match parent_joint[current] {
+                    Some(j) => current = link_index[&joints[j].parent],
+                    None => break,
+                }
+            }
+            if current != root {
+                return Err(Error::DisconnectedFrame(links[start].name.clone()));
+            }
+        }
+
+        Ok(Self {
+            name: name.into(),
+            links,
+            joints,
+            root,
+            link_index,
+            joint_index,
+            parent_joint,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Links in the order they were supplied.
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    /// Joints in the order they were supplied.
+    pub fn joints(&self) -> &[Joint] {
+        &self.joints
+    }
+
+    /// The link without a parent joint.
+    pub fn root(&self) -> &Link {
+        &self.links[self.root]
+    }
+
+    pub fn link(&self, name: &str) -> Option<&Link> {
+        self.link_index.get(name).map(|&i| &self.links[i])
+    }
+
+    pub fn joint(&self, name: &str) -> Option<&Joint> {
+        self.joint_index.get(name).map(|&j| &self.joints[j])
+    }
+
+    /// The joint whose child is `link`; `None` for the root or unknown links.
+    pub fn parent_joint(&self, link: &str) -> Option<&Joint> {
+        let &i = self.link_index.get(link)?;
+        self.parent_joint[i].map(|j| &self.joints[j])
+    }
+
+    /// Joints whose parent is `link`, in supply order.
+    pub fn child_joints<'a>(&'a self, link: &'a str) -> impl Iterator<Item = &'a Joint> + 'a {
+        self.joints.iter().filter(move |joint| joint.parent == link)
+    }
+
+    /// Parse `text` with the importer of format `F`.
+    pub fn parse<F: DescriptionFormat>(text: &str) -> Result<Self> {
+        F::parse(text)
+    }
+
+    /// Number of non-fixed joints.
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.joints
+            .iter()
+            .filter(|joint| joint.kind != JointKind::Fixed)
+            .count()
+    }
+}
+
+/// A robot description file format that can be read into a
+/// [`RobotDescription`], such as [`crate::mjcf::Mjcf`], [`crate::sdf::Sdf`] or
+/// [`crate::urdf::Urdf`].
+pub trait DescriptionFormat {
+    /// Parse a document in this format into a validated description.
+    fn parse(text: &str) -> Result<RobotDescription>;
+}
+
+impl TryFrom<RobotData> for RobotDescription {
+    type Error = Error;
+
+    fn try_from(data: RobotData) -> Result<Self> {
+        RobotDescription::new(data.name, data.links, data.joints)
+    }
+}
+
+impl From<RobotDescription> for RobotData {
+    fn from(robot: RobotDescription) -> Self {
+        Self {
+            name: robot.name,
+            links: robot.links,
+            joints: robot.joints,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::ops::RangeInclusive;
+
+use nalgebra::Vector3;
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+
+/// A screw axis stored as its unit twist \(S = [\omega, v]\): either
+/// \(\lVert\omega\rVert = 1\), or \(\omega = 0\) and \(\lVert v\rVert = 1\)
+/// for a pure translation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Screw {
+    twist: [f64; 6],
+}
+
+impl Screw {
+    /// Rotation about the line through `point` along `axis`, advancing
+    /// `pitch` (m/rad) along it: \(S = [\hat\omega, -\hat\omega \times q + h \hat\omega]\).
+    ///
+    /// Returns [`Error::ZeroNorm`] for a zero axis.
+    pub fn revolute(axis: [f64; 3], point: [f64; 3], pitch: f64) -> Result<Self> {
+        let axis = Vector3::from(axis)
+            .try_normalize(0.0)
+            .ok_or(Error::ZeroNorm)?;
+        let v = -axis.cross(&Vector3::from(point)) + axis * pitch;
+        Ok(Self {
+            twist: [axis.x, axis.y, axis.z, v.x, v.y, v.z],
+        })
+    }
+
+    /// Pure translation along `direction` (infinite pitch).
+    ///
+    /// Returns [`Error::ZeroNorm`] for a zero direction.
+    pub fn prismatic(direction: [f64; 3]) -> Result<Self> {
+        let v = Vector3::from(direction)
+            .try_normalize(0.0)
+            .ok_or(Error::ZeroNorm)?;
+        Ok(Self {
+            twist: [0.0, 0.0, 0.0, v.x, v.y, v.z],
+        })
+    }
+
+    /// Normalize an arbitrary twist into its screw axis, so that
+    /// `Se3::exp(twist)` is `pose(θ)` for the returned magnitude \(\theta\).
+    ///
+    /// Returns [`Error::ZeroNorm`] for a zero twist.
+    pub fn from_twist(twist: [f64; 6]) -> Result<(Self, f64)> {
+        let angular = Vector3::new(twist[0], twist[1], twist[2]).norm();
+        let magnitude = if angular > 0.0 {
+            angular
+        } else {
+            Vector3::new(twist[3], twist[4], twist[5]).norm()
+        };
+        if magnitude == 0.0 || !magnitude.is_finite() {
+            return Err(Error::ZeroNorm);
+        }
+        Ok((
+            Self {
+                twist: twist.map(|x| x / magnitude),
+            },
+            magnitude,
+        ))
+    }
+
+    /// The unit twist \([\omega, v]\).
+    pub fn twist(&self) -> [f64; 6] {
+        self.twist
+    }
+
+    /// Pose \(\mathrm{Exp}(S \theta)\) after moving `theta` (rad, or m for a
+    /// pure translation) along the screw.
+    pub fn pose(&self, theta: f64) -> Se3 {
+        Se3::from_matrix(Se3::exp(self.twist, Some(theta)))
+    }
+
+    /// `n` poses at evenly spaced \(\theta\) over `range`, both ends
+    /// included; a single sample sits at the start.
+    pub fn sample(&self, range: RangeInclusive<f64>, n: usize) -> Vec<Se3> {
+        let (start, end) = range.into_inner();
+        (0..n)
+            .map(|k| {
+                let s = if n > 1 {
+                    k as f64 / (n - 1) as f64
+                } else {
+                    0.0
+                };
+                self.pose(start + s * (end - start))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,300 @@
+//! Import of SDFormat (Gazebo) models into a [`RobotDescription`].
+//!
+//! The first `<model>` of the document is read: links with `<pose>` and
+//! `<inertial>`, and `fixed`, `revolute`, `continuous` and `prismatic`
+//! joints with `<axis>` and `<limit>`. Poses follow SDFormat 1.7 defaults
+//! (links relative to the model frame, joints relative to their child link)
+//! and may name another link or `__model__` in `relative_to`. Nested models
+//! and other joint types are rejected.
+
+use std::collections::HashMap;
+
+use nalgebra::{Matrix3, Vector3};
+use roxmltree::Node;
+
+use crate::error::Result;
+use crate::lie::LieGroup;
+use crate::robot::{
+    DescriptionFormat, Inertial, Joint, JointKind, JointLimits, Link, RobotDescription,
+};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::xml::{self, child, children, floats, invalid};
+
+/// Name of the link created for joints attached to the world.
+pub const WORLD_LINK: &str = "world";
+
+const MODEL_FRAME: &str = "__model__";
+
+/// The SDFormat format, for use with [`RobotDescription::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sdf;
+
+impl DescriptionFormat for Sdf {
+    fn parse(text: &str) -> Result<RobotDescription> {
+        parse(text)
+    }
+}
+
+/// Parse an SDFormat document into a validated [`RobotDescription`].
+///
+/// SDFormat places a joint frame anywhere relative to its child link, while
+/// a [`RobotDescription`] puts each child link frame at its parent joint. Link
+/// frames are therefore moved to their parent joint frame, and inertials and
+/// joint origins are re-expressed accordingly; root links keep their own
+/// frame. Joints with parent `world` get a [`WORLD_LINK`] placed at the world
+/// origin, using the model `<pose>` if present.
+pub fn parse(text: &str) -> Result<RobotDescription> {
+    let document = xml::parse(text)?;
+    let root = document.root_element();
+    if !root.has_tag_name("sdf") {
+        return Err(invalid("root element must be <sdf>"));
+    }
+    let model = child(root, "model").ok_or_else(|| invalid("missing <model> element"))?;
+    if child(model, "model").is_some() || child(model, "include").is_some() {
+        return Err(invalid("nested models are not supported"));
+    }
+    let name = model.attribute("name").unwrap_or("model");
+
+    let link_nodes: Vec<Node> = children(model, "link").collect();
+    let joint_nodes: Vec<Node> = children(model, "joint").collect();
+    let mut frames = Frames {
+        nodes: link_nodes
+            .iter()
+            .map(|link| Ok((required_name(*link)?, *link)))
+            .collect::<Result<_>>()?,
+        resolved: HashMap::new(),
+    };
+
+    // Model-frame pose of the frame each link will use: its parent joint's
+    // frame, or the SDF link frame for roots.
+    let mut link_frames: HashMap<&str, Se3> = HashMap::new();
+    let mut parsed_joints = Vec::with_capacity(joint_nodes.len());
+    for node in &joint_nodes {
+        let joint_name = required_name(*node)?;
+        let parent = text_of(*node, "parent")?;
+        let child_link = text_of(*node, "child")?;
+        let relative_to = pose_frame(*node).unwrap_or(child_link);
+        let frame = frames
+            .model_from(relative_to)?
+            .compose(&pose(*node)?.unwrap_or_else(Se3::identity));
+        link_frames.insert(child_link, frame.clone());
+        parsed_joints.push((joint_name, parent, child_link, *node, frame));
+    }
+
+    let mut links = Vec::with_capacity(link_nodes.len() + 1);
+    if parsed_joints
+        .iter()
+        .any(|(_, parent, ..)| *parent == WORLD_LINK)
+    {
+        let world = pose(model)?.unwrap_or_else(Se3::identity).inverse();
+        link_frames.insert(WORLD_LINK, world);
+        links.push(Link::new(WORLD_LINK, None));
+    }
+    for node in &link_nodes {
+        let link_name = required_name(*node)?;
+        let sdf_frame = frames.model_from(link_name)?;
+        let frame = link_frames
+            .entry(link_name)
+            .or_insert_with(|| sdf_frame.clone())
+            .clone();
+        let inertial = inertial(*node, &frame.inverse().compose(&sdf_frame))?;
+        links.push(Link::new(link_name, inertial));
+    }
+
+    let mut joints = Vec::with_capacity(parsed_joints.len());
+    for (joint_name, parent, child_link, node, frame) in parsed_joints {
+        let parent_frame = link_frames.get(parent).ok_or_else(|| {
+            invalid(format!(
+                "joint '{joint_name}' has unknown parent '{parent}'"
+            ))
+        })?;
+        let origin = parent_frame.inverse().compose(&frame);
+        let (kind, limits) = joint_kind(node, &frame, &mut frames)?;
+        let mut joint = Joint::new(joint_name, parent, child_link, origin, kind);
+        joint.limits = limits;
+        joints.push(joint);
+    }
+
+    RobotDescription::new(name, links, joints)
+}
+
+/// Resolves the model-frame pose of SDF link frames through `relative_to`.
+struct Frames<'a, 'input> {
+    nodes: HashMap<&'a str, Node<'a, 'input>>,
+    resolved: HashMap<&'a str, Se3>,
+}
+
+impl<'a> Frames<'a, '_> {
+    fn model_from(&mut self, frame: &'a str) -> Result<Se3> {
+        let mut chain = Vec::new();
+        let mut current = frame;
+        let mut anchor = Se3::identity();
+        loop {
+            if current == MODEL_FRAME {
+                break;
+            }
+            if let Some(resolved) = self.resolved.get(current) {
+                anchor = resolved.clone();
+                break;
+            }
+            if chain.contains(&current) {
+                return Err(invalid(format!("pose of '{frame}' is defined in a cycle")));
+            }
+            let node = *self
+                .nodes
+                .get(current)
+                .ok_or_else(|| invalid(format!("unknown frame '{current}'")))?;
+            chain.push(current);
+            current = pose_frame(node).unwrap_or(MODEL_FRAME);
+        }
+        for link in chain.into_iter().rev() {
+            anchor = anchor.compose(&pose(self.nodes[link])?.unwrap_or_else(Se3::identity));
+            self.resolved.insert(link, anchor.clone());
+        }
+        Ok(anchor)
+    }
+}
+
+fn required_name<'a>(node: Node<'a, '_>) -> Result<&'a str> {
+    node.attribute("name")
+        .ok_or_else(|| invalid(format!("<{}> requires a name", node.tag_name().name())))
+}
+
+fn text_of<'a>(node: Node<'a, '_>, tag: &'static str) -> Result<&'a str> {
+    child(node, tag)
+        .and_then(|element| element.text())
+        .map(str::trim)
+        .ok_or_else(|| invalid(format!("<{}> requires <{tag}>", node.tag_name().name())))
+}
+
+fn number(node: Node, tag: &'static str) -> Result<Option<f64>> {
+    child(node, tag)
+        .map(|element| floats::<1>(element.text().unwrap_or(""), tag).map(|[value]| value))
+        .transpose()
+}
+
+/// The frame named by the `relative_to` attribute of `node`'s `<pose>`.
+fn pose_frame<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    child(node, "pose").and_then(|pose| pose.attribute("relative_to"))
+}
+
+/// The `<pose>` child of `node`: `x y z roll pitch yaw` with fixed-axis
+/// roll–pitch–yaw, optionally in degrees, or `x y z qx qy qz qw`.
+fn pose(node: Node) -> Result<Option<Se3>> {
+    let Some(pose) = child(node, "pose") else {
+        return Ok(None);
+    };
+    let text = pose.text().unwrap_or("");
+    let degrees = pose.attribute("degrees") == Some("true");
+    let transform = match pose.attribute("rotation_format").unwrap_or("euler_rpy") {
+        "euler_rpy" => {
+            let [x, y, z, roll, pitch, yaw] = floats::<6>(text, "<pose>")?;
+            let angle = |a: f64| if degrees { a.to_radians() } else { a };
+            Se3::from_parts(
+                So3::from_euler_angles(angle(roll), angle(pitch), angle(yaw)),
+                [x, y, z],
+            )
+        }
+        "quat_xyzw" => {
+            let [x, y, z, qx, qy, qz, qw] = floats::<7>(text, "<pose>")?;
+            Se3::from_parts(So3::try_from_quaternion([qw, qx, qy, qz])?, [x, y, z])
+        }
+        other => return Err(invalid(format!("unknown rotation_format '{other}'"))),
+    };
+    Ok(Some(transform))
+}
+
+/// Mass properties of a link in the frame \(F\) chosen for it, given
+/// `frame_from_link` \(= T_{F,\text{link}}\).
+fn inertial(link: Node, frame_from_link: &Se3) -> Result<Option<Inertial>> {
+    let Some(inertial) = child(link, "inertial") else {
+        return Ok(None);
+    };
+    let mass = number(inertial, "mass")?.unwrap_or(1.0);
+    let frame_from_inertial =
+        frame_from_link.compose(&pose(inertial)?.unwrap_or_else(Se3::identity));
+    let moments = match child(inertial, "inertia") {
+        Some(inertia) => {
+            // SDFormat defaults to a unit diagonal.
+            let value = |tag, default| number(inertia, tag).map(|v| v.unwrap_or(default));
+            let (xx, yy, zz) = (value("ixx", 1.0)?, value("iyy", 1.0)?, value("izz", 1.0)?);
+            let (xy, xz, yz) = (value("ixy", 0.0)?, value("ixz", 0.0)?, value("iyz", 0.0)?);
+            Matrix3::new(xx, xy, xz, xy, yy, yz, xz, yz, zz)
+        }
+        None => Matrix3::identity(),
+    };
+    let rotation = frame_from_inertial.rotation().rotation().matrix();
+    let inertia = rotation * moments * rotation.transpose();
+    Ok(Some(Inertial {
+        mass,
+        center_of_mass: frame_from_inertial.translation(),
+        inertia: std::array::from_fn(|r| std::array::from_fn(|c| inertia[(r, c)])),
+    }))
+}
+
+/// Joint kind with its axis expressed in the joint frame, and its limits.
+fn joint_kind<'a>(
+    joint: Node<'a, '_>,
+    model_from_joint: &Se3,
+    frames: &mut Frames<'a, '_>,
+) -> Result<(JointKind, Option<JointLimits>)> {
+    let kind = joint
+        .attribute("type")
+        .ok_or_else(|| invalid("<joint> requires a type"))?;
+    if kind == "fixed" {
+        return Ok((JointKind::Fixed, None));
+    }
+
+    let axis_node = child(joint, "axis");
+    let axis = match axis_node.and_then(|axis| child(axis, "xyz")) {
+        Some(xyz) => {
+            let vector = Vector3::from(floats::<3>(xyz.text().unwrap_or(""), "<xyz>")?);
+            let expressed_in = if axis_node
+                .and_then(|axis| child(axis, "use_parent_model_frame"))
+                .and_then(|flag| flag.text())
+                .map(str::trim)
+                == Some("true")
+            {
+                Some(MODEL_FRAME)
+            } else {
+                xyz.attribute("expressed_in")
+            };
+            let joint_from_axis = match expressed_in {
+                None => So3::identity(),
+                Some(frame) => model_from_joint
+                    .rotation()
+                    .inverse()
+                    .compose(frames.model_from(frame)?.rotation()),
+            };
+            Vector3::from(joint_from_axis.apply(vector.into()))
+        }
+        None => Vector3::z(),
+    };
+    if axis.norm() == 0.0 {
+        return Err(invalid("joint axis has zero length"));
+    }
+    let axis = axis.normalize().into();
+
+    let limit = axis_node.and_then(|axis| child(axis, "limit"));
+    let limits = match limit {
+        Some(limit) if kind != "continuous" => {
+            let unlimited = |value: Option<f64>| value.filter(|v| *v >= 0.0);
+            Some(JointLimits {
+                lower: number(limit, "lower")?.unwrap_or(-1e16),
+                upper: number(limit, "upper")?.unwrap_or(1e16),
+                velocity: unlimited(number(limit, "velocity")?),
+                effort: unlimited(number(limit, "effort")?),
+            })
+        }
+        _ => None,
+    };
+
+    let kind = match kind {
+        "revolute" => JointKind::Revolute { axis },
+        "continuous" => JointKind::Continuous { axis },
+        "prismatic" => JointKind::Prismatic { axis },
+        other => return Err(invalid(format!("joint type '{other}' is not supported"))),
+    };
+    Ok((kind, limits))
+}
@@ -0,0 +1,245 @@
+use nalgebra::{Matrix2, Matrix3, SMatrix, SVector, Translation2, Vector2};
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::lie::{HasAdjoint, HasExp, LieGroup, matrix_to_array};
+use crate::so2::So2;
+
+/// Below this angle the \(V\) matrix of the exponential uses its series
+/// expansion.
+const SMALL_ANGLE: f64 = 1e-12;
+
+/// A planar rigid transform in the special Euclidean group \(\mathrm{SE}(2)\),
+/// storing a rotation and translation. Twists are ordered \([\omega, v_x, v_y]\),
+/// matching the \([\omega, v]\) ordering of [`crate::se3::Se3`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Se2 {
+    rotation: So2,
+    translation: Translation2<f64>,
+}
+
+impl Se2 {
+    pub fn from_parts(rotation: So2, translation: [f64; 2]) -> Self {
+        Self {
+            rotation,
+            translation: Translation2::new(translation[0], translation[1]),
+        }
+    }
+
+    /// Rotation by `angle` (rad) followed by `translation`.
+    pub fn from_angle_translation(angle: f64, translation: [f64; 2]) -> Self {
+        Self::from_parts(So2::from_angle(angle), translation)
+    }
+
+    /// Build an SE(2) element from a 3×3 homogeneous matrix. The bottom row is
+    /// assumed to be `[0, 0, 1]`.
+    pub fn from_matrix(matrix: [[f64; 3]; 3]) -> Self {
+        Self::from_parts(
+            So2::from_matrix([[matrix[0][0], matrix[0][1]], [matrix[1][0], matrix[1][1]]]),
+            [matrix[0][2], matrix[1][2]],
+        )
+    }
+
+    /// Left-multiply two transforms so that the result maps a point by `other`
+    /// and then by `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        let translated =
+            self.translation.vector + self.rotation.rotation() * other.translation.vector;
+        Self {
+            rotation: self.rotation.compose(&other.rotation),
+            translation: Translation2::from(translated),
+        }
+    }
+
+    /// Compute the inverse rigid motion: \(T^{-1} = [R^T, -R^T t]\).
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.inverse();
+        let translation = -(rotation.rotation() * self.translation.vector);
+        Self {
+            rotation,
+            translation: Translation2::from(translation),
+        }
+    }
+
+    /// Apply the rigid transform to a 2D point (rotate, then translate).
+    pub fn apply(&self, point: [f64; 2]) -> [f64; 2] {
+        let moved = self.rotation.rotation() * Vector2::from(point) + self.translation.vector;
+        moved.into()
+    }
+
+    /// Exponential map from a twist \([\omega, v_x, v_y]\) to a 3×3 homogeneous
+    /// matrix. The optional scale factor `a` multiplies the twist first, as in
+    /// [`crate::se3::Se3::exp`].
+    pub fn exp(twist: [f64; 3], a: Option<f64>) -> [[f64; 3]; 3] {
+        let scale = a.unwrap_or(1.0);
+        let theta = twist[0] * scale;
+        let v = Vector2::new(twist[1], twist[2]) * scale;
+        let translation = v_matrix(theta) * v;
+        Self::from_angle_translation(theta, translation.into()).to_matrix()
+    }
+
+    /// Twist \([\omega, v_x, v_y]\) with \(\exp(\xi^\wedge) = T\), inverting
+    /// [`Se2::exp`] through \(V^{-1}\). The angle is returned in
+    /// \((-\pi, \pi]\).
+    pub fn log(&self) -> [f64; 3] {
+        let theta = self.rotation.angle();
+        // V = aI + bJ with a² + b² > 0, so V⁻¹ = (aI - bJ) / (a² + b²).
+        let v_matrix = v_matrix(theta);
+        let v = v_matrix.transpose() / v_matrix.determinant() * self.translation.vector;
+        [theta, v.x, v.y]
+    }
+
+    /// [`Se2::log`] of a homogeneous matrix, the inverse of [`Se2::exp`].
+    pub fn log_matrix(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+        Self::from_matrix(matrix).log()
+    }
+
+    /// Lie-algebra hat operator mapping \([\omega, v_x, v_y]\) into a 3×3
+    /// matrix in `se(2)`.
+    pub fn hat(twist: [f64; 3]) -> [[f64; 3]; 3] {
+        let [omega, vx, vy] = twist;
+        [[0.0, -omega, vx], [omega, 0.0, vy], [0.0, 0.0, 0.0]]
+    }
+
+    /// Inverse of [`Se2::hat`]; the rotation block is symmetrized.
+    pub fn vee(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+        [
+            0.5 * (matrix[1][0] - matrix[0][1]),
+            matrix[0][2],
+            matrix[1][2],
+        ]
+    }
+
+    /// Adjoint \(\mathrm{Ad}_T = \begin{bmatrix} 1 & 0 \\ -J t & R \end{bmatrix}\)
+    /// on twists \([\omega, v]\), with \(J\) the rotation by \(\pi/2\).
+    pub fn adjoint(&self) -> SMatrix<f64, 3, 3> {
+        let rotation = self.rotation.rotation().matrix();
+        let t = self.translation.vector;
+        Matrix3::new(
+            1.0,
+            0.0,
+            0.0,
+            t.y,
+            rotation[(0, 0)],
+            rotation[(0, 1)],
+            -t.x,
+            rotation[(1, 0)],
+            rotation[(1, 1)],
+        )
+    }
+
+    pub fn rotation(&self) -> &So2 {
+        &self.rotation
+    }
+
+    pub fn translation(&self) -> [f64; 2] {
+        self.translation.vector.into()
+    }
+
+    /// Export the 3×3 homogeneous transform matrix.
+    pub fn to_matrix(&self) -> [[f64; 3]; 3] {
+        matrix_to_array(&self.as_matrix())
+    }
+}
+
+/// \(V(\theta) = \frac{\sin\theta}{\theta} I + \frac{1 - \cos\theta}{\theta} J\),
+/// mapping the linear part of a twist to the translation of its exponential.
+fn v_matrix(theta: f64) -> Matrix2<f64> {
+    let (a, b) = if theta.abs() < SMALL_ANGLE {
+        (1.0, 0.5 * theta)
+    } else {
+        let half = 0.5 * theta;
+        (theta.sin() / theta, 2.0 * half.sin() * half.sin() / theta)
+    };
+    Matrix2::new(a, -b, b, a)
+}
+
+/// Free-function form of [`Se2::exp`].
+pub fn exp(twist: [f64; 3]) -> [[f64; 3]; 3] {
+    Se2::exp(twist, None)
+}
+
+/// Free-function form of [`Se2::log_matrix`].
+pub fn log(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+    Se2::log_matrix(matrix)
+}
+
+/// Free-function form of [`Se2::hat`].
+pub fn hat(twist: [f64; 3]) -> [[f64; 3]; 3] {
+    Se2::hat(twist)
+}
+
+/// Free-function form of [`Se2::vee`].
+pub fn vee(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+    Se2::vee(matrix)
+}
+
+/// Adjoint \(\mathrm{Ad}_T\) of a homogeneous matrix, as nested rows.
+pub fn adjoint_of(matrix: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    matrix_to_array(&Se2::from_matrix(matrix).adjoint())
+}
+
+impl LieGroup<3> for Se2 {
+    fn identity() -> Self {
+        Self {
+            rotation: So2::identity(),
+            translation: Translation2::identity(),
+        }
+    }
+
+    fn compose(&self, other: &Self) -> Self {
+        self.compose(other)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn as_matrix(&self) -> SMatrix<f64, 3, 3> {
+        let mut matrix = Matrix3::<f64>::identity();
+        matrix
+            .fixed_view_mut::<2, 2>(0, 0)
+            .copy_from(self.rotation.rotation().matrix());
+        matrix
+            .fixed_view_mut::<2, 1>(0, 2)
+            .copy_from(&self.translation.vector);
+        matrix
+    }
+}
+
+impl HasAdjoint<3> for Se2 {
+    fn adjoint_matrix(&self) -> SMatrix<f64, 3, 3> {
+        self.adjoint()
+    }
+}
+
+impl HasExp<3> for Se2 {
+    fn exp_map(xi: &SVector<f64, 3>) -> Self {
+        Self::from_matrix(Self::exp((*xi).into(), None))
+    }
+
+    /// Bracket of twists \([\omega_a, v_a]\) and \([\omega_b, v_b]\):
+    /// \([0, \omega_a J v_b - \omega_b J v_a]\).
+    fn bracket(a: &SVector<f64, 3>, b: &SVector<f64, 3>) -> SVector<f64, 3> {
+        let linear = |omega: f64, v: [f64; 2]| [-omega * v[1], omega * v[0]];
+        let first = linear(a[0], [b[1], b[2]]);
+        let second = linear(b[0], [a[1], a[2]]);
+        SVector::<f64, 3>::new(0.0, first[0] - second[0], first[1] - second[1])
+    }
+}
+
+impl Mul for Se2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl<'a> Mul<&'a Se2> for Se2 {
+    type Output = Se2;
+
+    fn mul(self, rhs: &'a Se2) -> Self::Output {
+        self.compose(rhs)
+    }
+}
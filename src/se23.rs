@@ -0,0 +1,275 @@
+use nalgebra::{Matrix3, SMatrix, SVector, Vector3};
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::lie::{HasAdjoint, HasExp, LieGroup, matrix_to_array};
+use crate::so3::So3;
+use crate::util::{skew_symmetric, vector3_from_array, vector3_to_array};
+
+/// An extended pose in \(\mathrm{SE}_2(3)\), the group of the invariant EKF:
+/// a rotation \(R\) together with a velocity \(v\) and position \(p\), both
+/// expressed in the reference frame. As a 5×5 matrix,
+/// \(\begin{bmatrix} R & v & p \\ 0 & 1 & 0 \\ 0 & 0 & 1 \end{bmatrix}\).
+///
+/// Tangent vectors are ordered \([\phi, \nu, \rho]\) (rotation, velocity,
+/// position), keeping the rotation first as in [`crate::se3::Se3`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Se23 {
+    rotation: So3,
+    velocity: Vector3<f64>,
+    position: Vector3<f64>,
+}
+
+impl Se23 {
+    pub fn from_parts(rotation: So3, velocity: [f64; 3], position: [f64; 3]) -> Self {
+        Self {
+            rotation,
+            velocity: vector3_from_array(velocity),
+            position: vector3_from_array(position),
+        }
+    }
+
+    /// Build an extended pose from a 5×5 matrix. The bottom two rows are
+    /// assumed to be `[0, 0, 0, 1, 0]` and `[0, 0, 0, 0, 1]`.
+    pub fn from_matrix(matrix: [[f64; 5]; 5]) -> Self {
+        let rotation = std::array::from_fn(|r| std::array::from_fn(|c| matrix[r][c]));
+        Self::from_parts(
+            So3::from_matrix(rotation),
+            std::array::from_fn(|r| matrix[r][3]),
+            std::array::from_fn(|r| matrix[r][4]),
+        )
+    }
+
+    /// Compose two extended poses: \((R_1 R_2, v_1 + R_1 v_2, p_1 + R_1 p_2)\).
+    pub fn compose(&self, other: &Self) -> Self {
+        let rotation = self.rotation.rotation();
+        Self {
+            rotation: self.rotation.compose(&other.rotation),
+            velocity: self.velocity + rotation * other.velocity,
+            position: self.position + rotation * other.position,
+        }
+    }
+
+    /// Inverse extended pose: \((R^T, -R^T v, -R^T p)\).
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.inverse();
+        let velocity = -(rotation.rotation() * self.velocity);
+        let position = -(rotation.rotation() * self.position);
+        Self {
+            rotation,
+            velocity,
+            position,
+        }
+    }
+
+    /// Exponential map from \([\phi, \nu, \rho]\) to a 5×5 matrix:
+    /// \(R = \mathrm{Exp}(\phi)\), \(v = J_l(\phi)\nu\), \(p = J_l(\phi)\rho\).
+    /// The optional scale factor `a` multiplies the tangent vector first, as in
+    /// [`crate::se3::Se3::exp`].
+    pub fn exp(xi: [f64; 9], a: Option<f64>) -> [[f64; 5]; 5] {
+        let xi = xi.map(|x| x * a.unwrap_or(1.0));
+        let (phi, nu, rho) = split(xi);
+        let jacobian = So3::left_jacobian(phi);
+        Self {
+            rotation: So3::from_rotation_vector(phi),
+            velocity: jacobian * vector3_from_array(nu),
+            position: jacobian * vector3_from_array(rho),
+        }
+        .to_matrix()
+    }
+
+    /// Tangent vector \([\phi, \nu, \rho]\) with \(\exp(\xi^\wedge) = X\),
+    /// inverting [`Se23::exp`] through \(J_l(\phi)^{-1}\).
+    pub fn log(&self) -> [f64; 9] {
+        let phi = self.rotation.to_rotation_vector();
+        let inverse = So3::left_jacobian_inverse(phi);
+        join(
+            phi,
+            vector3_to_array(&(inverse * self.velocity)),
+            vector3_to_array(&(inverse * self.position)),
+        )
+    }
+
+    /// [`Se23::log`] of a 5×5 matrix, the inverse of [`Se23::exp`].
+    pub fn log_matrix(matrix: [[f64; 5]; 5]) -> [f64; 9] {
+        Self::from_matrix(matrix).log()
+    }
+
+    /// Lie-algebra hat operator mapping \([\phi, \nu, \rho]\) into a 5×5
+    /// matrix in \(\mathfrak{se}_2(3)\).
+    pub fn hat(xi: [f64; 9]) -> [[f64; 5]; 5] {
+        let (phi, nu, rho) = split(xi);
+        let skew = So3::hat(phi);
+        let mut matrix = [[0.0; 5]; 5];
+        for r in 0..3 {
+            matrix[r][..3].copy_from_slice(&skew[r]);
+            matrix[r][3] = nu[r];
+            matrix[r][4] = rho[r];
+        }
+        matrix
+    }
+
+    /// Inverse of [`Se23::hat`]; the rotation block is symmetrized.
+    pub fn vee(matrix: [[f64; 5]; 5]) -> [f64; 9] {
+        let skew = std::array::from_fn(|r| std::array::from_fn(|c| matrix[r][c]));
+        join(
+            So3::vee(skew),
+            std::array::from_fn(|r| matrix[r][3]),
+            std::array::from_fn(|r| matrix[r][4]),
+        )
+    }
+
+    /// Adjoint
+    /// \(\mathrm{Ad}_X = \begin{bmatrix} R & 0 & 0 \\ [v]_\times R & R & 0 \\ [p]_\times R & 0 & R \end{bmatrix}\)
+    /// on tangent vectors \([\phi, \nu, \rho]\).
+    pub fn adjoint(&self) -> SMatrix<f64, 9, 9> {
+        let rotation: Matrix3<f64> = *self.rotation.rotation().matrix();
+        let mut adjoint = SMatrix::<f64, 9, 9>::zeros();
+        for i in 0..3 {
+            adjoint
+                .fixed_view_mut::<3, 3>(3 * i, 3 * i)
+                .copy_from(&rotation);
+        }
+        adjoint
+            .fixed_view_mut::<3, 3>(3, 0)
+            .copy_from(&(skew_symmetric(&self.velocity) * rotation));
+        adjoint
+            .fixed_view_mut::<3, 3>(6, 0)
+            .copy_from(&(skew_symmetric(&self.position) * rotation));
+        adjoint
+    }
+
+    pub fn rotation(&self) -> &So3 {
+        &self.rotation
+    }
+
+    pub fn velocity(&self) -> [f64; 3] {
+        vector3_to_array(&self.velocity)
+    }
+
+    pub fn position(&self) -> [f64; 3] {
+        vector3_to_array(&self.position)
+    }
+
+    /// Export the 5×5 matrix.
+    pub fn to_matrix(&self) -> [[f64; 5]; 5] {
+        matrix_to_array(&self.as_matrix())
+    }
+}
+
+fn split(xi: [f64; 9]) -> ([f64; 3], [f64; 3], [f64; 3]) {
+    (
+        [xi[0], xi[1], xi[2]],
+        [xi[3], xi[4], xi[5]],
+        [xi[6], xi[7], xi[8]],
+    )
+}
+
+fn join(phi: [f64; 3], nu: [f64; 3], rho: [f64; 3]) -> [f64; 9] {
+    let mut xi = [0.0; 9];
+    xi[..3].copy_from_slice(&phi);
+    xi[3..6].copy_from_slice(&nu);
+    xi[6..].copy_from_slice(&rho);
+    xi
+}
+
+/// Free-function form of [`Se23::exp`].
+pub fn exp(xi: [f64; 9]) -> [[f64; 5]; 5] {
+    Se23::exp(xi, None)
+}
+
+/// Free-function form of [`Se23::log_matrix`].
+pub fn log(matrix: [[f64; 5]; 5]) -> [f64; 9] {
+    Se23::log_matrix(matrix)
+}
+
+/// Free-function form of [`Se23::hat`].
+pub fn hat(xi: [f64; 9]) -> [[f64; 5]; 5] {
+    Se23::hat(xi)
+}
+
+/// Free-function form of [`Se23::vee`].
+pub fn vee(matrix: [[f64; 5]; 5]) -> [f64; 9] {
+    Se23::vee(matrix)
+}
+
+/// Adjoint \(\mathrm{Ad}_X\) of a 5×5 matrix, as nested rows.
+pub fn adjoint_of(matrix: [[f64; 5]; 5]) -> [[f64; 9]; 9] {
+    matrix_to_array(&Se23::from_matrix(matrix).adjoint())
+}
+
+impl LieGroup<5> for Se23 {
+    fn identity() -> Self {
+        Self {
+            rotation: So3::identity(),
+            velocity: Vector3::zeros(),
+            position: Vector3::zeros(),
+        }
+    }
+
+    fn compose(&self, other: &Self) -> Self {
+        self.compose(other)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn as_matrix(&self) -> SMatrix<f64, 5, 5> {
+        let mut matrix = SMatrix::<f64, 5, 5>::identity();
+        matrix
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(self.rotation.rotation().matrix());
+        matrix
+            .fixed_view_mut::<3, 1>(0, 3)
+            .copy_from(&self.velocity);
+        matrix
+            .fixed_view_mut::<3, 1>(0, 4)
+            .copy_from(&self.position);
+        matrix
+    }
+}
+
+impl HasAdjoint<9> for Se23 {
+    fn adjoint_matrix(&self) -> SMatrix<f64, 9, 9> {
+        self.adjoint()
+    }
+}
+
+impl HasExp<9> for Se23 {
+    fn exp_map(xi: &SVector<f64, 9>) -> Self {
+        Self::from_matrix(Self::exp((*xi).into(), None))
+    }
+
+    /// Bracket \([\phi_a \times \phi_b, \phi_a \times \nu_b - \phi_b \times \nu_a,
+    /// \phi_a \times \rho_b - \phi_b \times \rho_a]\).
+    fn bracket(a: &SVector<f64, 9>, b: &SVector<f64, 9>) -> SVector<f64, 9> {
+        let part = |x: &SVector<f64, 9>, i: usize| x.fixed_rows::<3>(3 * i).into_owned();
+        let (phi_a, phi_b) = (part(a, 0), part(b, 0));
+        let mut bracket = SVector::<f64, 9>::zeros();
+        bracket
+            .fixed_rows_mut::<3>(0)
+            .copy_from(&phi_a.cross(&phi_b));
+        for i in 1..3 {
+            let linear = phi_a.cross(&part(b, i)) - phi_b.cross(&part(a, i));
+            bracket.fixed_rows_mut::<3>(3 * i).copy_from(&linear);
+        }
+        bracket
+    }
+}
+
+impl Mul for Se23 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl<'a> Mul<&'a Se23> for Se23 {
+    type Output = Se23;
+
+    fn mul(self, rhs: &'a Se23) -> Self::Output {
+        self.compose(rhs)
+    }
+}
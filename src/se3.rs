@@ -1,20 +1,32 @@
-use nalgebra::{Matrix3, Matrix4, Rotation3, SMatrix, Translation3, Vector3};
+use nalgebra::{Matrix3, Matrix4, Rotation3, SMatrix, SVector, Translation3, Vector3};
+use serde::{Deserialize, Serialize};
 use std::ops::Mul;
+use std::sync::Arc;
 
 use crate::{
-    lie::{HasAdjoint, LieGroup, matrix_to_array},
+    error::Result,
+    health::TransformHealth,
+    lie::{
+        HasAdjoint, HasExp, LieGroup, apply_linear, matrix_to_array, matrix_to_col_major,
+        matrix_to_row_major,
+    },
     so3::So3,
-    util::{vector3_from_array, vector3_to_array},
+    util::{skew_symmetric, vector3_from_array, vector3_to_array},
 };
 
 /// A rigid-body transform in the special Euclidean group \(\mathrm{SE}(3)\),
 /// storing a rotation and translation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Se3 {
     rotation: So3,
     translation: Translation3<f64>,
 }
 
+/// Cheaply clonable handle for sharing an immutable transform (e.g. a sensor
+/// calibration) between threads. `Se3` is `Send + Sync`, so readers only pay
+/// for the reference-count update on clone.
+pub type ArcSe3 = Arc<Se3>;
+
 impl Se3 {
     /// Build an SE(3) element directly from a 4×4 homogeneous matrix.
     /// The bottom row is assumed to be `[0, 0, 0, 1]` and the top-left
@@ -38,17 +50,19 @@ impl Se3 {
             matrix[3][2],
             matrix[3][3],
         ];
-        let mat = Matrix4::from_row_slice(&flat);
+        Self::from_flat_matrix(&flat)
+    }
 
-        let rotation_matrix: [[f64; 3]; 3] = [
-            [mat[(0, 0)], mat[(0, 1)], mat[(0, 2)]],
-            [mat[(1, 0)], mat[(1, 1)], mat[(1, 2)]],
-            [mat[(2, 0)], mat[(2, 1)], mat[(2, 2)]],
+    /// Build an SE(3) element from a flat row-major 4×4 homogeneous matrix,
+    /// reading element `(r, c)` from index `4 * r + c`. The bottom row is
+    /// ignored, matching [`Se3::from_matrix`].
+    pub fn from_flat_matrix(flat: &[f64; 16]) -> Self {
+        let rotation = [
+            flat[0], flat[1], flat[2], flat[4], flat[5], flat[6], flat[8], flat[9], flat[10],
         ];
+        let translation = [flat[3], flat[7], flat[11]];
 
-        let translation = [mat[(0, 3)], mat[(1, 3)], mat[(2, 3)]];
-
-        Self::from_parts(So3::from_matrix(rotation_matrix), translation)
+        Self::from_parts(So3::from_flat(&rotation), translation)
     }
 
     /// Construct the Lie-algebra hat operator mapping a 6D twist vector
@@ -176,6 +190,104 @@ impl Se3 {
         vector3_to_array(&translated)
     }
 
+    /// Screw interpolation \(T\,\mathrm{Exp}(t \log(T^{-1} T'))\), moving from
+    /// `self` at `t = 0` to `other` at `t = 1` with a constant body twist.
+    /// Values of `t` outside \([0, 1]\) extrapolate along the same screw.
+    pub fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let delta = self.inverse().compose(other).log();
+        self.compose(&Self::from_matrix(Self::exp(delta, Some(t))))
+    }
+
+    /// Fractional power \(T^t = \mathrm{Exp}(t \log T)\): `t` times the screw
+    /// motion of `self`, so that \(T^{1/2} T^{1/2} = T\).
+    pub fn powf(&self, t: f64) -> Self {
+        Self::from_matrix(Self::exp(self.log(), Some(t)))
+    }
+
+    /// Integer power \(X^n\) by repeated squaring; negative `n` powers the
+    /// inverse.
+    pub fn pow(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { self.clone() };
+        let mut exponent = n.unsigned_abs();
+        let mut result = Self::identity();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.compose(&base);
+            }
+            base = base.compose(&base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Weighted distance \(\sqrt{(w_r \theta)^2 + (w_t \lVert t - t' \rVert)^2}\)
+    /// combining the rotation angle [`So3::angle_to`] and the distance between
+    /// positions, e.g. with `rot_weight` a characteristic length per radian.
+    pub fn distance_to(&self, other: &Self, rot_weight: f64, trans_weight: f64) -> f64 {
+        let rotation = rot_weight * self.rotation.angle_to(&other.rotation);
+        let translation = trans_weight * self.translation_distance(other);
+        rotation.hypot(translation)
+    }
+
+    /// Weighted chordal distance
+    /// \(\sqrt{(w_r \lVert R - R' \rVert_F)^2 + (w_t \lVert t - t' \rVert)^2}\),
+    /// the counterpart of [`Se3::distance_to`] built on
+    /// [`So3::chordal_distance`].
+    pub fn chordal_distance(&self, other: &Self, rot_weight: f64, trans_weight: f64) -> f64 {
+        let rotation = rot_weight * self.rotation.chordal_distance(&other.rotation);
+        let translation = trans_weight * self.translation_distance(other);
+        rotation.hypot(translation)
+    }
+
+    fn translation_distance(&self, other: &Self) -> f64 {
+        (self.translation.vector - other.translation.vector).norm()
+    }
+
+    /// Cayley map on SE(3), \(\mathrm{cay}(\hat\xi) = (I - \hat\xi/2)^{-1}(I + \hat\xi/2)\)
+    /// for \(\xi = [\omega, v]\): the rotation [`So3::cayley`]\((\omega)\)
+    /// and translation \((I - \hat\omega/2)^{-1} v\). Cheaper than
+    /// [`Se3::exp`], which it matches to second order.
+    pub fn cayley(twist: [f64; 6]) -> Self {
+        let omega = Vector3::new(twist[0], twist[1], twist[2]);
+        let v = Vector3::new(twist[3], twist[4], twist[5]);
+        let skew = skew_symmetric(&omega);
+        let scale = 2.0 / (4.0 + omega.norm_squared());
+        let translation = v + (skew + skew * skew * 0.5) * v * scale;
+        Self::from_parts(
+            So3::cayley([twist[0], twist[1], twist[2]]),
+            vector3_to_array(&translation),
+        )
+    }
+
+    /// Inverse of [`Se3::cayley`]: \(\omega\) from [`So3::inverse_cayley`]
+    /// and \(v = (I - \hat\omega/2)\,t\).
+    ///
+    /// Returns [`Error::Singular`](crate::error::Error::Singular) when the
+    /// rotation is a half turn.
+    pub fn inverse_cayley(&self) -> Result<[f64; 6]> {
+        let omega = vector3_from_array(self.rotation.inverse_cayley()?);
+        let t = vector3_from_array(self.translation());
+        let v = t - skew_symmetric(&omega) * t * 0.5;
+        Ok([omega.x, omega.y, omega.z, v.x, v.y, v.z])
+    }
+
+    /// `n` poses evenly spaced along the constant-twist (screw) path from
+    /// `self` to `other`, both included: \(T_k = T\,\mathrm{Exp}(s_k \log(T^{-1} T'))\)
+    /// with \(s_k = k / (n - 1)\). A single pose is `self`.
+    pub fn interpolate_n(&self, other: &Self, n: usize) -> Vec<Self> {
+        let delta = self.inverse().compose(other).log();
+        (0..n)
+            .map(|k| {
+                let s = if n > 1 {
+                    k as f64 / (n - 1) as f64
+                } else {
+                    0.0
+                };
+                self.compose(&Self::from_matrix(Self::exp(delta, Some(s))))
+            })
+            .collect()
+    }
+
     /// Export the 4×4 homogeneous transform matrix.
     pub fn to_matrix(&self) -> [[f64; 4]; 4] {
         let mut matrix = Matrix4::<f64>::identity();
@@ -189,6 +301,34 @@ impl Se3 {
         matrix_to_array(&matrix)
     }
 
+    /// Export the 4×4 homogeneous matrix as a flat row-major array, with
+    /// element `(r, c)` stored at index `4 * r + c`.
+    pub fn to_row_major_flat(&self) -> [f64; 16] {
+        matrix_to_row_major(&self.as_matrix())
+    }
+
+    /// Export the 4×4 homogeneous matrix as a flat column-major array, with
+    /// element `(r, c)` stored at index `4 * c + r`. The translation occupies
+    /// indices 12–14, matching OpenGL's `glLoadMatrixd` layout.
+    pub fn to_col_major_flat(&self) -> [f64; 16] {
+        matrix_to_col_major(&self.as_matrix())
+    }
+
+    /// Write the homogeneous matrix into a caller-provided buffer using the
+    /// row-major layout accepted by [`Se3::from_flat_matrix`].
+    pub fn write_flat_matrix(&self, out: &mut [f64; 16]) {
+        *out = self.to_row_major_flat();
+    }
+
+    /// Report orthogonality and determinant drift of the rotation block along
+    /// with the translation magnitude, for runtime monitoring.
+    pub fn health_check(&self) -> TransformHealth {
+        TransformHealth::from_rotation(
+            self.rotation.rotation().matrix(),
+            self.translation.vector.norm(),
+        )
+    }
+
     pub fn rotation(&self) -> &So3 {
         &self.rotation
     }
@@ -198,6 +338,29 @@ impl Se3 {
         vector3_to_array(&self.translation.vector)
     }
 
+    /// Twist \([\omega, v]\) with \(\exp(\xi^\wedge) = T\), inverting
+    /// [`Se3::exp`] through \(V^{-1}\). Near the identity the series limit of
+    /// the \([\omega]_\times^2\) coefficient is used. The rotation angle is
+    /// returned in \([0, \pi]\).
+    pub fn log(&self) -> [f64; 6] {
+        let omega = Vector3::from(self.rotation.to_rotation_vector());
+        let theta = omega.norm();
+        let hat = crate::util::skew_symmetric(&omega);
+        let coefficient = if theta < 1e-6 {
+            1.0 / 12.0
+        } else {
+            (1.0 - theta * theta.sin() / (2.0 * (1.0 - theta.cos()))) / (theta * theta)
+        };
+        let v_inverse = Matrix3::identity() - 0.5 * hat + coefficient * hat * hat;
+        let v = v_inverse * self.translation.vector;
+        [omega.x, omega.y, omega.z, v.x, v.y, v.z]
+    }
+
+    /// [`Se3::log`] of a homogeneous matrix, the inverse of [`Se3::exp`].
+    pub fn log_matrix(matrix: [[f64; 4]; 4]) -> [f64; 6] {
+        Self::from_matrix(matrix).log()
+    }
+
     /// Compute the adjoint representation \(\mathrm{Ad}_T\) that maps twists
     /// from the child frame into the parent frame.
     pub fn adjoint(&self) -> SMatrix<f64, 6, 6> {
@@ -222,6 +385,73 @@ impl Se3 {
 
         matrix
     }
+
+    /// Map a twist \([\omega, v]\) expressed in the child frame into the
+    /// parent frame, \(V_p = \mathrm{Ad}_T V_c\).
+    pub fn transform_twist(&self, twist: [f64; 6]) -> [f64; 6] {
+        apply_linear(&self.adjoint(), twist)
+    }
+
+    /// Map a wrench \([\tau, f]\) expressed in the child frame into the
+    /// parent frame using the co-adjoint, \(F_p = \mathrm{Ad}_{T^{-1}}^T F_c\).
+    /// This preserves the power \(V \cdot F\) paired with
+    /// [`Se3::transform_twist`].
+    pub fn transform_wrench(&self, wrench: [f64; 6]) -> [f64; 6] {
+        apply_linear(&self.inverse().adjoint().transpose(), wrench)
+    }
+}
+
+/// Homogeneous matrix \(\exp(\xi^\wedge)\) of a twist \([\omega, v]\); a
+/// free-function form of [`Se3::exp`] for use as a function value.
+pub fn exp(twist: [f64; 6]) -> [[f64; 4]; 4] {
+    Se3::exp(twist, None)
+}
+
+/// Free-function form of [`Se3::log_matrix`].
+pub fn log(matrix: [[f64; 4]; 4]) -> [f64; 6] {
+    Se3::log_matrix(matrix)
+}
+
+/// Free-function form of [`Se3::hat`].
+pub fn hat(twist: [f64; 6]) -> [[f64; 4]; 4] {
+    Se3::hat(twist)
+}
+
+/// Free-function form of [`Se3::vee`].
+pub fn vee(matrix: [[f64; 4]; 4]) -> [f64; 6] {
+    Se3::vee(matrix)
+}
+
+/// Adjoint \(\mathrm{Ad}_T\) of a homogeneous matrix, as nested rows.
+pub fn adjoint_of(matrix: [[f64; 4]; 4]) -> [[f64; 6]; 6] {
+    matrix_to_array(&Se3::from_matrix(matrix).adjoint())
+}
+
+/// Group commutator \(T_1 T_2 T_1^{-1} T_2^{-1}\), the identity exactly when
+/// the transforms commute.
+pub fn commutator(first: &Se3, second: &Se3) -> Se3 {
+    first
+        .compose(second)
+        .compose(&first.inverse())
+        .compose(&second.inverse())
+}
+
+/// Norm of the twist \(\log(T_1 T_2 T_1^{-1} T_2^{-1})\), which to leading
+/// order is the norm of the bracket \([\log T_1, \log T_2]\). Rotation (rad)
+/// and translation parts are mixed, so scale translations to the task's
+/// length unit first when comparing against a threshold.
+pub fn non_commutativity_magnitude(first: &Se3, second: &Se3) -> f64 {
+    SVector::from(commutator(first, second).log()).norm()
+}
+
+/// Third-order Baker–Campbell–Hausdorff approximation of
+/// \(\log(\mathrm{Exp}(x)\,\mathrm{Exp}(y))\):
+/// \(x + y + \tfrac12[x, y] + \tfrac1{12}([x, [x, y]] + [y, [y, x]])\).
+pub fn bch(x: [f64; 6], y: [f64; 6]) -> [f64; 6] {
+    let (x, y) = (SVector::from(x), SVector::from(y));
+    let xy = Se3::bracket(&x, &y);
+    let correction = Se3::bracket(&x, &xy) - Se3::bracket(&y, &xy);
+    (x + y + xy * 0.5 + correction / 12.0).into()
 }
 
 impl LieGroup<4> for Se3 {
@@ -259,6 +489,24 @@ impl HasAdjoint<6> for Se3 {
     }
 }
 
+impl HasExp<6> for Se3 {
+    fn exp_map(xi: &SVector<f64, 6>) -> Self {
+        Self::from_matrix(Self::exp((*xi).into(), None))
+    }
+
+    /// Bracket of twists \([\omega_a, v_a]\) and \([\omega_b, v_b]\):
+    /// \([\omega_a \times \omega_b, \omega_a \times v_b - \omega_b \times v_a]\).
+    fn bracket(a: &SVector<f64, 6>, b: &SVector<f64, 6>) -> SVector<f64, 6> {
+        let (wa, va) = (a.fixed_rows::<3>(0), a.fixed_rows::<3>(3));
+        let (wb, vb) = (b.fixed_rows::<3>(0), b.fixed_rows::<3>(3));
+        let angular = wa.cross(&wb);
+        let linear = wa.cross(&vb) - wb.cross(&va);
+        SVector::<f64, 6>::new(
+            angular[0], angular[1], angular[2], linear[0], linear[1], linear[2],
+        )
+    }
+}
+
 impl Mul for Se3 {
     type Output = Self;
 
@@ -275,7 +523,7 @@ impl<'a> Mul<&'a Se3> for Se3 {
     }
 }
 
-impl<'a> Mul<Se3> for &'a Se3 {
+impl Mul<Se3> for &Se3 {
     type Output = Se3;
 
     fn mul(self, rhs: Se3) -> Self::Output {
@@ -283,7 +531,7 @@ impl<'a> Mul<Se3> for &'a Se3 {
     }
 }
 
-impl<'a, 'b> Mul<&'a Se3> for &'b Se3 {
+impl<'a> Mul<&'a Se3> for &Se3 {
     type Output = Se3;
 
     fn mul(self, rhs: &'a Se3) -> Self::Output {
@@ -0,0 +1,182 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+use crate::cmtm::Vector6;
+use crate::se3::Se3;
+
+/// A causal filter over 6D spatial signals (twists \([\omega, v]\) or wrenches
+/// \([\tau, f]\)), applied independently per component.
+pub trait SpatialFilter {
+    /// Feed one sample and return the filtered value.
+    fn update(&mut self, sample: [f64; 6]) -> [f64; 6];
+    /// Forget the filter history; the next sample re-initializes the state.
+    fn reset(&mut self);
+}
+
+/// First-order (exponential) low-pass filter
+/// \(y_k = y_{k-1} + \alpha (x_k - y_{k-1})\) with
+/// \(\alpha = 1 - e^{-2\pi f_c / f_s}\).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirstOrderLowPass {
+    alpha: f64,
+    state: Option<Vector6>,
+}
+
+impl FirstOrderLowPass {
+    /// Create a filter with cutoff `cutoff_hz` for samples arriving at
+    /// `sample_rate_hz`.
+    pub fn new(cutoff_hz: f64, sample_rate_hz: f64) -> Self {
+        Self {
+            alpha: 1.0 - (-2.0 * PI * cutoff_hz / sample_rate_hz).exp(),
+            state: None,
+        }
+    }
+
+    /// Smoothing factor \(\alpha \in (0, 1]\) applied per sample.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+impl SpatialFilter for FirstOrderLowPass {
+    fn update(&mut self, sample: [f64; 6]) -> [f64; 6] {
+        let x = Vector6::from_row_slice(&sample);
+        let y = match self.state {
+            Some(previous) => previous + (x - previous) * self.alpha,
+            None => x,
+        };
+        self.state = Some(y);
+        y.into()
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+/// Second-order IIR section (biquad) in transposed direct form II, with
+/// coefficients from the RBJ audio-EQ cookbook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z1: Vector6,
+    z2: Vector6,
+    initialized: bool,
+}
+
+impl Biquad {
+    fn from_coefficients(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b: [b[0] / a[0], b[1] / a[0], b[2] / a[0]],
+            a: [a[1] / a[0], a[2] / a[0]],
+            z1: Vector6::zeros(),
+            z2: Vector6::zeros(),
+            initialized: false,
+        }
+    }
+
+    /// Second-order low-pass with cutoff `cutoff_hz` and quality factor `q`.
+    pub fn low_pass(cutoff_hz: f64, sample_rate_hz: f64, q: f64) -> Self {
+        let w0 = 2.0 * PI * cutoff_hz / sample_rate_hz;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        Self::from_coefficients(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Maximally flat (Butterworth, \(q = 1/\sqrt{2}\)) second-order low-pass.
+    pub fn butterworth_low_pass(cutoff_hz: f64, sample_rate_hz: f64) -> Self {
+        Self::low_pass(cutoff_hz, sample_rate_hz, FRAC_1_SQRT_2)
+    }
+
+    /// Notch rejecting `center_hz`; `q` sets the notch width
+    /// (bandwidth ≈ `center_hz / q`).
+    pub fn notch(center_hz: f64, sample_rate_hz: f64, q: f64) -> Self {
+        let w0 = 2.0 * PI * center_hz / sample_rate_hz;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        Self::from_coefficients(
+            [1.0, -2.0 * cos, 1.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// Steady-state gain for a constant input.
+    fn dc_gain(&self) -> f64 {
+        (self.b[0] + self.b[1] + self.b[2]) / (1.0 + self.a[0] + self.a[1])
+    }
+}
+
+impl SpatialFilter for Biquad {
+    fn update(&mut self, sample: [f64; 6]) -> [f64; 6] {
+        let x = Vector6::from_row_slice(&sample);
+        if !self.initialized {
+            // Start from the steady state for the first sample so a constant
+            // signal passes through without a start-up transient.
+            let y = x * self.dc_gain();
+            self.z1 = x * (self.b[1] + self.b[2]) - y * (self.a[0] + self.a[1]);
+            self.z2 = x * self.b[2] - y * self.a[1];
+            self.initialized = true;
+        }
+        let y = x * self.b[0] + self.z1;
+        self.z1 = x * self.b[1] - y * self.a[0] + self.z2;
+        self.z2 = x * self.b[2] - y * self.a[1];
+        y.into()
+    }
+
+    fn reset(&mut self) {
+        self.z1 = Vector6::zeros();
+        self.z2 = Vector6::zeros();
+        self.initialized = false;
+    }
+}
+
+/// Whether a 6D signal transforms like a twist or like a wrench.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialQuantity {
+    /// Transforms with the adjoint, see [`Se3::transform_twist`].
+    Twist,
+    /// Transforms with the co-adjoint, see [`Se3::transform_wrench`].
+    Wrench,
+}
+
+/// Runs a [`SpatialFilter`] in a chosen filter frame. Each sample is first
+/// expressed in the filter frame, so filtering is not corrupted by motion of
+/// the frame the signal was measured in (e.g. a wrist force sensor rotating
+/// with the tool).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameFilter<F> {
+    filter: F,
+    quantity: SpatialQuantity,
+}
+
+impl<F: SpatialFilter> FrameFilter<F> {
+    pub fn new(filter: F, quantity: SpatialQuantity) -> Self {
+        Self { filter, quantity }
+    }
+
+    /// Filter `sample`, measured in the signal frame, in the filter frame.
+    /// `filter_from_signal` is the current pose of the signal frame in the
+    /// filter frame. The result is expressed in the filter frame; transform
+    /// it onward with [`Se3::transform_twist`] or [`Se3::transform_wrench`].
+    pub fn update(&mut self, sample: [f64; 6], filter_from_signal: &Se3) -> [f64; 6] {
+        let expressed = match self.quantity {
+            SpatialQuantity::Twist => filter_from_signal.transform_twist(sample),
+            SpatialQuantity::Wrench => filter_from_signal.transform_wrench(sample),
+        };
+        let filtered = self.filter.update(expressed);
+        trace_event!(trace, ?expressed, ?filtered, "frame filter update");
+        filtered
+    }
+
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+
+    /// Borrow the wrapped filter.
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+}
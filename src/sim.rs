@@ -0,0 +1,231 @@
+use nalgebra::Vector3;
+
+use crate::dynamics::{RigidBodyState, SpatialInertia, variational_step};
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+
+/// A free rigid body in a [`Simulation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Body {
+    pub inertia: SpatialInertia,
+    pub state: RigidBodyState,
+    /// Applied body-frame wrench \([\tau, f]\) at the body origin, held
+    /// constant in the body frame until changed (e.g. thrusters).
+    pub wrench: [f64; 6],
+}
+
+/// Handle to a body returned by [`Simulation::add_body`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BodyId(usize);
+
+/// Settings for [`Simulation::advance`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveStepping {
+    /// Largest accepted local error per step, measured as the change in
+    /// position (m), attitude (rad) and twist between one full step and two
+    /// half steps.
+    pub tolerance: f64,
+    pub min_step: f64,
+    pub max_step: f64,
+}
+
+impl AdaptiveStepping {
+    /// Check that `0 < min_step <= max_step` with both finite and that the
+    /// tolerance is positive, returning [`Error::InvalidParameter`] otherwise.
+    pub fn validate(&self) -> Result<()> {
+        if !(self.min_step.is_finite() && self.max_step.is_finite())
+            || self.min_step <= 0.0
+            || self.min_step > self.max_step
+        {
+            return Err(Error::InvalidParameter(format!(
+                "step sizes [{}, {}] must satisfy 0 < min_step <= max_step",
+                self.min_step, self.max_step
+            )));
+        }
+        if self.tolerance.is_nan() || self.tolerance <= 0.0 {
+            return Err(Error::InvalidParameter(format!(
+                "tolerance {} must be positive",
+                self.tolerance
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for AdaptiveStepping {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-6,
+            min_step: 1e-6,
+            max_step: 0.1,
+        }
+    }
+}
+
+/// Independent free rigid bodies under uniform gravity and per-body applied
+/// wrenches, advanced with [`variational_step`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    bodies: Vec<Body>,
+    gravity: Vector3<f64>,
+    time: f64,
+    step_size: Option<f64>,
+}
+
+impl Simulation {
+    /// Create an empty simulation with world-frame gravitational acceleration
+    /// `gravity`, e.g. `[0.0, 0.0, -9.81]`.
+    pub fn new(gravity: [f64; 3]) -> Self {
+        Self {
+            bodies: Vec::new(),
+            gravity: Vector3::from(gravity),
+            time: 0.0,
+            step_size: None,
+        }
+    }
+
+    /// Add a body with no applied wrench.
+    pub fn add_body(&mut self, inertia: SpatialInertia, state: RigidBodyState) -> BodyId {
+        self.bodies.push(Body {
+            inertia,
+            state,
+            wrench: [0.0; 6],
+        });
+        BodyId(self.bodies.len() - 1)
+    }
+
+    pub fn body(&self, id: BodyId) -> &Body {
+        &self.bodies[id.0]
+    }
+
+    pub fn body_mut(&mut self, id: BodyId) -> &mut Body {
+        &mut self.bodies[id.0]
+    }
+
+    pub fn bodies(&self) -> &[Body] {
+        &self.bodies
+    }
+
+    /// Simulated time in seconds.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn gravity(&self) -> [f64; 3] {
+        self.gravity.into()
+    }
+
+    /// Total kinetic plus gravitational potential energy of all bodies.
+    pub fn energy(&self) -> f64 {
+        self.bodies
+            .iter()
+            .map(|body| {
+                let com = Vector3::from(body.state.pose.apply(body.inertia.center_of_mass()));
+                body.inertia.kinetic_energy(body.state.twist)
+                    - body.inertia.mass() * self.gravity.dot(&com)
+            })
+            .sum()
+    }
+
+    /// Advance every body by one fixed step of `h` seconds. Returns
+    /// [`Error::Singular`] for a body without
+    /// invertible mass properties, leaving the simulation unchanged.
+    pub fn step(&mut self, h: f64) -> Result<()> {
+        let states = self.stepped_states(h)?;
+        for (body, state) in self.bodies.iter_mut().zip(states) {
+            body.state = state;
+        }
+        self.time += h;
+        Ok(())
+    }
+
+    /// Advance by `duration` seconds with adaptive step sizes chosen by step
+    /// doubling, and return the number of accepted steps. The step size is
+    /// remembered between calls. Steps at `min_step` are accepted even if
+    /// they miss the tolerance. Returns the errors of
+    /// [`AdaptiveStepping::validate`] before stepping, and errors like
+    /// [`Simulation::step`], keeping the steps accepted so far.
+    pub fn advance(&mut self, duration: f64, options: &AdaptiveStepping) -> Result<usize> {
+        options.validate()?;
+        let end = self.time + duration;
+        let mut accepted = 0;
+        let mut h = self
+            .step_size
+            .unwrap_or(options.max_step)
+            .clamp(options.min_step, options.max_step);
+
+        while end - self.time > f64::EPSILON * end.abs().max(1.0) {
+            let trial = h.min(end - self.time);
+            let full = self.stepped_states(trial)?;
+            let mut halves = self.clone();
+            halves.step(0.5 * trial)?;
+            halves.step(0.5 * trial)?;
+            let error = full
+                .iter()
+                .zip(&halves.bodies)
+                .map(|(a, b)| state_distance(a, &b.state))
+                .fold(0.0, f64::max);
+
+            // The integrator is second order, so the local error scales with h³.
+            let factor = if error > 0.0 {
+                (0.9 * (options.tolerance / error).cbrt()).clamp(0.2, 5.0)
+            } else {
+                5.0
+            };
+            if error <= options.tolerance || trial <= options.min_step {
+                self.bodies = halves.bodies;
+                self.time += trial;
+                accepted += 1;
+            }
+            h = (trial * factor).clamp(options.min_step, options.max_step);
+        }
+
+        self.step_size = Some(h);
+        Ok(accepted)
+    }
+
+    fn stepped_states(&self, h: f64) -> Result<Vec<RigidBodyState>> {
+        self.bodies
+            .iter()
+            .map(|body| {
+                let weight = self.gravity * body.inertia.mass();
+                let c = Vector3::from(body.inertia.center_of_mass());
+                variational_step(&body.inertia, &body.state, self.time, h, |_, pose: &Se3| {
+                    let force = Vector3::from(pose.rotation().inverse().apply(weight.into()));
+                    let torque = c.cross(&force);
+                    let w = body.wrench;
+                    [
+                        w[0] + torque.x,
+                        w[1] + torque.y,
+                        w[2] + torque.z,
+                        w[3] + force.x,
+                        w[4] + force.y,
+                        w[5] + force.z,
+                    ]
+                })
+            })
+            .collect()
+    }
+}
+
+/// Combined position, attitude and twist difference used for error control.
+fn state_distance(a: &RigidBodyState, b: &RigidBodyState) -> f64 {
+    let position =
+        (Vector3::from(a.pose.translation()) - Vector3::from(b.pose.translation())).norm();
+    let attitude = Vector3::from(
+        a.pose
+            .rotation()
+            .inverse()
+            .compose(b.pose.rotation())
+            .to_rotation_vector(),
+    )
+    .norm();
+    let twist = a
+        .twist
+        .iter()
+        .zip(&b.twist)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    position.max(attitude).max(twist)
+}
@@ -0,0 +1,171 @@
+use nalgebra::{Matrix2, Rotation2, SMatrix, SVector};
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::lie::{HasAdjoint, HasExp, LieGroup, apply_linear, matrix_to_array};
+
+/// A planar rotation, an element of the special orthogonal group
+/// \(\mathrm{SO}(2)\). The Lie algebra is identified with the angle
+/// \(\theta \in \mathbb{R}\).
+///
+/// Serializes as the four rotation-matrix entries in column-major order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct So2 {
+    rotation: Rotation2<f64>,
+}
+
+impl So2 {
+    /// Rotation by `angle` (rad), counter-clockwise.
+    pub fn from_angle(angle: f64) -> Self {
+        Self {
+            rotation: Rotation2::new(angle),
+        }
+    }
+
+    /// Construct a rotation directly from a 2×2 matrix. As with
+    /// [`crate::so3::So3::from_matrix`], no orthonormality checks are
+    /// performed.
+    pub fn from_matrix(matrix: [[f64; 2]; 2]) -> Self {
+        Self {
+            rotation: Rotation2::from_matrix_unchecked(Matrix2::new(
+                matrix[0][0],
+                matrix[0][1],
+                matrix[1][0],
+                matrix[1][1],
+            )),
+        }
+    }
+
+    /// Rotation angle in \((-\pi, \pi]\).
+    pub fn angle(&self) -> f64 {
+        self.rotation.angle()
+    }
+
+    /// Compose two rotations: \(R_1 R_2\).
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            rotation: self.rotation * other.rotation,
+        }
+    }
+
+    /// Return the inverse rotation, the transpose of the rotation matrix.
+    pub fn inverse(&self) -> Self {
+        Self {
+            rotation: self.rotation.inverse(),
+        }
+    }
+
+    /// Apply the rotation to a 2D vector.
+    pub fn apply(&self, vector: [f64; 2]) -> [f64; 2] {
+        apply_linear(&self.as_matrix(), vector)
+    }
+
+    /// Exponential map \(\exp(\theta^\wedge)\) as a 2×2 rotation matrix.
+    pub fn exp(angle: f64) -> [[f64; 2]; 2] {
+        Self::from_angle(angle).to_matrix()
+    }
+
+    /// Logarithm map, the angle in \((-\pi, \pi]\); the same as [`So2::angle`].
+    pub fn log(&self) -> f64 {
+        self.angle()
+    }
+
+    /// Skew-symmetric matrix \(\theta^\wedge = \begin{bmatrix} 0 & -\theta \\ \theta & 0 \end{bmatrix}\).
+    pub fn hat(angle: f64) -> [[f64; 2]; 2] {
+        [[0.0, -angle], [angle, 0.0]]
+    }
+
+    /// Inverse of [`So2::hat`]; the off-diagonal elements are symmetrized.
+    pub fn vee(matrix: [[f64; 2]; 2]) -> f64 {
+        0.5 * (matrix[1][0] - matrix[0][1])
+    }
+
+    /// Adjoint of a planar rotation, which acts trivially on the angle.
+    pub fn adjoint(&self) -> f64 {
+        1.0
+    }
+
+    /// Export the 2×2 rotation matrix.
+    pub fn to_matrix(&self) -> [[f64; 2]; 2] {
+        matrix_to_array(&self.as_matrix())
+    }
+
+    /// Access the nalgebra `Rotation2` backing this object.
+    pub fn rotation(&self) -> &Rotation2<f64> {
+        &self.rotation
+    }
+}
+
+/// Free-function form of [`So2::exp`].
+pub fn exp(angle: f64) -> [[f64; 2]; 2] {
+    So2::exp(angle)
+}
+
+/// Angle of a rotation matrix, the inverse of [`exp`].
+pub fn log(matrix: [[f64; 2]; 2]) -> f64 {
+    So2::from_matrix(matrix).log()
+}
+
+/// Free-function form of [`So2::hat`].
+pub fn hat(angle: f64) -> [[f64; 2]; 2] {
+    So2::hat(angle)
+}
+
+/// Free-function form of [`So2::vee`].
+pub fn vee(matrix: [[f64; 2]; 2]) -> f64 {
+    So2::vee(matrix)
+}
+
+impl LieGroup<2> for So2 {
+    fn identity() -> Self {
+        Self {
+            rotation: Rotation2::identity(),
+        }
+    }
+
+    fn compose(&self, other: &Self) -> Self {
+        self.compose(other)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+
+    fn as_matrix(&self) -> SMatrix<f64, 2, 2> {
+        self.rotation.matrix().clone_owned()
+    }
+}
+
+impl HasAdjoint<1> for So2 {
+    fn adjoint_matrix(&self) -> SMatrix<f64, 1, 1> {
+        SMatrix::<f64, 1, 1>::new(self.adjoint())
+    }
+}
+
+impl HasExp<1> for So2 {
+    fn exp_map(xi: &SVector<f64, 1>) -> Self {
+        Self::from_angle(xi[0])
+    }
+
+    /// SO(2) is abelian, so the bracket vanishes.
+    fn bracket(_: &SVector<f64, 1>, _: &SVector<f64, 1>) -> SVector<f64, 1> {
+        SVector::<f64, 1>::zeros()
+    }
+}
+
+impl Mul for So2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl<'a> Mul<&'a So2> for So2 {
+    type Output = So2;
+
+    fn mul(self, rhs: &'a So2) -> Self::Output {
+        self.compose(rhs)
+    }
+}
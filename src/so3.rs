@@ -1,16 +1,162 @@
-use nalgebra::{Matrix3, Quaternion, Rotation3, UnitQuaternion};
+use nalgebra::{Matrix3, Quaternion, Rotation3, SVector, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
 use std::ops::Mul;
+use std::sync::Arc;
 
-use crate::lie::{LieGroup, apply_linear, matrix_to_array};
+use crate::error::{Error, Result};
+use crate::health::TransformHealth;
+use crate::lie::{
+    HasExp, LieGroup, apply_linear, matrix_to_array, matrix_to_col_major, matrix_to_row_major,
+};
 use crate::util::{skew_symmetric, vector3_from_array, vector3_to_array};
 
+/// Below this sine of the angle between unit vectors,
+/// [`So3::rotation_between`] treats opposite vectors as exactly antiparallel.
+const ANTIPARALLEL_TOLERANCE: f64 = 1e-12;
+
+/// Distance (rad) of the middle Euler angle from its singular values within
+/// which [`So3::to_euler`] treats the rotation as gimbal locked.
+const GIMBAL_TOLERANCE: f64 = 1e-9;
+
+/// Lower bound on \(1 + \mathrm{tr}\,R\) below which [`So3::inverse_cayley`]
+/// treats the rotation as a half turn.
+const CAYLEY_TOLERANCE: f64 = 1e-12;
+
+/// Euler angle conventions: the three rotation axes, in the order their
+/// angles are given, and whether they are intrinsic (about the rotating frame,
+/// \(R = R_1 R_2 R_3\)) or extrinsic (about the fixed frame,
+/// \(R = R_3 R_2 R_1\)), as in [`So3::from_sequence`]. Sequences repeating
+/// the first axis (e.g. ZYZ) are proper Euler angles, the others Tait–Bryan
+/// angles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EulerOrder {
+    IntrinsicXyz,
+    IntrinsicXzy,
+    IntrinsicYxz,
+    IntrinsicYzx,
+    IntrinsicZxy,
+    IntrinsicZyx,
+    IntrinsicXyx,
+    IntrinsicXzx,
+    IntrinsicYxy,
+    IntrinsicYzy,
+    IntrinsicZxz,
+    IntrinsicZyz,
+    ExtrinsicXyz,
+    ExtrinsicXzy,
+    ExtrinsicYxz,
+    ExtrinsicYzx,
+    ExtrinsicZxy,
+    ExtrinsicZyx,
+    ExtrinsicXyx,
+    ExtrinsicXzx,
+    ExtrinsicYxy,
+    ExtrinsicYzy,
+    ExtrinsicZxz,
+    ExtrinsicZyz,
+}
+
+impl EulerOrder {
+    /// Every convention, intrinsic ones first.
+    pub const ALL: [EulerOrder; 24] = [
+        Self::IntrinsicXyz,
+        Self::IntrinsicXzy,
+        Self::IntrinsicYxz,
+        Self::IntrinsicYzx,
+        Self::IntrinsicZxy,
+        Self::IntrinsicZyx,
+        Self::IntrinsicXyx,
+        Self::IntrinsicXzx,
+        Self::IntrinsicYxy,
+        Self::IntrinsicYzy,
+        Self::IntrinsicZxz,
+        Self::IntrinsicZyz,
+        Self::ExtrinsicXyz,
+        Self::ExtrinsicXzy,
+        Self::ExtrinsicYxz,
+        Self::ExtrinsicYzx,
+        Self::ExtrinsicZxy,
+        Self::ExtrinsicZyx,
+        Self::ExtrinsicXyx,
+        Self::ExtrinsicXzx,
+        Self::ExtrinsicYxy,
+        Self::ExtrinsicYzy,
+        Self::ExtrinsicZxz,
+        Self::ExtrinsicZyz,
+    ];
+
+    /// Axis indices (0 for \(x\)) in the order the angles are given.
+    pub fn axes(self) -> [usize; 3] {
+        match self {
+            Self::IntrinsicXyz | Self::ExtrinsicXyz => [0, 1, 2],
+            Self::IntrinsicXzy | Self::ExtrinsicXzy => [0, 2, 1],
+            Self::IntrinsicYxz | Self::ExtrinsicYxz => [1, 0, 2],
+            Self::IntrinsicYzx | Self::ExtrinsicYzx => [1, 2, 0],
+            Self::IntrinsicZxy | Self::ExtrinsicZxy => [2, 0, 1],
+            Self::IntrinsicZyx | Self::ExtrinsicZyx => [2, 1, 0],
+            Self::IntrinsicXyx | Self::ExtrinsicXyx => [0, 1, 0],
+            Self::IntrinsicXzx | Self::ExtrinsicXzx => [0, 2, 0],
+            Self::IntrinsicYxy | Self::ExtrinsicYxy => [1, 0, 1],
+            Self::IntrinsicYzy | Self::ExtrinsicYzy => [1, 2, 1],
+            Self::IntrinsicZxz | Self::ExtrinsicZxz => [2, 0, 2],
+            Self::IntrinsicZyz | Self::ExtrinsicZyz => [2, 1, 2],
+        }
+    }
+
+    pub fn is_intrinsic(self) -> bool {
+        matches!(
+            self,
+            Self::IntrinsicXyz
+                | Self::IntrinsicXzy
+                | Self::IntrinsicYxz
+                | Self::IntrinsicYzx
+                | Self::IntrinsicZxy
+                | Self::IntrinsicZyx
+                | Self::IntrinsicXyx
+                | Self::IntrinsicXzx
+                | Self::IntrinsicYxy
+                | Self::IntrinsicYzy
+                | Self::IntrinsicZxz
+                | Self::IntrinsicZyz
+        )
+    }
+
+    /// Whether the first and last axes coincide.
+    pub fn is_proper(self) -> bool {
+        let [first, _, last] = self.axes();
+        first == last
+    }
+}
+
+/// Which body axes [`So3::look_at_with`] aligns with the viewing direction
+/// and the up vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LookAtConvention {
+    /// Cameras as in [`crate::camera`] and OpenCV: \(+z\) forward, \(x\)
+    /// right and \(y\) down.
+    #[default]
+    Camera,
+    /// OpenGL cameras: \(-z\) forward, \(x\) right and \(y\) up.
+    OpenGl,
+    /// Vehicle and robot bodies (ROS REP 103): \(x\) forward, \(y\) left and
+    /// \(z\) up.
+    Body,
+}
+
 /// A 3D rotation represented as an element of the special orthogonal group
 /// \(\mathrm{SO}(3)\).
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serializes as the nine rotation-matrix entries in column-major order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct So3 {
     rotation: Rotation3<f64>,
 }
 
+/// Cheaply clonable handle for sharing an immutable rotation between threads.
+pub type ArcSo3 = Arc<So3>;
+
 impl So3 {
     /// Build an element of SO(3) from an axis and angle using Rodrigues'
     /// rotation formula. Zero-length axes fall back to the identity
@@ -48,7 +194,14 @@ impl So3 {
             matrix[2][1],
             matrix[2][2],
         ];
-        let mat = Matrix3::from_row_slice(&flat);
+        Self::from_flat(&flat)
+    }
+
+    /// Construct a rotation from a flat row-major 3×3 matrix, reading element
+    /// `(r, c)` from index `3 * r + c`. As with [`So3::from_matrix`], no
+    /// orthonormality checks are performed.
+    pub fn from_flat(flat: &[f64; 9]) -> Self {
+        let mat = Matrix3::from_row_slice(flat);
         Self {
             rotation: Rotation3::from_matrix_unchecked(mat),
         }
@@ -79,6 +232,18 @@ impl So3 {
         }
     }
 
+    /// Validated variant of [`So3::from_quaternion`] that rejects quaternions
+    /// with non-finite components or zero norm instead of producing NaNs.
+    pub fn try_from_quaternion(quaternion: [f64; 4]) -> Result<Self> {
+        if quaternion.iter().any(|v| !v.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+        if quaternion.iter().all(|v| *v == 0.0) {
+            return Err(Error::ZeroNorm);
+        }
+        Ok(Self::from_quaternion(quaternion))
+    }
+
     /// Export the rotation as a normalized quaternion \([w, x, y, z]\).
     pub fn to_quaternion(&self) -> [f64; 4] {
         let unit = UnitQuaternion::from_rotation_matrix(&self.rotation);
@@ -86,6 +251,32 @@ impl So3 {
         [quat.w, quat.i, quat.j, quat.k]
     }
 
+    /// Explicitly named alias of [`So3::from_quaternion`] taking the
+    /// scalar-first layout \([w, x, y, z]\).
+    pub fn from_quaternion_wxyz(quaternion: [f64; 4]) -> Self {
+        Self::from_quaternion(quaternion)
+    }
+
+    /// Explicitly named alias of [`So3::to_quaternion`] returning the
+    /// scalar-first layout \([w, x, y, z]\).
+    pub fn to_quaternion_wxyz(&self) -> [f64; 4] {
+        self.to_quaternion()
+    }
+
+    /// Construct a rotation from a quaternion in the scalar-last layout
+    /// \([x, y, z, w]\) used by scipy, ROS and Eigen's `coeffs()`.
+    pub fn from_quaternion_xyzw(quaternion: [f64; 4]) -> Self {
+        let [x, y, z, w] = quaternion;
+        Self::from_quaternion([w, x, y, z])
+    }
+
+    /// Export the rotation as a normalized quaternion in the scalar-last
+    /// layout \([x, y, z, w]\).
+    pub fn to_quaternion_xyzw(&self) -> [f64; 4] {
+        let [w, x, y, z] = self.to_quaternion();
+        [x, y, z, w]
+    }
+
     /// Build a rotation from roll–pitch–yaw angles applied in ZYX order.
     pub fn from_euler_angles(roll: f64, pitch: f64, yaw: f64) -> Self {
         Self {
@@ -99,6 +290,342 @@ impl So3 {
         self.rotation.euler_angles()
     }
 
+    /// Rotation from Euler angles `a`, `b`, `c` (rad) about the axes of
+    /// `order`, in that order.
+    pub fn from_euler(order: EulerOrder, a: f64, b: f64, c: f64) -> Self {
+        let elementary = |axis: usize, angle: f64| {
+            Self::from_axis_angle(
+                std::array::from_fn(|i| f64::from(u8::from(i == axis))),
+                angle,
+            )
+        };
+        let [first, second, third] = order.axes();
+        let (r1, r2, r3) = (
+            elementary(first, a),
+            elementary(second, b),
+            elementary(third, c),
+        );
+        if order.is_intrinsic() {
+            r1.compose(&r2).compose(&r3)
+        } else {
+            r3.compose(&r2).compose(&r1)
+        }
+    }
+
+    /// Euler angles `(a, b, c)` with [`So3::from_euler`]`(order, a, b, c)`
+    /// equal to `self`, by the quaternion method of Bernardes and Viollet
+    /// (2022), which works uniformly for all conventions.
+    ///
+    /// The outer angles lie in \((-\pi, \pi]\); the middle one in
+    /// \([0, \pi]\) for proper and \([-\pi/2, \pi/2]\) for Tait–Bryan
+    /// orders. At gimbal lock only a combination of the outer angles is
+    /// determined, and the angle of the leftmost factor of the product (`a`
+    /// for intrinsic, `c` for extrinsic orders) is set to zero.
+    pub fn to_euler(&self, order: EulerOrder) -> (f64, f64, f64) {
+        // Intrinsic angles about (i, j, k) are extrinsic ones about (k, j, i)
+        // in reverse order, so only the extrinsic case is solved.
+        let [i, j, k] = if order.is_intrinsic() {
+            let [i, j, k] = order.axes();
+            [k, j, i]
+        } else {
+            order.axes()
+        };
+        let proper = i == k;
+        let k = if proper { 3 - i - j } else { k };
+        let sign = ((i as f64 - j as f64) * (j as f64 - k as f64) * (k as f64 - i as f64)) / 2.0;
+
+        let q = self.to_quaternion();
+        let (a, b, c, d) = if proper {
+            (q[0], q[1 + i], q[1 + j], q[1 + k] * sign)
+        } else {
+            (
+                q[0] - q[1 + j],
+                q[1 + i] + q[1 + k] * sign,
+                q[1 + j] + q[0],
+                q[1 + k] * sign - q[1 + i],
+            )
+        };
+        let middle = 2.0 * c.hypot(d).atan2(a.hypot(b));
+        let (plus, minus) = (b.atan2(a), d.atan2(c));
+        let (first, mut third) = if middle.abs() < GIMBAL_TOLERANCE {
+            (2.0 * plus, 0.0)
+        } else if (middle - PI).abs() < GIMBAL_TOLERANCE {
+            (-2.0 * minus, 0.0)
+        } else {
+            (plus - minus, plus + minus)
+        };
+        let middle = if proper {
+            middle
+        } else {
+            third *= sign;
+            middle - 0.5 * PI
+        };
+        let (first, third) = (wrap_angle(first), wrap_angle(third));
+        if order.is_intrinsic() {
+            (third, middle, first)
+        } else {
+            (first, middle, third)
+        }
+    }
+
+    /// Compose elementary rotations from an axis-sequence string, mirroring
+    /// scipy's `Rotation.from_euler`. Upper-case axes (`"ZYX"`) denote
+    /// intrinsic rotations about the rotating frame, so `R = R_1 R_2 R_3`;
+    /// lower-case axes (`"zyx"`) denote extrinsic rotations about the fixed
+    /// frame, so `R = R_3 R_2 R_1`. The sequence must hold one to three axes,
+    /// use a single case, never repeat an axis back-to-back, and match the
+    /// number of `angles` (in radians).
+    pub fn from_sequence(sequence: &str, angles: &[f64]) -> Result<Self> {
+        let invalid = |reason: String| Err(Error::InvalidSequence(reason));
+
+        let count = sequence.chars().count();
+        if !(1..=3).contains(&count) {
+            return invalid(format!("expected 1 to 3 axes, got '{sequence}'"));
+        }
+        if count != angles.len() {
+            return invalid(format!(
+                "'{sequence}' needs {count} angles, got {}",
+                angles.len()
+            ));
+        }
+        let intrinsic = sequence.chars().all(|c| c.is_ascii_uppercase());
+        let extrinsic = sequence.chars().all(|c| c.is_ascii_lowercase());
+        if !intrinsic && !extrinsic {
+            return invalid(format!(
+                "'{sequence}' mixes intrinsic (upper-case) and extrinsic (lower-case) axes"
+            ));
+        }
+
+        let mut rotation = Self::identity();
+        let mut previous = None;
+        for (axis_char, &angle) in sequence.chars().zip(angles) {
+            let axis = match axis_char.to_ascii_lowercase() {
+                'x' => [1.0, 0.0, 0.0],
+                'y' => [0.0, 1.0, 0.0],
+                'z' => [0.0, 0.0, 1.0],
+                other => return invalid(format!("unknown axis '{other}' in '{sequence}'")),
+            };
+            if previous == Some(axis_char) {
+                return invalid(format!("consecutive axes in '{sequence}' must differ"));
+            }
+            previous = Some(axis_char);
+
+            let elementary = Self::from_axis_angle(axis, angle);
+            rotation = if intrinsic {
+                rotation.compose(&elementary)
+            } else {
+                elementary.compose(&rotation)
+            };
+        }
+        Ok(rotation)
+    }
+
+    /// Decompose the rotation as `self = swing * twist`, where `twist` rotates
+    /// about `axis` and `swing` rotates about an axis perpendicular to it.
+    /// Returns `(swing, twist, twist_angle)` with the signed twist angle in
+    /// \([-\pi, \pi]\) measured about the normalized `axis`.
+    ///
+    /// A zero-length `axis` or a half-turn swing (where the twist is
+    /// undefined) yields an identity twist.
+    pub fn swing_twist(&self, axis: [f64; 3]) -> (So3, So3, f64) {
+        let axis_vector = vector3_from_array(axis);
+        if axis_vector.norm() == 0.0 {
+            return (self.clone(), Self::identity(), 0.0);
+        }
+        let axis_vector = axis_vector.normalize();
+
+        let mut quat = UnitQuaternion::from_rotation_matrix(&self.rotation).into_inner();
+        if quat.w < 0.0 {
+            quat = -quat;
+        }
+        let projection = quat.imag().dot(&axis_vector);
+        let twist_quat = Quaternion::from_parts(quat.w, axis_vector * projection);
+        if twist_quat.norm() == 0.0 {
+            return (self.clone(), Self::identity(), 0.0);
+        }
+
+        let twist_angle = 2.0 * projection.atan2(quat.w);
+        let twist = Self::from_axis_angle(axis, twist_angle);
+        let swing = self.compose(&twist.inverse());
+        (swing, twist, twist_angle)
+    }
+
+    /// Project the rotation onto the set of rotations that tilt `axis` by at
+    /// most `max_angle` radians. The swing component (see
+    /// [`So3::swing_twist`]) is shortened to `max_angle` when it exceeds the
+    /// cone; the twist about `axis` is preserved.
+    pub fn clamp_to_cone(&self, axis: [f64; 3], max_angle: f64) -> So3 {
+        let (swing, twist, _) = self.swing_twist(axis);
+        let swing_vector = vector3_from_array(swing.to_rotation_vector());
+        let swing_angle = swing_vector.norm();
+        let max_angle = max_angle.max(0.0);
+        if swing_angle <= max_angle {
+            return self.clone();
+        }
+
+        let clamped = Self::from_rotation_vector(vector3_to_array(
+            &(swing_vector * (max_angle / swing_angle)),
+        ));
+        clamped.compose(&twist)
+    }
+
+    /// Clamp the roll–pitch–yaw angles (see [`So3::to_euler_angles`]) to the
+    /// per-axis ranges `[min[i], max[i]]`, ordered `[roll, pitch, yaw]`, and
+    /// rebuild the rotation.
+    pub fn clamp_euler_angles(&self, min: [f64; 3], max: [f64; 3]) -> So3 {
+        let (roll, pitch, yaw) = self.to_euler_angles();
+        let clamp = |value: f64, i: usize| value.max(min[i]).min(max[i]);
+        Self::from_euler_angles(clamp(roll, 0), clamp(pitch, 1), clamp(yaw, 2))
+    }
+
+    /// The minimal rotation taking the direction of `from` onto the direction
+    /// of `to`, about the axis \(\text{from} \times \text{to}\). For
+    /// antiparallel vectors the half turn is about an axis perpendicular to
+    /// `from`; a zero vector yields the identity.
+    pub fn rotation_between(from: [f64; 3], to: [f64; 3]) -> Self {
+        let (a, b) = (vector3_from_array(from), vector3_from_array(to));
+        if a.norm() == 0.0 || b.norm() == 0.0 {
+            return Self::identity();
+        }
+        let (a, b) = (a.normalize(), b.normalize());
+        let axis = a.cross(&b);
+        let (sin, cos) = (axis.norm(), a.dot(&b));
+        if sin < ANTIPARALLEL_TOLERANCE && cos < 0.0 {
+            // Any axis perpendicular to `from` works; avoid the coordinate
+            // axis closest to it.
+            let least = a.iamin();
+            let perpendicular = a.cross(&Vector3::ith(least, 1.0)).normalize();
+            return Self::from_rotation_vector(vector3_to_array(&(perpendicular * PI)));
+        }
+        if sin == 0.0 {
+            return Self::identity();
+        }
+        Self::from_rotation_vector(vector3_to_array(&(axis * (sin.atan2(cos) / sin))))
+    }
+
+    /// [`So3::look_at_with`] in the [`LookAtConvention::Camera`] convention.
+    pub fn look_at(forward: [f64; 3], up: [f64; 3]) -> Result<Self> {
+        Self::look_at_with(forward, up, LookAtConvention::Camera)
+    }
+
+    /// Orientation whose forward axis points along `forward` and whose up
+    /// axis lies in the plane of `forward` and `up`, on the side of `up`; the
+    /// axes follow `convention`. `up` need not be perpendicular to `forward`.
+    ///
+    /// Returns [`Error::NonFinite`] for non-finite input, [`Error::ZeroNorm`]
+    /// if `forward` is zero and [`Error::Singular`] if `up` is zero or
+    /// parallel to `forward`.
+    pub fn look_at_with(
+        forward: [f64; 3],
+        up: [f64; 3],
+        convention: LookAtConvention,
+    ) -> Result<Self> {
+        if forward.iter().chain(&up).any(|v| !v.is_finite()) {
+            return Err(Error::NonFinite);
+        }
+        let forward = vector3_from_array(forward)
+            .try_normalize(0.0)
+            .ok_or(Error::ZeroNorm)?;
+        let up = vector3_from_array(up);
+        let right = forward
+            .cross(&up)
+            .try_normalize(ANTIPARALLEL_TOLERANCE * up.norm())
+            .ok_or(Error::Singular)?;
+        let up = right.cross(&forward);
+        let axes = match convention {
+            LookAtConvention::Camera => [right, -up, forward],
+            LookAtConvention::OpenGl => [right, up, -forward],
+            LookAtConvention::Body => [forward, -right, up],
+        };
+        let matrix = Matrix3::from_columns(&axes);
+        Ok(Self::from_matrix(matrix_to_array(&matrix)))
+    }
+
+    /// Geodesic (slerp) interpolation \(R\,\mathrm{Exp}(t \log(R^T R'))\),
+    /// returning `self` at `t = 0` and `other` at `t = 1`. Values of `t`
+    /// outside \([0, 1]\) extrapolate along the same constant angular
+    /// velocity. The shorter arc is taken; for a relative rotation of exactly
+    /// \(\pi\) either arc may be returned.
+    pub fn interpolate(&self, other: &So3, t: f64) -> So3 {
+        let delta = self.inverse().compose(other).to_rotation_vector();
+        self.compose(&Self::from_rotation_vector(delta.map(|x| x * t)))
+    }
+
+    /// Fractional power \(R^t = \mathrm{Exp}(t \log R)\): the rotation by `t`
+    /// times the angle about the same axis, e.g. half of it for `t = 0.5`.
+    pub fn powf(&self, t: f64) -> Self {
+        Self::from_rotation_vector(self.to_rotation_vector().map(|x| x * t))
+    }
+
+    /// Integer power \(X^n\) by repeated squaring; negative `n` powers the
+    /// inverse.
+    pub fn pow(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { self.clone() };
+        let mut exponent = n.unsigned_abs();
+        let mut result = Self::identity();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.compose(&base);
+            }
+            base = base.compose(&base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Geodesic distance: the angle (rad, in \([0, \pi]\)) of \(R^\top R'\).
+    pub fn angle_to(&self, other: &Self) -> f64 {
+        vector3_from_array(self.inverse().compose(other).to_rotation_vector()).norm()
+    }
+
+    /// Chordal distance \(\lVert R - R' \rVert_F = 2\sqrt{2}\sin(\theta/2)\),
+    /// cheaper than [`So3::angle_to`] and monotonic in it.
+    pub fn chordal_distance(&self, other: &Self) -> f64 {
+        (self.rotation.matrix() - other.rotation.matrix()).norm()
+    }
+
+    /// Quaternion distance \(\min(\lVert q - q' \rVert, \lVert q + q' \rVert)
+    /// = 2\sin(\theta/4)\), insensitive to the sign of either quaternion.
+    pub fn quaternion_distance(&self, other: &Self) -> f64 {
+        let (q, p) = (self.to_quaternion(), other.to_quaternion());
+        let minus: f64 = q.iter().zip(&p).map(|(a, b)| (a - b) * (a - b)).sum();
+        let plus: f64 = q.iter().zip(&p).map(|(a, b)| (a + b) * (a + b)).sum();
+        minus.min(plus).sqrt()
+    }
+
+    /// Cayley map \(\mathrm{cay}(\omega) = (I - \hat\omega/2)^{-1}(I + \hat\omega/2)
+    /// = I + \frac{4}{4 + \lVert\omega\rVert^2}(\hat\omega + \hat\omega^2/2)\),
+    /// a rational approximation of the exponential map agreeing with it to
+    /// second order. It rotates by \(2\arctan(\lVert\omega\rVert/2)\).
+    pub fn cayley(omega: [f64; 3]) -> Self {
+        let omega = vector3_from_array(omega);
+        let skew = skew_symmetric(&omega);
+        let scale = 4.0 / (4.0 + omega.norm_squared());
+        Self {
+            rotation: Rotation3::from_matrix_unchecked(
+                Matrix3::identity() + (skew + skew * skew * 0.5) * scale,
+            ),
+        }
+    }
+
+    /// Inverse of [`So3::cayley`],
+    /// \(\omega = \frac{2}{1 + \mathrm{tr}\,R}(R - R^\top)^\vee\).
+    ///
+    /// Returns [`Error::Singular`] for half turns, which lie at infinity.
+    pub fn inverse_cayley(&self) -> Result<[f64; 3]> {
+        let m = self.rotation.matrix();
+        let denominator = 1.0 + m.trace();
+        if denominator < CAYLEY_TOLERANCE {
+            return Err(Error::Singular);
+        }
+        let axis = Vector3::new(
+            m[(2, 1)] - m[(1, 2)],
+            m[(0, 2)] - m[(2, 0)],
+            m[(1, 0)] - m[(0, 1)],
+        );
+        Ok(vector3_to_array(&(axis * (2.0 / denominator))))
+    }
+
     /// Build a rotation directly from the so(3) tangent vector using the
     /// exponential map.
     pub fn from_rotation_vector(vector: [f64; 3]) -> Self {
@@ -111,9 +638,117 @@ impl So3 {
     /// Recover the tangent vector representation (logarithm map) using the
     /// Rodrigues rotation vector.
     pub fn to_rotation_vector(&self) -> [f64; 3] {
+        let m = self.rotation.matrix();
+        let cos = 0.5 * (m.trace() - 1.0);
+        if cos > 0.0 {
+            // For angles below π/2 recover θ with atan2 on the antisymmetric
+            // part, which stays accurate for tiny rotations where
+            // acos((tr R - 1) / 2) rounds to zero.
+            let axis = Vector3::new(
+                m[(2, 1)] - m[(1, 2)],
+                m[(0, 2)] - m[(2, 0)],
+                m[(1, 0)] - m[(0, 1)],
+            );
+            let sin = 0.5 * axis.norm();
+            let scale = if sin == 0.0 {
+                0.5
+            } else {
+                sin.atan2(cos) / (2.0 * sin)
+            };
+            return vector3_to_array(&(axis * scale));
+        }
         vector3_to_array(&self.rotation.scaled_axis())
     }
 
+    /// Rotation from modified Rodrigues parameters
+    /// \(\sigma = \hat n \tan(\theta / 4)\). Both \(\sigma\) and its shadow
+    /// (see [`So3::shadow_mrp`]) describe the same rotation.
+    pub fn from_mrp(mrp: [f64; 3]) -> Self {
+        let sigma = vector3_from_array(mrp);
+        let norm_squared = sigma.norm_squared();
+        let vector = sigma * 2.0;
+        Self::from_quaternion([1.0 - norm_squared, vector.x, vector.y, vector.z])
+    }
+
+    /// Modified Rodrigues parameters of the rotation, taken from the
+    /// quaternion with non-negative scalar part so that
+    /// \(\lVert\sigma\rVert \le 1\).
+    pub fn to_mrp(&self) -> [f64; 3] {
+        let [w, x, y, z] = self.to_quaternion();
+        let (w, vector) = if w < 0.0 {
+            (-w, -Vector3::new(x, y, z))
+        } else {
+            (w, Vector3::new(x, y, z))
+        };
+        vector3_to_array(&(vector / (1.0 + w)))
+    }
+
+    /// Shadow parameters \(-\sigma / \lVert\sigma\rVert^2\), describing the
+    /// same rotation the other way around the principal axis.
+    ///
+    /// # Panics
+    /// Panics if `mrp` is zero, whose shadow lies at infinity.
+    pub fn shadow_mrp(mrp: [f64; 3]) -> [f64; 3] {
+        let sigma = vector3_from_array(mrp);
+        let norm_squared = sigma.norm_squared();
+        assert!(norm_squared > 0.0, "the zero MRP has no finite shadow");
+        vector3_to_array(&(-sigma / norm_squared))
+    }
+
+    /// Shadow-set switch: replace `mrp` by its shadow when
+    /// \(\lVert\sigma\rVert > 1\), keeping attitude propagated through MRPs
+    /// away from their singularity at \(\theta = 2\pi\).
+    pub fn switch_mrp(mrp: [f64; 3]) -> [f64; 3] {
+        if vector3_from_array(mrp).norm_squared() > 1.0 {
+            Self::shadow_mrp(mrp)
+        } else {
+            mrp
+        }
+    }
+
+    /// Right Jacobian \(J_r(\phi)\) of the exponential map, so that
+    /// \(\mathrm{Exp}(\phi + \delta) \approx \mathrm{Exp}(\phi)\,\mathrm{Exp}(J_r(\phi)\delta)\).
+    pub fn right_jacobian(vector: [f64; 3]) -> Matrix3<f64> {
+        let phi = vector3_from_array(vector);
+        let theta = phi.norm();
+        let (a, b) = if theta < 1e-4 {
+            let t2 = theta * theta;
+            (0.5 - t2 / 24.0, 1.0 / 6.0 - t2 / 120.0)
+        } else {
+            let t2 = theta * theta;
+            (
+                (1.0 - theta.cos()) / t2,
+                (theta - theta.sin()) / (t2 * theta),
+            )
+        };
+        let skew = skew_symmetric(&phi);
+        Matrix3::identity() - skew * a + skew * skew * b
+    }
+
+    /// Inverse of [`So3::right_jacobian`], valid for \(\|\phi\| < 2\pi\).
+    pub fn right_jacobian_inverse(vector: [f64; 3]) -> Matrix3<f64> {
+        let phi = vector3_from_array(vector);
+        let theta = phi.norm();
+        let c = if theta < 1e-4 {
+            1.0 / 12.0 + theta * theta / 720.0
+        } else {
+            1.0 / (theta * theta) - (1.0 + theta.cos()) / (2.0 * theta * theta.sin())
+        };
+        let skew = skew_symmetric(&phi);
+        Matrix3::identity() + skew * 0.5 + skew * skew * c
+    }
+
+    /// Left Jacobian \(J_l(\phi) = J_r(-\phi)\), so that
+    /// \(\mathrm{Exp}(\phi + \delta) \approx \mathrm{Exp}(J_l(\phi)\delta)\,\mathrm{Exp}(\phi)\).
+    pub fn left_jacobian(vector: [f64; 3]) -> Matrix3<f64> {
+        Self::right_jacobian(vector.map(|x| -x))
+    }
+
+    /// Inverse of [`So3::left_jacobian`], valid for \(\|\phi\| < 2\pi\).
+    pub fn left_jacobian_inverse(vector: [f64; 3]) -> Matrix3<f64> {
+        Self::right_jacobian_inverse(vector.map(|x| -x))
+    }
+
     /// Create the skew-symmetric matrix associated with a 3D vector.
     pub fn hat(vector: [f64; 3]) -> [[f64; 3]; 3] {
         matrix_to_array(&skew_symmetric(&vector3_from_array(vector)))
@@ -147,12 +782,56 @@ impl So3 {
         matrix_to_array(&self.rotation.matrix().clone_owned())
     }
 
+    /// Export the rotation matrix as a flat row-major array, with element
+    /// `(r, c)` stored at index `3 * r + c`.
+    pub fn to_row_major_flat(&self) -> [f64; 9] {
+        matrix_to_row_major(self.rotation.matrix())
+    }
+
+    /// Export the rotation matrix as a flat column-major array, with element
+    /// `(r, c)` stored at index `3 * c + r`.
+    pub fn to_col_major_flat(&self) -> [f64; 9] {
+        matrix_to_col_major(self.rotation.matrix())
+    }
+
+    /// Write the rotation matrix into a caller-provided buffer using the
+    /// row-major layout accepted by [`So3::from_flat`].
+    pub fn write_flat(&self, out: &mut [f64; 9]) {
+        *out = self.to_row_major_flat();
+    }
+
+    /// Report how far the stored matrix has drifted from a proper rotation.
+    pub fn health_check(&self) -> TransformHealth {
+        TransformHealth::from_rotation(self.rotation.matrix(), 0.0)
+    }
+
     /// Access the nalgebra `Rotation3` backing this object.
     pub fn rotation(&self) -> &Rotation3<f64> {
         &self.rotation
     }
 }
 
+/// Rotation matrix \(\exp(\phi^\wedge)\) of a rotation vector; a free-function
+/// form of [`So3::from_rotation_vector`] for use as a function value.
+pub fn exp(vector: [f64; 3]) -> [[f64; 3]; 3] {
+    So3::from_rotation_vector(vector).to_matrix()
+}
+
+/// Rotation vector of a rotation matrix, the inverse of [`exp`].
+pub fn log(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+    So3::from_matrix(matrix).to_rotation_vector()
+}
+
+/// Free-function form of [`So3::hat`].
+pub fn hat(vector: [f64; 3]) -> [[f64; 3]; 3] {
+    So3::hat(vector)
+}
+
+/// Free-function form of [`So3::vee`].
+pub fn vee(matrix: [[f64; 3]; 3]) -> [f64; 3] {
+    So3::vee(matrix)
+}
+
 impl LieGroup<3> for So3 {
     fn identity() -> Self {
         Self {
@@ -173,6 +852,18 @@ impl LieGroup<3> for So3 {
     }
 }
 
+impl HasExp<3> for So3 {
+    fn exp_map(xi: &SVector<f64, 3>) -> Self {
+        Self {
+            rotation: Rotation3::new(*xi),
+        }
+    }
+
+    fn bracket(a: &SVector<f64, 3>, b: &SVector<f64, 3>) -> SVector<f64, 3> {
+        a.cross(b)
+    }
+}
+
 impl Mul for So3 {
     type Output = Self;
 
@@ -189,7 +880,7 @@ impl<'a> Mul<&'a So3> for So3 {
     }
 }
 
-impl<'a> Mul<So3> for &'a So3 {
+impl Mul<So3> for &So3 {
     type Output = So3;
 
     fn mul(self, rhs: So3) -> Self::Output {
@@ -197,10 +888,20 @@ impl<'a> Mul<So3> for &'a So3 {
     }
 }
 
-impl<'a, 'b> Mul<&'a So3> for &'b So3 {
+impl<'a> Mul<&'a So3> for &So3 {
     type Output = So3;
 
     fn mul(self, rhs: &'a So3) -> Self::Output {
         self.compose(rhs)
     }
 }
+
+/// Wrap an angle into \((-\pi, \pi]\).
+fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
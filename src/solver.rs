@@ -0,0 +1,132 @@
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use nalgebra::DMatrix;
+
+/// Statistics of one iteration of an iterative solver, passed to callbacks
+/// together with the current estimate.
+///
+/// Callbacks have the shape `FnMut(&State, &IterationInfo) -> ControlFlow<()>`;
+/// returning [`ControlFlow::Break`] stops the solver, which then returns the
+/// current estimate as its result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IterationInfo {
+    /// Zero-based iteration index.
+    pub iteration: usize,
+    /// Objective at the current estimate, in the solver's own units (e.g.
+    /// squared pixel error or largest leg-length residual).
+    pub cost: f64,
+    /// Norm of the update computed in this iteration.
+    pub step_norm: f64,
+}
+
+/// Continue-always callback for the `*_with` solver variants, used by the
+/// plain entry points.
+pub fn no_callback<S>(_: &S, _: &IterationInfo) -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
+
+/// Iteration and wall-clock limits of a solve. Unset limits fall back to the
+/// solver's own iteration cap and no time limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub max_iterations: Option<usize>,
+    /// Checked before every iteration, so one iteration may overrun it.
+    pub max_duration: Option<Duration>,
+}
+
+impl Budget {
+    /// The solver's default limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_iterations(mut self, iterations: usize) -> Self {
+        self.max_iterations = Some(iterations);
+        self
+    }
+
+    pub fn with_max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Start timing a solve whose own iteration cap is `default_iterations`.
+    pub(crate) fn start(&self, default_iterations: usize) -> BudgetClock {
+        BudgetClock {
+            max_iterations: self.max_iterations.unwrap_or(default_iterations),
+            deadline: self.max_duration.map(|duration| (Instant::now(), duration)),
+        }
+    }
+}
+
+/// Running limits of a solve started with [`Budget::start`].
+pub(crate) struct BudgetClock {
+    max_iterations: usize,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl BudgetClock {
+    /// The status to stop with before iteration `completed + 1`, if any.
+    pub(crate) fn exhausted(&self, completed: usize) -> Option<SolveStatus> {
+        if completed >= self.max_iterations {
+            return Some(SolveStatus::IterationLimit);
+        }
+        match self.deadline {
+            Some((start, duration)) if start.elapsed() >= duration => Some(SolveStatus::TimeLimit),
+            _ => None,
+        }
+    }
+}
+
+/// Why a solve returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveStatus {
+    /// The solver's convergence test passed.
+    Converged,
+    /// The iteration budget ran out; the result is the best estimate so far.
+    IterationLimit,
+    /// The time budget ran out; the result is the best estimate so far.
+    TimeLimit,
+    /// The iteration callback asked to stop.
+    Stopped,
+    /// The linearized system became singular; the result is the last
+    /// estimate.
+    Singular,
+}
+
+impl SolveStatus {
+    pub fn is_converged(self) -> bool {
+        self == SolveStatus::Converged
+    }
+}
+
+/// Result of a budgeted solve: the estimate together with how it ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveReport<T> {
+    pub solution: T,
+    pub status: SolveStatus,
+    /// Iterations performed.
+    pub iterations: usize,
+    /// Objective at `solution`, in the solver's own units.
+    pub cost: f64,
+    /// State to hand to the next solve of a similar problem.
+    pub warm_start: WarmStart,
+}
+
+/// Opaque solver state carried from one solve to the next, such as the last
+/// Levenberg–Marquardt damping or a factorized Jacobian, so that repeated
+/// solves of a slowly changing problem start where the last one ended. Pass
+/// the previous [`SolveReport::solution`] as the initial guess together with
+/// its [`SolveReport::warm_start`]; [`WarmStart::default`] is a cold start.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WarmStart {
+    pub(crate) damping: Option<f64>,
+    pub(crate) inverse_jacobian: Option<DMatrix<f64>>,
+}
+
+impl WarmStart {
+    pub fn is_cold(&self) -> bool {
+        self.damping.is_none() && self.inverse_jacobian.is_none()
+    }
+}
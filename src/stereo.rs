@@ -0,0 +1,135 @@
+use nalgebra::{Matrix3, Matrix4, RowVector4, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::camera::CameraIntrinsics;
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+
+/// A calibrated stereo pair: intrinsics of both cameras and their poses in a
+/// common body frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StereoRig {
+    pub left: CameraIntrinsics,
+    pub right: CameraIntrinsics,
+    pub body_from_left: Se3,
+    pub body_from_right: Se3,
+}
+
+impl StereoRig {
+    pub fn new(
+        left: CameraIntrinsics,
+        right: CameraIntrinsics,
+        body_from_left: Se3,
+        body_from_right: Se3,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            body_from_left,
+            body_from_right,
+        }
+    }
+
+    /// Pose of the left camera in the right camera frame.
+    pub fn right_from_left(&self) -> Se3 {
+        self.body_from_right.inverse().compose(&self.body_from_left)
+    }
+
+    /// Distance between the optical centres.
+    pub fn baseline(&self) -> f64 {
+        Vector3::from(self.right_from_left().translation()).norm()
+    }
+
+    /// Rotations that make both image planes coplanar with rows aligned to
+    /// the baseline (Fusiello et al., 2000).
+    ///
+    /// The rectified \(x\) axis points from the left to the right optical
+    /// centre, \(y\) is orthogonal to it and to the left optical axis, and
+    /// \(z = x \times y\). Both rectified cameras share intrinsics with the
+    /// mean focal length of the pair and the left principal point.
+    ///
+    /// Returns [`Error::ZeroNorm`] for coincident optical centres and
+    /// [`Error::Singular`] when the baseline is along the left optical axis.
+    pub fn rectify(&self) -> Result<Rectification> {
+        let right_from_left = self.right_from_left();
+        let rotation = right_from_left.rotation().rotation().matrix();
+        // Right optical centre in the left frame.
+        let centre = -(rotation.transpose() * Vector3::from(right_from_left.translation()));
+        let baseline = centre.norm();
+        if baseline == 0.0 || !baseline.is_finite() {
+            return Err(Error::ZeroNorm);
+        }
+        let x = centre / baseline;
+        let y = Vector3::z().cross(&x);
+        if y.norm() <= 1e-9 {
+            return Err(Error::Singular);
+        }
+        let y = y.normalize();
+        let z = x.cross(&y);
+        let rectified_from_left =
+            Matrix3::from_rows(&[x.transpose(), y.transpose(), z.transpose()]);
+        let rectified_from_right = rectified_from_left * rotation.transpose();
+
+        let focal = 0.25 * (self.left.fx + self.left.fy + self.right.fx + self.right.fy);
+        Ok(Rectification {
+            rectified_from_left: to_so3(&rectified_from_left),
+            rectified_from_right: to_so3(&rectified_from_right),
+            intrinsics: CameraIntrinsics::new(focal, focal, self.left.cx, self.left.cy),
+            baseline,
+        })
+    }
+}
+
+/// Output of [`StereoRig::rectify`]. In the rectified frames the right
+/// camera sits at \((b, 0, 0)\) of the left one, so a point at depth \(Z\)
+/// has disparity \(d = u_l - u_r = f b / Z\).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rectification {
+    /// Rotation from the left camera frame to the rectified left frame.
+    pub rectified_from_left: So3,
+    /// Rotation from the right camera frame to the rectified right frame.
+    pub rectified_from_right: So3,
+    /// Intrinsics shared by both rectified images.
+    pub intrinsics: CameraIntrinsics,
+    pub baseline: f64,
+}
+
+impl Rectification {
+    /// Depth \(Z = f b / d\) of a disparity in pixels, or `None` unless
+    /// \(d > 0\).
+    pub fn depth(&self, disparity: f64) -> Option<f64> {
+        (disparity > 0.0).then(|| self.intrinsics.fx * self.baseline / disparity)
+    }
+
+    /// Disparity \(d = f b / Z\) of a depth, or `None` unless \(Z > 0\).
+    pub fn disparity(&self, depth: f64) -> Option<f64> {
+        (depth > 0.0).then(|| self.intrinsics.fx * self.baseline / depth)
+    }
+
+    /// The reprojection matrix \(Q\) mapping \((u, v, d, 1)\) to homogeneous
+    /// points in the rectified left frame.
+    pub fn disparity_to_depth(&self) -> Matrix4<f64> {
+        let CameraIntrinsics { fx, cx, cy, .. } = self.intrinsics;
+        Matrix4::from_rows(&[
+            RowVector4::new(1.0, 0.0, 0.0, -cx),
+            RowVector4::new(0.0, 1.0, 0.0, -cy),
+            RowVector4::new(0.0, 0.0, 0.0, fx),
+            RowVector4::new(0.0, 0.0, 1.0 / self.baseline, 0.0),
+        ])
+    }
+
+    /// Point in the rectified left frame seen at a rectified left pixel with
+    /// the given disparity, or `None` unless the disparity is positive.
+    pub fn reproject(&self, pixel: [f64; 2], disparity: f64) -> Option<[f64; 3]> {
+        let depth = self.depth(disparity)?;
+        let [x, y] = self.intrinsics.normalize(pixel);
+        Some([x * depth, y * depth, depth])
+    }
+}
+
+fn to_so3(matrix: &Matrix3<f64>) -> So3 {
+    So3::from_matrix(std::array::from_fn(|r| {
+        std::array::from_fn(|c| matrix[(r, c)])
+    }))
+}
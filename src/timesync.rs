@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::trajectory::{TimedPose, Trajectory};
+
+/// A twist \([\omega, v]\) stamped with the time (in seconds) it was measured.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimedTwist {
+    pub time: f64,
+    pub twist: [f64; 6],
+}
+
+impl TimedTwist {
+    pub fn new(time: f64, twist: [f64; 6]) -> Self {
+        Self { time, twist }
+    }
+}
+
+/// Largest number of resampled times, and of candidate shifts on either
+/// side of zero, that [`estimate_time_offset`] accepts.
+pub const MAX_OFFSET_SAMPLES: usize = 1 << 20;
+
+/// Result of [`estimate_time_offset`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeOffset {
+    /// Delay \(\delta\) of the second stream: an event stamped \(t\) in the
+    /// reference stream is stamped \(t + \delta\) in the delayed one.
+    pub offset: f64,
+    /// Normalized cross-correlation of the angular speeds at `offset`, in
+    /// \([-1, 1]\).
+    pub correlation: f64,
+}
+
+/// World-frame twists of the constant-rate segments of `trajectory`, stamped
+/// at the segment midpoints, as in [`Trajectory::segment_velocities`].
+pub fn trajectory_twists(trajectory: &Trajectory) -> Vec<TimedTwist> {
+    trajectory
+        .samples()
+        .windows(2)
+        .zip(trajectory.segment_velocities())
+        .map(|(pair, twist)| TimedTwist::new(0.5 * (pair[0].time + pair[1].time), twist))
+        .collect()
+}
+
+/// Poses of `stream` at each of `times`, interpolated along the geodesic and
+/// held constant outside the sampled interval (see [`Trajectory::sample`]).
+pub fn resample_poses(stream: &Trajectory, times: &[f64]) -> Vec<TimedPose> {
+    times
+        .iter()
+        .map(|&time| TimedPose::new(time, stream.sample(time)))
+        .collect()
+}
+
+/// Twists of `stream` at each of `times`, linearly interpolated per component
+/// and held constant outside the sampled interval.
+///
+/// Returns [`Error::InvalidTrajectory`] if `stream` is empty or its
+/// timestamps are not finite and strictly increasing.
+pub fn resample_twists(stream: &[TimedTwist], times: &[f64]) -> Result<Vec<TimedTwist>> {
+    validate(stream)?;
+    Ok(times
+        .iter()
+        .map(|&time| TimedTwist::new(time, interpolate(stream, time)))
+        .collect())
+}
+
+/// Estimate the constant time offset between two streams observing the same
+/// rotation, e.g. a camera and an IMU on one rig.
+///
+/// The angular speeds \(\lVert\omega\rVert\), which do not depend on the frame
+/// each sensor reports in, are resampled every `step` seconds, and the
+/// offset in \([-\text{max\_offset}, \text{max\_offset}]\) maximizing their
+/// normalized cross-correlation is refined by a parabolic fit. Subtract the
+/// returned offset from the delayed stream's timestamps to align it.
+///
+/// Returns [`Error::InvalidTrajectory`] if a stream is malformed, the streams
+/// overlap by fewer than three steps once shifted by `max_offset`, or the
+/// angular speed does not vary over the overlap. Returns
+/// [`Error::InvalidParameter`] unless `step` is positive and `max_offset`
+/// non-negative, both finite, and neither the overlap nor `max_offset` spans
+/// more than [`MAX_OFFSET_SAMPLES`] steps.
+pub fn estimate_time_offset(
+    reference: &[TimedTwist],
+    delayed: &[TimedTwist],
+    max_offset: f64,
+    step: f64,
+) -> Result<TimeOffset> {
+    validate(reference)?;
+    validate(delayed)?;
+    if !step.is_finite() || step <= 0.0 || !max_offset.is_finite() || max_offset < 0.0 {
+        return Err(Error::InvalidParameter(format!(
+            "step {step} must be positive and max_offset {max_offset} non-negative"
+        )));
+    }
+
+    // A window of reference times that stays inside the delayed stream for
+    // every candidate offset.
+    let start = reference[0].time.max(delayed[0].time + max_offset);
+    let end = reference[reference.len() - 1]
+        .time
+        .min(delayed[delayed.len() - 1].time - max_offset);
+    let steps = ((end - start) / step).floor();
+    let shifts = (max_offset / step).floor();
+    if steps >= MAX_OFFSET_SAMPLES as f64 || shifts >= MAX_OFFSET_SAMPLES as f64 {
+        return Err(Error::InvalidParameter(format!(
+            "step {step} needs more than {MAX_OFFSET_SAMPLES} samples"
+        )));
+    }
+    // Negative spans saturate to a count of one.
+    let count = steps as usize + 1;
+    if count < 3 {
+        return Err(Error::InvalidTrajectory(
+            "streams overlap too little for the requested max_offset".into(),
+        ));
+    }
+    let window: Vec<f64> = (0..count).map(|k| start + k as f64 * step).collect();
+    let speed = |stream: &[TimedTwist], time: f64| {
+        let [wx, wy, wz, ..] = interpolate(stream, time);
+        (wx * wx + wy * wy + wz * wz).sqrt()
+    };
+    let reference_speed: Vec<f64> = window.iter().map(|&t| speed(reference, t)).collect();
+
+    let shifts = shifts as i64;
+    let mut scores = Vec::with_capacity(2 * shifts as usize + 1);
+    for shift in -shifts..=shifts {
+        let offset = shift as f64 * step;
+        let delayed_speed: Vec<f64> = window.iter().map(|&t| speed(delayed, t + offset)).collect();
+        let score = normalized_correlation(&reference_speed, &delayed_speed).ok_or_else(|| {
+            Error::InvalidTrajectory("angular speed does not vary over the overlap".into())
+        })?;
+        scores.push(score);
+    }
+
+    let best = scores
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let mut refinement = 0.0;
+    if best > 0 && best + 1 < scores.len() {
+        let (left, centre, right) = (scores[best - 1], scores[best], scores[best + 1]);
+        let curvature = left - 2.0 * centre + right;
+        if curvature < 0.0 {
+            refinement = 0.5 * (left - right) / curvature;
+        }
+    }
+    Ok(TimeOffset {
+        offset: ((best as i64 - shifts) as f64 + refinement) * step,
+        correlation: scores[best],
+    })
+}
+
+fn validate(stream: &[TimedTwist]) -> Result<()> {
+    if stream.is_empty() {
+        return Err(Error::InvalidTrajectory("no samples".into()));
+    }
+    if let Some(i) = stream.iter().position(|s| !s.time.is_finite()) {
+        return Err(Error::InvalidTrajectory(format!(
+            "sample {i} has a non-finite timestamp"
+        )));
+    }
+    if let Some(i) = stream.windows(2).position(|w| w[1].time <= w[0].time) {
+        return Err(Error::InvalidTrajectory(format!(
+            "timestamp of sample {} does not increase",
+            i + 1
+        )));
+    }
+    Ok(())
+}
+
+fn interpolate(stream: &[TimedTwist], time: f64) -> [f64; 6] {
+    let last = stream.len() - 1;
+    if time <= stream[0].time {
+        return stream[0].twist;
+    }
+    if time >= stream[last].time {
+        return stream[last].twist;
+    }
+    let i = stream.partition_point(|s| s.time <= time) - 1;
+    let (a, b) = (&stream[i], &stream[i + 1]);
+    let s = (time - a.time) / (b.time - a.time);
+    std::array::from_fn(|j| a.twist[j] + s * (b.twist[j] - a.twist[j]))
+}
+
+/// Pearson correlation, or `None` if either signal is constant.
+fn normalized_correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len() as f64;
+    let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let (mut cross, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        let (dx, dy) = (x - mean_a, y - mean_b);
+        cross += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    }
+    let denominator = (var_a * var_b).sqrt();
+    (denominator > 0.0).then(|| cross / denominator)
+}
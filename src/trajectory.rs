@@ -0,0 +1,529 @@
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::se3::Se3;
+use crate::so3::So3;
+
+/// A pose stamped with the time (in seconds) at which it is reached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedPose {
+    pub time: f64,
+    pub pose: Se3,
+}
+
+impl TimedPose {
+    pub fn new(time: f64, pose: Se3) -> Self {
+        Self { time, pose }
+    }
+}
+
+/// A sampled SE(3) trajectory with strictly increasing timestamps.
+///
+/// Between samples the rotation follows the geodesic and the translation a
+/// straight line, both at constant rate, so the increment of segment \(i\) is
+/// \([\log(R_{i+1} R_i^T), p_{i+1} - p_i]\) expressed in the world frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<TimedPose>", into = "Vec<TimedPose>")]
+pub struct Trajectory {
+    samples: Vec<TimedPose>,
+}
+
+/// Per-axis magnitude limits in twist order \([\omega, v]\), expressed in the
+/// world frame. Use `f64::INFINITY` to leave an axis unconstrained.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionLimits {
+    pub velocity: [f64; 6],
+    pub acceleration: [f64; 6],
+}
+
+impl MotionLimits {
+    /// Check that every limit is positive (possibly infinite), returning
+    /// [`Error::InvalidParameter`] for zero, negative or NaN limits.
+    pub fn validate(&self) -> Result<()> {
+        for (kind, limits) in [
+            ("velocity", &self.velocity),
+            ("acceleration", &self.acceleration),
+        ] {
+            if let Some(axis) = limits.iter().position(|l| l.is_nan() || *l <= 0.0) {
+                return Err(Error::InvalidParameter(format!(
+                    "{kind} limit {} on axis {axis} must be positive",
+                    limits[axis]
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Result of [`Trajectory::time_scale`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeScaling {
+    /// The same poses with new timestamps.
+    pub trajectory: Trajectory,
+    /// Ratio of new to original duration for each segment; values above one
+    /// mean the segment was slowed down.
+    pub scale: Vec<f64>,
+}
+
+impl Trajectory {
+    /// Build a trajectory from samples ordered by time.
+    ///
+    /// Returns [`Error::InvalidTrajectory`] if there are no samples or the
+    /// timestamps are not finite and strictly increasing.
+    pub fn new(samples: Vec<TimedPose>) -> Result<Self> {
+        if samples.is_empty() {
+            return Err(Error::InvalidTrajectory("no samples".into()));
+        }
+        if let Some(i) = samples.iter().position(|s| !s.time.is_finite()) {
+            return Err(Error::InvalidTrajectory(format!(
+                "sample {i} has a non-finite timestamp"
+            )));
+        }
+        if let Some(i) = samples.windows(2).position(|w| w[1].time <= w[0].time) {
+            return Err(Error::InvalidTrajectory(format!(
+                "timestamp of sample {} does not increase",
+                i + 1
+            )));
+        }
+        Ok(Self { samples })
+    }
+
+    /// Build a trajectory visiting `poses` at the matching `times`.
+    pub fn from_poses(times: &[f64], poses: &[Se3]) -> Result<Self> {
+        if times.len() != poses.len() {
+            return Err(Error::InvalidTrajectory(format!(
+                "{} timestamps for {} poses",
+                times.len(),
+                poses.len()
+            )));
+        }
+        Self::new(
+            times
+                .iter()
+                .zip(poses)
+                .map(|(&time, pose)| TimedPose::new(time, pose.clone()))
+                .collect(),
+        )
+    }
+
+    pub fn samples(&self) -> &[TimedPose] {
+        &self.samples
+    }
+
+    /// Number of samples.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Always `false`; a trajectory holds at least one sample.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn start_time(&self) -> f64 {
+        self.samples[0].time
+    }
+
+    pub fn end_time(&self) -> f64 {
+        self.samples[self.samples.len() - 1].time
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.end_time() - self.start_time()
+    }
+
+    /// Pose at `time`, interpolated between the neighbouring samples and held
+    /// constant outside the sampled interval.
+    pub fn sample(&self, time: f64) -> Se3 {
+        let last = self.samples.len() - 1;
+        if time <= self.start_time() {
+            return self.samples[0].pose.clone();
+        }
+        if time >= self.end_time() {
+            return self.samples[last].pose.clone();
+        }
+        let i = self.samples.partition_point(|s| s.time <= time) - 1;
+        let (a, b) = (&self.samples[i], &self.samples[i + 1]);
+        let s = (time - a.time) / (b.time - a.time);
+        let increment = segment_increment(&a.pose, &b.pose);
+        let [rx, ry, rz, x, y, z] = increment.map(|v| v * s);
+        let rotation = So3::from_rotation_vector([rx, ry, rz]).compose(a.pose.rotation());
+        let translation = Vector3::from(a.pose.translation()) + Vector3::new(x, y, z);
+        Se3::from_parts(rotation, translation.into())
+    }
+
+    /// Constant world-frame velocity \([\omega, v]\) of each segment.
+    pub fn segment_velocities(&self) -> Vec<[f64; 6]> {
+        self.samples
+            .windows(2)
+            .map(|w| {
+                let dt = w[1].time - w[0].time;
+                segment_increment(&w[0].pose, &w[1].pose).map(|v| v / dt)
+            })
+            .collect()
+    }
+
+    /// Accelerations at the interior samples, estimated as the change of the
+    /// segment velocities over the mean duration of the adjacent segments.
+    pub fn knot_accelerations(&self) -> Vec<[f64; 6]> {
+        knot_accelerations(&self.segment_velocities(), &self.durations())
+    }
+
+    /// Retime the trajectory so no segment velocity or interior acceleration
+    /// exceeds `limits`, keeping the poses (and hence the path) unchanged.
+    ///
+    /// Segments are only ever slowed down: each is first stretched to meet the
+    /// velocity limits, then segment pairs around a sample violating the
+    /// acceleration limits are stretched until the violation disappears.
+    /// If local stretching does not settle, the whole trajectory is slowed
+    /// uniformly, which scales every acceleration by the inverse square.
+    ///
+    /// Returns the errors of [`MotionLimits::validate`].
+    pub fn time_scale(&self, limits: &MotionLimits) -> Result<TimeScaling> {
+        const MAX_PASSES: usize = 100;
+
+        limits.validate()?;
+
+        let original = self.durations();
+        let increments: Vec<[f64; 6]> = self
+            .samples
+            .windows(2)
+            .map(|w| segment_increment(&w[0].pose, &w[1].pose))
+            .collect();
+
+        let mut durations: Vec<f64> = increments
+            .iter()
+            .zip(&original)
+            .map(|(increment, &duration)| {
+                increment
+                    .iter()
+                    .zip(&limits.velocity)
+                    .map(|(d, limit)| d.abs() / limit)
+                    .fold(duration, f64::max)
+            })
+            .collect();
+
+        for _ in 0..MAX_PASSES {
+            let mut changed = false;
+            for knot in 1..durations.len() {
+                let ratio = acceleration_ratio(&increments, &durations, knot, limits);
+                if ratio > 1.0 {
+                    let stretch = ratio.sqrt();
+                    durations[knot - 1] *= stretch;
+                    durations[knot] *= stretch;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let worst = (1..durations.len())
+            .map(|knot| acceleration_ratio(&increments, &durations, knot, limits))
+            .fold(1.0, f64::max);
+        if worst > 1.0 {
+            let stretch = worst.sqrt();
+            durations.iter_mut().for_each(|d| *d *= stretch);
+        }
+
+        let mut time = self.start_time();
+        let mut samples = Vec::with_capacity(self.samples.len());
+        samples.push(TimedPose::new(time, self.samples[0].pose.clone()));
+        for (sample, duration) in self.samples[1..].iter().zip(&durations) {
+            time += duration;
+            samples.push(TimedPose::new(time, sample.pose.clone()));
+        }
+
+        Ok(TimeScaling {
+            trajectory: Self { samples },
+            scale: durations
+                .iter()
+                .zip(&original)
+                .map(|(new, old)| new / old)
+                .collect(),
+        })
+    }
+
+    fn durations(&self) -> Vec<f64> {
+        self.samples
+            .windows(2)
+            .map(|w| w[1].time - w[0].time)
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<TimedPose>> for Trajectory {
+    type Error = Error;
+
+    fn try_from(samples: Vec<TimedPose>) -> Result<Self> {
+        Trajectory::new(samples)
+    }
+}
+
+impl From<Trajectory> for Vec<TimedPose> {
+    fn from(trajectory: Trajectory) -> Self {
+        trajectory.samples
+    }
+}
+
+/// World-frame increment \([\log(R_b R_a^T), p_b - p_a]\) between two poses.
+fn segment_increment(a: &Se3, b: &Se3) -> [f64; 6] {
+    let [rx, ry, rz] = b
+        .rotation()
+        .compose(&a.rotation().inverse())
+        .to_rotation_vector();
+    let [ax, ay, az] = a.translation();
+    let [bx, by, bz] = b.translation();
+    [rx, ry, rz, bx - ax, by - ay, bz - az]
+}
+
+fn knot_accelerations(velocities: &[[f64; 6]], durations: &[f64]) -> Vec<[f64; 6]> {
+    (1..velocities.len())
+        .map(|knot| {
+            let span = 0.5 * (durations[knot - 1] + durations[knot]);
+            std::array::from_fn(|j| (velocities[knot][j] - velocities[knot - 1][j]) / span)
+        })
+        .collect()
+}
+
+/// Largest ratio of acceleration magnitude to its limit at an interior knot.
+fn acceleration_ratio(
+    increments: &[[f64; 6]],
+    durations: &[f64],
+    knot: usize,
+    limits: &MotionLimits,
+) -> f64 {
+    let span = 0.5 * (durations[knot - 1] + durations[knot]);
+    (0..6)
+        .map(|j| {
+            let change = increments[knot][j] / durations[knot]
+                - increments[knot - 1][j] / durations[knot - 1];
+            (change / span).abs() / limits.acceleration[j]
+        })
+        .fold(0.0, f64::max)
+}
+
+/// A waypoint of a [`BlendedTrajectory`] with the size of the corner blend
+/// allowed around it. The blend starts and ends at most `blend_radius`
+/// (translation) and `blend_angle` (rotation) away from the waypoint; zero
+/// passes exactly through it with a velocity discontinuity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub pose: Se3,
+    pub blend_radius: f64,
+    pub blend_angle: f64,
+}
+
+impl Waypoint {
+    pub fn new(pose: Se3, blend_radius: f64, blend_angle: f64) -> Self {
+        Self {
+            pose,
+            blend_radius,
+            blend_angle,
+        }
+    }
+}
+
+/// Straight segments between waypoints joined by parabolic blends, in the
+/// style of industrial-arm "linear with blend" moves.
+///
+/// Each segment moves at constant world-frame velocity \(V_i\), limited by
+/// the linear and angular cruise speeds. Around waypoint \(i\) a blend of
+/// half-width \(\tau_i\) changes the velocity linearly from \(V_{i-1}\) to
+/// \(V_i\), so pose and velocity are continuous (C1) whenever every blend is
+/// non-degenerate. The motion starts and ends at rest on the first and last
+/// waypoints. Time runs from zero to [`BlendedTrajectory::duration`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlendedTrajectory {
+    poses: Vec<Se3>,
+    times: Vec<f64>,
+    blends: Vec<f64>,
+    velocities: Vec<[f64; 6]>,
+}
+
+impl BlendedTrajectory {
+    /// Connect `waypoints` cruising at most at `linear_speed` (m/s) and
+    /// `angular_speed` (rad/s).
+    ///
+    /// Returns [`Error::InvalidTrajectory`] for fewer than two waypoints,
+    /// coincident consecutive waypoints, non-positive speeds or negative
+    /// blend sizes.
+    pub fn build(waypoints: &[Waypoint], linear_speed: f64, angular_speed: f64) -> Result<Self> {
+        if waypoints.len() < 2 {
+            return Err(Error::InvalidTrajectory(
+                "at least two waypoints are required".into(),
+            ));
+        }
+        if linear_speed.is_nan()
+            || angular_speed.is_nan()
+            || linear_speed <= 0.0
+            || angular_speed <= 0.0
+        {
+            return Err(Error::InvalidTrajectory(
+                "cruise speeds must be positive".into(),
+            ));
+        }
+        if let Some(i) = waypoints.iter().position(|w| {
+            w.blend_radius.is_nan()
+                || w.blend_radius < 0.0
+                || w.blend_angle.is_nan()
+                || w.blend_angle < 0.0
+        }) {
+            return Err(Error::InvalidTrajectory(format!(
+                "waypoint {i} has a negative blend size"
+            )));
+        }
+
+        let mut durations = Vec::with_capacity(waypoints.len() - 1);
+        let mut velocities = Vec::with_capacity(waypoints.len() - 1);
+        for (i, pair) in waypoints.windows(2).enumerate() {
+            let increment = segment_increment(&pair[0].pose, &pair[1].pose);
+            let (angle, distance) = split_norms(&increment);
+            let duration = (distance / linear_speed).max(angle / angular_speed);
+            if duration == 0.0 {
+                return Err(Error::InvalidTrajectory(format!(
+                    "waypoints {i} and {} coincide",
+                    i + 1
+                )));
+            }
+            durations.push(duration);
+            velocities.push(increment.map(|v| v / duration));
+        }
+
+        let last = waypoints.len() - 1;
+        let mut blends = vec![0.0; waypoints.len()];
+        for i in 1..last {
+            let mut tau = 0.5 * durations[i - 1].min(durations[i]);
+            for velocity in [&velocities[i - 1], &velocities[i]] {
+                let (angular, linear) = split_norms(velocity);
+                if linear > 0.0 {
+                    tau = tau.min(waypoints[i].blend_radius / linear);
+                }
+                if angular > 0.0 {
+                    tau = tau.min(waypoints[i].blend_angle / angular);
+                }
+            }
+            blends[i] = tau;
+        }
+        blends[0] = 0.5 * durations[0];
+        blends[last] = 0.5 * durations[last - 1];
+
+        let mut times = Vec::with_capacity(waypoints.len());
+        let mut time = blends[0];
+        times.push(time);
+        for duration in &durations {
+            time += duration;
+            times.push(time);
+        }
+
+        Ok(Self {
+            poses: waypoints.iter().map(|w| w.pose.clone()).collect(),
+            times,
+            blends,
+            velocities,
+        })
+    }
+
+    /// Total time from rest at the first waypoint to rest at the last.
+    pub fn duration(&self) -> f64 {
+        let last = self.times.len() - 1;
+        self.times[last] + self.blends[last]
+    }
+
+    /// Times at which the straight segments meet at each waypoint; the motion
+    /// passes through a waypoint at that time only if its blend is empty.
+    pub fn waypoint_times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Half-width in seconds of the blend around each waypoint.
+    pub fn blend_durations(&self) -> &[f64] {
+        &self.blends
+    }
+
+    /// Pose at `time`, held at the first or last waypoint outside
+    /// `[0, duration]`.
+    pub fn sample(&self, time: f64) -> Se3 {
+        let time = time.clamp(0.0, self.duration());
+        let last = self.times.len() - 1;
+        let segment = self.times[1..last].partition_point(|&t| t <= time);
+
+        // The closest waypoint owns the blend that may contain `time`.
+        let knot = if segment < last && time - self.times[segment] > self.times[segment + 1] - time
+        {
+            segment + 1
+        } else {
+            segment
+        };
+        let offset = time - self.times[knot];
+        let tau = self.blends[knot];
+        let displacement = if offset.abs() < tau {
+            let incoming = self.velocity_into(knot);
+            let outgoing = self.velocity_out_of(knot);
+            let s = offset + tau;
+            std::array::from_fn(|j| {
+                incoming[j] * (s - tau) + (outgoing[j] - incoming[j]) * s * s / (4.0 * tau)
+            })
+        } else if offset < 0.0 {
+            self.velocity_into(knot).map(|v| v * offset)
+        } else {
+            self.velocity_out_of(knot).map(|v| v * offset)
+        };
+
+        let [rx, ry, rz, x, y, z] = displacement;
+        let pose = &self.poses[knot];
+        let rotation = So3::from_rotation_vector([rx, ry, rz]).compose(pose.rotation());
+        let translation = Vector3::from(pose.translation()) + Vector3::new(x, y, z);
+        Se3::from_parts(rotation, translation.into())
+    }
+
+    /// Sample the motion every `dt` seconds (plus the final pose) as a
+    /// [`Trajectory`], e.g. to feed [`Trajectory::time_scale`]. Returns
+    /// [`Error::InvalidTrajectory`] unless `dt` is positive and finite.
+    pub fn to_trajectory(&self, dt: f64) -> Result<Trajectory> {
+        if !(dt.is_finite() && dt > 0.0) {
+            return Err(Error::InvalidTrajectory(format!(
+                "sample interval {dt} must be positive and finite"
+            )));
+        }
+        let duration = self.duration();
+        let steps = (duration / dt).ceil().max(1.0) as usize;
+        let samples = (0..=steps)
+            .map(|k| {
+                let time = (k as f64 * dt).min(duration);
+                TimedPose::new(time, self.sample(time))
+            })
+            .collect();
+        Trajectory::new(dedup_final(samples))
+    }
+
+    fn velocity_into(&self, knot: usize) -> [f64; 6] {
+        if knot == 0 {
+            [0.0; 6]
+        } else {
+            self.velocities[knot - 1]
+        }
+    }
+
+    fn velocity_out_of(&self, knot: usize) -> [f64; 6] {
+        self.velocities.get(knot).copied().unwrap_or([0.0; 6])
+    }
+}
+
+/// Drop a final sample that landed on the same time as its predecessor.
+fn dedup_final(mut samples: Vec<TimedPose>) -> Vec<TimedPose> {
+    let n = samples.len();
+    if n > 1 && samples[n - 1].time <= samples[n - 2].time {
+        samples.remove(n - 2);
+    }
+    samples
+}
+
+/// Norms of the rotational and translational parts of a twist-ordered vector.
+fn split_norms(vector: &[f64; 6]) -> (f64, f64) {
+    let angular = Vector3::new(vector[0], vector[1], vector[2]).norm();
+    let linear = Vector3::new(vector[3], vector[4], vector[5]).norm();
+    (angular, linear)
+}
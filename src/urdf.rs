@@ -0,0 +1,262 @@
+//! URDF export and import of a [`RobotDescription`], for visualization in
+//! tools such as RViz or Foxglove and for round-tripping the other importers.
+//!
+//! URDF requires `<limit>` on revolute and prismatic joints, including
+//! `effort` and `velocity`. On export, unspecified velocity and effort
+//! limits are written as `0`, which the importer reads back as unspecified;
+//! a revolute joint without limits is written as `continuous` and a
+//! prismatic joint without limits gets bounds of \(\pm 10^{16}\), which the
+//! importer reads back as no limits.
+
+use std::fmt::Write;
+
+use nalgebra::{Matrix3, Vector3};
+use roxmltree::Node;
+
+use crate::error::Result;
+use crate::lie::LieGroup;
+use crate::robot::{
+    DescriptionFormat, Inertial, Joint, JointKind, JointLimits, Link, RobotDescription,
+};
+use crate::se3::Se3;
+use crate::so3::So3;
+use crate::xml::{self, attribute, child, children, invalid};
+
+const UNBOUNDED: f64 = 1e16;
+
+/// The URDF format, for use with [`RobotDescription::parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Urdf;
+
+impl DescriptionFormat for Urdf {
+    fn parse(text: &str) -> Result<RobotDescription> {
+        parse(text)
+    }
+}
+
+/// Write `robot` as a URDF document. Floats use Rust's shortest round-trip
+/// formatting; orientations are written as fixed-axis roll–pitch–yaw.
+pub fn to_urdf(robot: &RobotDescription) -> String {
+    let mut out = String::new();
+    // Writing to a String cannot fail.
+    let _ = write_robot(&mut out, robot);
+    out
+}
+
+fn write_robot(out: &mut String, robot: &RobotDescription) -> std::fmt::Result {
+    writeln!(out, r#"<?xml version="1.0"?>"#)?;
+    writeln!(out, r#"<robot name="{}">"#, escape(robot.name()))?;
+    for link in robot.links() {
+        write_link(out, link)?;
+    }
+    for joint in robot.joints() {
+        write_joint(out, joint)?;
+    }
+    writeln!(out, "</robot>")
+}
+
+fn write_link(out: &mut String, link: &Link) -> std::fmt::Result {
+    let Some(inertial) = &link.inertial else {
+        return writeln!(out, r#"  <link name="{}"/>"#, escape(&link.name));
+    };
+    let [x, y, z] = inertial.center_of_mass;
+    let i = inertial.inertia;
+    writeln!(out, r#"  <link name="{}">"#, escape(&link.name))?;
+    writeln!(out, "    <inertial>")?;
+    writeln!(out, r#"      <origin xyz="{x} {y} {z}" rpy="0 0 0"/>"#)?;
+    writeln!(out, r#"      <mass value="{}"/>"#, inertial.mass)?;
+    writeln!(
+        out,
+        r#"      <inertia ixx="{}" ixy="{}" ixz="{}" iyy="{}" iyz="{}" izz="{}"/>"#,
+        i[0][0], i[0][1], i[0][2], i[1][1], i[1][2], i[2][2]
+    )?;
+    writeln!(out, "    </inertial>")?;
+    writeln!(out, "  </link>")
+}
+
+fn write_joint(out: &mut String, joint: &Joint) -> std::fmt::Result {
+    let kind = match (&joint.kind, joint.limits) {
+        (JointKind::Fixed, _) => "fixed",
+        (JointKind::Revolute { .. }, Some(_)) => "revolute",
+        (JointKind::Revolute { .. } | JointKind::Continuous { .. }, _) => "continuous",
+        (JointKind::Prismatic { .. }, _) => "prismatic",
+    };
+    let [x, y, z] = joint.origin.translation();
+    let (roll, pitch, yaw) = joint.origin.rotation().to_euler_angles();
+    writeln!(
+        out,
+        r#"  <joint name="{}" type="{kind}">"#,
+        escape(&joint.name)
+    )?;
+    writeln!(out, r#"    <parent link="{}"/>"#, escape(&joint.parent))?;
+    writeln!(out, r#"    <child link="{}"/>"#, escape(&joint.child))?;
+    writeln!(
+        out,
+        r#"    <origin xyz="{x} {y} {z}" rpy="{roll} {pitch} {yaw}"/>"#
+    )?;
+    if let Some([ax, ay, az]) = joint.kind.axis() {
+        writeln!(out, r#"    <axis xyz="{ax} {ay} {az}"/>"#)?;
+    }
+    let limits = match (kind, joint.limits) {
+        ("revolute" | "prismatic", Some(limits)) => Some(limits),
+        ("prismatic", None) => Some(JointLimits {
+            lower: -UNBOUNDED,
+            upper: UNBOUNDED,
+            velocity: None,
+            effort: None,
+        }),
+        _ => None,
+    };
+    if let Some(limits) = limits {
+        writeln!(
+            out,
+            r#"    <limit lower="{}" upper="{}" effort="{}" velocity="{}"/>"#,
+            limits.lower,
+            limits.upper,
+            limits.effort.unwrap_or(0.0),
+            limits.velocity.unwrap_or(0.0)
+        )?;
+    }
+    writeln!(out, "  </joint>")
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Parse a URDF document into a validated [`RobotDescription`].
+///
+/// `fixed`, `revolute`, `continuous` and `prismatic` joints are supported;
+/// `floating` and `planar` joints are rejected. Mimic, dynamics, safety and
+/// visual/collision elements are ignored.
+pub fn parse(text: &str) -> Result<RobotDescription> {
+    let document = xml::parse(text)?;
+    let root = document.root_element();
+    if !root.has_tag_name("robot") {
+        return Err(invalid("root element must be <robot>"));
+    }
+    let name = root.attribute("name").unwrap_or("robot");
+    let links = children(root, "link")
+        .map(|link| {
+            let name = link
+                .attribute("name")
+                .ok_or_else(|| invalid("<link> requires a name"))?;
+            Ok(Link::new(name, inertial(link)?))
+        })
+        .collect::<Result<_>>()?;
+    let joints = children(root, "joint").map(joint).collect::<Result<_>>()?;
+    RobotDescription::new(name, links, joints)
+}
+
+/// The `<origin>` child of `node`, or the identity.
+fn origin(node: Node) -> Result<Se3> {
+    let Some(origin) = child(node, "origin") else {
+        return Ok(Se3::identity());
+    };
+    let translation = attribute::<3>(origin, "xyz")?.unwrap_or([0.0; 3]);
+    let [roll, pitch, yaw] = attribute::<3>(origin, "rpy")?.unwrap_or([0.0; 3]);
+    Ok(Se3::from_parts(
+        So3::from_euler_angles(roll, pitch, yaw),
+        translation,
+    ))
+}
+
+fn link_attribute<'a>(joint: Node<'a, '_>, tag: &'static str) -> Result<&'a str> {
+    child(joint, tag)
+        .and_then(|element| element.attribute("link"))
+        .ok_or_else(|| invalid(format!("<joint> requires <{tag} link=...>")))
+}
+
+fn joint(node: Node) -> Result<Joint> {
+    let name = node
+        .attribute("name")
+        .ok_or_else(|| invalid("<joint> requires a name"))?;
+    let kind = node
+        .attribute("type")
+        .ok_or_else(|| invalid("<joint> requires a type"))?;
+    let axis = || -> Result<[f64; 3]> {
+        let axis = child(node, "axis")
+            .map(|axis| attribute::<3>(axis, "xyz"))
+            .transpose()?
+            .flatten()
+            .unwrap_or([1.0, 0.0, 0.0]);
+        let axis = Vector3::from(axis);
+        if axis.norm() == 0.0 {
+            return Err(invalid("joint axis has zero length"));
+        }
+        Ok(axis.normalize().into())
+    };
+    let kind = match kind {
+        "fixed" => JointKind::Fixed,
+        "revolute" => JointKind::Revolute { axis: axis()? },
+        "continuous" => JointKind::Continuous { axis: axis()? },
+        "prismatic" => JointKind::Prismatic { axis: axis()? },
+        other => return Err(invalid(format!("joint type '{other}' is not supported"))),
+    };
+    let mut joint = Joint::new(
+        name,
+        link_attribute(node, "parent")?,
+        link_attribute(node, "child")?,
+        origin(node)?,
+        kind,
+    );
+    if matches!(
+        joint.kind,
+        JointKind::Revolute { .. } | JointKind::Prismatic { .. }
+    ) && let Some(limit) = child(node, "limit")
+    {
+        let value = |name| attribute::<1>(limit, name).map(|v| v.map(|[v]| v));
+        let specified = |value: Option<f64>| value.filter(|v| *v != 0.0);
+        let limits = JointLimits {
+            lower: value("lower")?.unwrap_or(0.0),
+            upper: value("upper")?.unwrap_or(0.0),
+            velocity: specified(value("velocity")?),
+            effort: specified(value("effort")?),
+        };
+        let unbounded = limits.lower <= -UNBOUNDED
+            && limits.upper >= UNBOUNDED
+            && limits.velocity.is_none()
+            && limits.effort.is_none();
+        joint.limits = (!unbounded).then_some(limits);
+    }
+    Ok(joint)
+}
+
+fn inertial(link: Node) -> Result<Option<Inertial>> {
+    let Some(inertial) = child(link, "inertial") else {
+        return Ok(None);
+    };
+    let mass = child(inertial, "mass")
+        .map(|mass| attribute::<1>(mass, "value"))
+        .transpose()?
+        .flatten()
+        .ok_or_else(|| invalid("<inertial> requires <mass value=...>"))?[0];
+    let moments = match child(inertial, "inertia") {
+        Some(inertia) => {
+            let value = |name| attribute::<1>(inertia, name).map(|v| v.map_or(0.0, |[v]| v));
+            let (xx, yy, zz) = (value("ixx")?, value("iyy")?, value("izz")?);
+            let (xy, xz, yz) = (value("ixy")?, value("ixz")?, value("iyz")?);
+            Matrix3::new(xx, xy, xz, xy, yy, yz, xz, yz, zz)
+        }
+        None => Matrix3::zeros(),
+    };
+    let frame = origin(inertial)?;
+    let rotation = frame.rotation().rotation().matrix();
+    let inertia = rotation * moments * rotation.transpose();
+    Ok(Some(Inertial {
+        mass,
+        center_of_mass: frame.translation(),
+        inertia: std::array::from_fn(|r| std::array::from_fn(|c| inertia[(r, c)])),
+    }))
+}
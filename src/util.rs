@@ -1,5 +1,7 @@
 use nalgebra::{Matrix3, Vector3};
 
+use crate::error::{Error, Result};
+
 pub fn vector3_from_array(values: [f64; 3]) -> Vector3<f64> {
     Vector3::new(values[0], values[1], values[2])
 }
@@ -13,3 +15,103 @@ pub fn skew_symmetric(vector: &Vector3<f64>) -> Matrix3<f64> {
         0.0, -vector.z, vector.y, vector.z, 0.0, -vector.x, -vector.y, vector.x, 0.0,
     )
 }
+
+/// Small deterministic generator so seeded results are reproducible without
+/// an extra dependency.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in \([0, 1)\) from the top 53 bits.
+    pub(crate) fn uniform(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box–Muller).
+    pub(crate) fn gaussian(&mut self) -> f64 {
+        let radius = (-2.0 * (1.0 - self.uniform()).ln()).sqrt();
+        radius * (std::f64::consts::TAU * self.uniform()).cos()
+    }
+}
+
+/// Little-endian cursor over binary data, reporting truncation as
+/// [`Error::InvalidEncoding`].
+#[derive(Debug)]
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.offset == self.bytes.len()
+    }
+
+    pub(crate) fn take(&mut self, count: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(count)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| Error::InvalidEncoding(format!("truncated at byte {}", self.offset)))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    /// UTF-8 string prefixed by its `u32` byte length.
+    pub(crate) fn string(&mut self) -> Result<String> {
+        let length = self.u32()? as usize;
+        String::from_utf8(self.take(length)?.to_vec())
+            .map_err(|_| Error::InvalidEncoding("string is not UTF-8".to_string()))
+    }
+}
+
+/// Append `value` as a UTF-8 string prefixed by its `u32` byte length.
+///
+/// # Panics
+/// Panics if `value` is longer than `u32::MAX` bytes.
+pub(crate) fn put_string(buffer: &mut Vec<u8>, value: &str) {
+    let length = u32::try_from(value.len()).expect("string longer than u32::MAX bytes");
+    buffer.extend_from_slice(&length.to_le_bytes());
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+/// Append `values` little-endian, writing `-0.0` as `0.0` so equal numbers
+/// encode to equal bytes.
+pub(crate) fn put_f64s(buffer: &mut Vec<u8>, values: &[f64]) {
+    for &value in values {
+        let value = if value == 0.0 { 0.0 } else { value };
+        buffer.extend_from_slice(&value.to_le_bytes());
+    }
+}
@@ -0,0 +1,64 @@
+//! Attribute helpers shared by the XML robot-description readers.
+
+use roxmltree::Node;
+
+use crate::error::{Error, Result};
+
+pub(crate) fn invalid(reason: impl Into<String>) -> Error {
+    Error::InvalidDescription(reason.into())
+}
+
+/// Parse `text` into a document, reporting syntax errors as
+/// [`Error::InvalidDescription`].
+pub(crate) fn parse(text: &str) -> Result<roxmltree::Document<'_>> {
+    roxmltree::Document::parse(text).map_err(|err| invalid(err.to_string()))
+}
+
+/// Element children of `node` with the given tag name.
+pub(crate) fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.is_element() && child.has_tag_name(tag))
+}
+
+/// The first element child of `node` with the given tag name.
+pub(crate) fn child<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> Option<Node<'a, 'input>> {
+    children(node, tag).next()
+}
+
+/// Whitespace-separated finite floats, which must number exactly `N`.
+pub(crate) fn floats<const N: usize>(text: &str, what: &str) -> Result<[f64; N]> {
+    let values: Vec<f64> = text
+        .split_whitespace()
+        .map(|token| {
+            token
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| invalid(format!("{what}: '{token}' is not a finite number")))
+        })
+        .collect::<Result<_>>()?;
+    values.try_into().map_err(|values: Vec<f64>| {
+        invalid(format!(
+            "{what}: expected {N} values, found {}",
+            values.len()
+        ))
+    })
+}
+
+/// The optional attribute `name` of `node` parsed as `N` floats.
+pub(crate) fn attribute<const N: usize>(node: Node, name: &str) -> Result<Option<[f64; N]>> {
+    node.attribute(name)
+        .map(|text| {
+            floats(
+                text,
+                &format!("<{}> attribute '{name}'", node.tag_name().name()),
+            )
+        })
+        .transpose()
+}
@@ -0,0 +1,138 @@
+use mathroborust::allocation::{Actuator, Allocation};
+use mathroborust::{Error, RustSe3};
+
+const ARM: f64 = 0.2;
+const DRAG: f64 = 0.05;
+
+fn quadrotor() -> Allocation {
+    let rotors = [
+        ([ARM, ARM, 0.0], DRAG),
+        ([-ARM, ARM, 0.0], -DRAG),
+        ([-ARM, -ARM, 0.0], DRAG),
+        ([ARM, -ARM, 0.0], -DRAG),
+    ];
+    let actuators: Vec<Actuator> = rotors
+        .iter()
+        .map(|&(position, k)| {
+            Actuator::new(
+                RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, position),
+                [0.0, 0.0, 2.0],
+                k,
+            )
+            .unwrap()
+        })
+        .collect();
+    Allocation::new(&actuators)
+}
+
+fn assert_wrench(allocation: &Allocation, thrusts: &[f64], expected: [f64; 6], tol: f64) {
+    let wrench = allocation.wrench(thrusts).unwrap();
+    for (a, b) in wrench.iter().zip(expected) {
+        assert!((a - b).abs() < tol, "expected {expected:?}, got {wrench:?}");
+    }
+}
+
+#[test]
+fn actuator_columns_use_moment_arm_and_reaction_torque() {
+    let allocation = quadrotor();
+    assert_eq!(allocation.len(), 4);
+    let column: Vec<f64> = allocation.matrix().column(0).iter().copied().collect();
+    let expected = [ARM, -ARM, DRAG, 0.0, 0.0, 1.0];
+    for (a, b) in column.iter().zip(expected) {
+        assert!((a - b).abs() < 1e-15);
+    }
+
+    // A tilted thruster: the co-adjoint rotates the force and adds r × f.
+    let thruster = Actuator::new(
+        RustSe3::from_axis_angle_translation(
+            [0.0, 1.0, 0.0],
+            std::f64::consts::FRAC_PI_2,
+            [0.0, 1.0, 0.0],
+        ),
+        [0.0, 0.0, 1.0],
+        0.0,
+    )
+    .unwrap();
+    let wrench = thruster.unit_wrench();
+    let expected = [0.0, 0.0, -1.0, 1.0, 0.0, 0.0];
+    for (a, b) in wrench.iter().zip(expected) {
+        assert!((a - b).abs() < 1e-15);
+    }
+
+    let mount = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [0.0; 3]);
+    assert_eq!(
+        Actuator::new(mount.clone(), [0.0; 3], 0.0),
+        Err(Error::ZeroNorm)
+    );
+    assert_eq!(
+        Actuator::new(mount, [f64::NAN, 0.0, 1.0], 0.0),
+        Err(Error::NonFinite)
+    );
+}
+
+#[test]
+fn least_squares_allocation_reproduces_wrench() {
+    let allocation = quadrotor();
+    let hover = [0.0, 0.0, 0.0, 0.0, 0.0, 9.81];
+    let thrusts = allocation.solve_least_squares(hover);
+    for thrust in &thrusts {
+        assert!((thrust - 9.81 / 4.0).abs() < 1e-12);
+    }
+
+    let manoeuvre = [0.1, -0.05, 0.02, 0.0, 0.0, 12.0];
+    let thrusts = allocation.solve_least_squares(manoeuvre);
+    assert_wrench(&allocation, &thrusts, manoeuvre, 1e-12);
+
+    // Lateral force is unreachable for a planar quadrotor: the closest
+    // wrench drops it.
+    let thrusts = allocation.solve_least_squares([0.0, 0.0, 0.0, 1.0, 0.0, 10.0]);
+    assert_wrench(
+        &allocation,
+        &thrusts,
+        [0.0, 0.0, 0.0, 0.0, 0.0, 10.0],
+        1e-12,
+    );
+}
+
+#[test]
+fn bounded_allocation_respects_limits() {
+    let allocation = quadrotor();
+    let min = [0.0; 4];
+    let max = [5.0; 4];
+
+    let feasible = [0.1, -0.05, 0.02, 0.0, 0.0, 12.0];
+    let thrusts = allocation.solve_bounded(feasible, &min, &max).unwrap();
+    assert_wrench(&allocation, &thrusts, feasible, 1e-6);
+
+    // A yaw torque beyond what the rotors can deliver saturates them instead
+    // of commanding negative thrust.
+    let aggressive = [0.0, 0.0, 0.5, 0.0, 0.0, 10.0];
+    let unconstrained = allocation.solve_least_squares(aggressive);
+    assert!(unconstrained.iter().any(|u| *u < 0.0));
+    let thrusts = allocation.solve_bounded(aggressive, &min, &max).unwrap();
+    assert!(thrusts.iter().all(|u| (0.0..=5.0).contains(u)));
+    let wrench = allocation.wrench(&thrusts).unwrap();
+    assert!(wrench[2] > 0.0 && wrench[2] < 0.5);
+
+    // Inverted or NaN limits are rejected instead of panicking in `clamp`.
+    for bad in [[0.0, 0.0, 6.0, 0.0], [0.0, f64::NAN, 0.0, 0.0]] {
+        let err = allocation.solve_bounded(feasible, &bad, &max).unwrap_err();
+        assert_eq!(err.code(), 13);
+    }
+
+    // Limits or thrusts for the wrong number of actuators are errors too.
+    let mismatch = Error::DimensionMismatch {
+        expected: 4,
+        provided: 3,
+    };
+    let short = [0.0; 3];
+    assert_eq!(
+        allocation.solve_bounded(feasible, &short, &max),
+        Err(mismatch.clone())
+    );
+    assert_eq!(
+        allocation.solve_bounded(feasible, &min, &short),
+        Err(mismatch.clone())
+    );
+    assert_eq!(allocation.wrench(&short), Err(mismatch));
+}
@@ -0,0 +1,76 @@
+use mathroborust::averaging::{geodesic_median, karcher_mean};
+use mathroborust::error::Error;
+use mathroborust::so3::So3;
+
+fn distance(a: &So3, b: &So3) -> f64 {
+    let [x, y, z] = a.inverse().compose(b).to_rotation_vector();
+    (x * x + y * y + z * z).sqrt()
+}
+
+fn perturbed(base: &So3, offsets: &[[f64; 3]]) -> Vec<So3> {
+    offsets
+        .iter()
+        .map(|&offset| base.compose(&So3::from_rotation_vector(offset)))
+        .collect()
+}
+
+#[test]
+fn karcher_mean_of_symmetric_offsets_is_the_centre() {
+    let centre = So3::from_euler_angles(0.4, -0.2, 1.1);
+    let rotations = perturbed(
+        &centre,
+        &[
+            [0.1, 0.0, 0.0],
+            [-0.1, 0.0, 0.0],
+            [0.0, 0.2, 0.0],
+            [0.0, -0.2, 0.0],
+        ],
+    );
+    let mean = karcher_mean(&rotations).unwrap();
+    assert!(distance(&mean, &centre) < 1e-9);
+    let median = geodesic_median(&rotations).unwrap();
+    assert!(distance(&median, &centre) < 1e-6);
+
+    assert!(distance(&karcher_mean(&rotations[..1]).unwrap(), &rotations[0]) < 1e-15);
+}
+
+#[test]
+fn geodesic_median_resists_outliers() {
+    let truth = So3::from_euler_angles(-0.3, 0.5, 2.0);
+    let mut rotations = perturbed(
+        &truth,
+        &[
+            [0.01, -0.005, 0.0],
+            [-0.008, 0.002, 0.004],
+            [0.003, 0.009, -0.006],
+            [-0.004, -0.007, 0.002],
+            [0.0, 0.003, 0.008],
+            [0.006, -0.002, -0.009],
+            [-0.002, 0.006, 0.001],
+        ],
+    );
+    // Flipped markers: large, consistent errors.
+    rotations.extend(perturbed(
+        &truth,
+        &[[2.5, 0.0, 0.0], [2.4, 0.3, 0.0], [0.0, 0.0, 2.9]],
+    ));
+
+    let mean = karcher_mean(&rotations).unwrap();
+    let median = geodesic_median(&rotations).unwrap();
+    assert!(distance(&mean, &truth) > 0.3);
+    assert!(
+        distance(&median, &truth) < 0.02,
+        "{}",
+        distance(&median, &truth)
+    );
+}
+
+#[test]
+fn empty_input_is_rejected() {
+    let expected = Error::InsufficientData {
+        required: 1,
+        provided: 0,
+    };
+    assert_eq!(karcher_mean(&[]), Err(expected.clone()));
+    assert_eq!(geodesic_median(&[]).unwrap_err().code(), 15);
+}
@@ -0,0 +1,97 @@
+use mathroborust::Error;
+use mathroborust::binary::BinaryCodec;
+use mathroborust::frame::FrameTree;
+use mathroborust::lie::LieGroup;
+use mathroborust::se3::Se3;
+use mathroborust::so3::So3;
+use mathroborust::trajectory::{TimedPose, Trajectory};
+
+fn pose(yaw: f64, translation: [f64; 3]) -> Se3 {
+    Se3::from_axis_angle_translation([0.3, -0.2, 1.0], yaw, translation)
+}
+
+fn close(a: &Se3, b: &Se3, tol: f64) -> bool {
+    let (a, b) = (a.to_matrix(), b.to_matrix());
+    (0..4).all(|r| (0..4).all(|c| (a[r][c] - b[r][c]).abs() < tol))
+}
+
+#[test]
+fn poses_and_trajectories_round_trip_compactly() {
+    let original = pose(2.9, [1.5, -2.0, 0.25]);
+    let bytes = original.to_bytes();
+    assert_eq!(bytes.len(), 5 + 56);
+    assert_eq!(&bytes[..5], b"MRB\x01\x01");
+    let decoded = Se3::from_bytes(&bytes).unwrap();
+    assert!(close(&decoded, &original, 1e-15));
+    // Deterministic: re-encoding the decoded pose gives the same bytes.
+    assert_eq!(decoded.to_bytes(), bytes);
+    // Signed zeros are equal, so they encode alike.
+    assert_eq!(
+        Se3::from_parts(So3::identity(), [-0.0, 0.0, -0.0]).to_bytes(),
+        Se3::identity().to_bytes()
+    );
+
+    let sample = TimedPose::new(12.5, original.clone());
+    let decoded = TimedPose::from_bytes(&sample.to_bytes()).unwrap();
+    assert_eq!(decoded.time, 12.5);
+
+    let trajectory = Trajectory::from_poses(
+        &[0.0, 0.5, 1.0],
+        &[pose(0.0, [0.0; 3]), pose(0.4, [1.0, 0.0, 0.0]), original],
+    )
+    .unwrap();
+    let bytes = trajectory.to_bytes();
+    assert_eq!(bytes.len(), 5 + 4 + 3 * 64);
+    let decoded = Trajectory::from_bytes(&bytes).unwrap();
+    for (a, b) in decoded.samples().iter().zip(trajectory.samples()) {
+        assert_eq!(a.time, b.time);
+        assert!(close(&a.pose, &b.pose, 1e-15));
+    }
+}
+
+#[test]
+fn frame_tree_snapshots_round_trip() {
+    let mut tree = FrameTree::with_tolerance(1e-4, 1e-3);
+    tree.set_transform("map", "base", pose(0.5, [1.0, 2.0, 0.0]))
+        .unwrap();
+    tree.set_transform("base", "lidar", pose(-1.0, [0.2, 0.0, 0.6]))
+        .unwrap();
+    tree.set_transform("base", "camera", pose(0.1, [0.1, 0.1, 0.4]))
+        .unwrap();
+    let lidar_camera = tree.transform("lidar", "camera").unwrap();
+    tree.set_transform("lidar", "camera", lidar_camera).unwrap();
+    tree.set_transform("odom", "odom", Se3::identity()).unwrap();
+
+    let bytes = tree.to_bytes();
+    let decoded = FrameTree::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        decoded.frames().collect::<Vec<_>>(),
+        tree.frames().collect::<Vec<_>>()
+    );
+    assert!(close(
+        &decoded.transform("map", "camera").unwrap(),
+        &tree.transform("map", "camera").unwrap(),
+        1e-14
+    ));
+    assert!(decoded.transform("map", "odom").is_err());
+    assert_eq!(decoded.to_bytes(), bytes);
+}
+
+#[test]
+fn malformed_messages_are_rejected() {
+    let bytes = pose(0.3, [1.0, 0.0, 0.0]).to_bytes();
+    let invalid = |result: Result<Se3, Error>| matches!(result, Err(Error::InvalidEncoding(_)));
+    assert!(invalid(Se3::from_bytes(&bytes[..bytes.len() - 1])));
+    assert!(invalid(Se3::from_bytes(&[bytes.as_slice(), &[0]].concat())));
+    let mut version = bytes.clone();
+    version[3] = 2;
+    assert!(invalid(Se3::from_bytes(&version)));
+    // A pose is not a trajectory.
+    assert!(matches!(
+        Trajectory::from_bytes(&bytes),
+        Err(Error::InvalidEncoding(_))
+    ));
+    let mut zero = bytes.clone();
+    zero[5..37].fill(0);
+    assert!(invalid(Se3::from_bytes(&zero)));
+}
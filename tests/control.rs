@@ -0,0 +1,110 @@
+use mathroborust::RustSe3;
+use mathroborust::control::{Admittance, AdmittanceParams};
+use mathroborust::lie::LieGroup;
+
+fn approx_eq(a: &[f64], b: &[f64], tol: f64) {
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < tol, "expected {b:?}, got {a:?}");
+    }
+}
+
+fn params() -> AdmittanceParams {
+    AdmittanceParams::uniform([0.5, 4.0, 10.0], [2.0, 40.0, 100.0])
+}
+
+#[test]
+fn admittance_settles_at_compliant_offset_under_constant_wrench() {
+    let mut admittance = Admittance::new(params(), RustSe3::identity()).unwrap();
+    let wrench = [0.0, 0.0, 1.0, 2.0, 0.0, -5.0];
+    for _ in 0..20000 {
+        admittance.step(wrench, 1e-3);
+    }
+
+    // At equilibrium K x = F on every axis.
+    approx_eq(
+        &admittance.displacement(),
+        &[0.0, 0.0, 0.1, 0.02, 0.0, -0.05],
+        1e-6,
+    );
+    approx_eq(&admittance.twist(), &[0.0; 6], 1e-6);
+
+    let rotation = admittance.pose().rotation().to_rotation_vector();
+    approx_eq(&rotation, &[0.0, 0.0, 0.1], 1e-6);
+}
+
+#[test]
+fn admittance_returns_to_reference_when_released() {
+    let reference = RustSe3::from_axis_angle_translation([1.0, 1.0, 0.0], 0.4, [0.2, -0.1, 0.5]);
+    let mut admittance = Admittance::new(params(), reference.clone()).unwrap();
+    let displaced = reference.compose(&RustSe3::from_axis_angle_translation(
+        [0.0, 1.0, 0.0],
+        0.3,
+        [0.05, 0.0, 0.02],
+    ));
+    admittance.set_state(displaced, [0.1, 0.0, 0.0, 0.0, 0.2, 0.0]);
+
+    for _ in 0..20000 {
+        admittance.step([0.0; 6], 1e-3);
+    }
+
+    approx_eq(&admittance.displacement(), &[0.0; 6], 1e-8);
+    approx_eq(
+        &admittance.pose().to_row_major_flat(),
+        &reference.to_row_major_flat(),
+        1e-8,
+    );
+    assert!(admittance.pose().health_check().is_within(1e-9));
+}
+
+#[test]
+fn admittance_without_stiffness_moves_freely() {
+    let params = AdmittanceParams {
+        mass: [1.0; 6],
+        damping: [0.0; 6],
+        stiffness: [0.0; 6],
+    };
+    let mut admittance = Admittance::new(params, RustSe3::identity()).unwrap();
+    let dt = 1e-3;
+    for _ in 0..1000 {
+        admittance.step([0.0, 0.0, 0.0, 1.0, 0.0, 0.0], dt);
+    }
+    approx_eq(&admittance.twist(), &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0], 1e-12);
+    // Semi-implicit Euler: x = dt² · n(n + 1) / 2.
+    approx_eq(&admittance.pose().translation(), &[0.5005, 0.0, 0.0], 1e-12);
+
+    admittance.reset();
+    assert_eq!(admittance.pose(), &RustSe3::identity());
+    assert_eq!(admittance.twist(), [0.0; 6]);
+}
+
+#[test]
+fn admittance_rejects_invalid_params() {
+    let valid = params();
+    for (axis, bad) in [(0, 0.0), (3, -1.0), (5, f64::NAN), (1, f64::INFINITY)] {
+        let mut params = valid;
+        params.mass[axis] = bad;
+        let err = Admittance::new(params, RustSe3::identity()).unwrap_err();
+        assert_eq!(err.code(), 17, "mass {bad}");
+    }
+    for bad in [-1.0, f64::NAN, f64::INFINITY] {
+        let mut params = valid;
+        params.damping[2] = bad;
+        assert!(
+            Admittance::new(params, RustSe3::identity()).is_err(),
+            "damping {bad}"
+        );
+        let mut params = valid;
+        params.stiffness[4] = bad;
+        assert!(
+            Admittance::new(params, RustSe3::identity()).is_err(),
+            "stiffness {bad}"
+        );
+    }
+
+    // Rejected updates keep the previous parameters.
+    let mut admittance = Admittance::new(valid, RustSe3::identity()).unwrap();
+    let mut params = valid;
+    params.mass[0] = 0.0;
+    assert!(admittance.set_params(params).is_err());
+    assert_eq!(admittance.params(), &valid);
+}
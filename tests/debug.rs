@@ -0,0 +1,108 @@
+use mathroborust::debug::{dump_scene, dump_scene_with_trajectories};
+use mathroborust::frame::FrameTree;
+use mathroborust::se3::Se3;
+use mathroborust::so3::So3;
+use mathroborust::trajectory::Trajectory;
+use serde_json::Value;
+
+fn pose(yaw: f64, translation: [f64; 3]) -> Se3 {
+    Se3::from_axis_angle_translation([0.2, -0.4, 1.0], yaw, translation)
+}
+
+fn numbers(value: &Value) -> Vec<f64> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect()
+}
+
+fn parse_pose(value: &Value) -> Se3 {
+    let t = numbers(&value["translation"]);
+    let q = numbers(&value["rotation"]);
+    Se3::from_parts(
+        So3::from_quaternion([q[0], q[1], q[2], q[3]]),
+        [t[0], t[1], t[2]],
+    )
+}
+
+fn close(a: &Se3, b: &Se3, tol: f64) -> bool {
+    let (a, b) = (a.to_matrix(), b.to_matrix());
+    (0..4).all(|r| (0..4).all(|c| (a[r][c] - b[r][c]).abs() < tol))
+}
+
+#[test]
+fn scene_dump_round_trips_a_small_tree() {
+    let mut tree = FrameTree::new();
+    tree.set_transform("map", "base", pose(0.4, [1.0, 2.0, 0.0]))
+        .unwrap();
+    tree.set_transform("base", "lidar \"top\"", pose(-1.2, [0.1, 0.0, 0.5]))
+        .unwrap();
+    tree.set_transform("odom", "imu", pose(2.0, [0.0, -0.3, 0.1]))
+        .unwrap();
+
+    let scene: Value = serde_json::from_str(&dump_scene(&tree)).unwrap();
+    let mut rebuilt = FrameTree::new();
+    for edge in scene["edges"].as_array().unwrap() {
+        rebuilt
+            .set_transform(
+                edge["parent"].as_str().unwrap(),
+                edge["child"].as_str().unwrap(),
+                parse_pose(edge),
+            )
+            .unwrap();
+    }
+    for (target, source) in [
+        ("map", "lidar \"top\""),
+        ("odom", "imu"),
+        ("lidar \"top\"", "base"),
+    ] {
+        assert!(close(
+            &rebuilt.transform(target, source).unwrap(),
+            &tree.transform(target, source).unwrap(),
+            1e-15
+        ));
+    }
+    assert!(rebuilt.transform("map", "imu").is_err());
+
+    let frames = scene["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 5);
+    let lidar = &frames[2];
+    assert_eq!(lidar["name"], "lidar \"top\"");
+    assert_eq!(lidar["root"], "map");
+    let map_from_lidar = tree.transform("map", "lidar \"top\"").unwrap();
+    assert!(close(&parse_pose(lidar), &map_from_lidar, 1e-15));
+    let rotation = map_from_lidar.rotation().to_matrix();
+    for (axis, direction) in lidar["axes"].as_array().unwrap().iter().enumerate() {
+        let direction = numbers(direction);
+        for row in 0..3 {
+            assert!((direction[row] - rotation[row][axis]).abs() < 1e-15);
+        }
+    }
+    assert_eq!(frames[4]["root"], "odom");
+    assert_eq!(scene["trajectories"], Value::Array(Vec::new()));
+}
+
+#[test]
+fn scene_dump_includes_named_trajectories() {
+    let tree = FrameTree::new();
+    let path = Trajectory::from_poses(
+        &[0.0, 0.5],
+        &[pose(0.0, [0.0; 3]), pose(0.3, [1.0, 0.0, 0.0])],
+    )
+    .unwrap();
+    let scene: Value =
+        serde_json::from_str(&dump_scene_with_trajectories(&tree, &[("plan", &path)])).unwrap();
+    assert_eq!(scene["frames"], Value::Array(Vec::new()));
+    let trajectory = &scene["trajectories"][0];
+    assert_eq!(trajectory["name"], "plan");
+    let samples = trajectory["samples"].as_array().unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[1]["time"], 0.5);
+    assert!(close(
+        &parse_pose(&samples[1]),
+        &pose(0.3, [1.0, 0.0, 0.0]),
+        1e-15
+    ));
+}
@@ -0,0 +1,124 @@
+use mathroborust::directional::MatrixFisher;
+use mathroborust::{Error, RustSo3};
+use nalgebra::Matrix3;
+
+fn parameter() -> Matrix3<f64> {
+    let rotation = RustSo3::from_euler_angles(0.4, -0.2, 1.1);
+    let other = RustSo3::from_euler_angles(-0.3, 0.5, 0.2);
+    rotation.rotation().matrix()
+        * Matrix3::from_diagonal(&[12.0, 6.0, 2.0].into())
+        * other.rotation().matrix().transpose()
+}
+
+/// \(\log \int e^{\kappa (1 + 2\cos\theta)} (1 - \cos\theta) / \pi\,d\theta\),
+/// the normalizing constant of \(F = \kappa I\) from the Haar density of the
+/// rotation angle.
+fn isotropic_log_constant(kappa: f64) -> f64 {
+    let n = 20_000;
+    let h = std::f64::consts::PI / n as f64;
+    let sum: f64 = (0..=n)
+        .map(|k| {
+            let theta = k as f64 * h;
+            let weight = if k == 0 || k == n {
+                1.0
+            } else if k % 2 == 1 {
+                4.0
+            } else {
+                2.0
+            };
+            weight * (kappa * (2.0 * theta.cos() - 2.0)).exp() * (1.0 - theta.cos())
+                / std::f64::consts::PI
+        })
+        .sum();
+    3.0 * kappa + (sum * h / 3.0).ln()
+}
+
+#[test]
+fn normalizing_constant_matches_isotropic_closed_form() {
+    assert!(MatrixFisher::uniform().log_normalizing_constant().abs() < 1e-14);
+    for kappa in [0.5, 3.0, 40.0] {
+        let distribution = MatrixFisher::new(Matrix3::identity() * kappa);
+        let expected = isotropic_log_constant(kappa);
+        let actual = distribution.log_normalizing_constant();
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{kappa}: {actual} vs {expected}"
+        );
+    }
+}
+
+#[test]
+fn mean_is_the_gradient_of_the_log_constant() {
+    let distribution = MatrixFisher::new(parameter());
+    let mean = distribution.mean();
+    let h = 1e-5;
+    for (r, c) in [(0, 0), (1, 2), (2, 0)] {
+        let step = Matrix3::from_fn(|i, j| if (i, j) == (r, c) { h } else { 0.0 });
+        let forward = MatrixFisher::new(parameter() + step).log_normalizing_constant();
+        let backward = MatrixFisher::new(parameter() - step).log_normalizing_constant();
+        assert!(((forward - backward) / (2.0 * h) - mean[(r, c)]).abs() < 1e-7);
+    }
+}
+
+#[test]
+fn samples_match_the_mean_and_refit_the_parameter() {
+    let distribution = MatrixFisher::new(parameter());
+    let samples = distribution.sample(20_000, 7);
+    let sample_mean = samples
+        .iter()
+        .map(|r| r.rotation().matrix())
+        .sum::<Matrix3<f64>>()
+        / samples.len() as f64;
+    assert!((sample_mean - distribution.mean()).amax() < 0.01);
+
+    let fitted = MatrixFisher::fit(&samples).unwrap();
+    let relative = (fitted.parameter() - parameter()).norm() / parameter().norm();
+    assert!(relative < 0.05, "relative error {relative}");
+    assert_eq!(distribution.sample(3, 7), distribution.sample(3, 7));
+}
+
+#[test]
+fn tangent_gaussian_round_trips_and_approximates_concentrated_spread() {
+    let mode = RustSo3::from_euler_angles(0.2, 0.3, -0.4);
+    let covariance = Matrix3::new(4.0, 1.0, 0.0, 1.0, 3.0, -0.5, 0.0, -0.5, 2.0) * 1e-4;
+    let distribution = MatrixFisher::from_tangent_gaussian(&mode, &covariance).unwrap();
+    let (back_mode, back_covariance) = distribution.to_tangent_gaussian().unwrap();
+    assert!((back_mode.rotation().matrix() - mode.rotation().matrix()).amax() < 1e-12);
+    assert!((back_covariance - covariance).amax() < 1e-15);
+
+    let samples = distribution.sample(20_000, 3);
+    let spread = samples
+        .iter()
+        .map(|r| {
+            let xi = nalgebra::Vector3::from((mode.inverse() * r).to_rotation_vector());
+            xi * xi.transpose()
+        })
+        .sum::<Matrix3<f64>>()
+        / samples.len() as f64;
+    assert!((spread - covariance).amax() < 1e-5);
+
+    assert_eq!(
+        MatrixFisher::uniform().to_tangent_gaussian().unwrap_err(),
+        Error::Singular
+    );
+    assert_eq!(
+        MatrixFisher::from_tangent_gaussian(&mode, &Matrix3::zeros()).unwrap_err(),
+        Error::Singular
+    );
+}
+
+#[test]
+fn fit_rejects_degenerate_input() {
+    let rotation = RustSo3::from_euler_angles(0.1, 0.2, 0.3);
+    assert_eq!(
+        MatrixFisher::fit(std::slice::from_ref(&rotation)).unwrap_err(),
+        Error::InsufficientData {
+            required: 2,
+            provided: 1
+        }
+    );
+    assert_eq!(
+        MatrixFisher::fit(&[rotation.clone(), rotation]).unwrap_err(),
+        Error::Singular
+    );
+}
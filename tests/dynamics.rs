@@ -0,0 +1,302 @@
+use mathroborust::dynamics::{
+    AddedMass, MomentumObserver, RigidBodyState, SpatialInertia, base_parameter_columns,
+    body_acceleration, body_regressor, variational_step,
+};
+use mathroborust::lie::LieGroup;
+use mathroborust::{Error, RustSe3, RustSo3};
+
+fn asymmetric_body() -> SpatialInertia {
+    SpatialInertia::new(
+        2.0,
+        [0.1, -0.05, 0.2],
+        [[0.3, 0.01, 0.0], [0.01, 0.5, 0.02], [0.0, 0.02, 0.8]],
+    )
+}
+
+#[test]
+fn spatial_inertia_matches_center_of_mass_energy() {
+    let inertia = asymmetric_body();
+    let matrix = inertia.matrix();
+    assert!((matrix - matrix.transpose()).abs().max() < 1e-15);
+
+    let twist = [0.4, -0.2, 1.0, 0.3, 0.0, -0.5];
+    let omega = nalgebra::Vector3::new(twist[0], twist[1], twist[2]);
+    let v = nalgebra::Vector3::new(twist[3], twist[4], twist[5]);
+    let c = nalgebra::Vector3::from(inertia.center_of_mass());
+    let i_c = nalgebra::Matrix3::from_fn(|r, k| inertia.rotational_inertia()[r][k]);
+    let v_com = v + omega.cross(&c);
+    let expected = 0.5 * inertia.mass() * v_com.norm_squared() + 0.5 * omega.dot(&(i_c * omega));
+    assert!((inertia.kinetic_energy(twist) - expected).abs() < 1e-14);
+}
+
+#[test]
+fn variational_step_conserves_momentum_and_bounds_energy() {
+    let inertia = asymmetric_body();
+    let mut state = RigidBodyState::new(
+        RustSe3::from_axis_angle_translation([0.0, 1.0, 0.0], 0.3, [1.0, 0.0, 0.5]),
+        [0.5, 3.0, -0.4, 0.2, -0.1, 0.3],
+    );
+    let momentum = state.spatial_momentum(&inertia);
+    let energy = inertia.kinetic_energy(state.twist);
+
+    let mut max_energy_error: f64 = 0.0;
+    for k in 0..20000 {
+        state = variational_step(&inertia, &state, k as f64 * 0.01, 0.01, |_, _| [0.0; 6]).unwrap();
+        let error = (inertia.kinetic_energy(state.twist) - energy).abs() / energy;
+        max_energy_error = max_energy_error.max(error);
+    }
+
+    for (a, b) in state.spatial_momentum(&inertia).iter().zip(momentum) {
+        assert!((a - b).abs() < 1e-7, "momentum drifted: {a} vs {b}");
+    }
+    assert!(max_energy_error < 1e-3, "energy error {max_energy_error}");
+    assert!(state.pose.health_check().is_within(1e-9));
+    // A point mass has no invertible rotational inertia.
+    let point = SpatialInertia::from_principal(1.0, [0.0; 3]);
+    assert_eq!(
+        variational_step(&point, &state, 0.0, 0.01, |_, _| [0.0; 6]),
+        Err(Error::Singular)
+    );
+}
+
+#[test]
+fn variational_step_drops_center_of_mass_along_parabola() {
+    let inertia = asymmetric_body();
+    let gravity = [0.0, 0.0, -9.81];
+    let c = inertia.center_of_mass();
+    let weight = |_: f64, pose: &RustSe3| {
+        let force = pose
+            .rotation()
+            .inverse()
+            .apply(gravity.map(|g| g * inertia.mass()));
+        let torque = nalgebra::Vector3::from(c).cross(&nalgebra::Vector3::from(force));
+        [torque.x, torque.y, torque.z, force[0], force[1], force[2]]
+    };
+
+    let start = RustSe3::from_parts(RustSo3::from_rotation_vector([0.2, 0.1, 0.0]), [0.0; 3]);
+    let mut state = RigidBodyState::new(start.clone(), [0.0, 0.0, 1.0, 1.0, 0.0, 2.0]);
+    let com = |pose: &RustSe3| {
+        let [x, y, z] = pose.apply(c);
+        nalgebra::Vector3::new(x, y, z)
+    };
+    let com0 = com(&start);
+    let v_com0 = {
+        let omega = nalgebra::Vector3::new(0.0, 0.0, 1.0);
+        let v = nalgebra::Vector3::new(1.0, 0.0, 2.0);
+        nalgebra::Vector3::from(
+            start
+                .rotation()
+                .apply((v + omega.cross(&nalgebra::Vector3::from(c))).into()),
+        )
+    };
+
+    let h = 0.01;
+    for k in 0..100 {
+        state = variational_step(&inertia, &state, k as f64 * h, h, weight).unwrap();
+    }
+    let t = 1.0;
+    let expected = com0 + v_com0 * t + nalgebra::Vector3::from(gravity) * (0.5 * t * t);
+    assert!((com(&state.pose) - expected).norm() < 1e-10);
+    assert!(RustSe3::identity() != state.pose);
+}
+
+fn auv_added_mass() -> AddedMass {
+    // Prolate vehicle: little added mass along its axis, more across it.
+    AddedMass::from_fossen([
+        [5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        [0.0, 60.0, 0.0, 0.0, 0.0, 2.0],
+        [0.0, 0.0, 60.0, 0.0, -2.0, 0.0],
+        [0.0, 0.0, 0.0, 0.5, 0.0, 0.0],
+        [0.0, 0.0, -2.0, 0.0, 8.0, 0.0],
+        [0.0, 2.0, 0.0, 0.0, 0.0, 8.0],
+    ])
+}
+
+#[test]
+fn added_mass_reorders_fossen_layout_and_keeps_coriolis_skew() {
+    let added = auv_added_mass();
+    let matrix = added.matrix();
+    assert_eq!(matrix[(0, 0)], 0.5);
+    assert_eq!(matrix[(3, 3)], 5.0);
+    assert_eq!(matrix[(4, 2)], 2.0);
+    assert_eq!(matrix[(2, 4)], 2.0);
+
+    let inertia = asymmetric_body();
+    let twist = [0.1, -0.3, 0.2, 1.5, 0.2, -0.1];
+    let c = inertia.coriolis_matrix(twist) + added.coriolis_matrix(twist);
+    assert!((c + c.transpose()).abs().max() < 1e-12);
+    let v = nalgebra::Vector6::from(twist);
+    assert!(v.dot(&(c * v)).abs() < 1e-12);
+}
+
+#[test]
+fn body_acceleration_produces_munk_moment_and_conserves_energy() {
+    let inertia = SpatialInertia::from_principal(50.0, [2.0, 10.0, 10.0]);
+    let added = AddedMass::from_diagonal([0.5, 8.0, 8.0], [5.0, 60.0, 60.0]);
+
+    // Moving obliquely in the x-y plane, the larger sway added mass produces
+    // a destabilizing yaw (Munk) moment that turns the body broadside.
+    let yaw_rate = body_acceleration(
+        &inertia,
+        Some(&added),
+        [0.0, 0.0, 0.0, 1.0, 0.2, 0.0],
+        [0.0; 6],
+    )
+    .unwrap();
+    let expected = -(60.0 - 5.0) * 1.0 * 0.2 / (10.0 + 8.0);
+    assert!((yaw_rate[2] - expected).abs() < 1e-12);
+    let dry = body_acceleration(&inertia, None, [0.0, 0.0, 0.0, 1.0, 0.2, 0.0], [0.0; 6]).unwrap();
+    assert!(dry.iter().all(|a| a.abs() < 1e-12));
+    // Negative added mass exceeding the body's leaves no positive definite
+    // generalized mass.
+    let unphysical = AddedMass::from_diagonal([0.0; 3], [-60.0, 0.0, 0.0]);
+    assert_eq!(
+        body_acceleration(&inertia, Some(&unphysical), [0.0; 6], [0.0; 6]),
+        Err(Error::Singular)
+    );
+
+    // Unforced motion conserves the kinetic energy of body plus fluid.
+    let mass = inertia.matrix() + added.matrix();
+    let energy = |v: [f64; 6]| {
+        let v = nalgebra::Vector6::from(v);
+        0.5 * v.dot(&(mass * v))
+    };
+    let mut twist = [0.1, 0.3, -0.2, 1.0, 0.2, -0.1];
+    let initial = energy(twist);
+    let h = 1e-3;
+    let f = |v: [f64; 6]| body_acceleration(&inertia, Some(&added), v, [0.0; 6]).unwrap();
+    for _ in 0..2000 {
+        let k1 = f(twist);
+        let k2 = f(std::array::from_fn(|i| twist[i] + 0.5 * h * k1[i]));
+        let k3 = f(std::array::from_fn(|i| twist[i] + 0.5 * h * k2[i]));
+        let k4 = f(std::array::from_fn(|i| twist[i] + h * k3[i]));
+        twist = std::array::from_fn(|i| {
+            twist[i] + h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i])
+        });
+    }
+    assert!((energy(twist) - initial).abs() < 1e-9 * initial);
+}
+
+#[test]
+fn body_acceleration_without_added_mass_matches_variational_step() {
+    let inertia = asymmetric_body();
+    let state = RigidBodyState::new(RustSe3::identity(), [0.5, 1.0, -0.4, 0.2, -0.1, 0.3]);
+    let wrench = [0.1, 0.0, -0.2, 1.0, 0.5, 0.0];
+    let acceleration = body_acceleration(&inertia, None, state.twist, wrench).unwrap();
+
+    let h = 1e-5;
+    let next = variational_step(&inertia, &state, 0.0, h, |_, _| wrench).unwrap();
+    let previous = variational_step(&inertia, &state, 0.0, -h, |_, _| wrench).unwrap();
+    for (i, expected) in acceleration.iter().enumerate() {
+        let estimate = (next.twist[i] - previous.twist[i]) / (2.0 * h);
+        assert!((estimate - expected).abs() < 1e-5, "axis {i}");
+    }
+}
+
+#[test]
+fn momentum_observer_recovers_external_wrench() {
+    let inertia = asymmetric_body();
+    let added = auv_added_mass();
+    let mut observer = MomentumObserver::new(&inertia, Some(&added), [20.0; 6]);
+
+    let external = [0.2, -0.1, 0.05, 3.0, 0.0, -1.5];
+    let commanded = [0.0, 0.1, 0.0, 1.0, 2.0, 0.0];
+    let total: [f64; 6] = std::array::from_fn(|i| external[i] + commanded[i]);
+
+    let h = 1e-3;
+    let mut twist = [0.1, 0.0, 0.2, 0.5, 0.0, 0.0];
+    let f = |v: [f64; 6]| body_acceleration(&inertia, Some(&added), v, total).unwrap();
+    observer.update(twist, commanded, h);
+    for _ in 0..1000 {
+        let k1 = f(twist);
+        let k2 = f(std::array::from_fn(|i| twist[i] + 0.5 * h * k1[i]));
+        let k3 = f(std::array::from_fn(|i| twist[i] + 0.5 * h * k2[i]));
+        let k4 = f(std::array::from_fn(|i| twist[i] + h * k3[i]));
+        twist = std::array::from_fn(|i| {
+            twist[i] + h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i])
+        });
+        observer.update(twist, commanded, h);
+    }
+
+    for (estimate, actual) in observer.estimate().iter().zip(external) {
+        assert!(
+            (estimate - actual).abs() < 1e-2,
+            "estimate {estimate}, actual {actual}"
+        );
+    }
+    observer.reset();
+    assert_eq!(observer.estimate(), [0.0; 6]);
+}
+
+#[test]
+fn body_regressor_is_linear_in_inertial_parameters() {
+    let inertia = asymmetric_body();
+    let parameters = inertia.to_parameters();
+    let round_trip = SpatialInertia::from_parameters(parameters);
+    assert!((round_trip.matrix() - inertia.matrix()).abs().max() < 1e-14);
+
+    let twist = [0.3, -0.7, 0.2, 1.0, 0.4, -0.2];
+    let acceleration = [0.5, 0.1, -0.3, 0.0, 2.0, 1.0];
+    let regressor = body_regressor(twist, acceleration);
+    let predicted = regressor * nalgebra::SVector::<f64, 10>::from(parameters);
+
+    let expected = inertia.matrix() * nalgebra::Vector6::from(acceleration)
+        + inertia.coriolis_matrix(twist) * nalgebra::Vector6::from(twist);
+    assert!((predicted - expected).abs().max() < 1e-12);
+
+    // Consistent with the forward dynamics.
+    let wrench: [f64; 6] = expected.into();
+    let recovered = body_acceleration(&inertia, None, twist, wrench).unwrap();
+    for (a, b) in recovered.iter().zip(acceleration) {
+        assert!((a - b).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn base_parameters_follow_excitation() {
+    let samples = [
+        (
+            [0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.5, -1.0, 2.0],
+        ),
+        (
+            [0.0, 0.0, 0.0, 0.0, 1.0, 0.3],
+            [0.0, 0.0, 0.0, 1.0, 0.0, -0.5],
+        ),
+        (
+            [0.0, 0.0, 0.0, -0.2, 0.0, 1.0],
+            [0.0, 0.0, 0.0, 0.0, 0.3, 0.0],
+        ),
+    ];
+    let stack = |samples: &[([f64; 6], [f64; 6])]| {
+        let mut stacked = nalgebra::DMatrix::zeros(6 * samples.len(), 10);
+        for (k, (twist, acceleration)) in samples.iter().enumerate() {
+            stacked
+                .view_mut((6 * k, 0), (6, 10))
+                .copy_from(&body_regressor(*twist, *acceleration));
+        }
+        stacked
+    };
+
+    // Pure translation never excites the rotational inertia.
+    let translational = base_parameter_columns(&stack(&samples), 1e-9);
+    assert_eq!(translational, vec![0, 1, 2, 3]);
+
+    let mut rich = samples.to_vec();
+    rich.push((
+        [1.0, 0.5, -0.3, 0.0, 0.0, 0.0],
+        [0.2, -1.0, 0.7, 0.0, 0.0, 0.0],
+    ));
+    rich.push((
+        [0.1, -0.4, 0.9, 0.3, 0.0, 0.0],
+        [-0.6, 0.0, 0.4, 0.1, 0.0, 0.0],
+    ));
+    rich.push((
+        [0.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    ));
+    assert_eq!(
+        base_parameter_columns(&stack(&rich), 1e-9),
+        (0..10).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,127 @@
+use mathroborust::camera::CameraIntrinsics;
+use mathroborust::epipolar::{
+    decompose_essential, essential_from_pose, essential_from_poses, fundamental_from_poses,
+    relative_pose_from_essential, triangulate,
+};
+use mathroborust::error::Error;
+use mathroborust::se3::Se3;
+
+fn second_from_first() -> Se3 {
+    // Unit baseline, as recovered from an essential matrix.
+    let t = [0.6_f64, -0.1, 0.2];
+    let norm = (t[0] * t[0] + t[1] * t[1] + t[2] * t[2]).sqrt();
+    Se3::from_axis_angle_translation([0.1, 1.0, 0.2], 0.3, t.map(|x| x / norm))
+}
+
+fn scene() -> Vec<[f64; 3]> {
+    vec![
+        [-1.0, -0.5, 4.0],
+        [1.2, -0.4, 5.0],
+        [0.3, 0.9, 3.5],
+        [-0.7, 0.6, 6.0],
+        [0.8, 0.7, 4.5],
+        [0.0, -1.0, 5.5],
+    ]
+}
+
+fn normalized(point: [f64; 3]) -> [f64; 2] {
+    [point[0] / point[2], point[1] / point[2]]
+}
+
+fn views(pose: &Se3) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+    scene()
+        .into_iter()
+        .map(|p| (normalized(p), normalized(pose.apply(p))))
+        .unzip()
+}
+
+fn distance(a: &Se3, b: &Se3) -> f64 {
+    // Compare matrices: the twisted-pair candidates differ by a rotation of
+    // exactly π, where the rotation vector is ill-conditioned.
+    let (a, b) = (a.to_matrix(), b.to_matrix());
+    (0..4)
+        .flat_map(|r| (0..4).map(move |c| (r, c)))
+        .map(|(r, c)| (a[r][c] - b[r][c]).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[test]
+fn essential_matrix_satisfies_epipolar_constraint() {
+    let pose = second_from_first();
+    let essential = essential_from_pose(&pose);
+    let (first, second) = views(&pose);
+    for (a, b) in first.iter().zip(&second) {
+        let x1 = nalgebra::Vector3::new(a[0], a[1], 1.0);
+        let x2 = nalgebra::Vector3::new(b[0], b[1], 1.0);
+        assert!((x2.transpose() * essential * x1)[0].abs() < 1e-12);
+    }
+}
+
+#[test]
+fn one_decomposition_candidate_is_the_true_pose() {
+    let pose = second_from_first();
+    let candidates = decompose_essential(&(essential_from_pose(&pose) * -2.5));
+    let matches = candidates
+        .iter()
+        .filter(|candidate| distance(candidate, &pose) < 1e-9)
+        .count();
+    assert_eq!(matches, 1);
+}
+
+#[test]
+fn cheirality_selects_the_true_pose() {
+    let pose = second_from_first();
+    let (first, second) = views(&pose);
+    let recovered =
+        relative_pose_from_essential(&essential_from_pose(&pose), &first, &second).unwrap();
+    assert!(distance(&recovered, &pose) < 1e-9);
+}
+
+#[test]
+fn triangulation_recovers_scene_points() {
+    let pose = second_from_first();
+    for point in scene() {
+        let estimate =
+            triangulate(&pose, normalized(point), normalized(pose.apply(point))).unwrap();
+        for k in 0..3 {
+            assert!((estimate[k] - point[k]).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn relative_pose_requires_correspondences() {
+    let essential = essential_from_pose(&second_from_first());
+    assert!(matches!(
+        relative_pose_from_essential(&essential, &[], &[]),
+        Err(Error::InsufficientData { .. })
+    ));
+}
+
+#[test]
+fn matrices_from_world_poses_satisfy_epipolar_constraints() {
+    let world_from_first = Se3::from_axis_angle_translation([0.0, 0.2, 1.0], 0.7, [1.0, 2.0, 0.5]);
+    let world_from_second = world_from_first.compose(&second_from_first().inverse());
+    let essential = essential_from_poses(&world_from_first, &world_from_second);
+    let expected = essential_from_pose(&second_from_first());
+    assert!((essential - expected).norm() < 1e-12);
+
+    let first = CameraIntrinsics::new(500.0, 510.0, 320.0, 240.0);
+    let second = CameraIntrinsics::new(620.0, 600.0, 300.0, 250.0);
+    let fundamental =
+        fundamental_from_poses(&world_from_first, &world_from_second, &first, &second).unwrap();
+    for point in scene() {
+        let p1 = first.project(point).unwrap();
+        let p2 = second.project(second_from_first().apply(point)).unwrap();
+        let x1 = nalgebra::Vector3::new(p1[0], p1[1], 1.0);
+        let x2 = nalgebra::Vector3::new(p2[0], p2[1], 1.0);
+        assert!((x2.transpose() * fundamental * x1)[0].abs() < 1e-9);
+    }
+
+    let degenerate = CameraIntrinsics::new(0.0, 500.0, 320.0, 240.0);
+    assert!(matches!(
+        fundamental_from_poses(&world_from_first, &world_from_second, &degenerate, &second),
+        Err(Error::Singular)
+    ));
+}
@@ -0,0 +1,224 @@
+use mathroborust::estimation::{
+    MotionModel, PoseHypothesis, PoseMixture, RigidBodyTracker, chi_square_threshold, gate,
+    gate_pose, gate_rotation,
+};
+use mathroborust::lie::LieGroup;
+use mathroborust::motion::{MotionCovariance, MotionState};
+use mathroborust::{Error, RustSe3, RustSo3};
+use nalgebra::{Matrix3, Matrix6, Vector2};
+
+fn truth() -> MotionState {
+    MotionState::new(
+        RustSe3::from_parts(RustSo3::from_euler_angles(0.1, 0.2, -0.3), [1.0, 2.0, 0.5]),
+        [0.2, -0.1, 0.4, 1.0, 0.3, -0.2],
+        [0.05, 0.0, -0.1, 0.2, -0.1, 0.0],
+    )
+}
+
+fn max_difference(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn tracker_recovers_twist_and_acceleration_from_poses() {
+    let mut truth = truth();
+    let initial = MotionState::at_rest(truth.pose.clone());
+    let mut tracker = RigidBodyTracker::new(
+        initial,
+        MotionCovariance::identity() * 10.0,
+        MotionModel::ConstantAcceleration {
+            jerk_density: [1e-6; 6],
+        },
+    );
+    let noise = Matrix6::identity() * 1e-8;
+
+    let dt = 0.05;
+    for _ in 0..200 {
+        truth = truth.propagate(dt);
+        tracker.predict(dt);
+        tracker.update(&truth.pose, &noise).unwrap();
+    }
+
+    let estimate = tracker.state();
+    assert!(max_difference(&estimate.twist, &truth.twist) < 1e-3);
+    assert!(max_difference(&estimate.acceleration, &truth.acceleration) < 1e-2);
+    let pose_error = truth.pose.inverse().compose(&estimate.pose).log();
+    assert!(max_difference(&pose_error, &[0.0; 6]) < 1e-4);
+
+    let covariance = tracker.covariance();
+    assert!((covariance - covariance.transpose()).norm() < 1e-12);
+    assert!(covariance[(0, 0)] < 1e-8);
+}
+
+#[test]
+fn constant_twist_model_holds_acceleration_at_zero() {
+    let mut tracker = RigidBodyTracker::new(
+        truth(),
+        MotionCovariance::identity(),
+        MotionModel::ConstantTwist {
+            acceleration_density: [1e-3; 6],
+        },
+    );
+    assert_eq!(tracker.state().acceleration, [0.0; 6]);
+    assert_eq!(tracker.covariance()[(12, 12)], 0.0);
+
+    let before = tracker.covariance()[(6, 6)];
+    tracker.predict(0.1);
+    assert!(tracker.covariance()[(6, 6)] > before);
+    let measurement = tracker.state().pose.compose(&RustSe3::from_parts(
+        RustSo3::from_axis_angle([0.0, 0.0, 1.0], 0.01),
+        [0.02, 0.0, 0.0],
+    ));
+    let innovation = tracker
+        .update(&measurement, &(Matrix6::identity() * 1e-4))
+        .unwrap();
+    assert!((innovation[2] - 0.01).abs() < 1e-12);
+    assert_eq!(tracker.state().acceleration, [0.0; 6]);
+    assert!(tracker.covariance()[(0, 0)] < 1e-4);
+}
+
+#[test]
+fn update_rejects_singular_innovation_covariance() {
+    let mut tracker = RigidBodyTracker::new(
+        truth(),
+        MotionCovariance::zeros(),
+        MotionModel::ConstantAcceleration {
+            jerk_density: [0.0; 6],
+        },
+    );
+    assert_eq!(
+        tracker.update(&truth().pose, &Matrix6::zeros()),
+        Err(Error::Singular)
+    );
+}
+
+fn hypothesis(weight: f64, yaw: f64, x: f64) -> PoseHypothesis {
+    PoseHypothesis::new(
+        weight,
+        RustSe3::from_parts(
+            RustSo3::from_axis_angle([0.0, 0.0, 1.0], yaw),
+            [x, 0.0, 0.0],
+        ),
+        Matrix6::identity() * 1e-2,
+    )
+}
+
+#[test]
+fn pose_mixture_normalizes_prunes_and_finds_the_mode() {
+    let mut mixture = PoseMixture::new(vec![
+        hypothesis(1.0, 0.0, 0.0),
+        hypothesis(3.0, std::f64::consts::PI, 0.0),
+        hypothesis(0.01, 1.0, 2.0),
+        hypothesis(2.0, 0.5, 1.0),
+    ]);
+    mixture.normalize().unwrap();
+    let total: f64 = mixture.hypotheses().iter().map(|h| h.weight).sum();
+    assert!((total - 1.0).abs() < 1e-15);
+    assert_eq!(mixture.most_likely().unwrap().weight, 3.0 / 6.01);
+
+    mixture.prune(0.01, 2);
+    let weights: Vec<f64> = mixture.hypotheses().iter().map(|h| h.weight).collect();
+    assert_eq!(weights, [3.0 / 6.01, 2.0 / 6.01]);
+
+    assert_eq!(
+        PoseMixture::default().normalize(),
+        Err(Error::InsufficientData {
+            required: 1,
+            provided: 0
+        })
+    );
+    let mut zero = PoseMixture::new(vec![hypothesis(0.0, 0.0, 0.0)]);
+    assert_eq!(zero.normalize(), Err(Error::ZeroNorm));
+}
+
+#[test]
+fn pose_mixture_merges_nearby_hypotheses_by_moment_matching() {
+    let mut mixture = PoseMixture::new(vec![
+        hypothesis(1.0, 0.0, 0.1),
+        hypothesis(1.0, 0.0, -0.1),
+        hypothesis(2.0, std::f64::consts::FRAC_PI_2, 5.0),
+    ]);
+    mixture.merge(9.0);
+    assert_eq!(mixture.len(), 2);
+
+    // The far hypothesis survives untouched; the close pair averages.
+    assert_eq!(
+        mixture.hypotheses()[0],
+        hypothesis(2.0, std::f64::consts::FRAC_PI_2, 5.0)
+    );
+    let merged = &mixture.hypotheses()[1];
+    assert_eq!(merged.weight, 2.0);
+    assert!(merged.pose.translation()[0].abs() < 1e-15);
+    // Each member contributes its own covariance plus its 0.1 offset.
+    assert!((merged.covariance[(3, 3)] - (1e-2 + 1e-2)).abs() < 1e-15);
+    assert!((merged.covariance[(4, 4)] - 1e-2).abs() < 1e-15);
+}
+
+#[test]
+fn chi_square_thresholds_match_tables() {
+    for (dof, probability, expected) in [
+        (1, 0.95, 3.841_458_820_694_124),
+        (3, 0.95, 7.814_727_903_251_178),
+        (3, 0.99, 11.344_866_730_144_373),
+        (6, 0.95, 12.591_587_243_743_977),
+        (6, 0.99, 16.811_893_829_770_94),
+        (6, 0.5, 5.348_120_627_447_116),
+    ] {
+        let threshold = chi_square_threshold(dof, probability);
+        assert!(
+            (threshold - expected).abs() < 1e-9 * expected,
+            "{dof}, {probability}: {threshold}"
+        );
+    }
+}
+
+#[test]
+fn gates_accept_consistent_and_reject_outlying_residuals() {
+    let covariance = nalgebra::Matrix2::new(4.0, 0.0, 0.0, 1.0);
+    let inside = gate(&Vector2::new(2.0, 1.0), &covariance, 0.95).unwrap();
+    assert!((inside.distance_squared - 2.0).abs() < 1e-15);
+    assert!(inside.accepts());
+    assert!(
+        !gate(&Vector2::new(0.0, 3.0), &covariance, 0.95)
+            .unwrap()
+            .accepts()
+    );
+    assert_eq!(
+        gate(&Vector2::new(1.0, 0.0), &nalgebra::Matrix2::zeros(), 0.95),
+        Err(Error::Singular)
+    );
+
+    let predicted = RustSo3::from_euler_angles(0.1, 0.2, 0.3);
+    let nudged = predicted.compose(&RustSo3::from_axis_angle([1.0, 0.0, 0.0], 0.02));
+    let rotation_covariance = Matrix3::identity() * 1e-4;
+    assert!(
+        gate_rotation(&predicted, &rotation_covariance, &nudged, 0.99)
+            .unwrap()
+            .accepts()
+    );
+    let flipped = predicted.compose(&RustSo3::from_axis_angle([1.0, 0.0, 0.0], 0.1));
+    let far = gate_rotation(&predicted, &rotation_covariance, &flipped, 0.99).unwrap();
+    assert!((far.distance_squared - 100.0).abs() < 1e-9);
+    assert!(!far.accepts());
+
+    // The tracker gates on its own pose uncertainty plus the measurement's.
+    let tracker = RigidBodyTracker::new(
+        truth(),
+        MotionCovariance::identity() * 1e-4,
+        MotionModel::ConstantTwist {
+            acceleration_density: [1e-3; 6],
+        },
+    );
+    let measurement_noise = Matrix6::identity() * 1e-4;
+    let shifted = truth()
+        .pose
+        .compose(&RustSe3::from_parts(RustSo3::identity(), [0.05, 0.0, 0.0]));
+    let result = tracker.gate(&shifted, &measurement_noise, 0.99).unwrap();
+    assert!((result.distance_squared - 0.05 * 0.05 / 2e-4).abs() < 1e-9);
+    assert!(result.accepts());
+    let direct = gate_pose(&truth().pose, &(Matrix6::identity() * 2e-4), &shifted, 0.99).unwrap();
+    assert_eq!(direct, result);
+}
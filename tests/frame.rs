@@ -0,0 +1,249 @@
+use mathroborust::binary::BinaryCodec;
+use mathroborust::error::Error;
+use mathroborust::frame::{FrameTree, SharedFrameTree};
+use mathroborust::lie::LieGroup;
+use mathroborust::se3::Se3;
+
+fn pose(yaw: f64, translation: [f64; 3]) -> Se3 {
+    Se3::from_axis_angle_translation([0.0, 0.0, 1.0], yaw, translation)
+}
+
+fn close(a: &Se3, b: &Se3, tol: f64) -> bool {
+    let difference = a.inverse().compose(b);
+    difference.translation().iter().all(|v| v.abs() < tol)
+        && difference
+            .rotation()
+            .to_rotation_vector()
+            .iter()
+            .all(|v| v.abs() < tol)
+}
+
+fn robot() -> FrameTree {
+    let mut tree = FrameTree::new();
+    tree.set_transform("world", "base", pose(0.5, [1.0, 0.0, 0.0]))
+        .unwrap();
+    tree.set_transform("base", "camera", pose(-0.2, [0.0, 0.3, 0.5]))
+        .unwrap();
+    tree.set_transform("base", "lidar", pose(1.0, [0.2, 0.0, 0.8]))
+        .unwrap();
+    tree
+}
+
+#[test]
+fn lookups_chain_along_any_direction() {
+    let tree = robot();
+    let expected = pose(0.5, [1.0, 0.0, 0.0]).compose(&pose(-0.2, [0.0, 0.3, 0.5]));
+    assert!(close(
+        &tree.transform("world", "camera").unwrap(),
+        &expected,
+        1e-12
+    ));
+    assert!(close(
+        &tree.transform("camera", "world").unwrap(),
+        &expected.inverse(),
+        1e-12
+    ));
+    let camera_from_lidar = tree.transform("camera", "lidar").unwrap();
+    let expected = pose(-0.2, [0.0, 0.3, 0.5])
+        .inverse()
+        .compose(&pose(1.0, [0.2, 0.0, 0.8]));
+    assert!(close(&camera_from_lidar, &expected, 1e-12));
+    assert!(close(
+        &tree.transform("lidar", "lidar").unwrap(),
+        &Se3::identity(),
+        1e-15
+    ));
+}
+
+#[test]
+fn unknown_and_disconnected_frames_are_errors() {
+    let mut tree = robot();
+    assert_eq!(
+        tree.transform("world", "gripper"),
+        Err(Error::UnknownFrame("gripper".into()))
+    );
+    tree.set_transform("map", "odom", Se3::identity()).unwrap();
+    assert_eq!(
+        tree.transform("world", "odom"),
+        Err(Error::DisconnectedFrame("odom".into()))
+    );
+}
+
+#[test]
+fn loop_closures_must_agree_with_existing_paths() {
+    let mut tree = robot();
+    let camera_from_lidar = tree.transform("camera", "lidar").unwrap();
+
+    // A second, slightly perturbed path is accepted within tolerance.
+    let nudged = camera_from_lidar.compose(&pose(0.0, [1e-8, 0.0, 0.0]));
+    tree.set_transform("camera", "lidar", nudged).unwrap();
+    let report = tree.check_consistency();
+    assert_eq!(report.len(), 1);
+    assert!((report[0].translation - 1e-8).abs() < 1e-12);
+    assert!(report[0].rotation < 1e-12);
+
+    // A disagreeing calibration is rejected and reports its magnitude.
+    let error = pose(0.1, [0.0, 0.02, 0.0]);
+    let wrong = tree.transform("lidar", "world").unwrap().compose(&error);
+    let err = tree.set_transform("lidar", "world", wrong).unwrap_err();
+    assert_eq!(err.code(), 14);
+    let Error::InconsistentFrames {
+        parent,
+        child,
+        translation,
+        rotation,
+    } = err
+    else {
+        panic!("expected an inconsistency");
+    };
+    assert_eq!((parent.as_str(), child.as_str()), ("lidar", "world"));
+    assert!((translation - 0.02).abs() < 1e-9);
+    assert!((rotation - 0.1).abs() < 1e-9);
+
+    let wrong = camera_from_lidar.compose(&error);
+    let before = tree.to_bytes();
+    assert!(tree.set_transform("camera", "lidar", wrong).is_err());
+    // The rejected update left the accepted edge in place.
+    assert_eq!(tree.to_bytes(), before);
+    assert_eq!(tree.check_consistency().len(), 1);
+    assert!(close(
+        &tree.remove_transform("lidar", "camera").unwrap(),
+        &tree.transform("lidar", "camera").unwrap(),
+        1e-7
+    ));
+    assert!(tree.check_consistency().is_empty());
+}
+
+#[test]
+fn edges_can_be_replaced_without_a_second_path() {
+    let mut tree = robot();
+    tree.set_transform("base", "camera", pose(0.0, [0.0, 0.0, 1.0]))
+        .unwrap();
+    assert!(close(
+        &tree.transform("base", "camera").unwrap(),
+        &pose(0.0, [0.0, 0.0, 1.0]),
+        1e-12
+    ));
+    assert!(tree.check_consistency().is_empty());
+    assert!(
+        tree.set_transform("base", "base", pose(0.3, [0.0; 3]))
+            .is_err()
+    );
+    assert_eq!(tree.frames().count(), 4);
+}
+
+#[test]
+fn subscribers_receive_changed_lookups() {
+    let mut tree = robot();
+    let camera = tree.subscribe("world", "camera");
+    let gripper = tree.subscribe("world", "gripper");
+    let initial = camera.try_recv().unwrap();
+    assert!(close(
+        &initial,
+        &tree.transform("world", "camera").unwrap(),
+        1e-15
+    ));
+    assert!(gripper.try_recv().is_err());
+
+    // Unrelated edges do not change the watched lookup.
+    tree.set_transform("base", "lidar", pose(0.0, [0.0; 3]))
+        .unwrap();
+    assert!(camera.try_recv().is_err());
+
+    let moved = pose(0.7, [2.0, 0.0, 0.0]);
+    let worker = std::thread::spawn(move || camera.recv().unwrap());
+    tree.set_transform("world", "base", moved.clone()).unwrap();
+    let update = worker.join().unwrap();
+    assert!(close(
+        &update,
+        &moved.compose(&pose(-0.2, [0.0, 0.3, 0.5])),
+        1e-12
+    ));
+
+    tree.set_transform("base", "gripper", Se3::identity())
+        .unwrap();
+    assert!(close(&gripper.try_recv().unwrap(), &moved, 1e-12));
+}
+
+#[test]
+fn cached_chains_follow_every_edit() {
+    let mut tree = FrameTree::new();
+    let link = |k: usize| pose(0.1 * k as f64, [0.5, 0.0, 0.01 * k as f64]);
+    for k in 0..20 {
+        tree.set_transform(&format!("link{k}"), &format!("link{}", k + 1), link(k))
+            .unwrap();
+    }
+    let chained = |tree: &FrameTree| tree.transform("link0", "link20").unwrap();
+    let expected = (0..20).fold(Se3::identity(), |acc, k| acc.compose(&link(k)));
+    assert!(close(&chained(&tree), &expected, 1e-12));
+
+    // Replacing an edge in the middle updates lookups through it.
+    tree.set_transform("link10", "link11", pose(0.0, [0.0; 3]))
+        .unwrap();
+    let expected = (0..20)
+        .map(|k| if k == 10 { Se3::identity() } else { link(k) })
+        .fold(Se3::identity(), |acc, t| acc.compose(&t));
+    assert!(close(&chained(&tree), &expected, 1e-12));
+
+    // Removing it splits the chain; lookups within each half still work.
+    tree.remove_transform("link10", "link11").unwrap();
+    assert_eq!(
+        tree.transform("link0", "link20"),
+        Err(Error::DisconnectedFrame("link20".to_string()))
+    );
+    assert!(close(
+        &tree.transform("link11", "link12").unwrap(),
+        &link(11),
+        1e-12
+    ));
+
+    // A rejected edit leaves the tree unchanged, without registering frames.
+    tree.set_transform("ghost", "ghost", pose(0.3, [0.0; 3]))
+        .unwrap_err();
+    assert!(!tree.contains("ghost"));
+    assert_eq!(
+        tree.transform("link0", "ghost"),
+        Err(Error::UnknownFrame("ghost".to_string()))
+    );
+}
+
+#[test]
+fn shared_tree_readers_never_wait_on_writers() {
+    let shared = SharedFrameTree::new(robot());
+    let mut reader = shared.reader();
+    let before = reader.transform("world", "camera").unwrap();
+
+    // Inside an edit the writer lock is held, yet the reader still answers
+    // from its snapshot instead of blocking.
+    shared.update(|tree| {
+        tree.set_transform("base", "camera", pose(0.0, [0.0, 0.0, 1.0]))
+            .unwrap();
+        assert_eq!(reader.transform("world", "camera").unwrap(), before);
+    });
+    let after = reader.transform("world", "camera").unwrap();
+    let expected = pose(0.5, [1.0, 0.0, 0.0]).compose(&pose(0.0, [0.0, 0.0, 1.0]));
+    assert!(close(&after, &expected, 1e-12));
+    assert_eq!(reader.version(), 1);
+
+    // Readers on other threads see every publication.
+    let handle = shared.clone();
+    let worker = std::thread::spawn(move || {
+        let mut reader = handle.reader();
+        loop {
+            if let Ok(lidar) = reader.transform("camera", "lidar2") {
+                return lidar;
+            }
+            std::thread::yield_now();
+        }
+    });
+    shared
+        .set_transform("lidar", "lidar2", pose(0.1, [0.0; 3]))
+        .unwrap();
+    let lidar = worker.join().unwrap();
+    assert_eq!(
+        lidar,
+        shared.reader().transform("camera", "lidar2").unwrap()
+    );
+    assert!(shared.remove_transform("lidar", "lidar2").is_some());
+    assert!(reader.transform("camera", "lidar2").is_err());
+}
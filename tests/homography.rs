@@ -0,0 +1,88 @@
+use mathroborust::homography::{
+    closest_to_normal, decompose_homography, homography_from_pose, visible_decompositions,
+};
+use mathroborust::se3::Se3;
+use mathroborust::so3::So3;
+
+const NORMAL: [f64; 3] = [0.1, -0.2, 0.9746794344808963];
+const DISTANCE: f64 = 2.0;
+
+fn second_from_first() -> Se3 {
+    Se3::from_axis_angle_translation([0.3, 1.0, -0.2], 0.4, [0.5, 0.1, -0.2])
+}
+
+/// Points on the plane nᵀX = d, in the first camera frame.
+fn plane_points() -> Vec<[f64; 3]> {
+    [[-0.5, -0.4], [0.6, -0.3], [0.2, 0.5], [-0.4, 0.3]]
+        .iter()
+        .map(|&[x, y]| {
+            let z = (DISTANCE - NORMAL[0] * x - NORMAL[1] * y) / NORMAL[2];
+            [x, y, z]
+        })
+        .collect()
+}
+
+fn normalized(point: [f64; 3]) -> [f64; 2] {
+    [point[0] / point[2], point[1] / point[2]]
+}
+
+fn close(a: [f64; 3], b: [f64; 3], tolerance: f64) -> bool {
+    (0..3).all(|k| (a[k] - b[k]).abs() < tolerance)
+}
+
+fn same_pose(a: &Se3, b: &Se3) -> bool {
+    let (a, b) = (a.to_matrix(), b.to_matrix());
+    (0..4).all(|r| (0..4).all(|c| (a[r][c] - b[r][c]).abs() < 1e-8))
+}
+
+#[test]
+fn homography_maps_plane_points_between_views() {
+    let pose = second_from_first();
+    let h = homography_from_pose(&pose, NORMAL, DISTANCE);
+    for point in plane_points() {
+        let [x, y] = normalized(point);
+        let mapped = h * nalgebra::Vector3::new(x, y, 1.0);
+        let expected = normalized(pose.apply(point));
+        assert!((mapped.x / mapped.z - expected[0]).abs() < 1e-12);
+        assert!((mapped.y / mapped.z - expected[1]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn decomposition_contains_the_true_motion_and_plane() {
+    let pose = second_from_first();
+    // Arbitrary scale and sign, as from a DLT estimate.
+    let h = homography_from_pose(&pose, NORMAL, DISTANCE) * -3.7;
+    let solutions = decompose_homography(&h);
+    assert_eq!(solutions.len(), 4);
+    let scaled = pose.translation().map(|t| t / DISTANCE);
+    let truth = Se3::from_parts(pose.rotation().clone(), scaled);
+    assert!(solutions.iter().any(|solution| {
+        same_pose(&solution.second_from_first, &truth) && close(solution.normal, NORMAL, 1e-8)
+    }));
+}
+
+#[test]
+fn visibility_and_normal_prior_select_the_true_solution() {
+    let pose = second_from_first();
+    let solutions = decompose_homography(&homography_from_pose(&pose, NORMAL, DISTANCE));
+    let points = plane_points();
+    let first: Vec<_> = points.iter().map(|&p| normalized(p)).collect();
+    let second: Vec<_> = points.iter().map(|&p| normalized(pose.apply(p))).collect();
+
+    let visible = visible_decompositions(&solutions, &first, &second);
+    assert!(!visible.is_empty() && visible.len() <= 2);
+    let chosen = closest_to_normal(&visible, [0.0, 0.0, 1.0]).unwrap();
+    assert!(close(chosen.normal, NORMAL, 1e-8));
+}
+
+#[test]
+fn pure_rotation_yields_a_single_solution() {
+    let rotation = So3::from_axis_angle([0.0, 1.0, 0.0], 0.2);
+    let pose = Se3::from_parts(rotation, [0.0; 3]);
+    let solutions = decompose_homography(&homography_from_pose(&pose, NORMAL, DISTANCE));
+    assert_eq!(solutions.len(), 1);
+    assert!(same_pose(&solutions[0].second_from_first, &pose));
+    assert_eq!(solutions[0].normal, [0.0; 3]);
+    assert!(closest_to_normal(&[], NORMAL).is_none());
+}
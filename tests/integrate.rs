@@ -0,0 +1,91 @@
+use mathroborust::integrate::{integrate_rkmk4, lie_euler_step, rkmk4_step};
+use mathroborust::lie::LieGroup;
+use mathroborust::{RustSe3, RustSo3};
+
+fn rotation_error(a: &RustSo3, b: &RustSo3) -> f64 {
+    let [x, y, z] = a.inverse().compose(b).to_rotation_vector();
+    (x * x + y * y + z * z).sqrt()
+}
+
+fn angular_velocity(t: f64) -> [f64; 3] {
+    [t.sin(), (2.0 * t).cos(), 0.5 * t]
+}
+
+#[test]
+fn rkmk4_is_exact_for_constant_body_velocity() {
+    let start = RustSo3::from_axis_angle([1.0, 0.0, 0.0], 0.3);
+    let omega = [0.4, -1.0, 2.0];
+    let end = integrate_rkmk4(&start, 0.0, 2.0, 5, |_, _: &RustSo3| omega);
+    let exact = start.compose(&RustSo3::from_rotation_vector(omega.map(|w| w * 2.0)));
+    assert!(rotation_error(&end, &exact) < 1e-12);
+
+    let twist = [0.1, 0.2, -0.3, 1.0, 0.0, 0.5];
+    let pose = RustSe3::identity();
+    let stepped = rkmk4_step(&pose, 0.0, 1.5, |_, _: &RustSe3| twist);
+    let exact = RustSe3::from_matrix(RustSe3::exp(twist, Some(1.5)));
+    for (a, b) in stepped
+        .to_row_major_flat()
+        .iter()
+        .zip(exact.to_row_major_flat())
+    {
+        assert!((a - b).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn rkmk4_converges_with_fourth_order() {
+    let start = RustSo3::identity();
+    let field = |t: f64, _: &RustSo3| angular_velocity(t);
+    let reference = integrate_rkmk4(&start, 0.0, 2.0, 4096, field);
+
+    let coarse = rotation_error(&integrate_rkmk4(&start, 0.0, 2.0, 10, field), &reference);
+    let fine = rotation_error(&integrate_rkmk4(&start, 0.0, 2.0, 20, field), &reference);
+    let order = (coarse / fine).log2();
+    assert!(order > 3.7 && order < 4.5, "observed order {order}");
+
+    // Lie–Euler with the same number of steps is far less accurate.
+    let mut euler = start.clone();
+    for k in 0..20 {
+        euler = lie_euler_step(&euler, k as f64 * 0.1, 0.1, field);
+    }
+    assert!(rotation_error(&euler, &reference) > 100.0 * fine);
+    assert!(euler.health_check().is_within(1e-12));
+}
+
+#[test]
+fn rkmk4_handles_state_dependent_fields() {
+    // Torque-free rotation of an asymmetric body: the body angular velocity
+    // is advanced alongside the attitude, and the spatial angular momentum
+    // R I ω must stay constant.
+    let inertia = [1.0, 2.0, 3.0];
+    let omega0 = [0.3, 1.0, -0.2];
+    let euler_equations = |w: [f64; 3]| {
+        [
+            (inertia[1] - inertia[2]) * w[1] * w[2] / inertia[0],
+            (inertia[2] - inertia[0]) * w[2] * w[0] / inertia[1],
+            (inertia[0] - inertia[1]) * w[0] * w[1] / inertia[2],
+        ]
+    };
+
+    let h = 0.01;
+    let mut attitude = RustSo3::identity();
+    let mut omega = omega0;
+    for _ in 0..500 {
+        let current = omega;
+        attitude = rkmk4_step(&attitude, 0.0, h, |_, _: &RustSo3| current);
+        let k1 = euler_equations(omega);
+        let k2 = euler_equations(std::array::from_fn(|i| omega[i] + 0.5 * h * k1[i]));
+        let k3 = euler_equations(std::array::from_fn(|i| omega[i] + 0.5 * h * k2[i]));
+        let k4 = euler_equations(std::array::from_fn(|i| omega[i] + h * k3[i]));
+        omega = std::array::from_fn(|i| {
+            omega[i] + h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i])
+        });
+    }
+
+    let momentum = |r: &RustSo3, w: [f64; 3]| r.apply(std::array::from_fn(|i| inertia[i] * w[i]));
+    let initial = momentum(&RustSo3::identity(), omega0);
+    let final_ = momentum(&attitude, omega);
+    for (a, b) in initial.iter().zip(final_) {
+        assert!((a - b).abs() < 1e-2);
+    }
+}
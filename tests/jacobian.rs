@@ -0,0 +1,194 @@
+use mathroborust::RustSo3;
+use mathroborust::error::Error;
+use mathroborust::jacobian::{
+    OrientationCoordinates, analytic_to_geometric, apply_task_delta, euler_rate_matrix,
+    euler_rate_matrix_derivative, finite_difference_jacobian, geometric_to_analytic,
+    is_near_singularity, jacobian_error, joint_to_task, manipulability, quaternion_rate_matrix,
+    task_delta, task_to_joint,
+};
+use mathroborust::lie::LieGroup;
+use mathroborust::screw::Screw;
+use mathroborust::se3::Se3;
+use nalgebra::{DMatrix, Matrix3, Vector3};
+
+/// World-frame angular velocity from a central difference of `rotation`.
+fn angular_velocity(rotation: impl Fn(f64) -> RustSo3, t: f64, h: f64) -> Vector3<f64> {
+    let rate =
+        (rotation(t + h).rotation().matrix() - rotation(t - h).rotation().matrix()) / (2.0 * h);
+    let skew = rate * rotation(t).rotation().matrix().transpose();
+    Vector3::new(skew[(2, 1)], skew[(0, 2)], skew[(1, 0)])
+}
+
+fn euler_at(t: f64) -> [f64; 3] {
+    [0.3 + 0.5 * t, -0.6 + 0.2 * t * t, 1.1 - 0.8 * t]
+}
+
+#[test]
+fn euler_rate_matrix_maps_angle_rates_to_angular_velocity() {
+    let (t, h) = (0.4, 1e-6);
+    let rotation = |t: f64| {
+        let [r, p, y] = euler_at(t);
+        RustSo3::from_euler_angles(r, p, y)
+    };
+    let rates = Vector3::from_fn(|i, _| (euler_at(t + h)[i] - euler_at(t - h)[i]) / (2.0 * h));
+    let omega = euler_rate_matrix(euler_at(t)) * rates;
+    assert!((omega - angular_velocity(rotation, t, h)).norm() < 1e-8);
+
+    let derivative = euler_rate_matrix_derivative(euler_at(t), rates.into());
+    let expected: Matrix3<f64> =
+        (euler_rate_matrix(euler_at(t + h)) - euler_rate_matrix(euler_at(t - h))) / (2.0 * h);
+    assert!((derivative - expected).norm() < 1e-8);
+}
+
+#[test]
+fn quaternion_rate_matrix_maps_angular_velocity_to_quaternion_rates() {
+    let (t, h) = (0.2, 1e-6);
+    let rotation = |t: f64| RustSo3::from_rotation_vector([0.4 * t, -0.9 + t, 0.3 * t * t]);
+    let omega = angular_velocity(rotation, t, h);
+    let q = |t: f64| nalgebra::Vector4::from(rotation(t).to_quaternion());
+    let rate = (q(t + h) - q(t - h)) / (2.0 * h);
+    let e = quaternion_rate_matrix(rotation(t).to_quaternion());
+    assert!((e * omega - rate).norm() < 1e-8);
+    assert!((e.transpose() * e * 4.0 - Matrix3::identity()).norm() < 1e-12);
+}
+
+#[test]
+fn analytic_jacobian_round_trips() {
+    let jacobian = DMatrix::from_fn(6, 4, |r, c| ((r * 4 + c) as f64 * 0.37).sin());
+    let rotation = RustSo3::from_euler_angles(0.2, 0.7, -1.3);
+    for coordinates in [
+        OrientationCoordinates::EulerRpy,
+        OrientationCoordinates::Quaternion,
+    ] {
+        let analytic = geometric_to_analytic(&jacobian, &rotation, coordinates).unwrap();
+        assert_eq!(analytic.nrows(), coordinates.dimension() + 3);
+        assert_eq!(analytic.rows(analytic.nrows() - 3, 3), jacobian.rows(3, 3));
+        let geometric = analytic_to_geometric(&analytic, &rotation, coordinates);
+        assert!((geometric - &jacobian).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn euler_analytic_jacobian_rejects_gimbal_lock() {
+    let jacobian = DMatrix::identity(6, 6);
+    let locked = RustSo3::from_euler_angles(0.3, std::f64::consts::FRAC_PI_2, 0.1);
+    let err =
+        geometric_to_analytic(&jacobian, &locked, OrientationCoordinates::EulerRpy).unwrap_err();
+    assert_eq!(err, Error::Singular);
+    assert_eq!(err.code(), 12);
+    assert!(geometric_to_analytic(&jacobian, &locked, OrientationCoordinates::Quaternion).is_ok());
+}
+
+/// Position Jacobian of a planar two-link arm.
+fn two_link_jacobian(q: [f64; 2], lengths: [f64; 2]) -> DMatrix<f64> {
+    let (s1, c1) = q[0].sin_cos();
+    let (s12, c12) = (q[0] + q[1]).sin_cos();
+    DMatrix::from_row_slice(
+        2,
+        2,
+        &[
+            -lengths[0] * s1 - lengths[1] * s12,
+            -lengths[1] * s12,
+            lengths[0] * c1 + lengths[1] * c12,
+            lengths[1] * c12,
+        ],
+    )
+}
+
+#[test]
+fn manipulability_matches_two_link_closed_form_and_flags_singularity() {
+    let lengths = [0.8, 0.5];
+    let measures = manipulability(&two_link_jacobian([0.3, 1.1], lengths));
+    assert!((measures.yoshikawa - 0.8 * 0.5 * 1.1_f64.sin()).abs() < 1e-12);
+    assert!(measures.condition_number >= 1.0);
+    assert!(!measures.is_near_singularity(1e-3));
+
+    // Outstretched arm: the radial direction is lost.
+    let stretched = two_link_jacobian([0.3, 0.0], lengths);
+    let measures = manipulability(&stretched);
+    assert!(measures.yoshikawa.abs() < 1e-12);
+    assert!(measures.min_singular_value < 1e-12);
+    assert!(is_near_singularity(&stretched, 1e-6));
+
+    // A redundant 2x3 Jacobian uses sqrt(det(J Jᵀ)).
+    let redundant = DMatrix::<f64>::from_row_slice(2, 3, &[1.0, 0.0, 2.0, 0.0, 3.0, 1.0]);
+    let expected = (&redundant * redundant.transpose()).determinant().sqrt();
+    assert!((manipulability(&redundant).yoshikawa - expected).abs() < 1e-12);
+}
+
+/// Product-of-exponentials arm: yaw, shoulder pitch, then a prismatic joint.
+fn screws() -> [Screw; 3] {
+    [
+        Screw::revolute([0.0, 0.0, 1.0], [0.0; 3], 0.0).unwrap(),
+        Screw::revolute([0.0, 1.0, 0.0], [0.0, 0.0, 0.4], 0.0).unwrap(),
+        Screw::prismatic([1.0, 0.0, 0.0]).unwrap(),
+    ]
+}
+
+fn home() -> Se3 {
+    Se3::from_axis_angle_translation([1.0, 0.0, 0.0], 0.2, [0.5, 0.0, 0.4])
+}
+
+fn forward_kinematics(q: &[f64]) -> Se3 {
+    screws()
+        .iter()
+        .zip(q)
+        .fold(Se3::identity(), |pose, (screw, &angle)| {
+            pose.compose(&screw.pose(angle))
+        })
+        .compose(&home())
+}
+
+/// Geometric Jacobian [ω; ṗ] from the spatial screws Ad_{T_{i-1}} S_i.
+fn analytic_jacobian(q: &[f64]) -> DMatrix<f64> {
+    let p = Vector3::from(forward_kinematics(q).translation());
+    let mut jacobian = DMatrix::zeros(6, q.len());
+    let mut pose = Se3::identity();
+    for (j, screw) in screws().iter().enumerate() {
+        let [wx, wy, wz, vx, vy, vz] = pose.transform_twist(screw.twist());
+        let omega = Vector3::new(wx, wy, wz);
+        let velocity = Vector3::new(vx, vy, vz) + omega.cross(&p);
+        for i in 0..3 {
+            jacobian[(i, j)] = omega[i];
+            jacobian[(i + 3, j)] = velocity[i];
+        }
+        pose = pose.compose(&screw.pose(q[j]));
+    }
+    jacobian
+}
+
+#[test]
+fn hand_derived_jacobian_matches_finite_differences() {
+    let q = [0.4, -0.7, 0.3];
+    let jacobian = analytic_jacobian(&q);
+    assert!(jacobian_error(forward_kinematics, &jacobian, &q, 1e-6) < 1e-8);
+    let numeric = finite_difference_jacobian(forward_kinematics, &q, 1e-6);
+    assert_eq!(numeric.shape(), (6, 3));
+
+    // A wrong column (sign slip) is caught.
+    let mut wrong = jacobian.clone();
+    wrong.column_mut(1).neg_mut();
+    assert!(jacobian_error(forward_kinematics, &wrong, &q, 1e-6) > 1e-2);
+}
+
+#[test]
+fn small_joint_perturbations_map_to_pose_changes_and_back() {
+    let q = [0.4, -0.7, 0.3];
+    let dq = [1e-4, -2e-4, 3e-4];
+    let jacobian = analytic_jacobian(&q);
+    let predicted = apply_task_delta(&forward_kinematics(&q), joint_to_task(&jacobian, &dq));
+    let perturbed: Vec<f64> = q.iter().zip(dq).map(|(a, b)| a + b).collect();
+    let actual = forward_kinematics(&perturbed);
+    let residual = task_delta(&predicted, &actual);
+    assert!(residual.iter().all(|r| r.abs() < 1e-6));
+
+    let recovered = task_to_joint(&jacobian, task_delta(&forward_kinematics(&q), &actual), 0.0);
+    for (r, d) in recovered.iter().zip(dq) {
+        assert!((r - d).abs() < 1e-6);
+    }
+    // Damping shrinks the step but keeps its direction for a reachable change.
+    let damped = task_to_joint(&jacobian, joint_to_task(&jacobian, &dq), 1e-2);
+    let dot: f64 = damped.iter().zip(dq).map(|(a, b)| a * b).sum();
+    assert!(dot > 0.0);
+    assert!(damped.iter().map(|x| x * x).sum::<f64>() < dq.iter().map(|x| x * x).sum::<f64>());
+}
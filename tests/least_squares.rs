@@ -0,0 +1,290 @@
+use std::ops::ControlFlow;
+
+use mathroborust::error::Error;
+use mathroborust::least_squares::{
+    Constraints, LeastSquaresProblem, Manifold, levenberg_marquardt,
+    levenberg_marquardt_constrained, levenberg_marquardt_with,
+};
+use mathroborust::lie::LieGroup;
+use mathroborust::se3::Se3;
+use mathroborust::so3::So3;
+use mathroborust::solver::{Budget, SolveStatus, WarmStart};
+use nalgebra::{DMatrix, DVector, Vector3};
+
+/// Circle through noisy-free points, state [cx, cy, r].
+struct CircleFit(Vec<[f64; 2]>);
+
+impl LeastSquaresProblem for CircleFit {
+    type State = [f64; 3];
+
+    fn residuals(&self, state: &[f64; 3]) -> DVector<f64> {
+        let [cx, cy, r] = *state;
+        DVector::from_iterator(
+            self.0.len(),
+            self.0.iter().map(|[x, y]| (x - cx).hypot(y - cy) - r),
+        )
+    }
+}
+
+/// Rotation aligning body vectors with measured world vectors, with an
+/// analytic Jacobian: d(R Exp(δ) b)/dδ = -R [b]×.
+struct Alignment {
+    body: Vec<[f64; 3]>,
+    world: Vec<[f64; 3]>,
+}
+
+impl LeastSquaresProblem for Alignment {
+    type State = So3;
+
+    fn residuals(&self, rotation: &So3) -> DVector<f64> {
+        let mut residuals = DVector::zeros(3 * self.body.len());
+        for (i, (b, w)) in self.body.iter().zip(&self.world).enumerate() {
+            let rotated = rotation.rotation() * Vector3::from(*b);
+            for k in 0..3 {
+                residuals[3 * i + k] = rotated[k] - w[k];
+            }
+        }
+        residuals
+    }
+
+    fn jacobian(&self, rotation: &So3) -> DMatrix<f64> {
+        let mut jacobian = DMatrix::zeros(3 * self.body.len(), 3);
+        let matrix = rotation.rotation().matrix();
+        for (i, b) in self.body.iter().enumerate() {
+            let block = -matrix * mathroborust::util::skew_symmetric(&Vector3::from(*b));
+            jacobian.view_mut((3 * i, 0), (3, 3)).copy_from(&block);
+        }
+        jacobian
+    }
+}
+
+/// Pose and a scalar scale jointly: world = s · (T · body).
+struct ScaledPose {
+    body: Vec<[f64; 3]>,
+    world: Vec<[f64; 3]>,
+}
+
+impl LeastSquaresProblem for ScaledPose {
+    type State = (Se3, [f64; 1]);
+
+    fn residuals(&self, (pose, [scale]): &(Se3, [f64; 1])) -> DVector<f64> {
+        let mut residuals = DVector::zeros(3 * self.body.len());
+        for (i, (b, w)) in self.body.iter().zip(&self.world).enumerate() {
+            let p = pose.apply(*b);
+            for k in 0..3 {
+                residuals[3 * i + k] = scale * p[k] - w[k];
+            }
+        }
+        residuals
+    }
+}
+
+fn body_points() -> Vec<[f64; 3]> {
+    vec![
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.5, -0.3, 0.8],
+        [-0.6, 0.4, 0.2],
+    ]
+}
+
+#[test]
+fn fits_a_circle_with_numeric_jacobian() {
+    let points = (0..8)
+        .map(|k| {
+            let angle = k as f64 * 0.7;
+            [1.5 + 2.0 * angle.cos(), -0.5 + 2.0 * angle.sin()]
+        })
+        .collect();
+    let fit = levenberg_marquardt(&CircleFit(points), &[0.0, 0.0, 1.0]).unwrap();
+    for (a, b) in fit.iter().zip([1.5, -0.5, 2.0]) {
+        assert!((a - b).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn aligns_rotations_with_an_analytic_jacobian() {
+    let truth = So3::from_axis_angle([0.3, -1.0, 0.4], 1.2);
+    let body = body_points();
+    let world = body
+        .iter()
+        .map(|b| (truth.rotation() * Vector3::from(*b)).into())
+        .collect();
+    let problem = Alignment { body, world };
+
+    // The analytic and default numeric Jacobians agree.
+    let state = So3::from_axis_angle([1.0, 0.0, 0.0], 0.3);
+    let numeric = {
+        struct Numeric<'a>(&'a Alignment);
+        impl LeastSquaresProblem for Numeric<'_> {
+            type State = So3;
+            fn residuals(&self, state: &So3) -> DVector<f64> {
+                self.0.residuals(state)
+            }
+        }
+        Numeric(&problem).jacobian(&state)
+    };
+    assert!((numeric - problem.jacobian(&state)).amax() < 1e-8);
+
+    let report = levenberg_marquardt_with(
+        &problem,
+        &So3::identity(),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    )
+    .unwrap();
+    assert_eq!(report.status, SolveStatus::Converged);
+    assert!(report.cost < 1e-20);
+    let error = report
+        .solution
+        .inverse()
+        .compose(&truth)
+        .to_rotation_vector();
+    assert!(error.iter().all(|e| e.abs() < 1e-10));
+    assert!(!report.warm_start.is_cold());
+}
+
+#[test]
+fn solves_over_a_product_manifold() {
+    let truth = Se3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.4, [0.2, -0.1, 0.3]);
+    let body = body_points();
+    let world = body
+        .iter()
+        .map(|&b| truth.apply(b).map(|x| 1.7 * x))
+        .collect();
+    let problem = ScaledPose { body, world };
+    let initial = (Se3::identity(), [1.0]);
+    assert_eq!(initial.dimension(), 7);
+    let (pose, [scale]) = levenberg_marquardt(&problem, &initial).unwrap();
+    assert!((scale - 1.7).abs() < 1e-8);
+    let [x, y, z] = pose.inverse().compose(&truth).translation();
+    assert!(x.abs() < 1e-8 && y.abs() < 1e-8 && z.abs() < 1e-8);
+}
+
+#[test]
+fn budget_and_empty_problems() {
+    let points = vec![[2.0, 0.0], [0.0, 2.0], [-2.0, 0.0], [0.0, -2.0]];
+    let report = levenberg_marquardt_with(
+        &CircleFit(points),
+        &[0.5, 0.5, 1.0],
+        &Budget::unlimited().with_max_iterations(1),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    )
+    .unwrap();
+    assert_eq!(report.status, SolveStatus::IterationLimit);
+    assert_eq!(report.iterations, 1);
+
+    assert!(matches!(
+        levenberg_marquardt(&CircleFit(Vec::new()), &[0.0; 3]),
+        Err(Error::InsufficientData { .. })
+    ));
+}
+
+fn circle_points() -> Vec<[f64; 2]> {
+    (0..8)
+        .map(|k| {
+            let angle = k as f64 * 0.7;
+            [1.5 + 2.0 * angle.cos(), -0.5 + 2.0 * angle.sin()]
+        })
+        .collect()
+}
+
+#[test]
+fn bounds_are_enforced_by_projection() {
+    let problem = CircleFit(circle_points());
+    // Radius may grow by at most 0.5 from the initial 1.0; the centre x is
+    // pinned to [0, 1], starting outside it.
+    let constraints = Constraints::new(3)
+        .with_bounds(2, f64::NEG_INFINITY, 0.5)
+        .with_bounds(0, 1.0, 2.0);
+    let report = levenberg_marquardt_constrained(
+        &problem,
+        &[-1.0, 0.0, 1.0],
+        &constraints,
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |state, _| {
+            assert!(state[2] <= 1.5 + 1e-12);
+            assert!((0.0 - 1e-12..=1.0 + 1e-12).contains(&state[0]));
+            ControlFlow::Continue(())
+        },
+    )
+    .unwrap();
+    let [cx, _, r] = report.solution;
+    assert!((r - 1.5).abs() < 1e-12);
+    assert!((cx - 1.0).abs() < 1e-12);
+    assert!(report.cost > 1e-3);
+}
+
+#[test]
+fn equalities_hold_at_the_solution() {
+    let problem = CircleFit(circle_points());
+    // Move the centre only along the diagonal cx = cy.
+    let constraints = Constraints::new(3).with_equality(&[1.0, -1.0, 0.0], 0.0);
+    let report = levenberg_marquardt_constrained(
+        &problem,
+        &[0.3, 0.1, 1.0],
+        &constraints,
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    )
+    .unwrap();
+    let [cx, cy, _] = report.solution;
+    assert!(((cx - 0.3) - (cy - 0.1)).abs() < 1e-12);
+
+    // Stationary along the feasible directions (1, 1, 0) and (0, 0, 1).
+    let gradient =
+        problem.jacobian(&report.solution).transpose() * problem.residuals(&report.solution);
+    assert!((gradient[0] + gradient[1]).abs() < 1e-6);
+    assert!(gradient[2].abs() < 1e-6);
+}
+
+#[test]
+fn unconstrained_problems_match_the_plain_solver() {
+    let problem = CircleFit(circle_points());
+    let plain = levenberg_marquardt(&problem, &[0.0, 0.0, 1.0]).unwrap();
+    let constrained = levenberg_marquardt_constrained(
+        &problem,
+        &[0.0, 0.0, 1.0],
+        &Constraints::new(3),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    )
+    .unwrap();
+    assert_eq!(plain, constrained.solution);
+}
+
+#[test]
+fn invalid_constraints_are_reported_by_the_solver() {
+    let problem = CircleFit(circle_points());
+    let solve = |constraints: &Constraints| {
+        levenberg_marquardt_constrained(
+            &problem,
+            &[0.0, 0.0, 1.0],
+            constraints,
+            &Budget::unlimited(),
+            &WarmStart::default(),
+            |_, _| ControlFlow::Continue(()),
+        )
+        .unwrap_err()
+    };
+    for constraints in [
+        Constraints::new(3).with_bounds(3, 0.0, 1.0),
+        Constraints::new(3).with_bounds(0, 1.0, 0.0),
+        Constraints::new(3).with_bounds(1, f64::NAN, 1.0),
+    ] {
+        assert_eq!(solve(&constraints).code(), 17, "{constraints:?}");
+    }
+    let mismatch = Error::DimensionMismatch {
+        expected: 3,
+        provided: 2,
+    };
+    let short_row = Constraints::new(3).with_equality(&[1.0, -1.0], 0.0);
+    assert_eq!(solve(&short_row), mismatch);
+    assert_eq!(solve(&Constraints::new(2)), mismatch);
+}
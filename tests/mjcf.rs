@@ -0,0 +1,119 @@
+use mathroborust::error::Error;
+use mathroborust::mjcf::{self, WORLD_LINK};
+use mathroborust::robot::JointKind;
+
+const ARM: &str = r#"
+<mujoco model="arm">
+  <compiler angle="degree"/>
+  <worldbody>
+    <geom type="plane" size="1 1 0.1"/>
+    <body name="base" pos="0 0 0.1">
+      <inertial pos="0 0 0.05" mass="4" diaginertia="0.1 0.1 0.05"/>
+      <body name="upper" pos="0 0 0.2" euler="0 0 90">
+        <joint name="shoulder" type="hinge" axis="0 0 2" range="-90 90"/>
+        <inertial pos="0.2 0 0" mass="1.5" fullinertia="0.01 0.02 0.03 0.001 0 0"/>
+        <body name="lower" pos="0.4 0 0">
+          <joint name="elbow" pos="0.1 0 0" axis="0 1 0"/>
+          <joint name="slide" type="slide" pos="0.1 0 0" axis="1 0 0" range="0 0.2"/>
+          <inertial pos="0.3 0 0" mass="0.5" diaginertia="0.001 0.002 0.002"/>
+          <body name="tool" pos="0.5 0 0"/>
+        </body>
+      </body>
+    </body>
+  </worldbody>
+</mujoco>
+"#;
+
+fn close(a: [f64; 3], b: [f64; 3]) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12)
+}
+
+#[test]
+fn imports_kinematic_tree_and_mass_properties() {
+    let robot = mjcf::parse(ARM).unwrap();
+    assert_eq!(robot.name(), "arm");
+    assert_eq!(robot.root().name, WORLD_LINK);
+    assert_eq!(robot.degrees_of_freedom(), 3);
+
+    let base = robot.joint("base_fixed").unwrap();
+    assert_eq!(base.kind, JointKind::Fixed);
+    assert!(close(base.origin.translation(), [0.0, 0.0, 0.1]));
+
+    let shoulder = robot.joint("shoulder").unwrap();
+    assert_eq!(
+        shoulder.kind,
+        JointKind::Revolute {
+            axis: [0.0, 0.0, 1.0]
+        }
+    );
+    let limits = shoulder.limits.unwrap();
+    assert!((limits.upper - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    assert!(close(
+        shoulder.origin.rotation().apply([1.0, 0.0, 0.0]),
+        [0.0, 1.0, 0.0]
+    ));
+
+    // Two joints in one body: an intermediate massless link, with the final
+    // link frame moved to the joint anchor.
+    let elbow = robot.joint("elbow").unwrap();
+    assert_eq!(elbow.child, "lower::elbow");
+    assert!(matches!(elbow.kind, JointKind::Continuous { .. }));
+    assert!(close(elbow.origin.translation(), [0.5, 0.0, 0.0]));
+    let slide = robot.joint("slide").unwrap();
+    assert_eq!(
+        (slide.parent.as_str(), slide.child.as_str()),
+        ("lower::elbow", "lower")
+    );
+    assert!(close(slide.origin.translation(), [0.0; 3]));
+    assert_eq!(slide.limits.unwrap().upper, 0.2);
+
+    let lower = robot.link("lower").unwrap().inertial.as_ref().unwrap();
+    assert!(close(lower.center_of_mass, [0.2, 0.0, 0.0]));
+    let tool = robot.joint("tool_fixed").unwrap();
+    assert!(close(tool.origin.translation(), [0.4, 0.0, 0.0]));
+
+    let upper = robot.link("upper").unwrap().inertial.as_ref().unwrap();
+    assert_eq!(upper.inertia[0][1], 0.001);
+}
+
+#[test]
+fn single_free_body_becomes_floating_root() {
+    let model = r#"
+<mujoco>
+  <compiler angle="radian" eulerseq="XYZ"/>
+  <worldbody>
+    <body name="torso" pos="0 0 1">
+      <freejoint/>
+      <body name="head" pos="0 0 0.3" euler="0 0 1.5707963267948966">
+        <joint name="neck" axis="0 0 1"/>
+      </body>
+    </body>
+  </worldbody>
+</mujoco>"#;
+    let robot = mjcf::parse(model).unwrap();
+    assert_eq!(robot.root().name, "torso");
+    assert!(robot.link(WORLD_LINK).is_none());
+    assert_eq!(robot.degrees_of_freedom(), 1);
+}
+
+#[test]
+fn unsupported_or_malformed_models_are_rejected() {
+    let ball = r#"<mujoco><worldbody><body><joint type="ball"/></body></worldbody></mujoco>"#;
+    let err = mjcf::parse(ball).unwrap_err();
+    assert!(matches!(err, Error::InvalidDescription(_)));
+    assert_eq!(err.code(), 13);
+
+    assert!(mjcf::parse("<mujoco><worldbody>").is_err());
+    assert!(mjcf::parse("<robot/>").is_err());
+    assert!(mjcf::parse("<mujoco/>").is_err());
+    let bad_pos = r#"<mujoco><worldbody><body pos="0 0"/></worldbody></mujoco>"#;
+    assert!(matches!(
+        mjcf::parse(bad_pos),
+        Err(Error::InvalidDescription(_))
+    ));
+    let infinite_pos = r#"<mujoco><worldbody><body pos="0 0 inf"/></worldbody></mujoco>"#;
+    assert!(matches!(
+        mjcf::parse(infinite_pos),
+        Err(Error::InvalidDescription(_))
+    ));
+}
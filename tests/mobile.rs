@@ -0,0 +1,123 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use mathroborust::Error;
+use mathroborust::mobile::{ModuleState, OmniDrive, OmniWheel, SwerveDrive, point_velocity};
+
+fn approx_eq(a: &[f64], b: &[f64]) {
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() < 1e-12, "expected {b:?}, got {a:?}");
+    }
+}
+
+fn swerve() -> SwerveDrive {
+    SwerveDrive::new(
+        vec![[0.3, 0.25], [-0.3, 0.25], [-0.3, -0.25], [0.3, -0.25]],
+        4.0,
+    )
+}
+
+#[test]
+fn swerve_modules_follow_rigid_body_velocity() {
+    let drive = swerve();
+    let strafe = drive.module_states([0.0, 0.0, 1.5]);
+    for state in &strafe {
+        assert!((state.angle - FRAC_PI_2).abs() < 1e-12 && (state.speed - 1.5).abs() < 1e-12);
+    }
+
+    // Spinning in place points every wheel tangentially.
+    let spin = drive.module_states([2.0, 0.0, 0.0]);
+    for (state, &[x, y]) in spin.iter().zip(drive.positions()) {
+        let [vx, vy] = point_velocity([2.0, 0.0, 0.0], [x, y]);
+        assert!((state.speed - vx.hypot(vy)).abs() < 1e-12);
+        assert!((state.angle.cos() * x + state.angle.sin() * y).abs() < 1e-12);
+    }
+
+    let twist = [0.7, 1.2, -0.4];
+    let recovered = drive.body_twist(&drive.module_states(twist)).unwrap();
+    approx_eq(&recovered, &twist);
+}
+
+#[test]
+fn swerve_states_optimize_and_desaturate() {
+    let state = ModuleState {
+        angle: 0.1,
+        speed: 2.0,
+    };
+    let flipped = state.optimized(PI);
+    assert!((flipped.angle - (0.1 - PI)).abs() < 1e-12);
+    assert_eq!(flipped.speed, -2.0);
+    assert_eq!(state.optimized(0.5), state);
+
+    let drive = swerve();
+    let fast = [6.0, 3.0, 0.0];
+    assert!(!drive.is_feasible(fast));
+    let limited = drive.desaturate(fast);
+    assert!(drive.is_feasible(limited));
+    let fastest = drive
+        .module_states(limited)
+        .iter()
+        .map(|s| s.speed)
+        .fold(0.0, f64::max);
+    assert!((fastest - 4.0).abs() < 1e-12);
+    assert!((limited[0] / limited[1] - 2.0).abs() < 1e-12);
+
+    let single = SwerveDrive::new(vec![[0.0, 0.0]], 1.0);
+    let states = single.module_states([0.0, 1.0, 0.0]);
+    assert_eq!(single.body_twist(&states), Err(Error::Singular));
+    assert_eq!(
+        drive.body_twist(&states),
+        Err(Error::InsufficientData {
+            required: 4,
+            provided: 1
+        })
+    );
+}
+
+#[test]
+fn mecanum_and_omni_drives_round_trip() {
+    let (a, b) = (0.2, 0.15);
+    let mecanum = OmniDrive::new(
+        vec![
+            OmniWheel::new([a, b], 0.0, -FRAC_PI_4),
+            OmniWheel::new([-a, b], 0.0, FRAC_PI_4),
+            OmniWheel::new([-a, -b], 0.0, -FRAC_PI_4),
+            OmniWheel::new([a, -b], 0.0, FRAC_PI_4),
+        ],
+        3.0,
+    );
+    // Strafing spins diagonal wheel pairs in opposite directions.
+    let speeds = mecanum.wheel_speeds([0.0, 0.0, 1.0]);
+    approx_eq(&speeds, &[1.0, -1.0, 1.0, -1.0]);
+    approx_eq(&mecanum.wheel_speeds([0.0, 1.0, 0.0]), &[1.0; 4]);
+
+    let twist = [0.5, -0.3, 0.8];
+    approx_eq(
+        &mecanum.body_twist(&mecanum.wheel_speeds(twist)).unwrap(),
+        &twist,
+    );
+
+    // Three omni wheels at 120° driving tangentially.
+    let kiwi = OmniDrive::new(
+        (0..3)
+            .map(|i| {
+                let angle = i as f64 * 2.0 * PI / 3.0;
+                OmniWheel::new(
+                    [0.2 * angle.cos(), 0.2 * angle.sin()],
+                    angle + FRAC_PI_2,
+                    0.0,
+                )
+            })
+            .collect(),
+        1.0,
+    );
+    approx_eq(&kiwi.wheel_speeds([1.0, 0.0, 0.0]), &[0.2; 3]);
+    approx_eq(&kiwi.body_twist(&kiwi.wheel_speeds(twist)).unwrap(), &twist);
+    assert_eq!(
+        kiwi.body_twist(&[1.0, 2.0]),
+        Err(Error::InsufficientData {
+            required: 3,
+            provided: 2
+        })
+    );
+    assert!(kiwi.is_feasible(kiwi.desaturate([10.0, 5.0, 0.0])));
+}
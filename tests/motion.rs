@@ -0,0 +1,235 @@
+use mathroborust::integrate::integrate_rkmk4;
+use mathroborust::lie::LieGroup;
+use mathroborust::motion::{
+    MotionCovariance, MotionState, compensate_velocity, constant_acceleration_noise, lever_arm,
+    transform_acceleration, transform_twist_rate,
+};
+use mathroborust::{Error, RustCmtm, RustSe3, RustSo3};
+use nalgebra::{Matrix4, Vector3};
+
+/// A smooth, non-screw body trajectory.
+fn pose_at(t: f64) -> RustSe3 {
+    let first = RustSe3::from_matrix(RustSe3::exp([0.3, -0.5, 0.8, 1.0, 0.2, -0.4], Some(t)));
+    let second = RustSe3::from_matrix(RustSe3::exp([0.0, 0.9, 0.1, 0.0, 0.5, 0.3], Some(t * t)));
+    first.compose(&second)
+}
+
+fn body_twist(t: f64, h: f64) -> [f64; 6] {
+    let rate: Matrix4<f64> = (pose_at(t + h).as_matrix() - pose_at(t - h).as_matrix()) / (2.0 * h);
+    let body = pose_at(t).inverse().as_matrix() * rate;
+    [
+        body[(2, 1)],
+        body[(0, 2)],
+        body[(1, 0)],
+        body[(0, 3)],
+        body[(1, 3)],
+        body[(2, 3)],
+    ]
+}
+
+fn mount() -> RustSe3 {
+    RustSe3::from_parts(RustSo3::from_euler_angles(0.2, -0.4, 1.0), [0.5, -0.2, 0.3])
+}
+
+#[test]
+fn lever_arm_matches_differentiated_sensor_trajectory() {
+    let t = 0.7;
+    let h = 1e-4;
+    let twist = body_twist(t, 1e-6);
+    let before = body_twist(t - h, 1e-6);
+    let after = body_twist(t + h, 1e-6);
+    let twist_rate: [f64; 6] = std::array::from_fn(|i| (after[i] - before[i]) / (2.0 * h));
+
+    let motion = lever_arm(&mount(), twist, twist_rate);
+
+    let sensor = |t: f64| pose_at(t).compose(&mount());
+    let position = |t: f64| Vector3::from(sensor(t).translation());
+    let world_velocity = (position(t + h) - position(t - h)) / (2.0 * h);
+    let world_acceleration = (position(t + h) - 2.0 * position(t) + position(t - h)) / (h * h);
+    let to_sensor = sensor(t).rotation().inverse();
+
+    let velocity = Vector3::from(to_sensor.apply(world_velocity.into()));
+    let acceleration = Vector3::from(to_sensor.apply(world_acceleration.into()));
+    assert!((Vector3::from(motion.velocity) - velocity).norm() < 1e-6);
+    assert!((Vector3::from(motion.acceleration) - acceleration).norm() < 1e-4);
+
+    let gyro = mount()
+        .rotation()
+        .inverse()
+        .apply([twist[0], twist[1], twist[2]]);
+    assert!((Vector3::from(motion.angular_velocity) - Vector3::from(gyro)).norm() < 1e-12);
+}
+
+#[test]
+fn lever_arm_reduces_to_centripetal_term_for_pure_spin() {
+    let offset = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [2.0, 0.0, 0.0]);
+    let motion = lever_arm(&offset, [0.0, 0.0, 3.0, 0.0, 0.0, 0.0], [0.0; 6]);
+    let expected_velocity = [0.0, 6.0, 0.0];
+    let expected_acceleration = [-18.0, 0.0, 0.0];
+    for (a, b) in motion.velocity.iter().zip(expected_velocity) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    for (a, b) in motion.acceleration.iter().zip(expected_acceleration) {
+        assert!((a - b).abs() < 1e-12);
+    }
+
+    // Compensating the antenna velocity recovers the body velocity.
+    let twist = [0.1, -0.3, 0.5, 1.0, 2.0, -0.5];
+    let measured = lever_arm(&mount(), twist, [0.0; 6]);
+    let body = compensate_velocity(&mount(), [twist[0], twist[1], twist[2]], measured.velocity);
+    for (a, b) in body.iter().zip(&twist[3..]) {
+        assert!((a - b).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn transform_acceleration_agrees_with_lever_arm_and_differs_from_adjoint() {
+    let twist = [0.4, -0.7, 1.1, 0.9, -0.2, 0.6];
+    let twist_rate = [0.3, 0.2, -0.5, -1.0, 0.4, 0.8];
+    let [wx, wy, wz, vx, vy, vz] = twist;
+    let coriolis = Vector3::new(wx, wy, wz).cross(&Vector3::new(vx, vy, vz));
+    let classical = [
+        twist_rate[0],
+        twist_rate[1],
+        twist_rate[2],
+        twist_rate[3] + coriolis.x,
+        twist_rate[4] + coriolis.y,
+        twist_rate[5] + coriolis.z,
+    ];
+
+    let sensor_from_body = mount().inverse();
+    let mapped = transform_acceleration(&sensor_from_body, twist, classical);
+    let motion = lever_arm(&mount(), twist, twist_rate);
+    for (a, b) in mapped[3..].iter().zip(motion.acceleration) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    for (a, b) in mapped[..3].iter().zip(motion.angular_acceleration) {
+        assert!((a - b).abs() < 1e-12);
+    }
+
+    // Treating the classical acceleration as adjoint-covariant drops the
+    // centripetal terms.
+    let naive = sensor_from_body.transform_twist(classical);
+    let gap: f64 = naive.iter().zip(mapped).map(|(a, b)| (a - b).powi(2)).sum();
+    assert!(gap.sqrt() > 0.1);
+}
+
+#[test]
+fn transform_twist_rate_includes_frame_motion() {
+    // A twist with its own time dependence, expressed in a moving frame.
+    let local = |t: f64| [0.2 * t, -0.4, 0.1 + t * t, 1.0, 0.5 * t, -0.3];
+    let expressed = |t: f64| pose_at(t).transform_twist(local(t));
+
+    let t = 0.4;
+    let h = 1e-5;
+    let local_rate: [f64; 6] =
+        std::array::from_fn(|i| (local(t + h)[i] - local(t - h)[i]) / (2.0 * h));
+    let (twist, rate) =
+        transform_twist_rate(&pose_at(t), body_twist(t, 1e-6), local(t), local_rate);
+
+    let expected = expressed(t);
+    let expected_rate: [f64; 6] =
+        std::array::from_fn(|i| (expressed(t + h)[i] - expressed(t - h)[i]) / (2.0 * h));
+    for i in 0..6 {
+        assert!((twist[i] - expected[i]).abs() < 1e-12);
+        assert!((rate[i] - expected_rate[i]).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn motion_state_round_trips_through_cmtm() {
+    let state = MotionState::new(
+        mount(),
+        [0.3, -0.1, 0.7, 1.2, 0.0, -0.5],
+        [0.05, 0.2, -0.3, 0.4, -0.6, 0.1],
+    );
+    let cmtm = RustCmtm::from(&state);
+    assert_eq!(cmtm.order(), 3);
+
+    let back = MotionState::try_from(&cmtm).unwrap();
+    assert_eq!(back.twist, state.twist);
+    assert_eq!(back.acceleration, state.acceleration);
+    let difference = back.pose.as_matrix() - state.pose.as_matrix();
+    assert!(difference.norm() < 1e-15);
+
+    // Missing derivatives read back as zero; extra ones cannot be represented.
+    let at_rest = MotionState::from_cmtm(&RustCmtm::from_se3(&mount())).unwrap();
+    assert_eq!(at_rest, MotionState::at_rest(at_rest.pose.clone()));
+    let deep = RustCmtm::from_se3_with_derivatives(&mount(), vec![[0.0; 6]; 3]);
+    assert!(matches!(
+        MotionState::from_cmtm(&deep),
+        Err(Error::OrderOutOfRange {
+            requested: 4,
+            available: 3
+        })
+    ));
+}
+
+fn moving_state() -> MotionState {
+    MotionState::new(
+        mount(),
+        [0.4, -0.3, 0.9, 1.5, 0.2, -0.6],
+        [-0.2, 0.5, 0.1, 0.3, -0.8, 0.4],
+    )
+}
+
+#[test]
+fn propagate_matches_integrated_trajectory() {
+    let state = moving_state();
+    let field = |t: f64, _: &RustSe3| -> [f64; 6] {
+        std::array::from_fn(|i| state.twist[i] + state.acceleration[i] * t)
+    };
+    for dt in [0.05, 0.2, 0.4] {
+        let propagated = state.propagate(dt);
+        let reference = integrate_rkmk4(&state.pose, 0.0, dt, 200, field);
+        let error = (propagated.pose.as_matrix() - reference.as_matrix()).norm();
+        assert!(error < 1e-2 * dt.powi(5), "dt {dt}: error {error}");
+        for i in 0..6 {
+            let twist = state.twist[i] + state.acceleration[i] * dt;
+            assert!((propagated.twist[i] - twist).abs() < 1e-15);
+        }
+        assert_eq!(propagated.acceleration, state.acceleration);
+    }
+}
+
+#[test]
+fn propagated_covariance_follows_perturbed_states() {
+    let state = moving_state();
+    let dt = 0.01;
+    let delta: [f64; 18] = std::array::from_fn(|i| 1e-6 * (1.0 + i as f64).sin());
+    let perturbed = MotionState::new(
+        state.pose.compose(&RustSe3::from_matrix(RustSe3::exp(
+            std::array::from_fn(|i| delta[i]),
+            None,
+        ))),
+        std::array::from_fn(|i| state.twist[i] + delta[6 + i]),
+        std::array::from_fn(|i| state.acceleration[i] + delta[12 + i]),
+    );
+
+    let nominal = state.propagate(dt);
+    let moved = perturbed.propagate(dt);
+    let pose_error = nominal.pose.inverse().compose(&moved.pose).log();
+    let error: [f64; 18] = std::array::from_fn(|i| match i {
+        0..6 => pose_error[i],
+        6..12 => moved.twist[i - 6] - nominal.twist[i - 6],
+        _ => moved.acceleration[i - 12] - nominal.acceleration[i - 12],
+    });
+
+    // With P = δδᵀ and no process noise the prediction is (Fδ)(Fδ)ᵀ.
+    let delta = nalgebra::SVector::<f64, 18>::from(delta);
+    let (_, predicted) = state.propagate_with_covariance(
+        dt,
+        &(delta * delta.transpose()),
+        &MotionCovariance::zeros(),
+    );
+    for i in 0..18 {
+        let spread = predicted[(i, i)].sqrt();
+        assert!((spread - error[i].abs()).abs() < 1e-10, "component {i}");
+    }
+
+    let noise = constant_acceleration_noise(dt, [2.0; 6]);
+    let (_, noisy) = state.propagate_with_covariance(dt, &MotionCovariance::zeros(), &noise);
+    assert_eq!(noisy, noise);
+    assert_eq!(noise[(12, 12)], 2.0 * dt);
+    assert!((noise[(0, 12)] - 2.0 * dt.powi(3) / 6.0).abs() < 1e-20);
+}
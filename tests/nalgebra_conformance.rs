@@ -0,0 +1,188 @@
+//! Conformance checks against nalgebra's `UnitQuaternion`, `Rotation3` and
+//! `Isometry3`. Where the conventions differ, the difference is spelled out as
+//! an executable assertion rather than prose.
+
+use mathroborust::lie::LieGroup;
+use mathroborust::{RustSe3, RustSo3};
+use nalgebra::{
+    Isometry3, Matrix3, Point3, Quaternion, Rotation3, Translation3, UnitQuaternion, Vector3,
+};
+
+const TOL: f64 = 1e-12;
+
+/// A deterministic spread of rotation vectors, including the identity, tiny
+/// angles and angles close to \(\pi\).
+fn rotation_vectors() -> Vec<[f64; 3]> {
+    vec![
+        [0.0, 0.0, 0.0],
+        [1e-10, -3e-10, 2e-10],
+        [0.3, -0.2, 0.1],
+        [0.0, 0.0, std::f64::consts::FRAC_PI_2],
+        [-1.2, 0.4, 2.0],
+        [3.0, 0.1, -0.05],
+        [0.5, -2.5, 1.0],
+    ]
+}
+
+fn translations() -> Vec<[f64; 3]> {
+    vec![[0.0, 0.0, 0.0], [1.0, -2.0, 0.5], [-0.25, 0.75, 3.0]]
+}
+
+fn to_isometry(transform: &RustSe3) -> Isometry3<f64> {
+    let [x, y, z] = transform.translation();
+    Isometry3::from_parts(
+        Translation3::new(x, y, z),
+        UnitQuaternion::from_rotation_matrix(transform.rotation().rotation()),
+    )
+}
+
+fn assert_matrix_close(actual: &Matrix3<f64>, expected: &Matrix3<f64>) {
+    assert!(
+        (actual - expected).abs().max() < TOL,
+        "expected {expected}, got {actual}"
+    );
+}
+
+fn assert_slice_close(actual: &[f64], expected: &[f64]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < TOL, "expected {e}, got {a}");
+    }
+}
+
+#[test]
+fn rotation_vector_matches_nalgebra_scaled_axis() {
+    for vector in rotation_vectors() {
+        let ours = RustSo3::from_rotation_vector(vector);
+        let theirs = UnitQuaternion::from_scaled_axis(Vector3::from(vector));
+        assert_matrix_close(
+            ours.rotation().matrix(),
+            theirs.to_rotation_matrix().matrix(),
+        );
+        assert_slice_close(&ours.to_rotation_vector(), theirs.scaled_axis().as_slice());
+    }
+}
+
+#[test]
+fn quaternion_layout_is_scalar_first() {
+    // So3 takes and returns [w, x, y, z]. nalgebra's `Quaternion::new` also
+    // takes the scalar first, but its `coords` storage is [x, y, z, w].
+    for vector in rotation_vectors() {
+        let theirs = UnitQuaternion::from_scaled_axis(Vector3::from(vector));
+        let coords = theirs.coords;
+        let wxyz = [coords[3], coords[0], coords[1], coords[2]];
+
+        let ours = RustSo3::from_quaternion(wxyz);
+        assert_matrix_close(
+            ours.rotation().matrix(),
+            theirs.to_rotation_matrix().matrix(),
+        );
+
+        let exported = ours.to_quaternion();
+        let rebuilt = Quaternion::new(exported[0], exported[1], exported[2], exported[3]);
+        // q and -q describe the same rotation; compare up to sign.
+        assert!((rebuilt.dot(theirs.quaternion()).abs() - 1.0).abs() < TOL);
+    }
+}
+
+#[test]
+fn euler_angles_follow_nalgebra_roll_pitch_yaw() {
+    // Both APIs build R = Rz(yaw) · Ry(pitch) · Rx(roll), i.e. roll about the
+    // fixed X axis is applied first.
+    let (roll, pitch, yaw) = (0.3, -0.7, 1.9);
+    let ours = RustSo3::from_euler_angles(roll, pitch, yaw);
+    let theirs = Rotation3::from_euler_angles(roll, pitch, yaw);
+    assert_matrix_close(ours.rotation().matrix(), theirs.matrix());
+
+    let composed = RustSo3::from_axis_angle([0.0, 0.0, 1.0], yaw)
+        * RustSo3::from_axis_angle([0.0, 1.0, 0.0], pitch)
+        * RustSo3::from_axis_angle([1.0, 0.0, 0.0], roll);
+    assert_matrix_close(ours.rotation().matrix(), composed.rotation().matrix());
+
+    let (r, p, y) = ours.to_euler_angles();
+    let (nr, np, ny) = theirs.euler_angles();
+    assert_slice_close(&[r, p, y], &[nr, np, ny]);
+}
+
+#[test]
+fn se3_group_operations_match_isometry3() {
+    for (i, vector) in rotation_vectors().into_iter().enumerate() {
+        for translation in translations() {
+            let a = RustSe3::from_parts(RustSo3::from_rotation_vector(vector), translation);
+            let other_vector = rotation_vectors()[(i + 3) % rotation_vectors().len()];
+            let b = RustSe3::from_parts(
+                RustSo3::from_rotation_vector(other_vector),
+                [0.5, 0.25, -1.0],
+            );
+            let (iso_a, iso_b) = (to_isometry(&a), to_isometry(&b));
+
+            let composed = to_isometry(&(&a * &b));
+            assert!(
+                (composed.to_homogeneous() - (iso_a * iso_b).to_homogeneous())
+                    .abs()
+                    .max()
+                    < TOL
+            );
+
+            let inverse = to_isometry(&a.inverse());
+            assert!(
+                (inverse.to_homogeneous() - iso_a.inverse().to_homogeneous())
+                    .abs()
+                    .max()
+                    < TOL
+            );
+
+            let point = [0.3, -1.1, 2.2];
+            let expected = iso_a.transform_point(&Point3::from(point));
+            assert_slice_close(&a.apply(point), expected.coords.as_slice());
+
+            let ours = a.as_matrix();
+            assert!((ours - iso_a.to_homogeneous()).abs().max() < TOL);
+        }
+    }
+}
+
+#[test]
+fn se3_exp_differs_from_isometry_new_except_in_special_cases() {
+    // `Isometry3::new(t, r)` uses `t` verbatim as the translation, whereas
+    // `Se3::exp([ω, v])` integrates the screw motion, so the translation is
+    // V(ω) v. The two only agree when ω = 0 or v is parallel to ω.
+    let omega = Vector3::new(0.0, 0.0, 1.0);
+    let along = Vector3::new(0.0, 0.0, 2.0);
+    let across = Vector3::new(1.0, 0.0, 0.0);
+
+    let pure_translation = RustSe3::exp([0.0, 0.0, 0.0, 1.0, 2.0, 3.0], None);
+    let iso = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::zeros());
+    assert_slice_close(
+        pure_translation.as_flattened(),
+        iso.to_homogeneous().transpose().as_slice(),
+    );
+
+    let twist = |v: Vector3<f64>| [omega.x, omega.y, omega.z, v.x, v.y, v.z];
+    let parallel = RustSe3::exp(twist(along), None);
+    let iso = Isometry3::new(along, omega);
+    assert_slice_close(
+        parallel.as_flattened(),
+        iso.to_homogeneous().transpose().as_slice(),
+    );
+
+    let screw = RustSe3::from_matrix(RustSe3::exp(twist(across), None));
+    let iso = Isometry3::new(across, omega);
+    let difference = Vector3::from(screw.translation()) - iso.translation.vector;
+    assert!(difference.norm() > 0.1);
+    assert_matrix_close(
+        screw.rotation().rotation().matrix(),
+        iso.rotation.to_rotation_matrix().matrix(),
+    );
+}
+
+#[test]
+fn so3_hat_matches_nalgebra_cross_matrix() {
+    let vector = Vector3::new(0.4, -1.3, 2.1);
+    let hat = RustSo3::hat([vector.x, vector.y, vector.z]);
+    let cross = vector.cross_matrix();
+    for (r, row) in hat.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            assert!((value - cross[(r, c)]).abs() < TOL);
+        }
+    }
+}
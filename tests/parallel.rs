@@ -0,0 +1,201 @@
+use std::ops::ControlFlow;
+
+use mathroborust::parallel::{DeltaRobot, StewartPlatform};
+use mathroborust::solver::{Budget, SolveStatus, WarmStart};
+use mathroborust::{Error, RustSe3, RustSo3};
+
+fn hexapod() -> StewartPlatform {
+    StewartPlatform::symmetric(1.0, 0.6, 0.3, 0.3)
+}
+
+fn home() -> RustSe3 {
+    RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [0.0, 0.0, 1.2])
+}
+
+#[test]
+fn stewart_forward_kinematics_inverts_leg_lengths() {
+    let platform = hexapod();
+    let target = RustSe3::from_parts(
+        RustSo3::from_euler_angles(0.1, -0.08, 0.2),
+        [0.05, -0.1, 1.3],
+    );
+    let lengths = platform.inverse_kinematics(&target);
+    assert!(lengths.iter().all(|l| *l > 1.0));
+
+    let solved = platform.forward_kinematics(lengths, &home()).unwrap();
+    for (a, b) in solved
+        .to_row_major_flat()
+        .iter()
+        .zip(target.to_row_major_flat())
+    {
+        assert!((a - b).abs() < 1e-10);
+    }
+
+    // The symmetric home pose has equal legs.
+    let home_lengths = platform.inverse_kinematics(&home());
+    assert!(
+        home_lengths
+            .iter()
+            .all(|l| (l - home_lengths[0]).abs() < 1e-12)
+    );
+}
+
+#[test]
+fn stewart_jacobian_matches_finite_differences() {
+    let platform = hexapod();
+    let pose = RustSe3::from_parts(RustSo3::from_euler_angles(0.05, 0.1, -0.1), [0.1, 0.0, 1.1]);
+    let jacobian = platform.jacobian(&pose);
+    let h = 1e-7;
+    for axis in 0..6 {
+        let mut delta = [0.0; 6];
+        delta[axis] = h;
+        let rotation = RustSo3::from_rotation_vector([delta[0], delta[1], delta[2]]);
+        let [x, y, z] = pose.translation();
+        let moved = RustSe3::from_parts(
+            rotation.compose(pose.rotation()),
+            [x + delta[3], y + delta[4], z + delta[5]],
+        );
+        let before = platform.inverse_kinematics(&pose);
+        let after = platform.inverse_kinematics(&moved);
+        for leg in 0..6 {
+            let estimate = (after[leg] - before[leg]) / h;
+            assert!((estimate - jacobian[(leg, axis)]).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn delta_robot_round_trips_through_kinematics() {
+    let robot = DeltaRobot::new(0.2, 0.05, 0.3, 0.8);
+    for target in [[0.0, 0.0, -0.6], [0.1, -0.05, -0.7], [-0.15, 0.12, -0.55]] {
+        let angles = robot.inverse_kinematics(target).unwrap();
+        let position = robot.forward_kinematics(angles).unwrap();
+        for (a, b) in position.iter().zip(target) {
+            assert!(
+                (a - b).abs() < 1e-12,
+                "expected {target:?}, got {position:?}"
+            );
+        }
+    }
+
+    // Equal joint angles keep the effector on the central axis.
+    let [x, y, z] = robot.forward_kinematics([0.4; 3]).unwrap();
+    assert!(x.abs() < 1e-12 && y.abs() < 1e-12 && z < 0.0);
+
+    assert_eq!(
+        robot.inverse_kinematics([0.0, 0.0, -2.0]),
+        Err(Error::Unreachable)
+    );
+    assert_eq!(Error::Unreachable.code(), 11);
+
+    // At 120° every upper arm folds its sphere centre onto the central axis.
+    let folded = 2.0 * std::f64::consts::PI / 3.0;
+    for angles in [[folded; 3], [folded, folded, 0.4], [0.4, folded, folded]] {
+        assert_eq!(robot.forward_kinematics(angles), Err(Error::Singular));
+    }
+    assert_eq!(
+        robot.forward_kinematics([0.4, f64::NAN, 0.4]),
+        Err(Error::NonFinite)
+    );
+}
+
+#[test]
+fn stewart_forward_kinematics_reports_shrinking_residuals() {
+    let platform = hexapod();
+    let target = RustSe3::from_parts(
+        RustSo3::from_euler_angles(0.05, 0.1, -0.1),
+        [-0.05, 0.02, 1.25],
+    );
+    let lengths = platform.inverse_kinematics(&target);
+    let mut residuals = Vec::new();
+    let report = platform.forward_kinematics_with(
+        lengths,
+        &home(),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, info| {
+            residuals.push(info.cost);
+            ControlFlow::Continue(())
+        },
+    );
+    assert_eq!(report.status, SolveStatus::Converged);
+    assert!(report.cost < 1e-12);
+    assert!(residuals.len() >= 2);
+    assert!(residuals.windows(2).all(|pair| pair[1] < pair[0]));
+
+    let stopped = platform.forward_kinematics_with(
+        lengths,
+        &home(),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Break(()),
+    );
+    assert_eq!(stopped.status, SolveStatus::Stopped);
+    assert_eq!(stopped.iterations, 1);
+    assert!(stopped.cost < residuals[0]);
+
+    let limited = platform.forward_kinematics_with(
+        lengths,
+        &home(),
+        &Budget::unlimited().with_max_iterations(1),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    );
+    assert_eq!(limited.status, SolveStatus::IterationLimit);
+    assert_eq!(limited.solution, stopped.solution);
+
+    // A platform with all legs meeting at one point cannot resolve rotations.
+    let degenerate = StewartPlatform::symmetric(1.0, 0.0, 0.3, 0.3);
+    let singular = degenerate.forward_kinematics_with(
+        degenerate.inverse_kinematics(&target),
+        &home(),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    );
+    assert_eq!(singular.status, SolveStatus::Singular);
+    assert_eq!(singular.iterations, 0);
+    assert_eq!(singular.solution, home());
+    assert!(singular.warm_start.is_cold());
+    assert_eq!(
+        degenerate.forward_kinematics(degenerate.inverse_kinematics(&target), &home()),
+        Err(Error::NotConverged { iterations: 0 })
+    );
+}
+
+#[test]
+fn stewart_tracking_reuses_the_warm_start() {
+    let platform = hexapod();
+    let mut pose = home();
+    let mut warm_start = WarmStart::default();
+    for k in 0..20 {
+        let t = k as f64 * 0.01;
+        let target = RustSe3::from_parts(
+            RustSo3::from_euler_angles(0.02 * t, -0.03 * t, 0.05 * t),
+            [0.01 * t, -0.02 * t, 1.2 + 0.05 * t],
+        );
+        let lengths = platform.inverse_kinematics(&target);
+        let report = platform.forward_kinematics_with(
+            lengths,
+            &pose,
+            &Budget::unlimited(),
+            &warm_start,
+            |_, _| ControlFlow::Continue(()),
+        );
+        assert_eq!(report.status, SolveStatus::Converged);
+        let cold = platform.forward_kinematics(lengths, &pose).unwrap();
+        for (a, b) in report
+            .solution
+            .to_row_major_flat()
+            .iter()
+            .zip(cold.to_row_major_flat())
+        {
+            assert!((a - b).abs() < 1e-10);
+        }
+        pose = report.solution;
+        warm_start = report.warm_start;
+        if k > 0 {
+            assert!(!warm_start.is_cold());
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use mathroborust::RustSo3;
+use mathroborust::parameterization::{
+    EulerManifold, Parameterization, QuaternionManifold, RotationVectorManifold,
+};
+use nalgebra::{Matrix3, SMatrix};
+
+fn rotation() -> RustSo3 {
+    RustSo3::from_euler_angles(0.4, -0.3, 2.2)
+}
+
+/// Check plus/minus consistency and both Jacobians against central
+/// differences for one parameterization.
+fn check<P: Parameterization<N>, const N: usize>() {
+    let x = P::to_parameters(&rotation());
+    let delta = [0.03, -0.02, 0.05];
+
+    let y = P::plus(x, delta);
+    let recovered = P::minus(y, x);
+    for (a, b) in recovered.iter().zip(delta) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    let expected = rotation().compose(&RustSo3::from_rotation_vector(delta));
+    let difference = P::from_parameters(y).inverse().compose(&expected);
+    assert!(
+        difference
+            .to_rotation_vector()
+            .iter()
+            .all(|v| v.abs() < 1e-12)
+    );
+
+    let h = 1e-6;
+    let mut plus = SMatrix::<f64, N, 3>::zeros();
+    let mut minus = SMatrix::<f64, 3, N>::zeros();
+    for k in 0..3 {
+        let mut step = [0.0; 3];
+        step[k] = h;
+        let forward = P::plus(x, step);
+        step[k] = -h;
+        let backward = P::plus(x, step);
+        for i in 0..N {
+            plus[(i, k)] = (forward[i] - backward[i]) / (2.0 * h);
+        }
+    }
+    for k in 0..N {
+        let mut forward = x;
+        let mut backward = x;
+        forward[k] += h;
+        backward[k] -= h;
+        let (f, b) = (P::minus(forward, x), P::minus(backward, x));
+        for i in 0..3 {
+            minus[(i, k)] = (f[i] - b[i]) / (2.0 * h);
+        }
+    }
+    assert!((P::plus_jacobian(x) - plus).norm() < 1e-8);
+    assert!((P::minus_jacobian(x) * P::plus_jacobian(x) - Matrix3::identity()).norm() < 1e-12);
+    if N == 3 {
+        assert!((P::minus_jacobian(x) - minus).norm() < 1e-8);
+    }
+}
+
+#[test]
+fn rotation_vector_manifold_is_consistent() {
+    check::<RotationVectorManifold, 3>();
+}
+
+#[test]
+fn quaternion_manifold_is_consistent() {
+    check::<QuaternionManifold, 4>();
+    let x = QuaternionManifold::to_parameters(&rotation());
+    let y = QuaternionManifold::plus(x, [0.1, 0.2, -0.3]);
+    let norm: f64 = y.iter().map(|v| v * v).sum();
+    assert!((norm - 1.0).abs() < 1e-15);
+}
+
+#[test]
+fn euler_manifold_is_consistent() {
+    check::<EulerManifold, 3>();
+}
+
+#[test]
+fn right_jacobian_inverse_matches_series_near_identity() {
+    for vector in [[1e-7, -2e-7, 3e-8], [0.8, -1.1, 0.6]] {
+        let product = RustSo3::right_jacobian(vector) * RustSo3::right_jacobian_inverse(vector);
+        assert!((product - Matrix3::identity()).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn left_jacobian_matches_finite_differences() {
+    let phi = [0.4, -0.7, 0.2];
+    let left = RustSo3::left_jacobian(phi);
+    let step = 1e-6;
+    for k in 0..3 {
+        let mut perturbed = phi;
+        perturbed[k] += step;
+        // Exp(φ + δ) Exp(φ)⁻¹ ≈ Exp(J_l δ).
+        let relative = RustSo3::from_rotation_vector(perturbed)
+            .compose(&RustSo3::from_rotation_vector(phi).inverse())
+            .to_rotation_vector();
+        for (row, value) in relative.iter().enumerate() {
+            assert!((value / step - left[(row, k)]).abs() < 1e-5);
+        }
+    }
+    let product = left * RustSo3::left_jacobian_inverse(phi);
+    assert!((product - Matrix3::identity()).norm() < 1e-12);
+    let rotation = RustSo3::from_rotation_vector(phi);
+    assert!((left - rotation.rotation().matrix() * RustSo3::right_jacobian(phi)).norm() < 1e-12);
+}
@@ -0,0 +1,218 @@
+use mathroborust::camera::CameraIntrinsics;
+use mathroborust::error::Error;
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use mathroborust::pnp::{p3p, refine_pnp, refine_pnp_with, reprojection_error, solve_pnp};
+use mathroborust::se3::Se3;
+use mathroborust::solver::{Budget, SolveStatus, WarmStart};
+
+fn intrinsics() -> CameraIntrinsics {
+    CameraIntrinsics::new(600.0, 610.0, 320.0, 240.0)
+}
+
+fn camera_from_world() -> Se3 {
+    Se3::from_axis_angle_translation([0.2, 1.0, -0.3], 0.6, [0.1, -0.2, 4.0])
+}
+
+fn world_points() -> Vec<[f64; 3]> {
+    vec![
+        [-1.0, -0.5, 0.2],
+        [1.2, -0.4, -0.3],
+        [0.3, 0.9, 0.5],
+        [-0.7, 0.6, -0.6],
+        [0.8, 0.7, 0.1],
+        [0.0, -1.0, -0.2],
+        [-0.2, 0.1, 0.9],
+    ]
+}
+
+fn pixels(pose: &Se3, world: &[[f64; 3]]) -> Vec<[f64; 2]> {
+    world
+        .iter()
+        .map(|&p| intrinsics().project(pose.apply(p)).unwrap())
+        .collect()
+}
+
+fn distance(a: &Se3, b: &Se3) -> f64 {
+    let difference = a.inverse().compose(b);
+    let [x, y, z] = difference.translation();
+    let [rx, ry, rz] = difference.rotation().to_rotation_vector();
+    (x * x + y * y + z * z + rx * rx + ry * ry + rz * rz).sqrt()
+}
+
+#[test]
+fn p3p_candidates_include_the_true_pose() {
+    let truth = camera_from_world();
+    let world = world_points();
+    let triplet = [world[0], world[1], world[2]];
+    let observed = pixels(&truth, &triplet);
+    let bearings = [0, 1, 2].map(|i| intrinsics().bearing(observed[i]));
+    let candidates = p3p(&triplet, &bearings);
+    assert!(!candidates.is_empty() && candidates.len() <= 4);
+    assert!(
+        candidates.iter().any(|pose| distance(pose, &truth) < 1e-8),
+        "{candidates:?}"
+    );
+}
+
+#[test]
+fn solve_pnp_recovers_pose_and_refinement_handles_noise() {
+    let truth = camera_from_world();
+    let world = world_points();
+    let observed = pixels(&truth, &world);
+    let pose = solve_pnp(&world, &observed, &intrinsics()).unwrap();
+    assert!(distance(&pose, &truth) < 1e-9);
+    assert!(reprojection_error(&pose, &world, &observed, &intrinsics()) < 1e-12);
+
+    // Half-pixel noise: refinement from a perturbed start lands near the truth
+    // and lowers the reprojection error below that of the true pose.
+    let noisy: Vec<[f64; 2]> = observed
+        .iter()
+        .enumerate()
+        .map(|(i, &[u, v])| [u + 0.5 * (i as f64).sin(), v - 0.5 * (i as f64).cos()])
+        .collect();
+    let start =
+        Se3::from_axis_angle_translation([1.0, 0.0, 0.0], 0.05, [0.05, 0.0, 0.1]).compose(&truth);
+    let refined = refine_pnp(&start, &world, &noisy, &intrinsics()).unwrap();
+    assert!(distance(&refined, &truth) < 2e-2);
+    assert!(
+        reprojection_error(&refined, &world, &noisy, &intrinsics())
+            <= reprojection_error(&truth, &world, &noisy, &intrinsics())
+    );
+}
+
+#[test]
+fn pnp_needs_four_correspondences() {
+    let world = world_points();
+    let observed = pixels(&camera_from_world(), &world);
+    assert_eq!(
+        solve_pnp(&world[..3], &observed[..3], &intrinsics()),
+        Err(Error::InsufficientData {
+            required: 4,
+            provided: 3
+        })
+    );
+    assert_eq!(intrinsics().project([0.0, 0.0, -1.0]), None);
+}
+
+#[test]
+fn refinement_reports_iterations_and_stops_on_request() {
+    let world = world_points();
+    let observed = pixels(&camera_from_world(), &world);
+    let start = Se3::from_axis_angle_translation([0.0, 1.0, 0.0], 0.05, [0.0, 0.0, 0.1])
+        .compose(&camera_from_world());
+
+    let mut costs = Vec::new();
+    let refined = refine_pnp_with(
+        &start,
+        &world,
+        &observed,
+        &intrinsics(),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, info| {
+            costs.push((info.iteration, info.cost));
+            ControlFlow::Continue(())
+        },
+    )
+    .unwrap();
+    assert_eq!(refined.status, SolveStatus::Converged);
+    assert_eq!(refined.iterations, costs.len());
+    assert!(distance(&refined.solution, &camera_from_world()) < 1e-8);
+    assert!(costs.len() > 1);
+    assert!(
+        costs
+            .iter()
+            .enumerate()
+            .all(|(i, &(iteration, _))| i == iteration)
+    );
+    assert!(costs.windows(2).all(|pair| pair[1].1 <= pair[0].1));
+
+    // Stopping after the first iteration returns that estimate.
+    let mut calls = 0;
+    let early = refine_pnp_with(
+        &start,
+        &world,
+        &observed,
+        &intrinsics(),
+        &Budget::unlimited(),
+        &WarmStart::default(),
+        |_, _| {
+            calls += 1;
+            ControlFlow::Break(())
+        },
+    )
+    .unwrap();
+    assert_eq!(calls, 1);
+    assert_eq!(early.status, SolveStatus::Stopped);
+    let cost = reprojection_error(&early.solution, &world, &observed, &intrinsics());
+    assert!((cost - costs[0].1).abs() <= 1e-9 * costs[0].1.max(1.0));
+}
+
+#[test]
+fn exhausted_budgets_return_the_best_pose_so_far() {
+    let world = world_points();
+    let observed = pixels(&camera_from_world(), &world);
+    let start = Se3::from_axis_angle_translation([0.0, 1.0, 0.0], 0.05, [0.0, 0.0, 0.1])
+        .compose(&camera_from_world());
+    let initial_cost = reprojection_error(&start, &world, &observed, &intrinsics());
+
+    let budget = Budget::unlimited().with_max_iterations(2);
+    let report = refine_pnp_with(
+        &start,
+        &world,
+        &observed,
+        &intrinsics(),
+        &budget,
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    )
+    .unwrap();
+    assert_eq!(report.status, SolveStatus::IterationLimit);
+    assert_eq!(report.iterations, 2);
+    assert!(report.cost < initial_cost);
+
+    let budget = Budget::unlimited().with_max_duration(Duration::ZERO);
+    let report = refine_pnp_with(
+        &start,
+        &world,
+        &observed,
+        &intrinsics(),
+        &budget,
+        &WarmStart::default(),
+        |_, _| ControlFlow::Continue(()),
+    )
+    .unwrap();
+    assert_eq!(report.status, SolveStatus::TimeLimit);
+    assert_eq!(report.iterations, 0);
+    assert_eq!(report.solution, start);
+}
+
+#[test]
+fn warm_started_refinement_tracks_a_moving_camera() {
+    let world = world_points();
+    let mut pose = camera_from_world();
+    let mut warm_start = WarmStart::default();
+    for k in 1..6 {
+        let motion =
+            Se3::from_axis_angle_translation([0.0, 1.0, 0.2], 0.01 * k as f64, [0.02, 0.0, 0.01]);
+        let truth = motion.compose(&camera_from_world());
+        let observed = pixels(&truth, &world);
+        let report = refine_pnp_with(
+            &pose,
+            &world,
+            &observed,
+            &intrinsics(),
+            &Budget::unlimited(),
+            &warm_start,
+            |_, _| ControlFlow::Continue(()),
+        )
+        .unwrap();
+        assert_eq!(report.status, SolveStatus::Converged);
+        assert!(distance(&report.solution, &truth) < 1e-8);
+        assert!(!report.warm_start.is_cold());
+        pose = report.solution;
+        warm_start = report.warm_start;
+    }
+}
@@ -0,0 +1,81 @@
+use mathroborust::quat::Quat;
+use mathroborust::{Error, So3};
+
+fn approx_eq(a: &[f64], b: &[f64], tol: f64) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() < tol, "expected {y}, got {x}");
+    }
+}
+
+#[test]
+fn hamilton_product_composes_rotations() {
+    let p = Quat::from_axis_angle([1.0, 2.0, -1.0], 0.8);
+    let q = Quat::from_axis_angle([0.0, -1.0, 3.0], -1.4);
+    let (rp, rq) = (p.to_so3(), q.to_so3());
+    let product = (p * q).to_so3().to_matrix();
+    approx_eq(
+        product.as_flattened(),
+        rp.compose(&rq).to_matrix().as_flattened(),
+        1e-14,
+    );
+    let sign = (p * q).w.signum();
+    approx_eq(
+        &Quat::from(&rp.compose(&rq)).to_wxyz(),
+        &(p * q).to_wxyz().map(|c| c * sign),
+        1e-15,
+    );
+
+    let v = [0.3, -1.2, 2.0];
+    approx_eq(&p.rotate(v), &rp.apply(v), 1e-14);
+    approx_eq(&(p * p.conjugate()).to_wxyz(), &[1.0, 0.0, 0.0, 0.0], 1e-15);
+    assert_eq!(Quat::identity() * q, q);
+    // i j = k.
+    let (i, j) = (Quat::new(0.0, 1.0, 0.0, 0.0), Quat::new(0.0, 0.0, 1.0, 0.0));
+    assert_eq!(i * j, Quat::new(0.0, 0.0, 0.0, 1.0));
+}
+
+#[test]
+fn normalize_rejects_degenerate_quaternions() {
+    let q = Quat::new(2.0, 0.0, 0.0, 0.0).normalize().unwrap();
+    assert_eq!(q, Quat::identity());
+    assert_eq!(
+        Quat::new(0.0, 0.0, 0.0, 0.0).normalize(),
+        Err(Error::ZeroNorm)
+    );
+    assert_eq!(
+        Quat::new(f64::NAN, 1.0, 0.0, 0.0).normalize(),
+        Err(Error::NonFinite)
+    );
+}
+
+#[test]
+fn slerp_follows_the_short_geodesic() {
+    let start = Quat::from_axis_angle([0.0, 0.0, 1.0], 0.2);
+    let end = Quat::from_axis_angle([0.0, 0.0, 1.0], 1.4);
+    let middle = start.slerp(&end, 0.25);
+    approx_eq(
+        &middle.to_wxyz(),
+        &Quat::from_axis_angle([0.0, 0.0, 1.0], 0.5).to_wxyz(),
+        1e-15,
+    );
+
+    // The antipodal representative of `end` gives the same path.
+    let flipped = Quat::new(-end.w, -end.x, -end.y, -end.z);
+    approx_eq(
+        &start.slerp(&flipped, 0.25).to_wxyz(),
+        &middle.to_wxyz(),
+        1e-15,
+    );
+
+    // Agrees with the geodesic on SO(3) for general axes.
+    let a = So3::from_axis_angle([1.0, -1.0, 0.5], 2.0);
+    let b = So3::from_axis_angle([0.2, 0.4, -1.0], 1.1);
+    let slerped = Quat::from(&a).slerp(&Quat::from(&b), 0.6).to_so3();
+    approx_eq(
+        slerped.to_matrix().as_flattened(),
+        a.interpolate(&b, 0.6).to_matrix().as_flattened(),
+        1e-14,
+    );
+    assert_eq!(start.slerp(&start, 0.5), start);
+}
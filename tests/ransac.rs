@@ -0,0 +1,115 @@
+use mathroborust::error::Error;
+use mathroborust::ransac::{RansacOptions, ransac};
+use mathroborust::registration::{
+    PlanarRegistration, PlanarTransform, RigidRegistration, fit_planar, fit_rigid,
+};
+use mathroborust::se3::Se3;
+
+fn grid() -> Vec<[f64; 3]> {
+    (0..20)
+        .map(|i| {
+            let i = i as f64;
+            [i.sin(), (1.7 * i).cos(), 0.1 * i - 1.0]
+        })
+        .collect()
+}
+
+#[test]
+fn rigid_fit_recovers_exact_transform_without_reflection() {
+    let truth = Se3::from_axis_angle_translation([1.0, 2.0, -0.5], 2.4, [0.3, -1.0, 2.0]);
+    let source = grid();
+    let target: Vec<_> = source.iter().map(|&p| truth.apply(p)).collect();
+    let fitted = fit_rigid(&source, &target).unwrap();
+    for (s, t) in source.iter().zip(&target) {
+        let p = fitted.apply(*s);
+        assert!((0..3).all(|k| (p[k] - t[k]).abs() < 1e-12));
+    }
+
+    let collinear = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]];
+    assert_eq!(fit_rigid(&collinear, &collinear), Err(Error::Singular));
+    assert_eq!(
+        fit_rigid(&source[..2], &target[..2]).unwrap_err().code(),
+        15
+    );
+}
+
+#[test]
+fn rigid_ransac_rejects_outlier_correspondences() {
+    let truth = Se3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.8, [1.0, 0.0, -0.5]);
+    let source = grid();
+    let mut target: Vec<_> = source.iter().map(|&p| truth.apply(p)).collect();
+    let corrupted = [2, 5, 11, 17, 18];
+    for &i in &corrupted {
+        target[i][0] += 3.0 + i as f64;
+    }
+
+    let problem = RigidRegistration {
+        source: &source,
+        target: &target,
+    };
+    let result = ransac(&problem, &RansacOptions::new(1e-6)).unwrap();
+    assert_eq!(result.inlier_count, source.len() - corrupted.len());
+    for (i, inlier) in result.inliers.iter().enumerate() {
+        assert_eq!(*inlier, !corrupted.contains(&i));
+    }
+    let p = result.model.apply(source[0]);
+    assert!((0..3).all(|k| (p[k] - target[0][k]).abs() < 1e-9));
+    assert!(result.iterations < 1000);
+
+    let again = ransac(&problem, &RansacOptions::new(1e-6)).unwrap();
+    assert_eq!(again, result);
+}
+
+#[test]
+fn planar_ransac_estimates_se2_pose() {
+    let truth = PlanarTransform {
+        angle: -2.1,
+        translation: [0.5, 4.0],
+    };
+    let source: Vec<[f64; 2]> = (0..30)
+        .map(|i| [(i % 6) as f64, (i / 6) as f64 * 0.5])
+        .collect();
+    let mut target: Vec<_> = source.iter().map(|&p| truth.apply(p)).collect();
+    for i in (0..30).step_by(4) {
+        target[i] = [10.0 - i as f64, i as f64];
+    }
+
+    let exact = fit_planar(&source[1..4], &target[1..4]).unwrap();
+    assert!((exact.angle - truth.angle).abs() < 1e-12);
+
+    let mut options = RansacOptions::new(1e-6);
+    options.max_iterations = 200;
+    let problem = PlanarRegistration {
+        source: &source,
+        target: &target,
+    };
+    let result = ransac(&problem, &options).unwrap();
+    assert_eq!(result.inlier_count, 30 - 8);
+    assert!((result.model.angle - truth.angle).abs() < 1e-9);
+    assert!((result.model.translation[1] - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn ransac_needs_a_minimal_sample() {
+    let points = [[0.0; 3]; 2];
+    let problem = RigidRegistration {
+        source: &points,
+        target: &points,
+    };
+    assert_eq!(
+        ransac(&problem, &RansacOptions::new(0.1)),
+        Err(Error::InsufficientData {
+            required: 3,
+            provided: 2
+        })
+    );
+    let coincident = [[1.0; 3]; 5];
+    let problem = RigidRegistration {
+        source: &coincident,
+        target: &coincident,
+    };
+    assert!(matches!(
+        ransac(&problem, &RansacOptions::new(0.1)),
+        Err(Error::NotConverged { .. })
+    ));
+}
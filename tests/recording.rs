@@ -0,0 +1,264 @@
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use mathroborust::Error;
+use mathroborust::frame::{FrameTree, SharedFrameTree};
+use mathroborust::recording::{FrameEvent, FrameLog, FrameRecord, FrameRecorder, replay};
+use mathroborust::se3::Se3;
+
+fn pose(yaw: f64, translation: [f64; 3]) -> Se3 {
+    Se3::from_axis_angle_translation([0.0, 0.0, 1.0], yaw, translation)
+}
+
+fn close(a: &Se3, b: &Se3, tol: f64) -> bool {
+    let (a, b) = (a.to_matrix(), b.to_matrix());
+    (0..4).all(|r| (0..4).all(|c| (a[r][c] - b[r][c]).abs() < tol))
+}
+
+/// Writer into a shared buffer that fails while `fail` is set.
+#[derive(Clone, Default)]
+struct SharedBuffer {
+    bytes: Arc<Mutex<Vec<u8>>>,
+    fail: Arc<Mutex<bool>>,
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if *self.fail.lock().unwrap() {
+            return Err(io::Error::other("disk full"));
+        }
+        self.bytes.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn session() -> Vec<u8> {
+    let mut recorder = FrameRecorder::new(Vec::new()).unwrap();
+    recorder
+        .record_set(0.0, "map", "base", &pose(0.1, [1.0, 0.0, 0.0]))
+        .unwrap();
+    recorder
+        .record_set(0.01, "base", "lidar", &pose(-0.4, [0.2, 0.0, 0.5]))
+        .unwrap();
+    recorder
+        .record_set(0.02, "map", "base", &pose(0.2, [1.1, 0.0, 0.0]))
+        .unwrap();
+    recorder.record_remove(0.03, "base", "lidar").unwrap();
+    recorder.into_inner()
+}
+
+#[test]
+fn recorded_updates_read_back_in_order() {
+    let bytes = session();
+    // Names are written once: header, two definitions per new pair, and the
+    // repeated "map"/"base" set costs only its fixed-size record.
+    let fixed_set = 1 + 8 + 4 + 4 + 7 * 8;
+    assert_eq!(
+        bytes.len(),
+        6 + (5 + 3 + 5 + 4) + (5 + 5) + 3 * fixed_set + (1 + 8 + 4 + 4)
+    );
+
+    let records: Vec<_> = FrameLog::new(&bytes)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[1].time, 0.01);
+    match &records[2].event {
+        FrameEvent::Set {
+            parent,
+            child,
+            parent_from_child,
+        } => {
+            assert_eq!((parent.as_str(), child.as_str()), ("map", "base"));
+            assert!(close(parent_from_child, &pose(0.2, [1.1, 0.0, 0.0]), 1e-15));
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    assert_eq!(
+        records[3].event,
+        FrameEvent::Remove {
+            parent: "base".to_string(),
+            child: "lidar".to_string()
+        }
+    );
+}
+
+#[test]
+fn replay_rebuilds_the_tree_at_the_requested_speed() {
+    let bytes = session();
+    let mut tree = FrameTree::new();
+    let mut lidar_seen = Vec::new();
+    let start = Instant::now();
+    let count = replay(FrameLog::new(&bytes).unwrap(), &mut tree, 3.0, |_, tree| {
+        lidar_seen.push(tree.transform("map", "lidar").is_ok());
+    })
+    .unwrap();
+    // 30 ms of log at triple speed.
+    assert!(start.elapsed().as_secs_f64() >= 0.009);
+    assert_eq!(count, 4);
+    assert_eq!(lidar_seen, [false, true, true, false]);
+    assert!(close(
+        &tree.transform("map", "base").unwrap(),
+        &pose(0.2, [1.1, 0.0, 0.0]),
+        1e-15
+    ));
+
+    let mut fresh = FrameTree::new();
+    let count = replay(
+        FrameLog::new(&bytes).unwrap(),
+        &mut fresh,
+        f64::INFINITY,
+        |_, _| {},
+    )
+    .unwrap();
+    assert_eq!(count, 4);
+}
+
+#[test]
+fn malformed_logs_are_reported() {
+    assert!(matches!(
+        FrameLog::new(b"JUNK\x01\x00"),
+        Err(Error::InvalidEncoding(_))
+    ));
+    assert!(matches!(
+        FrameLog::new(b"MRFL\x02\x00"),
+        Err(Error::InvalidEncoding(_))
+    ));
+
+    // A log cut mid-record yields the complete records, then one error.
+    let bytes = session();
+    let truncated = &bytes[..bytes.len() - 3];
+    let results: Vec<_> = FrameLog::new(truncated).unwrap().collect();
+    assert_eq!(results.len(), 4);
+    assert!(results[..3].iter().all(Result::is_ok));
+    let error = results[3].clone().unwrap_err();
+    assert_eq!(error.code(), 16);
+}
+
+#[test]
+fn failed_writes_leave_the_log_readable() {
+    let buffer = SharedBuffer::default();
+    let mut recorder = FrameRecorder::new(buffer.clone()).unwrap();
+    *buffer.fail.lock().unwrap() = true;
+    assert!(
+        recorder
+            .record_set(0.0, "map", "base", &pose(0.1, [1.0, 0.0, 0.0]))
+            .is_err()
+    );
+    *buffer.fail.lock().unwrap() = false;
+    // The names of the failed record are defined again on retry.
+    recorder
+        .record_set(0.1, "map", "base", &pose(0.1, [1.0, 0.0, 0.0]))
+        .unwrap();
+    recorder.record_remove(0.2, "map", "base").unwrap();
+
+    let bytes = buffer.bytes.lock().unwrap().clone();
+    let records: Vec<_> = FrameLog::new(&bytes)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].time, 0.1);
+}
+
+#[test]
+fn replay_rejects_timestamps_it_cannot_wait_for() {
+    let remove = |time| {
+        Ok(FrameRecord {
+            time,
+            event: FrameEvent::Remove {
+                parent: "map".to_string(),
+                child: "base".to_string(),
+            },
+        })
+    };
+    for jump in [1e300, -1.0, f64::NAN] {
+        let mut tree = FrameTree::new();
+        let mut applied = 0;
+        let result = replay([remove(0.0), remove(jump)], &mut tree, 1.0, |_, _| {
+            applied += 1;
+        });
+        assert!(matches!(result, Err(Error::InvalidEncoding(_))), "{jump}");
+        assert_eq!(applied, 1);
+    }
+}
+
+#[test]
+fn replay_rejects_timestamps_that_go_back() {
+    let remove = |time| {
+        Ok(FrameRecord {
+            time,
+            event: FrameEvent::Remove {
+                parent: "map".to_string(),
+                child: "base".to_string(),
+            },
+        })
+    };
+    for speed in [1.0, f64::INFINITY] {
+        let mut tree = FrameTree::new();
+        let mut applied = 0;
+        let records = [remove(0.0), remove(0.02), remove(0.01), remove(0.03)];
+        let result = replay(records, &mut tree, speed, |_, _| applied += 1);
+        assert!(matches!(result, Err(Error::InvalidEncoding(_))), "{speed}");
+        assert_eq!(applied, 2);
+    }
+}
+
+#[test]
+fn shared_trees_record_every_accepted_edit() {
+    let buffer = SharedBuffer::default();
+    let mut tree = FrameTree::new();
+    tree.set_transform("map", "base", pose(0.1, [1.0, 0.0, 0.0]))
+        .unwrap();
+    let shared = SharedFrameTree::new(tree);
+    shared.start_recording(buffer.clone()).unwrap();
+
+    let handle = shared.clone();
+    std::thread::spawn(move || {
+        handle
+            .set_transform("base", "lidar", pose(-0.4, [0.2, 0.0, 0.5]))
+            .unwrap();
+    })
+    .join()
+    .unwrap();
+    // Rejected edits and removals of missing edges are not logged.
+    shared
+        .set_transform("map", "lidar", pose(1.0, [0.0; 3]))
+        .unwrap_err();
+    assert!(shared.remove_transform("map", "camera").is_none());
+    shared.update(|tree| tree.remove_transform("base", "lidar"));
+    shared.stop_recording().unwrap();
+    // Edits after stopping are not logged either.
+    shared
+        .set_transform("base", "camera", pose(0.0, [0.1, 0.0, 0.0]))
+        .unwrap();
+
+    let bytes = buffer.bytes.lock().unwrap().clone();
+    let records: Vec<_> = FrameLog::new(&bytes)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].time, 0.0);
+    assert!(records.windows(2).all(|w| w[0].time <= w[1].time));
+
+    let mut replayed = FrameTree::new();
+    replay(
+        records.into_iter().map(Ok),
+        &mut replayed,
+        f64::INFINITY,
+        |_, _| {},
+    )
+    .unwrap();
+    assert!(close(
+        &replayed.transform("map", "base").unwrap(),
+        &pose(0.1, [1.0, 0.0, 0.0]),
+        1e-15
+    ));
+    assert!(replayed.transform("map", "lidar").is_err());
+}
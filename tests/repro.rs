@@ -1,8 +1,13 @@
 use std::f64::consts::FRAC_PI_2;
+use std::sync::Arc;
 
+use mathroborust::frame::Transform;
 use mathroborust::lie::LieGroup;
 use mathroborust::util::{skew_symmetric, vector3_from_array};
-use mathroborust::{RotationalCmtm, RustCmtm, RustSe3, RustSo3};
+use mathroborust::{
+    ArcSe3, CmtmN, Error, EulerOrder, LookAtConvention, RotationalCmtm, RustCmtm, RustSe3, RustSo3,
+    SensorExtrinsic, SensorRig,
+};
 use nalgebra::{DMatrix, SMatrix, SVector};
 
 fn approx_eq(a: &[f64], b: &[f64], tol: f64) {
@@ -281,3 +286,802 @@ fn se3_from_matrix_round_trip() {
     );
     approx_eq(&transform.translation(), &rebuilt.translation(), 1e-12);
 }
+
+#[test]
+fn flat_exports_follow_documented_layout() {
+    let rotation = RustSo3::from_axis_angle([0.0, 0.0, 1.0], FRAC_PI_2);
+    let transform = RustSe3::from_parts(rotation.clone(), [0.25, -0.5, 0.75]);
+
+    let rot_matrix = rotation.to_matrix();
+    let rot_row = rotation.to_row_major_flat();
+    let rot_col = rotation.to_col_major_flat();
+    for r in 0..3 {
+        for c in 0..3 {
+            assert_eq!(rot_row[3 * r + c], rot_matrix[r][c]);
+            assert_eq!(rot_col[3 * c + r], rot_matrix[r][c]);
+        }
+    }
+
+    let matrix = transform.to_matrix();
+    let row = transform.to_row_major_flat();
+    let col = transform.to_col_major_flat();
+    for r in 0..4 {
+        for c in 0..4 {
+            assert_eq!(row[4 * r + c], matrix[r][c]);
+            assert_eq!(col[4 * c + r], matrix[r][c]);
+        }
+    }
+    approx_eq(&col[12..15], &transform.translation(), 1e-12);
+
+    let adjoint = RustCmtm::from_se3(&transform);
+    let adjoint_matrix = adjoint.to_matrix();
+    let adjoint_row = adjoint.to_row_major_flat();
+    let adjoint_col = adjoint.to_col_major_flat();
+    for r in 0..6 {
+        for c in 0..6 {
+            assert_eq!(adjoint_row[6 * r + c], adjoint_matrix[r][c]);
+            assert_eq!(adjoint_col[6 * c + r], adjoint_matrix[r][c]);
+        }
+    }
+}
+
+#[test]
+fn flat_constructors_round_trip_through_caller_buffers() {
+    let rotation = RustSo3::from_axis_angle([1.0, 2.0, -0.5], 0.7);
+    let transform = RustSe3::from_parts(rotation.clone(), [0.25, -0.5, 0.75]);
+
+    let mut rot_buffer = [0.0_f64; 9];
+    rotation.write_flat(&mut rot_buffer);
+    assert_eq!(rot_buffer, rotation.to_row_major_flat());
+    let rebuilt_rotation = RustSo3::from_flat(&rot_buffer);
+    approx_eq_matrix(&rebuilt_rotation.to_matrix(), &rotation.to_matrix(), 1e-12);
+
+    let mut buffer = [0.0_f64; 16];
+    transform.write_flat_matrix(&mut buffer);
+    assert_eq!(buffer, transform.to_row_major_flat());
+    let rebuilt = RustSe3::from_flat_matrix(&buffer);
+    approx_eq_matrix4(&rebuilt.to_matrix(), &transform.to_matrix(), 1e-12);
+    approx_eq(&rebuilt.translation(), &[0.25, -0.5, 0.75], 1e-12);
+}
+
+#[test]
+fn cmtm_try_block_matrix_reports_invalid_orders() {
+    let adjoint = RotationalCmtm::from_so3_with_derivatives(&RustSo3::identity(), vec![[0.1; 3]]);
+
+    assert_eq!(adjoint.try_to_block_matrix(Some(2)).unwrap().nrows(), 6);
+
+    let zero = adjoint.try_to_block_matrix(Some(0)).unwrap_err();
+    assert_eq!(zero, Error::ZeroOrder);
+    assert_eq!(zero.code(), 1);
+
+    let too_high = adjoint.try_to_block_matrix(Some(3)).unwrap_err();
+    assert_eq!(
+        too_high,
+        Error::OrderOutOfRange {
+            requested: 3,
+            available: 2
+        }
+    );
+    assert_eq!(too_high.code(), 2);
+
+    let base = adjoint.truncated(1).unwrap();
+    assert_eq!(base.order(), 1);
+    assert_eq!(
+        base.to_block_matrix(None),
+        adjoint.to_block_matrix(None).view((0, 0), (3, 3))
+    );
+    assert_eq!(adjoint.truncated(0).unwrap_err(), Error::ZeroOrder);
+    assert_eq!(
+        adjoint.truncated(3).unwrap_err(),
+        Error::OrderOutOfRange {
+            requested: 3,
+            available: 2
+        }
+    );
+}
+
+#[test]
+fn so3_try_from_quaternion_rejects_degenerate_input() {
+    assert_eq!(
+        RustSo3::try_from_quaternion([0.0; 4]).unwrap_err(),
+        Error::ZeroNorm
+    );
+    assert_eq!(
+        RustSo3::try_from_quaternion([f64::NAN, 0.0, 0.0, 1.0]).unwrap_err(),
+        Error::NonFinite
+    );
+
+    let rotation = RustSo3::try_from_quaternion([2.0, 0.0, 0.0, 0.0]).unwrap();
+    approx_eq_matrix(
+        &rotation.to_matrix(),
+        &RustSo3::identity().to_matrix(),
+        1e-12,
+    );
+}
+
+#[test]
+fn core_types_are_thread_safe_and_shareable() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RustSo3>();
+    assert_send_sync::<RustSe3>();
+    assert_send_sync::<RustCmtm>();
+    assert_send_sync::<RotationalCmtm>();
+    assert_send_sync::<Error>();
+
+    let calibration: ArcSe3 = Arc::new(RustSe3::from_axis_angle_translation(
+        [0.0, 0.0, 1.0],
+        FRAC_PI_2,
+        [0.1, 0.2, 0.3],
+    ));
+    let expected = calibration.apply([1.0, 0.0, 0.0]);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = Arc::clone(&calibration);
+            std::thread::spawn(move || shared.apply([1.0, 0.0, 0.0]))
+        })
+        .collect();
+    for handle in handles {
+        approx_eq(&handle.join().unwrap(), &expected, 1e-12);
+    }
+}
+
+fn camera_rig() -> SensorRig {
+    let lidar = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], FRAC_PI_2, [0.0, 0.0, 1.5]);
+    let camera = RustSe3::from_axis_angle_translation([1.0, 0.0, 0.0], -FRAC_PI_2, [0.1, 0.0, 0.0]);
+    SensorRig::new(
+        "base_link",
+        vec![
+            SensorExtrinsic::new("camera", "lidar", camera),
+            SensorExtrinsic::new("lidar", "base_link", lidar),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn sensor_rig_composes_mounting_chain() {
+    let rig = camera_rig();
+    assert_eq!(rig.base_frame(), "base_link");
+    assert_eq!(rig.len(), 2);
+    assert_eq!(rig.sensor("camera").unwrap().parent, "lidar");
+    assert!(rig.sensor("imu").is_none());
+
+    let lidar = &rig.sensor("lidar").unwrap().transform;
+    let camera = &rig.sensor("camera").unwrap().transform;
+    let expected = lidar.compose(camera);
+    let base_from_camera = rig.base_from_sensor("camera").unwrap();
+    approx_eq_matrix4(&base_from_camera.to_matrix(), &expected.to_matrix(), 1e-12);
+
+    let world_from_base =
+        RustSe3::from_axis_angle_translation([0.0, 1.0, 0.0], 0.3, [5.0, 0.0, 0.0]);
+    let world_from_camera = rig.world_from_sensor(&world_from_base, "camera").unwrap();
+    approx_eq_matrix4(
+        &world_from_camera.to_matrix(),
+        &world_from_base.compose(&expected).to_matrix(),
+        1e-12,
+    );
+
+    let lidar_from_camera = rig.relative("lidar", "camera").unwrap();
+    approx_eq_matrix4(&lidar_from_camera.to_matrix(), &camera.to_matrix(), 1e-12);
+    approx_eq_matrix4(
+        &rig.base_from_sensor("base_link").unwrap().to_matrix(),
+        &RustSe3::identity().to_matrix(),
+        1e-12,
+    );
+}
+
+#[test]
+fn sensor_rig_validates_names_and_connectivity() {
+    let pose = RustSe3::identity();
+    let duplicate = SensorRig::new(
+        "base",
+        vec![
+            SensorExtrinsic::new("cam", "base", pose.clone()),
+            SensorExtrinsic::new("cam", "base", pose.clone()),
+        ],
+    );
+    assert_eq!(duplicate.unwrap_err(), Error::DuplicateFrame("cam".into()));
+
+    let unknown = SensorRig::new(
+        "base",
+        vec![SensorExtrinsic::new("cam", "imu", pose.clone())],
+    );
+    assert_eq!(unknown.unwrap_err(), Error::UnknownFrame("imu".into()));
+
+    let cycle = SensorRig::new(
+        "base",
+        vec![
+            SensorExtrinsic::new("a", "b", pose.clone()),
+            SensorExtrinsic::new("b", "a", pose),
+        ],
+    );
+    assert_eq!(cycle.unwrap_err(), Error::DisconnectedFrame("a".into()));
+}
+
+#[test]
+fn sensor_rig_serde_round_trip_revalidates() {
+    let rig = camera_rig();
+    let json = serde_json::to_string(&rig).unwrap();
+    let restored: SensorRig = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.sensors(), rig.sensors());
+    approx_eq_matrix4(
+        &restored.base_from_sensor("camera").unwrap().to_matrix(),
+        &rig.base_from_sensor("camera").unwrap().to_matrix(),
+        1e-12,
+    );
+
+    let duplicated = json.replace("\"lidar\",\"parent\"", "\"camera\",\"parent\"");
+    assert!(serde_json::from_str::<SensorRig>(&duplicated).is_err());
+}
+
+#[test]
+fn health_check_flags_degraded_transforms() {
+    let transform = RustSe3::from_axis_angle_translation([1.0, -1.0, 0.5], 0.8, [3.0, 4.0, 0.0]);
+    let health = transform.health_check();
+    assert!(health.is_within(1e-12));
+    assert!((health.translation_norm - 5.0).abs() < 1e-12);
+
+    let mut matrix = transform.to_matrix();
+    for row in matrix.iter_mut().take(3) {
+        row[0] *= 1.01;
+    }
+    let degraded = RustSe3::from_matrix(matrix).health_check();
+    assert!(!degraded.is_within(1e-6));
+    assert!(degraded.orthogonality_error > 1e-3);
+    assert!(degraded.determinant_deviation > 1e-3);
+
+    let mut nan_matrix = RustSo3::identity().to_matrix();
+    nan_matrix[1][2] = f64::NAN;
+    assert!(!RustSo3::from_matrix(nan_matrix).health_check().finite);
+}
+
+#[test]
+fn cmtm_health_check_detects_block_inconsistency() {
+    let transform = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.4, [1.0, 2.0, 2.0]);
+    let adjoint = RustCmtm::from_se3_with_derivatives(&transform, vec![[0.1; 6]]);
+    let health = adjoint.health_check();
+    assert!(health.is_within(1e-12));
+    assert!((health.base.translation_norm - 3.0).abs() < 1e-12);
+
+    let mut corrupted = *adjoint.matrix();
+    corrupted[(0, 4)] = 1e-3;
+    let corrupted = RustCmtm::with_derivatives(corrupted, vec![[f64::INFINITY; 6]]);
+    let health = corrupted.health_check();
+    assert!(health.block_structure_error >= 1e-3);
+    assert!(!health.derivatives_finite);
+    assert!(!health.is_within(1e-6));
+
+    let rotational = RotationalCmtm::from_so3(transform.rotation());
+    assert!(rotational.health_check().is_within(1e-12));
+}
+
+#[test]
+fn quaternion_layout_adapters_agree() {
+    let rotation = RustSo3::from_axis_angle([1.0, -2.0, 0.5], 1.1);
+    let [w, x, y, z] = rotation.to_quaternion_wxyz();
+    assert_eq!(rotation.to_quaternion(), [w, x, y, z]);
+    assert_eq!(rotation.to_quaternion_xyzw(), [x, y, z, w]);
+
+    let from_xyzw = RustSo3::from_quaternion_xyzw([x, y, z, w]);
+    let from_wxyz = RustSo3::from_quaternion_wxyz([w, x, y, z]);
+    approx_eq_matrix(&from_xyzw.to_matrix(), &rotation.to_matrix(), 1e-12);
+    approx_eq_matrix(&from_wxyz.to_matrix(), &rotation.to_matrix(), 1e-12);
+
+    // A 90° turn about Z: the scalar part is cos(45°) in both layouts.
+    let quarter_turn = RustSo3::from_quaternion_xyzw([0.0, 0.0, 1.0, 1.0]);
+    approx_eq(
+        &quarter_turn.apply([1.0, 0.0, 0.0]),
+        &[0.0, 1.0, 0.0],
+        1e-12,
+    );
+}
+
+#[test]
+fn so3_from_sequence_follows_scipy_conventions() {
+    let (a, b, c) = (0.3, -0.6, 1.2);
+    let rx = RustSo3::from_axis_angle([1.0, 0.0, 0.0], a);
+    let ry = RustSo3::from_axis_angle([0.0, 1.0, 0.0], b);
+    let rz = RustSo3::from_axis_angle([0.0, 0.0, 1.0], c);
+
+    let intrinsic = RustSo3::from_sequence("XYZ", &[a, b, c]).unwrap();
+    approx_eq_matrix(
+        &intrinsic.to_matrix(),
+        &(&rx * &ry * &rz).to_matrix(),
+        1e-12,
+    );
+
+    let extrinsic = RustSo3::from_sequence("xyz", &[a, b, c]).unwrap();
+    approx_eq_matrix(
+        &extrinsic.to_matrix(),
+        &(&rz * &ry * &rx).to_matrix(),
+        1e-12,
+    );
+
+    // Extrinsic roll-pitch-yaw is the existing ZYX Euler convention.
+    let rpy = RustSo3::from_sequence("xyz", &[a, b, c]).unwrap();
+    approx_eq_matrix(
+        &rpy.to_matrix(),
+        &RustSo3::from_euler_angles(a, b, c).to_matrix(),
+        1e-12,
+    );
+
+    let proper = RustSo3::from_sequence("ZXZ", &[c, a, b]).unwrap();
+    let expected = RustSo3::from_axis_angle([0.0, 0.0, 1.0], c)
+        * RustSo3::from_axis_angle([1.0, 0.0, 0.0], a)
+        * RustSo3::from_axis_angle([0.0, 0.0, 1.0], b);
+    approx_eq_matrix(&proper.to_matrix(), &expected.to_matrix(), 1e-12);
+
+    for (sequence, angles) in [
+        ("XyZ", vec![a, b, c]),
+        ("XXY", vec![a, b, c]),
+        ("XYW", vec![a, b, c]),
+        ("XYZ", vec![a, b]),
+        ("", vec![]),
+    ] {
+        let err = RustSo3::from_sequence(sequence, &angles).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidSequence(_)),
+            "{sequence}: {err}"
+        );
+        assert_eq!(err.code(), 8);
+    }
+}
+
+#[test]
+fn so3_swing_twist_reassembles_rotation() {
+    let axis = [0.0, 0.0, 1.0];
+    let swing_in = RustSo3::from_axis_angle([1.0, 1.0, 0.0], 0.4);
+    let twist_in = RustSo3::from_axis_angle(axis, -2.3);
+    let rotation = &swing_in * &twist_in;
+
+    let (swing, twist, angle) = rotation.swing_twist(axis);
+    assert!((angle + 2.3).abs() < 1e-12);
+    approx_eq_matrix(&twist.to_matrix(), &twist_in.to_matrix(), 1e-12);
+    approx_eq_matrix(&swing.to_matrix(), &swing_in.to_matrix(), 1e-12);
+    approx_eq_matrix(&(&swing * &twist).to_matrix(), &rotation.to_matrix(), 1e-12);
+
+    // The swing axis is perpendicular to the twist axis.
+    let swing_vector = swing.to_rotation_vector();
+    assert!(swing_vector[2].abs() < 1e-12);
+
+    // Pure twists have no swing; a half-turn swing has no defined twist.
+    let (swing, _, angle) = RustSo3::from_axis_angle(axis, 0.7).swing_twist([0.0, 0.0, 2.0]);
+    assert!((angle - 0.7).abs() < 1e-12);
+    approx_eq_matrix(&swing.to_matrix(), &RustSo3::identity().to_matrix(), 1e-12);
+
+    let half_turn = RustSo3::from_axis_angle([1.0, 0.0, 0.0], std::f64::consts::PI);
+    let (swing, twist, angle) = half_turn.swing_twist(axis);
+    assert_eq!(angle, 0.0);
+    approx_eq_matrix(&twist.to_matrix(), &RustSo3::identity().to_matrix(), 1e-12);
+    approx_eq_matrix(&swing.to_matrix(), &half_turn.to_matrix(), 1e-12);
+}
+
+#[test]
+fn so3_clamp_to_cone_limits_tilt_and_keeps_twist() {
+    let axis = [0.0, 0.0, 1.0];
+    let twist = RustSo3::from_axis_angle(axis, 0.9);
+    let tilted = RustSo3::from_axis_angle([0.0, 1.0, 0.0], 0.8) * &twist;
+
+    let clamped = tilted.clamp_to_cone(axis, 0.3);
+    let pointing = clamped.apply(axis);
+    assert!((pointing[2].clamp(-1.0, 1.0).acos() - 0.3).abs() < 1e-12);
+    let (_, clamped_twist, _) = clamped.swing_twist(axis);
+    approx_eq_matrix(&clamped_twist.to_matrix(), &twist.to_matrix(), 1e-12);
+
+    // Rotations already inside the cone are returned unchanged.
+    let inside = tilted.clamp_to_cone(axis, 1.0);
+    approx_eq_matrix(&inside.to_matrix(), &tilted.to_matrix(), 1e-12);
+}
+
+#[test]
+fn so3_clamp_euler_angles_limits_each_axis() {
+    let rotation = RustSo3::from_euler_angles(0.5, -0.9, 2.5);
+    let clamped = rotation.clamp_euler_angles([-0.2, -0.4, -1.0], [0.2, 0.4, 1.0]);
+    let (roll, pitch, yaw) = clamped.to_euler_angles();
+    approx_eq(&[roll, pitch, yaw], &[0.2, -0.4, 1.0], 1e-12);
+
+    let unchanged = rotation.clamp_euler_angles([-1.0; 3], [3.0; 3]);
+    approx_eq_matrix(&unchanged.to_matrix(), &rotation.to_matrix(), 1e-12);
+}
+
+#[test]
+fn so3_rotation_between_aligns_vectors() {
+    let cases = [
+        ([1.0, 0.0, 0.0], [0.0, 2.0, 0.0]),
+        ([0.3, -1.2, 0.5], [2.0, 0.1, -0.7]),
+        ([0.0, 0.0, 1.0], [0.0, 0.0, 3.0]),
+        ([1.0, 2.0, 3.0], [-2.0, -4.0, -6.0]),
+        ([0.0, 1.0, 0.0], [1e-14, -1.0, 0.0]),
+    ];
+    for (from, to) in cases {
+        let rotation = RustSo3::rotation_between(from, to);
+        let norm = |v: [f64; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        let rotated = rotation.apply(from).map(|x| x / norm(from));
+        approx_eq(&rotated, &to.map(|x| x / norm(to)), 1e-12);
+        // Minimal: the angle equals the angle between the vectors.
+        let cos = (from[0] * to[0] + from[1] * to[1] + from[2] * to[2]) / (norm(from) * norm(to));
+        let angle = norm(rotation.to_rotation_vector());
+        assert!((angle - cos.clamp(-1.0, 1.0).acos()).abs() < 1e-7);
+    }
+    assert_eq!(
+        RustSo3::rotation_between([0.0; 3], [1.0, 0.0, 0.0]),
+        RustSo3::identity()
+    );
+}
+
+#[test]
+fn so3_look_at_follows_axis_conventions() {
+    // Looking north (+y) with up +z; the up hint need not be perpendicular.
+    let (forward, up) = ([0.0, 2.0, 0.0], [0.0, 0.5, 1.0]);
+    let camera = RustSo3::look_at(forward, up).unwrap();
+    approx_eq(&camera.apply([0.0, 0.0, 1.0]), &[0.0, 1.0, 0.0], 1e-15);
+    approx_eq(&camera.apply([1.0, 0.0, 0.0]), &[1.0, 0.0, 0.0], 1e-15);
+    approx_eq(&camera.apply([0.0, -1.0, 0.0]), &[0.0, 0.0, 1.0], 1e-15);
+
+    let opengl = RustSo3::look_at_with(forward, up, LookAtConvention::OpenGl).unwrap();
+    approx_eq(&opengl.apply([0.0, 0.0, -1.0]), &[0.0, 1.0, 0.0], 1e-15);
+    approx_eq(&opengl.apply([0.0, 1.0, 0.0]), &[0.0, 0.0, 1.0], 1e-15);
+
+    let body = RustSo3::look_at_with(forward, up, LookAtConvention::Body).unwrap();
+    approx_eq(&body.apply([1.0, 0.0, 0.0]), &[0.0, 1.0, 0.0], 1e-15);
+    approx_eq(&body.apply([0.0, 1.0, 0.0]), &[-1.0, 0.0, 0.0], 1e-15);
+    assert!((body.rotation().matrix().determinant() - 1.0).abs() < 1e-15);
+
+    assert_eq!(
+        RustSo3::look_at([0.0; 3], [0.0, 0.0, 1.0]).unwrap_err(),
+        Error::ZeroNorm
+    );
+    assert_eq!(
+        RustSo3::look_at([0.0, 0.0, 1.0], [0.0, 0.0, -3.0]).unwrap_err(),
+        Error::Singular
+    );
+    assert_eq!(
+        RustSo3::look_at([f64::NAN, 0.0, 1.0], [0.0, 1.0, 0.0]).unwrap_err(),
+        Error::NonFinite
+    );
+}
+
+#[test]
+fn so3_interpolate_follows_the_geodesic() {
+    let start = RustSo3::from_euler_angles(0.3, -0.2, 0.5);
+    let end = RustSo3::from_euler_angles(-0.6, 0.4, 2.4);
+    approx_eq_matrix(
+        &start.interpolate(&end, 0.0).to_matrix(),
+        &start.to_matrix(),
+        1e-15,
+    );
+    approx_eq_matrix(
+        &start.interpolate(&end, 1.0).to_matrix(),
+        &end.to_matrix(),
+        1e-12,
+    );
+
+    // Equal steps in t are equal relative rotations, also beyond [0, 1].
+    let step = start.inverse() * &start.interpolate(&end, 0.25);
+    for t in [-0.5, 0.25, 0.5, 1.0, 1.5] {
+        let from = start.interpolate(&end, t);
+        let to = start.interpolate(&end, t + 0.25);
+        approx_eq_matrix(
+            &(from.inverse() * &to).to_matrix(),
+            &step.to_matrix(),
+            1e-12,
+        );
+    }
+
+    // The midpoint of a rotation about one axis is the half-angle rotation.
+    let quarter = RustSo3::from_axis_angle([0.0, 0.0, 1.0], FRAC_PI_2);
+    let eighth = RustSo3::identity().interpolate(&quarter, 0.5);
+    approx_eq(
+        &eighth.to_rotation_vector(),
+        &[0.0, 0.0, FRAC_PI_2 / 2.0],
+        1e-15,
+    );
+}
+
+#[test]
+fn block_matrix_into_reuses_buffer_and_matches_allocating_path() {
+    let transform = RustSe3::from_matrix(RustSe3::exp([0.2, -0.4, 0.1, 0.5, 0.3, -0.2], None));
+    let cmtm = RustCmtm::from_se3_with_derivatives(
+        &transform,
+        vec![
+            [0.1, 0.2, -0.3, 0.4, 0.0, 0.5],
+            [-0.2, 0.1, 0.0, 0.3, -0.1, 0.2],
+        ],
+    );
+
+    let mut buffer = DMatrix::from_element(18, 18, f64::NAN);
+    let pointer = buffer.as_ptr();
+    cmtm.build_block_matrix_into(None, &mut buffer).unwrap();
+    assert_eq!(buffer.as_ptr(), pointer);
+    assert_eq!(buffer, cmtm.to_block_matrix(None));
+
+    cmtm.build_block_matrix_into(Some(2), &mut buffer).unwrap();
+    assert_eq!(buffer, cmtm.to_block_matrix(Some(2)));
+    assert_eq!(
+        cmtm.build_block_matrix_into(Some(4), &mut buffer)
+            .unwrap_err(),
+        Error::OrderOutOfRange {
+            requested: 4,
+            available: 3
+        }
+    );
+}
+
+#[test]
+fn fixed_order_cmtm_matches_generic_cmtm() {
+    let transform = RustSe3::from_matrix(RustSe3::exp([0.4, 0.1, -0.3, 0.2, -0.6, 0.9], None));
+    let other = RustSe3::from_matrix(RustSe3::exp([-0.1, 0.5, 0.2, 0.3, 0.1, -0.4], None));
+    let derivatives = [
+        [0.3, -0.1, 0.2, 0.0, 0.4, -0.5],
+        [0.1, 0.2, 0.0, -0.3, 0.1, 0.2],
+    ];
+
+    let fixed = CmtmN::<6, 3>::from_se3_with_derivatives(&transform, derivatives);
+    let generic = RustCmtm::from_se3_with_derivatives(&transform, derivatives.to_vec());
+    let block: SMatrix<f64, 18, 18> = fixed.to_block_matrix();
+    approx_eq(
+        block.as_slice(),
+        generic.to_block_matrix(None).as_slice(),
+        1e-15,
+    );
+
+    let fixed_other = CmtmN::<6, 3>::from_se3_with_derivatives(&other, [[0.0; 6], [1.0; 6]]);
+    let generic_other = RustCmtm::from_se3_with_derivatives(&other, vec![[0.0; 6], [1.0; 6]]);
+    let composed: SMatrix<f64, 18, 18> = fixed.compose(&fixed_other).to_block_matrix();
+    approx_eq(
+        composed.as_slice(),
+        generic
+            .compose(&generic_other)
+            .to_block_matrix(None)
+            .as_slice(),
+        1e-15,
+    );
+
+    assert_eq!(fixed.to_generic(), generic);
+    assert_eq!(CmtmN::<6, 3>::try_from_generic(&generic).unwrap(), fixed);
+    assert_eq!(
+        CmtmN::<6, 2>::try_from_generic(&generic).unwrap_err(),
+        Error::OrderOutOfRange {
+            requested: 2,
+            available: 3
+        }
+    );
+
+    let rotational = CmtmN::<3, 1>::from_so3_with_derivatives(transform.rotation(), []);
+    let block: SMatrix<f64, 3, 3> = rotational.to_block_matrix();
+    assert_eq!(&block, transform.rotation().rotation().matrix());
+    let identity: SMatrix<f64, 12, 12> = CmtmN::<6, 2>::identity().to_block_matrix();
+    assert_eq!(identity, SMatrix::<f64, 12, 12>::identity());
+}
+
+#[test]
+fn zero_order_cmtm_reduces_to_adjoint_arithmetic() {
+    let a = RustSe3::from_matrix(RustSe3::exp([0.3, -0.2, 0.5, 1.0, 0.4, -0.7], None));
+    let b = RustSe3::from_matrix(RustSe3::exp([-0.1, 0.6, 0.2, -0.3, 0.8, 0.1], None));
+    let composed = RustCmtm::from_se3(&a).compose(&RustCmtm::from_se3(&b));
+    assert_eq!(composed.order(), 1);
+    assert_eq!(*composed.matrix(), a.adjoint() * b.adjoint());
+
+    let twist = [0.1, -0.2, 0.3, 0.4, -0.5, 0.6];
+    let expected = a.adjoint() * SVector::<f64, 6>::from(twist);
+    assert_eq!(
+        RustCmtm::from_se3(&a).apply_twist(twist),
+        <[f64; 6]>::from(expected)
+    );
+    assert_eq!(
+        RustCmtm::from_se3(&a).to_block_matrix(None),
+        DMatrix::from_column_slice(6, 6, a.adjoint().as_slice())
+    );
+}
+
+#[test]
+fn typed_transforms_compose_like_se3_and_serialize_transparently() {
+    struct World;
+    struct Body;
+    struct Camera;
+
+    let world_from_body = Transform::<World, Body>::from_se3(RustSe3::from_axis_angle_translation(
+        [0.0, 0.0, 1.0],
+        FRAC_PI_2,
+        [1.0, 2.0, 3.0],
+    ));
+    let body_from_camera = Transform::<Body, Camera>::from_se3(
+        RustSe3::from_axis_angle_translation([1.0, 0.0, 0.0], 0.3, [0.1, 0.0, 0.0]),
+    );
+    let world_from_camera: Transform<World, Camera> = &world_from_body * &body_from_camera;
+    assert_eq!(
+        world_from_camera.to_se3(),
+        world_from_body.as_se3().compose(body_from_camera.as_se3())
+    );
+
+    let camera_from_world: Transform<Camera, World> = world_from_camera.inverse();
+    let round_trip = camera_from_world.compose(&world_from_camera);
+    approx_eq(
+        &round_trip.apply([0.4, -0.2, 0.7]),
+        &[0.4, -0.2, 0.7],
+        1e-12,
+    );
+    assert_eq!(
+        Transform::<World, World>::identity().apply([1.0, 2.0, 3.0]),
+        [1.0, 2.0, 3.0]
+    );
+
+    let json = serde_json::to_string(&world_from_body).unwrap();
+    assert_eq!(
+        json,
+        serde_json::to_string(world_from_body.as_se3()).unwrap()
+    );
+    let parsed: Transform<World, Body> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, world_from_body);
+}
+
+#[test]
+fn se3_log_inverts_exp() {
+    let twists = [
+        [0.3, -0.2, 0.5, 1.0, -2.0, 0.5],
+        [0.0, 0.0, 0.0, 1.0, 2.0, 3.0],
+        [0.0, 2.5, 0.0, -0.4, 0.0, 0.7],
+    ];
+    for twist in twists {
+        let matrix = RustSe3::exp(twist, None);
+        approx_eq(&RustSe3::log_matrix(matrix), &twist, 1e-10);
+        approx_eq(&RustSe3::from_matrix(matrix).log(), &twist, 1e-10);
+    }
+    approx_eq(&RustSe3::identity().log(), &[0.0; 6], 1e-15);
+
+    // Small-angle branch: \(v \approx t - \tfrac{1}{2} \omega \times t\).
+    let omega = [1e-8, -2e-8, 0.0];
+    let transform = RustSe3::from_parts(RustSo3::from_rotation_vector(omega), [0.1, 0.2, -0.3]);
+    let half_cross = [3e-9, 1.5e-9, 2e-9];
+    let expected = [
+        omega[0],
+        omega[1],
+        omega[2],
+        0.1 - half_cross[0],
+        0.2 - half_cross[1],
+        -0.3 - half_cross[2],
+    ];
+    approx_eq(&transform.log(), &expected, 1e-15);
+}
+
+#[test]
+fn free_functions_round_trip_as_function_values() {
+    fn round_trip<V: Copy, M>(exp: fn(V) -> M, log: fn(M) -> V, value: V) -> V {
+        log(exp(value))
+    }
+    use mathroborust::{se2, se3, so2, so3};
+
+    let vector = [0.3, -0.2, 0.5];
+    approx_eq(&round_trip(so3::exp, so3::log, vector), &vector, 1e-12);
+    approx_eq(&so3::vee(so3::hat(vector)), &vector, 1e-15);
+    let twist = [0.3, -0.2, 0.5, 1.0, 2.0, -1.0];
+    approx_eq(&round_trip(se3::exp, se3::log, twist), &twist, 1e-12);
+    approx_eq(&se3::vee(se3::hat(twist)), &twist, 1e-15);
+    assert!((round_trip(so2::exp, so2::log, 0.7) - 0.7).abs() < 1e-15);
+    approx_eq(
+        &round_trip(se2::exp, se2::log, [0.7, 1.0, -1.0]),
+        &[0.7, 1.0, -1.0],
+        1e-12,
+    );
+
+    let transform = RustSe3::from_matrix(se3::exp(twist));
+    let adjoint = se3::adjoint_of(transform.to_matrix());
+    let expected = transform.adjoint();
+    for r in 0..6 {
+        for c in 0..6 {
+            assert_eq!(adjoint[r][c], expected[(r, c)]);
+        }
+    }
+    let planar = se2::adjoint_of(se2::exp([0.7, 1.0, -1.0]));
+    assert_eq!(planar[0], [1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn so3_euler_orders_round_trip_including_gimbal_lock() {
+    let letters = ["X", "Y", "Z"];
+    for order in EulerOrder::ALL {
+        let sequence: String = order.axes().iter().map(|&axis| letters[axis]).collect();
+        let sequence = if order.is_intrinsic() {
+            sequence
+        } else {
+            sequence.to_lowercase()
+        };
+        let middle = if order.is_proper() { 1.1 } else { -0.6 };
+        let rotation = RustSo3::from_euler(order, 0.3, middle, 2.5);
+        approx_eq_matrix(
+            &rotation.to_matrix(),
+            &RustSo3::from_sequence(&sequence, &[0.3, middle, 2.5])
+                .unwrap()
+                .to_matrix(),
+            1e-14,
+        );
+        let (a, b, c) = rotation.to_euler(order);
+        approx_eq(&[a, b, c], &[0.3, middle, 2.5], 1e-12);
+
+        // At gimbal lock the leftmost angle is zeroed and the rotation kept.
+        let singular = if order.is_proper() {
+            [0.0, std::f64::consts::PI]
+        } else {
+            [-FRAC_PI_2, FRAC_PI_2]
+        };
+        for middle in singular {
+            let locked = RustSo3::from_euler(order, 0.4, middle, -1.3);
+            let (a, b, c) = locked.to_euler(order);
+            assert!(a.is_finite() && b.is_finite() && c.is_finite());
+            let zeroed = if order.is_intrinsic() { a } else { c };
+            assert_eq!(zeroed, 0.0, "{order:?}");
+            approx_eq_matrix(
+                &RustSo3::from_euler(order, a, b, c).to_matrix(),
+                &locked.to_matrix(),
+                1e-12,
+            );
+        }
+    }
+
+    // Extrinsic XYZ is the existing roll-pitch-yaw convention.
+    let rpy = RustSo3::from_euler_angles(0.3, -0.6, 1.2);
+    let (roll, pitch, yaw) = rpy.to_euler_angles();
+    approx_eq(
+        &<[f64; 3]>::from(rpy.to_euler(EulerOrder::ExtrinsicXyz)),
+        &[roll, pitch, yaw],
+        1e-12,
+    );
+}
+
+#[test]
+fn so3_modified_rodrigues_parameters_and_shadow_set() {
+    let axis = [2.0_f64 / 7.0, -3.0 / 7.0, 6.0 / 7.0];
+    let angle = 1.3_f64;
+    let rotation = RustSo3::from_axis_angle(axis, angle);
+    let mrp = rotation.to_mrp();
+    approx_eq(&mrp, &axis.map(|a| a * (angle / 4.0).tan()), 1e-15);
+    approx_eq_matrix(
+        &RustSo3::from_mrp(mrp).to_matrix(),
+        &rotation.to_matrix(),
+        1e-15,
+    );
+
+    // The shadow describes the same rotation and is switched back to the
+    // short set.
+    let shadow = RustSo3::shadow_mrp(mrp);
+    approx_eq(
+        &shadow,
+        &axis.map(|a| a * ((angle - 2.0 * std::f64::consts::PI) / 4.0).tan()),
+        1e-14,
+    );
+    approx_eq_matrix(
+        &RustSo3::from_mrp(shadow).to_matrix(),
+        &rotation.to_matrix(),
+        1e-14,
+    );
+    approx_eq(&RustSo3::switch_mrp(shadow), &mrp, 1e-15);
+    assert_eq!(RustSo3::switch_mrp(mrp), mrp);
+
+    // Conversions always return the short set.
+    approx_eq(&RustSo3::from_mrp(shadow).to_mrp(), &mrp, 1e-15);
+    assert_eq!(RustSo3::identity().to_mrp(), [0.0; 3]);
+}
+
+#[test]
+fn rotation_and_pose_distance_metrics_agree() {
+    let a = RustSo3::from_axis_angle([1.0, 2.0, -0.5], 0.9);
+    let angle = 2.4_f64;
+    let b = a.compose(&RustSo3::from_axis_angle([0.3, -1.0, 0.2], angle));
+    assert!((a.angle_to(&b) - angle).abs() < 1e-12);
+    assert!((b.angle_to(&a) - angle).abs() < 1e-12);
+    let chordal = 2.0 * std::f64::consts::SQRT_2 * (angle / 2.0).sin();
+    assert!((a.chordal_distance(&b) - chordal).abs() < 1e-12);
+    assert!((a.quaternion_distance(&b) - 2.0 * (angle / 4.0).sin()).abs() < 1e-12);
+    // Tiny angles are resolved rather than rounded to zero.
+    let nudged = a.compose(&RustSo3::from_axis_angle([0.0, 0.0, 1.0], 1e-9));
+    assert!((a.angle_to(&nudged) - 1e-9).abs() < 1e-15);
+    assert_eq!(a.angle_to(&a), 0.0);
+
+    let p = RustSe3::from_parts(a.clone(), [1.0, 0.0, 0.0]);
+    let q = RustSe3::from_parts(b.clone(), [1.0, 3.0, 4.0]);
+    let distance = p.distance_to(&q, 2.0, 0.5);
+    assert!((distance - (2.0 * angle).hypot(2.5)).abs() < 1e-12);
+    assert!((p.distance_to(&q, 0.0, 1.0) - 5.0).abs() < 1e-15);
+    assert!((p.chordal_distance(&q, 2.0, 0.5) - (2.0 * chordal).hypot(2.5)).abs() < 1e-12);
+}
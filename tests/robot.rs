@@ -0,0 +1,124 @@
+use mathroborust::error::Error;
+use mathroborust::robot::{Inertial, Joint, JointKind, JointLimits, Link, RobotDescription};
+use mathroborust::{RustSe3, RustSo3};
+
+fn inertial(mass: f64) -> Option<Inertial> {
+    Some(Inertial {
+        mass,
+        center_of_mass: [0.0, 0.0, 0.1],
+        inertia: [[0.01, 0.0, 0.0], [0.0, 0.02, 0.001], [0.0, 0.001, 0.015]],
+    })
+}
+
+fn links() -> Vec<Link> {
+    vec![
+        Link::new("base", inertial(5.0)),
+        Link::new("upper", inertial(1.5)),
+        Link::new("lower", inertial(0.8)),
+        Link::new("tool", None),
+    ]
+}
+
+fn joints() -> Vec<Joint> {
+    let limits = JointLimits {
+        lower: -2.0,
+        upper: 2.0,
+        velocity: Some(3.0),
+        effort: Some(40.0),
+    };
+    vec![
+        Joint::new(
+            "shoulder",
+            "base",
+            "upper",
+            RustSe3::from_parts(RustSo3::from_euler_angles(0.1, 0.0, 0.3), [0.0, 0.0, 0.2]),
+            JointKind::Revolute {
+                axis: [0.0, 0.0, 1.0],
+            },
+        )
+        .with_limits(limits),
+        Joint::new(
+            "slide",
+            "upper",
+            "lower",
+            RustSe3::from_parts(RustSo3::from_rotation_vector([0.0; 3]), [0.4, 0.0, 0.0]),
+            JointKind::Prismatic {
+                axis: [1.0, 0.0, 0.0],
+            },
+        ),
+        Joint::new(
+            "flange",
+            "lower",
+            "tool",
+            RustSe3::from_parts(RustSo3::from_rotation_vector([0.0; 3]), [0.1, 0.0, 0.0]),
+            JointKind::Fixed,
+        ),
+    ]
+}
+
+#[test]
+fn description_round_trips_through_json_losslessly() {
+    let robot = RobotDescription::new("arm", links(), joints()).unwrap();
+    assert_eq!(robot.root().name, "base");
+    assert_eq!(robot.degrees_of_freedom(), 2);
+    assert_eq!(robot.parent_joint("lower").unwrap().name, "slide");
+    assert!(robot.parent_joint("base").is_none());
+    assert_eq!(
+        robot
+            .child_joints("upper")
+            .map(|j| j.name.as_str())
+            .collect::<Vec<_>>(),
+        ["slide"]
+    );
+
+    let json = serde_json::to_string_pretty(&robot).unwrap();
+    let restored: RobotDescription = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, robot);
+
+    let inertia = robot
+        .link("upper")
+        .unwrap()
+        .inertial
+        .as_ref()
+        .unwrap()
+        .to_spatial_inertia();
+    assert_eq!(
+        Inertial::from(&inertia),
+        *robot.link("upper").unwrap().inertial.as_ref().unwrap()
+    );
+}
+
+#[test]
+fn invalid_descriptions_are_rejected() {
+    let mut duplicate = links();
+    duplicate.push(Link::new("upper", None));
+    assert_eq!(
+        RobotDescription::new("arm", duplicate, joints()).unwrap_err(),
+        Error::DuplicateFrame("upper".into())
+    );
+
+    let mut unknown = joints();
+    unknown[1].parent = "elbow".into();
+    assert_eq!(
+        RobotDescription::new("arm", links(), unknown).unwrap_err(),
+        Error::UnknownFrame("elbow".into())
+    );
+
+    let mut two_roots = joints();
+    two_roots.pop();
+    assert_eq!(
+        RobotDescription::new("arm", links(), two_roots).unwrap_err(),
+        Error::DisconnectedFrame("tool".into())
+    );
+
+    let mut cycle = joints();
+    cycle[0].parent = "tool".into();
+    assert!(matches!(
+        RobotDescription::new("arm", links(), cycle),
+        Err(Error::DisconnectedFrame(_))
+    ));
+
+    // Validation also runs on deserialization.
+    let json = r#"{"name":"arm","links":[{"name":"a"},{"name":"a"}],"joints":[]}"#;
+    assert!(serde_json::from_str::<RobotDescription>(json).is_err());
+}
@@ -0,0 +1,176 @@
+use std::f64::consts::PI;
+
+use mathroborust::error::Error;
+use mathroborust::lie::{HasExp, LieGroup};
+use mathroborust::screw::Screw;
+use mathroborust::se3::{self, Se3};
+
+fn close(a: &Se3, b: &Se3, tolerance: f64) -> bool {
+    let (a, b) = (a.to_matrix(), b.to_matrix());
+    (0..4).all(|r| (0..4).all(|c| (a[r][c] - b[r][c]).abs() < tolerance))
+}
+
+#[test]
+fn revolute_screw_rotates_about_an_offset_axis_and_advances_by_pitch() {
+    let screw = Screw::revolute([0.0, 0.0, 2.0], [1.0, 0.0, 0.0], 0.1).unwrap();
+    let poses = screw.sample(0.0..=PI, 5);
+    assert_eq!(poses.len(), 5);
+    assert!(close(&poses[0], &Se3::identity(), 1e-12));
+    // Half a turn about x = 1 maps the origin to (2, 0, 0.1π).
+    let [x, y, z] = poses[4].apply([0.0; 3]);
+    assert!((x - 2.0).abs() < 1e-12 && y.abs() < 1e-12 && (z - 0.1 * PI).abs() < 1e-12);
+    // Every sample keeps its distance to the axis.
+    for pose in &poses {
+        let [x, y, _] = pose.apply([0.0; 3]);
+        assert!(((x - 1.0).hypot(y) - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn prismatic_screw_translates() {
+    let screw = Screw::prismatic([0.0, 3.0, 4.0]).unwrap();
+    let pose = screw.pose(2.0);
+    let [x, y, z] = pose.translation();
+    assert!(x.abs() < 1e-12 && (y - 1.2).abs() < 1e-12 && (z - 1.6).abs() < 1e-12);
+    assert!(matches!(Screw::prismatic([0.0; 3]), Err(Error::ZeroNorm)));
+    assert!(matches!(
+        Screw::revolute([0.0; 3], [1.0, 0.0, 0.0], 0.0),
+        Err(Error::ZeroNorm)
+    ));
+}
+
+#[test]
+fn screw_from_twist_reproduces_the_exponential() {
+    let twist = [0.3, -0.2, 0.5, 1.0, 0.4, -0.7];
+    let (screw, theta) = Screw::from_twist(twist).unwrap();
+    let expected = Se3::from_matrix(Se3::exp(twist, None));
+    assert!(close(&screw.pose(theta), &expected, 1e-12));
+    let norm: f64 = screw.twist()[..3].iter().map(|w| w * w).sum();
+    assert!((norm - 1.0).abs() < 1e-12);
+    assert!(matches!(Screw::from_twist([0.0; 6]), Err(Error::ZeroNorm)));
+}
+
+#[test]
+fn interpolation_follows_the_screw_between_poses() {
+    let start = Se3::from_axis_angle_translation([1.0, 0.0, 0.0], 0.3, [0.5, -1.0, 2.0]);
+    let end = Se3::from_axis_angle_translation([0.2, 1.0, -0.4], 2.1, [1.5, 0.2, -0.3]);
+    let poses = start.interpolate_n(&end, 7);
+    assert_eq!(poses.len(), 7);
+    assert!(close(&poses[0], &start, 1e-12));
+    assert!(close(&poses[6], &end, 1e-10));
+    // Constant twist: consecutive relative motions are all equal.
+    let step = poses[0].inverse().compose(&poses[1]);
+    for pair in poses.windows(2) {
+        assert!(close(&pair[0].inverse().compose(&pair[1]), &step, 1e-10));
+    }
+    assert_eq!(start.interpolate_n(&end, 1), vec![start.clone()]);
+    assert!(start.interpolate_n(&end, 0).is_empty());
+}
+
+#[test]
+fn interpolate_matches_sampled_screw_and_extrapolates() {
+    let start = Se3::from_axis_angle_translation([0.0, 1.0, 0.0], -0.4, [1.0, 0.0, 2.0]);
+    let end = Se3::from_axis_angle_translation([1.0, 1.0, 0.5], 1.3, [-0.5, 3.0, 1.0]);
+    let poses = start.interpolate_n(&end, 5);
+    for (k, pose) in poses.iter().enumerate() {
+        assert!(close(&start.interpolate(&end, k as f64 / 4.0), pose, 1e-12));
+    }
+    assert!(close(&start.interpolate(&end, 1.0), &end, 1e-10));
+
+    // Beyond t = 1 the same relative motion keeps repeating.
+    let step = start.inverse().compose(&end);
+    let twice = start.interpolate(&end, 2.0);
+    assert!(close(&twice, &end.compose(&step), 1e-10));
+    let before = start.interpolate(&end, -1.0);
+    assert!(close(&before, &start.compose(&step.inverse()), 1e-10));
+}
+
+#[test]
+fn powers_split_and_repeat_the_screw_motion() {
+    let pose = Se3::from_axis_angle_translation([1.0, 1.0, 0.5], 1.3, [-0.5, 3.0, 1.0]);
+    let half = pose.powf(0.5);
+    assert!(close(&half.compose(&half), &pose, 1e-12));
+    assert!(close(
+        &pose.powf(0.5),
+        &Se3::identity().interpolate(&pose, 0.5),
+        1e-12
+    ));
+
+    let cubed = pose.compose(&pose).compose(&pose);
+    assert!(close(&pose.pow(3), &cubed, 1e-12));
+    assert!(close(&pose.powf(3.0), &cubed, 1e-10));
+    assert!(close(&pose.pow(-3), &cubed.inverse(), 1e-12));
+    assert_eq!(pose.pow(0), Se3::identity());
+
+    let rotation = pose.rotation();
+    let third = rotation.powf(1.0 / 3.0);
+    let product = third.compose(&third).compose(&third);
+    let (a, b) = (product.to_matrix(), rotation.to_matrix());
+    assert!((0..3).all(|r| (0..3).all(|c| (a[r][c] - b[r][c]).abs() < 1e-12)));
+    let (a, b) = (
+        rotation.pow(-2).to_matrix(),
+        rotation.inverse().compose(&rotation.inverse()).to_matrix(),
+    );
+    assert!((0..3).all(|r| (0..3).all(|c| (a[r][c] - b[r][c]).abs() < 1e-12)));
+}
+
+#[test]
+fn cayley_maps_invert_and_approximate_the_exponential() {
+    let twist = [0.4, -0.3, 0.8, 1.0, -2.0, 0.5];
+    let hat = nalgebra::Matrix4::from(Se3::hat(twist)).transpose() * 0.5;
+    let identity = nalgebra::Matrix4::identity();
+    let expected = (identity - hat).try_inverse().unwrap() * (identity + hat);
+    let pose = Se3::cayley(twist);
+    let matrix = pose.to_matrix();
+    assert!((0..4).all(|r| (0..4).all(|c| (matrix[r][c] - expected[(r, c)]).abs() < 1e-14)));
+
+    let back = pose.inverse_cayley().unwrap();
+    assert!(back.iter().zip(twist).all(|(a, b)| (a - b).abs() < 1e-14));
+    let rotation = pose.rotation().inverse_cayley().unwrap();
+    assert!(
+        rotation
+            .iter()
+            .zip(twist)
+            .all(|(a, b)| (a - b).abs() < 1e-14)
+    );
+
+    // The maps agree with exp to second order, differing at third.
+    let small = twist.map(|x| x * 1e-3);
+    let exact = Se3::from_matrix(Se3::exp(small, None));
+    assert!(close(&Se3::cayley(small), &exact, 1e-9));
+    assert!(!close(&Se3::cayley(small), &exact, 1e-11));
+
+    let half_turn = Se3::from_axis_angle_translation([0.0, 1.0, 0.0], PI, [1.0, 0.0, 0.0]);
+    assert_eq!(half_turn.inverse_cayley(), Err(Error::Singular));
+}
+
+#[test]
+fn commutator_measures_how_far_transforms_are_from_commuting() {
+    let about_z = Se3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.7, [0.0, 0.0, 2.0]);
+    let also_z = Se3::from_axis_angle_translation([0.0, 0.0, 1.0], -1.2, [0.0, 0.0, 0.5]);
+    assert!(close(
+        &se3::commutator(&about_z, &also_z),
+        &Se3::identity(),
+        1e-14
+    ));
+    assert!(se3::non_commutativity_magnitude(&about_z, &also_z) < 1e-14);
+
+    let about_x = Se3::from_axis_angle_translation([1.0, 0.0, 0.0], 0.7, [0.3, 0.0, 0.0]);
+    assert!(se3::non_commutativity_magnitude(&about_z, &about_x) > 0.1);
+
+    // For small motions the commutator is the bracket of the twists, and BCH
+    // recovers the log of the product to fourth order.
+    let x = [0.02, -0.01, 0.03, 0.05, 0.0, -0.04];
+    let y = [-0.01, 0.03, 0.02, 0.0, 0.06, 0.01];
+    let (a, b) = (Se3::exp_map(&x.into()), Se3::exp_map(&y.into()));
+    let bracket = Se3::bracket(&x.into(), &y.into()).norm();
+    assert!((se3::non_commutativity_magnitude(&a, &b) - bracket).abs() < 1e-2 * bracket);
+    let product = a.compose(&b).log();
+    let approximation = se3::bch(x, y);
+    let error = product
+        .iter()
+        .zip(approximation)
+        .map(|(p, q)| (p - q).abs())
+        .fold(0.0, f64::max);
+    assert!(error < 1e-7, "{error}");
+}
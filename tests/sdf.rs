@@ -0,0 +1,163 @@
+use mathroborust::error::Error;
+use mathroborust::mjcf::Mjcf;
+use mathroborust::robot::{JointKind, RobotDescription};
+use mathroborust::sdf::{self, Sdf, WORLD_LINK};
+
+const ARM: &str = r#"
+<sdf version="1.7">
+  <model name="arm">
+    <link name="base">
+      <inertial>
+        <mass>4</mass>
+        <pose>0 0 0.05 0 0 0</pose>
+        <inertia><ixx>0.1</ixx><iyy>0.1</iyy><izz>0.05</izz></inertia>
+      </inertial>
+    </link>
+    <link name="upper">
+      <pose>0 0 0.3 0 0 1.5707963267948966</pose>
+      <inertial>
+        <mass>1.5</mass>
+        <pose>0.2 0 0 0 0 0</pose>
+        <inertia><ixx>0.01</ixx><ixy>0.001</ixy><iyy>0.02</iyy><izz>0.03</izz></inertia>
+      </inertial>
+    </link>
+    <link name="lower">
+      <pose relative_to="upper">0.4 0 0 0 0 0</pose>
+      <inertial><mass>0.5</mass><pose>0.3 0 0 0 0 0</pose></inertial>
+    </link>
+    <joint name="anchor" type="fixed">
+      <parent>world</parent>
+      <child>base</child>
+    </joint>
+    <joint name="shoulder" type="revolute">
+      <parent>base</parent>
+      <child>upper</child>
+      <axis>
+        <xyz>0 0 2</xyz>
+        <limit><lower>-1.5</lower><upper>1.5</upper><effort>20</effort><velocity>-1</velocity></limit>
+      </axis>
+    </joint>
+    <joint name="elbow" type="continuous">
+      <parent>upper</parent>
+      <child>lower</child>
+      <pose>0.1 0 0 0 0 0</pose>
+      <axis><xyz expressed_in="__model__">1 0 0</xyz></axis>
+    </joint>
+  </model>
+</sdf>
+"#;
+
+fn close(a: [f64; 3], b: [f64; 3]) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12)
+}
+
+#[test]
+fn imports_links_joints_and_mass_properties() {
+    let robot = sdf::parse(ARM).unwrap();
+    assert_eq!(robot.name(), "arm");
+    assert_eq!(robot.root().name, WORLD_LINK);
+    assert_eq!(robot.degrees_of_freedom(), 2);
+
+    let shoulder = robot.joint("shoulder").unwrap();
+    assert_eq!(
+        shoulder.kind,
+        JointKind::Revolute {
+            axis: [0.0, 0.0, 1.0]
+        }
+    );
+    assert!(close(shoulder.origin.translation(), [0.0, 0.0, 0.3]));
+    let limits = shoulder.limits.unwrap();
+    assert_eq!((limits.lower, limits.upper), (-1.5, 1.5));
+    assert_eq!((limits.effort, limits.velocity), (Some(20.0), None));
+
+    // The elbow frame sits 0.1 along the child link's x axis, so the lower
+    // link frame moves there and its centre of mass moves back.
+    let elbow = robot.joint("elbow").unwrap();
+    assert!(close(elbow.origin.translation(), [0.5, 0.0, 0.0]));
+    assert!(elbow.limits.is_none());
+    let JointKind::Continuous { axis } = elbow.kind else {
+        panic!("elbow should be continuous");
+    };
+    // Model x is the negative y axis of the yawed upper/lower links.
+    assert!(close(axis, [0.0, -1.0, 0.0]));
+    let lower = robot.link("lower").unwrap().inertial.as_ref().unwrap();
+    assert!(close(lower.center_of_mass, [0.2, 0.0, 0.0]));
+    assert_eq!(
+        lower.inertia,
+        [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+    );
+
+    let upper = robot.link("upper").unwrap().inertial.as_ref().unwrap();
+    assert!(close(upper.center_of_mass, [0.2, 0.0, 0.0]));
+    assert_eq!(upper.inertia[0][1], 0.001);
+}
+
+#[test]
+fn importers_share_the_description_format_trait() {
+    let robot = RobotDescription::parse::<Sdf>(ARM).unwrap();
+    assert_eq!(robot, sdf::parse(ARM).unwrap());
+
+    let mjcf = r#"<mujoco><worldbody><body name="a"/></worldbody></mujoco>"#;
+    assert_eq!(
+        RobotDescription::parse::<Mjcf>(mjcf).unwrap().links().len(),
+        2
+    );
+    assert!(RobotDescription::parse::<Sdf>(mjcf).is_err());
+}
+
+#[test]
+fn model_without_world_joint_keeps_its_root_link() {
+    let model = r#"
+<sdf version="1.9">
+  <model name="cart">
+    <link name="body"><pose degrees="true">0 0 0.1 0 0 90</pose></link>
+    <link name="slider"/>
+    <joint name="rail" type="prismatic">
+      <parent>body</parent>
+      <child>slider</child>
+      <axis><xyz>1 0 0</xyz><limit><lower>0</lower><upper>0.5</upper></limit></axis>
+    </joint>
+  </model>
+</sdf>"#;
+    let robot = sdf::parse(model).unwrap();
+    assert_eq!(robot.root().name, "body");
+    let rail = robot.joint("rail").unwrap();
+    // The slider sits at the model origin, below and unrotated from the body.
+    assert!(close(rail.origin.translation(), [0.0, 0.0, -0.1]));
+    assert!(close(
+        rail.origin.rotation().apply([0.0, 1.0, 0.0]),
+        [1.0, 0.0, 0.0]
+    ));
+    assert_eq!(rail.limits.unwrap().upper, 0.5);
+}
+
+#[test]
+fn unsupported_or_malformed_models_are_rejected() {
+    let ball = r#"<sdf><model name="m"><link name="a"/><link name="b"/>
+        <joint name="j" type="ball"><parent>a</parent><child>b</child></joint></model></sdf>"#;
+    let err = sdf::parse(ball).unwrap_err();
+    assert!(matches!(err, Error::InvalidDescription(_)));
+    assert_eq!(err.code(), 13);
+
+    assert!(sdf::parse("<sdf><model>").is_err());
+    assert!(sdf::parse("<robot/>").is_err());
+    assert!(sdf::parse("<sdf/>").is_err());
+    let cycle = r#"<sdf><model name="m">
+        <link name="a"><pose relative_to="b">0 0 0 0 0 0</pose></link>
+        <link name="b"><pose relative_to="a">0 0 0 0 0 0</pose></link></model></sdf>"#;
+    assert!(matches!(
+        sdf::parse(cycle),
+        Err(Error::InvalidDescription(_))
+    ));
+    let bad_pose = r#"<sdf><model name="m"><link name="a"><pose>0 0</pose></link></model></sdf>"#;
+    assert!(matches!(
+        sdf::parse(bad_pose),
+        Err(Error::InvalidDescription(_))
+    ));
+    let nan_pose =
+        r#"<sdf><model name="m"><link name="a"><pose>0 NaN 0 0 0 0</pose></link></model></sdf>"#;
+    assert!(matches!(
+        sdf::parse(nan_pose),
+        Err(Error::InvalidDescription(_))
+    ));
+}
@@ -0,0 +1,97 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use mathroborust::lie::{HasAdjoint, HasExp, LieGroup};
+use mathroborust::{Se2, So2};
+use nalgebra::{Matrix3, SVector, Vector3};
+
+fn approx_eq(a: &[f64], b: &[f64], tol: f64) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() < tol, "expected {y}, got {x}");
+    }
+}
+
+#[test]
+fn so2_matches_planar_rotation() {
+    let rotation = So2::from_angle(FRAC_PI_2);
+    approx_eq(&rotation.apply([1.0, 0.0]), &[0.0, 1.0], 1e-15);
+    approx_eq(
+        &rotation.compose(&So2::from_angle(PI)).apply([1.0, 0.0]),
+        &[0.0, -1.0],
+        1e-15,
+    );
+    assert!((rotation.inverse().angle() + FRAC_PI_2).abs() < 1e-15);
+    assert!((So2::from_angle(3.0 * PI / 2.0).log() + FRAC_PI_2).abs() < 1e-12);
+    assert_eq!(So2::vee(So2::hat(0.7)), 0.7);
+    assert_eq!(So2::from_matrix(So2::exp(0.4)), So2::from_angle(0.4));
+    assert_eq!(So2::identity().adjoint_matrix()[(0, 0)], 1.0);
+}
+
+#[test]
+fn se2_log_inverts_exp() {
+    for twist in [
+        [0.0, 1.0, -2.0],
+        [1e-14, 0.3, 0.4],
+        [0.9, -0.5, 2.0],
+        [-3.0, 1.0, 1.0],
+    ] {
+        let matrix = Se2::exp(twist, None);
+        approx_eq(&Se2::log_matrix(matrix), &twist, 1e-12);
+        approx_eq(&Se2::vee(Se2::hat(twist)), &twist, 1e-15);
+    }
+    // A quarter turn along a unit-radius arc ends at (1, 1).
+    let arc = Se2::from_matrix(Se2::exp([FRAC_PI_2, FRAC_PI_2, 0.0], None));
+    approx_eq(&arc.translation(), &[1.0, 1.0], 1e-15);
+}
+
+#[test]
+fn se2_exp_matches_matrix_exponential() {
+    let twist = [0.8, 0.3, -0.6];
+    let hat = Matrix3::from_row_slice(Se2::hat(twist).as_flattened());
+    // Taylor series of the matrix exponential.
+    let mut term = Matrix3::identity();
+    let mut sum = Matrix3::identity();
+    for k in 1..30 {
+        term = term * hat / k as f64;
+        sum += term;
+    }
+    let exp = Se2::exp(twist, None);
+    approx_eq(exp.as_flattened(), sum.transpose().as_slice(), 1e-14);
+}
+
+#[test]
+fn se2_group_operations_and_adjoint() {
+    let a = Se2::from_angle_translation(0.7, [1.0, -2.0]);
+    let b = Se2::from_angle_translation(-1.2, [0.5, 0.25]);
+    let point = [0.3, -0.8];
+    approx_eq(&a.compose(&b).apply(point), &a.apply(b.apply(point)), 1e-14);
+    approx_eq(
+        &(a.clone() * &b).translation(),
+        &a.compose(&b).translation(),
+        1e-15,
+    );
+    approx_eq(&a.inverse().apply(a.apply(point)), &point, 1e-14);
+
+    // Ad_T ξ = (T ξ^ T⁻¹)^∨.
+    let xi = [0.4, -0.2, 0.9];
+    let conjugated = a.as_matrix()
+        * Matrix3::from_row_slice(Se2::hat(xi).as_flattened())
+        * a.inverse().as_matrix();
+    let expected: [[f64; 3]; 3] = conjugated.transpose().into();
+    let adjoint = a.adjoint_matrix() * Vector3::from(xi);
+    approx_eq(adjoint.as_slice(), &Se2::vee(expected), 1e-14);
+
+    // The bracket agrees with the matrix commutator.
+    let (x, y) = (SVector::from(xi), SVector::from([-0.3, 1.1, 0.2]));
+    let (hx, hy) = (
+        Matrix3::from_row_slice(Se2::hat(xi).as_flattened()),
+        Matrix3::from_row_slice(Se2::hat([-0.3, 1.1, 0.2]).as_flattened()),
+    );
+    let commutator: [[f64; 3]; 3] = (hx * hy - hy * hx).transpose().into();
+    approx_eq(
+        Se2::bracket(&x, &y).as_slice(),
+        &Se2::vee(commutator),
+        1e-15,
+    );
+    let _ = Se2::exp_map(&x);
+}
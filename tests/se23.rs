@@ -0,0 +1,102 @@
+use mathroborust::lie::{HasAdjoint, HasExp, LieGroup};
+use mathroborust::{Se23, So3};
+use nalgebra::{SMatrix, SVector};
+
+type Matrix5 = SMatrix<f64, 5, 5>;
+
+fn approx_eq(a: &[f64], b: &[f64], tol: f64) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() < tol, "expected {y}, got {x}");
+    }
+}
+
+fn matrix(rows: [[f64; 5]; 5]) -> Matrix5 {
+    Matrix5::from_row_slice(rows.as_flattened())
+}
+
+fn sample() -> Se23 {
+    Se23::from_parts(
+        So3::from_euler_angles(0.3, -0.7, 1.1),
+        [1.0, -0.5, 0.25],
+        [-2.0, 0.4, 3.0],
+    )
+}
+
+#[test]
+fn se23_log_inverts_exp() {
+    for xi in [
+        [0.0, 0.0, 0.0, 1.0, -2.0, 0.5, 0.3, 0.1, -0.2],
+        [1e-9, -2e-9, 0.0, 0.3, 0.4, 0.0, -1.0, 2.0, 0.5],
+        [0.9, -0.5, 0.2, 2.0, 0.0, -1.0, 0.7, 0.7, 0.1],
+        [-2.0, 1.0, 1.5, 1.0, 1.0, 1.0, -3.0, 0.2, 0.0],
+    ] {
+        approx_eq(&Se23::log_matrix(Se23::exp(xi, None)), &xi, 1e-12);
+        approx_eq(&Se23::vee(Se23::hat(xi)), &xi, 1e-15);
+    }
+}
+
+#[test]
+fn se23_exp_matches_matrix_exponential() {
+    let xi = [0.8, 0.3, -0.6, 1.0, -0.2, 0.4, 0.5, 2.0, -1.5];
+    let hat = matrix(Se23::hat(xi));
+    // Taylor series of the matrix exponential.
+    let mut term = Matrix5::identity();
+    let mut sum = Matrix5::identity();
+    for k in 1..30 {
+        term = term * hat / k as f64;
+        sum += term;
+    }
+    approx_eq(
+        matrix(Se23::exp(xi, None)).as_slice(),
+        sum.as_slice(),
+        1e-14,
+    );
+}
+
+#[test]
+fn se23_group_operations_and_adjoint() {
+    let a = sample();
+    let b = Se23::from_parts(
+        So3::from_euler_angles(-0.2, 0.4, 0.1),
+        [0.3, 0.0, -1.0],
+        [0.5, 0.5, 0.5],
+    );
+    approx_eq(
+        a.compose(&b).as_matrix().as_slice(),
+        (a.as_matrix() * b.as_matrix()).as_slice(),
+        1e-14,
+    );
+    approx_eq(
+        (a.clone() * &b).as_matrix().as_slice(),
+        a.compose(&b).as_matrix().as_slice(),
+        1e-15,
+    );
+    approx_eq(
+        a.compose(&a.inverse()).as_matrix().as_slice(),
+        Matrix5::identity().as_slice(),
+        1e-15,
+    );
+
+    // Ad_X ξ = (X ξ^ X⁻¹)^∨.
+    let xi = [0.4, -0.2, 0.9, 1.0, 0.3, -0.7, 0.2, -1.1, 0.6];
+    let conjugated = a.as_matrix() * matrix(Se23::hat(xi)) * a.inverse().as_matrix();
+    let expected: [[f64; 5]; 5] = conjugated.transpose().into();
+    let adjoint = a.adjoint_matrix() * SVector::from(xi);
+    approx_eq(adjoint.as_slice(), &Se23::vee(expected), 1e-14);
+
+    // The bracket agrees with the matrix commutator.
+    let other = [-0.3, 1.1, 0.2, 0.5, -0.5, 0.0, 1.0, 0.3, -0.4];
+    let (hx, hy) = (matrix(Se23::hat(xi)), matrix(Se23::hat(other)));
+    let commutator: [[f64; 5]; 5] = (hx * hy - hy * hx).transpose().into();
+    approx_eq(
+        Se23::bracket(&SVector::from(xi), &SVector::from(other)).as_slice(),
+        &Se23::vee(commutator),
+        1e-15,
+    );
+    approx_eq(
+        Se23::exp_map(&SVector::from(xi)).as_matrix().as_slice(),
+        matrix(Se23::exp(xi, None)).as_slice(),
+        1e-15,
+    );
+}
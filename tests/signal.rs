@@ -0,0 +1,113 @@
+use std::f64::consts::PI;
+
+use mathroborust::RustSe3;
+use mathroborust::signal::{
+    Biquad, FirstOrderLowPass, FrameFilter, SpatialFilter, SpatialQuantity,
+};
+
+fn approx_eq(a: &[f64], b: &[f64], tol: f64) {
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < tol, "expected {y}, got {x}");
+    }
+}
+
+fn dot(a: &[f64; 6], b: &[f64; 6]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[test]
+fn first_order_low_pass_step_response() {
+    let mut filter = FirstOrderLowPass::new(1.0, 100.0);
+    let alpha = filter.alpha();
+    assert_eq!(filter.update([0.0; 6]), [0.0; 6]);
+
+    let step = [1.0, -2.0, 0.5, 0.0, 3.0, -1.0];
+    let first = filter.update(step);
+    approx_eq(&first, &step.map(|v| v * alpha), 1e-12);
+
+    let mut last = first;
+    for _ in 0..2000 {
+        last = filter.update(step);
+    }
+    approx_eq(&last, &step, 1e-9);
+
+    filter.reset();
+    assert_eq!(filter.update(step), step);
+}
+
+#[test]
+fn biquad_low_pass_passes_dc_and_attenuates_high_frequency() {
+    let sample_rate = 1000.0;
+    let mut filter = Biquad::butterworth_low_pass(5.0, sample_rate);
+    let constant = [0.5, -1.0, 2.0, 0.0, 0.25, 4.0];
+    for _ in 0..10 {
+        approx_eq(&filter.update(constant), &constant, 1e-12);
+    }
+
+    filter.reset();
+    let mut peak: f64 = 0.0;
+    for k in 0..4000 {
+        let value = (2.0 * PI * 200.0 * k as f64 / sample_rate).sin();
+        let out = filter.update([value; 6]);
+        if k > 2000 {
+            peak = peak.max(out[0].abs());
+        }
+    }
+    assert!(peak < 0.01, "peak {peak}");
+}
+
+#[test]
+fn biquad_notch_removes_center_frequency() {
+    let sample_rate = 500.0;
+    let mut notch = Biquad::notch(50.0, sample_rate, 5.0);
+    let mut peak: f64 = 0.0;
+    for k in 0..5000 {
+        let t = k as f64 / sample_rate;
+        let hum = (2.0 * PI * 50.0 * t).sin();
+        let out = notch.update([1.0 + hum, 0.0, 0.0, 0.0, 0.0, hum]);
+        if k > 4000 {
+            peak = peak.max((out[0] - 1.0).abs()).max(out[5].abs());
+        }
+    }
+    assert!(peak < 1e-3, "peak {peak}");
+}
+
+#[test]
+fn wrench_and_twist_transforms_preserve_power() {
+    let transform = RustSe3::from_axis_angle_translation([1.0, 2.0, -1.0], 0.7, [0.3, -0.4, 1.2]);
+    let twist = [0.1, -0.2, 0.3, 1.0, 0.5, -0.25];
+    let wrench = [0.5, 0.0, -1.0, 10.0, -3.0, 2.0];
+
+    let twist_parent = transform.transform_twist(twist);
+    let wrench_parent = transform.transform_wrench(wrench);
+    assert!((dot(&twist, &wrench) - dot(&twist_parent, &wrench_parent)).abs() < 1e-12);
+
+    // A pure force at an offset produces the expected moment.
+    let offset = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [1.0, 0.0, 0.0]);
+    let moved = offset.transform_wrench([0.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+    approx_eq(&moved, &[0.0, 0.0, 1.0, 0.0, 1.0, 0.0], 1e-12);
+}
+
+#[test]
+fn frame_filter_filters_in_requested_frame() {
+    // A constant world-frame wrench measured by a sensor that spins about Z.
+    let world_wrench = [0.0, 0.2, 0.0, 5.0, 0.0, -9.81];
+    let mut world_filter =
+        FrameFilter::new(FirstOrderLowPass::new(0.5, 100.0), SpatialQuantity::Wrench);
+    let mut sensor_filter = FirstOrderLowPass::new(0.5, 100.0);
+
+    let mut sensor_frame_error: f64 = 0.0;
+    for k in 0..200 {
+        let world_from_sensor =
+            RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.05 * k as f64, [0.1, 0.0, 0.3]);
+        let measured = world_from_sensor.inverse().transform_wrench(world_wrench);
+
+        let filtered = world_filter.update(measured, &world_from_sensor);
+        approx_eq(&filtered, &world_wrench, 1e-9);
+
+        let naive = sensor_filter.update(measured);
+        let naive_world = world_from_sensor.transform_wrench(naive);
+        sensor_frame_error = sensor_frame_error.max((naive_world[3] - world_wrench[3]).abs());
+    }
+    assert!(sensor_frame_error > 1.0);
+}
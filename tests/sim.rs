@@ -0,0 +1,122 @@
+use mathroborust::dynamics::{RigidBodyState, SpatialInertia};
+use mathroborust::lie::LieGroup;
+use mathroborust::sim::{AdaptiveStepping, Simulation};
+use mathroborust::{RustSe3, RustSo3};
+
+fn tumbling_body() -> (SpatialInertia, RigidBodyState) {
+    (
+        SpatialInertia::new(
+            1.5,
+            [0.05, 0.0, -0.1],
+            [[0.2, 0.0, 0.0], [0.0, 0.6, 0.0], [0.0, 0.0, 0.9]],
+        ),
+        RigidBodyState::new(
+            RustSe3::from_parts(RustSo3::from_rotation_vector([0.1, 0.2, 0.3]), [0.0; 3]),
+            [0.2, 4.0, 0.3, 1.0, 0.0, 3.0],
+        ),
+    )
+}
+
+#[test]
+fn simulation_drops_bodies_under_gravity() {
+    let mut sim = Simulation::new([0.0, 0.0, -9.81]);
+    let ball = sim.add_body(
+        SpatialInertia::from_principal(1.0, [0.01; 3]),
+        RigidBodyState::new(RustSe3::identity(), [0.0, 0.0, 0.0, 2.0, 0.0, 5.0]),
+    );
+    let (inertia, state) = tumbling_body();
+    let brick = sim.add_body(inertia, state);
+
+    let energy = sim.energy();
+    for _ in 0..100 {
+        sim.step(0.01).unwrap();
+    }
+    assert!((sim.time() - 1.0).abs() < 1e-12);
+
+    let [x, y, z] = sim.body(ball).state.pose.translation();
+    assert!((x - 2.0).abs() < 1e-12 && y.abs() < 1e-12);
+    assert!((z - (5.0 - 0.5 * 9.81)).abs() < 1e-12);
+    assert!((sim.energy() - energy).abs() / energy.abs() < 1e-3);
+    assert_eq!(sim.bodies().len(), 2);
+    assert!(sim.body(brick).state.pose.health_check().is_within(1e-12));
+}
+
+#[test]
+fn simulation_applies_body_frame_wrench() {
+    let mut sim = Simulation::new([0.0; 3]);
+    let id = sim.add_body(
+        SpatialInertia::from_principal(2.0, [0.1; 3]),
+        RigidBodyState::new(RustSe3::identity(), [0.0; 6]),
+    );
+    sim.body_mut(id).wrench = [0.0, 0.0, 0.0, 4.0, 0.0, 0.0];
+    for _ in 0..50 {
+        sim.step(0.02).unwrap();
+    }
+    // Constant acceleration f / m = 2 m/s² along body x for one second.
+    let state = &sim.body(id).state;
+    assert!((state.twist[3] - 2.0).abs() < 1e-12);
+    assert!((state.pose.translation()[0] - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn adaptive_stepping_matches_fine_fixed_steps() {
+    let (inertia, state) = tumbling_body();
+    let mut reference = Simulation::new([0.0, 0.0, -9.81]);
+    let id = reference.add_body(inertia.clone(), state.clone());
+    for _ in 0..20000 {
+        reference.step(1e-4).unwrap();
+    }
+
+    let mut adaptive = Simulation::new([0.0, 0.0, -9.81]);
+    adaptive.add_body(inertia, state);
+    let options = AdaptiveStepping {
+        tolerance: 1e-7,
+        ..AdaptiveStepping::default()
+    };
+    let steps = adaptive.advance(2.0, &options).unwrap();
+    assert!((adaptive.time() - 2.0).abs() < 1e-12);
+    assert!(steps < 20000, "took {steps} steps");
+
+    let a = adaptive.body(id).state.pose.to_row_major_flat();
+    let b = reference.body(id).state.pose.to_row_major_flat();
+    let difference = a
+        .iter()
+        .zip(&b)
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f64::max);
+    assert!(difference < 1e-4, "difference {difference}");
+}
+
+#[test]
+fn adaptive_stepping_rejects_invalid_settings() {
+    let (inertia, state) = tumbling_body();
+    let mut sim = Simulation::new([0.0, 0.0, -9.81]);
+    sim.add_body(inertia, state);
+    let defaults = AdaptiveStepping::default();
+    for options in [
+        AdaptiveStepping {
+            min_step: 0.2,
+            ..defaults
+        },
+        AdaptiveStepping {
+            min_step: 0.0,
+            ..defaults
+        },
+        AdaptiveStepping {
+            max_step: f64::NAN,
+            ..defaults
+        },
+        AdaptiveStepping {
+            max_step: f64::INFINITY,
+            ..defaults
+        },
+        AdaptiveStepping {
+            tolerance: 0.0,
+            ..defaults
+        },
+    ] {
+        let err = sim.advance(1.0, &options).unwrap_err();
+        assert_eq!(err.code(), 17, "{options:?}");
+    }
+    assert_eq!(sim.time(), 0.0);
+}
@@ -0,0 +1,74 @@
+use mathroborust::camera::CameraIntrinsics;
+use mathroborust::error::Error;
+use mathroborust::lie::LieGroup;
+use mathroborust::se3::Se3;
+use mathroborust::stereo::StereoRig;
+
+fn rig() -> StereoRig {
+    // A slightly toed-in pair with a 12 cm baseline, mounted on a body.
+    let body_from_left = Se3::from_axis_angle_translation([0.0, 1.0, 0.0], 0.03, [0.2, 0.06, 0.5]);
+    let left_from_right =
+        Se3::from_axis_angle_translation([0.1, -1.0, 0.05], 0.05, [0.12, 0.002, -0.004]);
+    StereoRig::new(
+        CameraIntrinsics::new(700.0, 702.0, 320.0, 240.0),
+        CameraIntrinsics::new(698.0, 700.0, 322.0, 238.0),
+        body_from_left.clone(),
+        body_from_left.compose(&left_from_right),
+    )
+}
+
+#[test]
+fn baseline_is_the_distance_between_optical_centres() {
+    let rig = rig();
+    let expected = (0.12_f64.powi(2) + 0.002_f64.powi(2) + 0.004_f64.powi(2)).sqrt();
+    assert!((rig.baseline() - expected).abs() < 1e-12);
+}
+
+#[test]
+fn rectified_cameras_differ_only_along_x() {
+    let rig = rig();
+    let rectification = rig.rectify().unwrap();
+    // Rectified right camera pose in the rectified left frame.
+    let left = Se3::from_parts(rectification.rectified_from_left.clone(), [0.0; 3]);
+    let right = Se3::from_parts(rectification.rectified_from_right.clone(), [0.0; 3]);
+    let relative = left
+        .compose(&rig.right_from_left().inverse())
+        .compose(&right.inverse());
+    let [x, y, z] = relative.translation();
+    assert!((x - rectification.baseline).abs() < 1e-12);
+    assert!(y.abs() < 1e-12 && z.abs() < 1e-12);
+    let rotation = relative.rotation().to_rotation_vector();
+    assert!(rotation.iter().all(|r| r.abs() < 1e-12));
+}
+
+#[test]
+fn rectified_projections_share_rows_and_give_depth() {
+    let rig = rig();
+    let rectification = rig.rectify().unwrap();
+    let intrinsics = rectification.intrinsics;
+    let point = [0.3, -0.2, 2.5];
+    let left_pixel = intrinsics.project(point).unwrap();
+    let right_point = [point[0] - rectification.baseline, point[1], point[2]];
+    let right_pixel = intrinsics.project(right_point).unwrap();
+    assert!((left_pixel[1] - right_pixel[1]).abs() < 1e-12);
+
+    let disparity = left_pixel[0] - right_pixel[0];
+    assert!((rectification.depth(disparity).unwrap() - point[2]).abs() < 1e-12);
+    assert!((rectification.disparity(point[2]).unwrap() - disparity).abs() < 1e-12);
+    let reprojected = rectification.reproject(left_pixel, disparity).unwrap();
+    let q = rectification.disparity_to_depth()
+        * nalgebra::Vector4::new(left_pixel[0], left_pixel[1], disparity, 1.0);
+    for k in 0..3 {
+        assert!((reprojected[k] - point[k]).abs() < 1e-9);
+        assert!((q[k] / q[3] - point[k]).abs() < 1e-9);
+    }
+    assert!(rectification.depth(0.0).is_none());
+    assert!(rectification.disparity(-1.0).is_none());
+}
+
+#[test]
+fn coincident_cameras_cannot_be_rectified() {
+    let intrinsics = CameraIntrinsics::new(500.0, 500.0, 320.0, 240.0);
+    let rig = StereoRig::new(intrinsics, intrinsics, Se3::identity(), Se3::identity());
+    assert!(matches!(rig.rectify(), Err(Error::ZeroNorm)));
+}
@@ -0,0 +1,106 @@
+//! Validate the library against the checked-in golden vectors produced by
+//! `cargo run --example test_vectors`.
+
+use mathroborust::{RustSe3, RustSo3};
+use serde_json::Value;
+
+const GOLDEN: &str = include_str!("data/test_vectors.json");
+const TOL: f64 = 1e-12;
+
+fn golden() -> Value {
+    serde_json::from_str(GOLDEN).expect("golden file is valid JSON")
+}
+
+fn vector<const N: usize>(value: &Value) -> [f64; N] {
+    let items = value.as_array().expect("expected an array");
+    assert_eq!(items.len(), N);
+    std::array::from_fn(|i| items[i].as_f64().expect("expected a number"))
+}
+
+fn matrix<const N: usize>(value: &Value) -> [[f64; N]; N] {
+    let rows = value.as_array().expect("expected an array of rows");
+    assert_eq!(rows.len(), N);
+    std::array::from_fn(|r| vector::<N>(&rows[r]))
+}
+
+fn assert_close(actual: &[f64], expected: &[f64], what: &str, case: usize) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (a - e).abs() < TOL,
+            "{what} mismatch in case {case}: expected {e}, got {a}"
+        );
+    }
+}
+
+fn assert_close_matrix<const N: usize>(
+    actual: &[[f64; N]; N],
+    expected: &[[f64; N]; N],
+    what: &str,
+    case: usize,
+) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert_close(a, e, what, case);
+    }
+}
+
+#[test]
+fn so3_conversions_match_golden_vectors() {
+    let document = golden();
+    let cases = document["so3"].as_array().unwrap();
+    assert!(!cases.is_empty());
+
+    for (i, case) in cases.iter().enumerate() {
+        let expected_matrix = matrix::<3>(&case["matrix"]);
+        let quaternion = vector::<4>(&case["quaternion_wxyz"]);
+        let euler = vector::<3>(&case["euler_rpy"]);
+
+        let rotation = RustSo3::from_rotation_vector(vector(&case["rotation_vector"]));
+        assert_close_matrix(&rotation.to_matrix(), &expected_matrix, "exp", i);
+        assert_close(&rotation.to_quaternion(), &quaternion, "to_quaternion", i);
+        assert_close(
+            &rotation.to_rotation_vector(),
+            &vector::<3>(&case["log"]),
+            "log",
+            i,
+        );
+        let (roll, pitch, yaw) = rotation.to_euler_angles();
+        assert_close(&[roll, pitch, yaw], &euler, "to_euler_angles", i);
+
+        let from_quaternion = RustSo3::from_quaternion(quaternion);
+        assert_close_matrix(
+            &from_quaternion.to_matrix(),
+            &expected_matrix,
+            "from_quaternion",
+            i,
+        );
+        let from_euler = RustSo3::from_euler_angles(euler[0], euler[1], euler[2]);
+        assert_close_matrix(&from_euler.to_matrix(), &expected_matrix, "from_euler", i);
+    }
+}
+
+#[test]
+fn se3_exp_inverse_and_adjoint_match_golden_vectors() {
+    let document = golden();
+    let cases = document["se3"].as_array().unwrap();
+    assert!(!cases.is_empty());
+
+    for (i, case) in cases.iter().enumerate() {
+        let exp = RustSe3::exp(vector(&case["twist"]), None);
+        assert_close_matrix(&exp, &matrix::<4>(&case["exp"]), "exp", i);
+
+        let transform = RustSe3::from_matrix(exp);
+        let inverse = matrix::<4>(&case["inverse"]);
+        assert_close_matrix(&transform.inverse().to_matrix(), &inverse, "inverse", i);
+
+        let adjoint = transform.adjoint();
+        let expected = matrix::<6>(&case["adjoint"]);
+        for r in 0..6 {
+            for c in 0..6 {
+                assert!(
+                    (adjoint[(r, c)] - expected[r][c]).abs() < TOL,
+                    "adjoint mismatch in case {i} at ({r},{c})"
+                );
+            }
+        }
+    }
+}
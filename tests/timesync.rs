@@ -0,0 +1,99 @@
+use mathroborust::error::Error;
+use mathroborust::lie::LieGroup;
+use mathroborust::se3::Se3;
+use mathroborust::so3::So3;
+use mathroborust::timesync::{
+    TimedTwist, estimate_time_offset, resample_poses, resample_twists, trajectory_twists,
+};
+use mathroborust::trajectory::Trajectory;
+
+/// Angular velocity of a wobbling body, in its own frame.
+fn omega(t: f64) -> [f64; 3] {
+    [
+        (1.3 * t).sin() + 0.4 * (4.1 * t).cos(),
+        0.6 * (2.7 * t).sin(),
+        0.2 + 0.3 * (0.9 * t).cos(),
+    ]
+}
+
+fn stream(times: impl Iterator<Item = f64>, delay: f64, mount: &So3) -> Vec<TimedTwist> {
+    times
+        .map(|time| {
+            let [x, y, z] = mount.apply(omega(time - delay));
+            TimedTwist::new(time, [x, y, z, 0.0, 0.0, 0.0])
+        })
+        .collect()
+}
+
+#[test]
+fn recovers_offset_between_differently_mounted_sensors() {
+    let imu = stream((0..2000).map(|k| k as f64 * 0.005), 0.0, &So3::identity());
+    let mount = So3::from_euler_angles(0.3, -1.1, 2.0);
+    let delay = 0.0375;
+    let camera = stream((0..300).map(|k| 0.02 + k as f64 / 30.0), delay, &mount);
+
+    let estimate = estimate_time_offset(&imu, &camera, 0.2, 0.005).unwrap();
+    assert!((estimate.offset - delay).abs() < 2e-3, "{estimate:?}");
+    assert!(estimate.correlation > 0.99);
+
+    let reversed = estimate_time_offset(&camera, &imu, 0.2, 0.005).unwrap();
+    assert!((reversed.offset + delay).abs() < 2e-3, "{reversed:?}");
+}
+
+#[test]
+fn resamples_streams_onto_other_timestamps() {
+    let twists = vec![
+        TimedTwist::new(0.0, [0.0; 6]),
+        TimedTwist::new(1.0, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+    ];
+    let resampled = resample_twists(&twists, &[-1.0, 0.25, 2.0]).unwrap();
+    assert_eq!(resampled[0].twist, [0.0; 6]);
+    assert_eq!(resampled[1].twist, [0.25, 0.5, 0.75, 1.0, 1.25, 1.5]);
+    assert_eq!(resampled[2].twist, twists[1].twist);
+
+    let poses = [
+        Se3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [0.0; 3]),
+        Se3::from_axis_angle_translation([0.0, 0.0, 1.0], 1.0, [2.0, 0.0, 0.0]),
+    ];
+    let trajectory = Trajectory::from_poses(&[0.0, 2.0], &poses).unwrap();
+    let resampled = resample_poses(&trajectory, &[1.0]);
+    assert_eq!(resampled[0].time, 1.0);
+    assert!((resampled[0].pose.translation()[0] - 1.0).abs() < 1e-12);
+
+    let twists = trajectory_twists(&trajectory);
+    assert_eq!(twists.len(), 1);
+    assert_eq!(twists[0].time, 1.0);
+    assert!((twists[0].twist[2] - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn rejects_malformed_or_uninformative_streams() {
+    let unordered = vec![
+        TimedTwist::new(1.0, [0.0; 6]),
+        TimedTwist::new(0.5, [0.0; 6]),
+    ];
+    assert!(matches!(
+        resample_twists(&unordered, &[0.0]),
+        Err(Error::InvalidTrajectory(_))
+    ));
+
+    let constant: Vec<TimedTwist> = (0..100)
+        .map(|k| TimedTwist::new(k as f64 * 0.01, [1.0, 0.0, 0.0, 0.0, 0.0, 0.0]))
+        .collect();
+    assert!(estimate_time_offset(&constant, &constant, 0.1, 0.01).is_err());
+    // Too short for the requested search range.
+    assert!(estimate_time_offset(&constant, &constant, 0.6, 0.01).is_err());
+
+    // Degenerate or excessively fine steps are rejected before allocating.
+    for (max_offset, step) in [
+        (0.1, 0.0),
+        (0.1, f64::NAN),
+        (0.1, f64::INFINITY),
+        (f64::INFINITY, 0.01),
+        (0.1, 1e-300),
+        (0.0, 1e-12),
+    ] {
+        let err = estimate_time_offset(&constant, &constant, max_offset, step).unwrap_err();
+        assert_eq!(err.code(), 17, "{max_offset} {step}");
+    }
+}
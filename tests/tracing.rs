@@ -0,0 +1,55 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mathroborust::averaging::karcher_mean;
+use mathroborust::so3::So3;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Counts spans and events without formatting them.
+#[derive(Default)]
+struct Counter {
+    spans: AtomicUsize,
+    events: AtomicUsize,
+}
+
+struct Counting(Arc<Counter>);
+
+impl Subscriber for Counting {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(self.0.spans.fetch_add(1, Ordering::SeqCst) as u64 + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {
+        self.0.events.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn iterative_solvers_emit_spans_and_step_events() {
+    let counter = Arc::new(Counter::default());
+    let rotations = [
+        So3::from_axis_angle([1.0, 0.0, 0.0], 0.2),
+        So3::from_axis_angle([0.0, 1.0, 0.0], 0.3),
+        So3::from_axis_angle([0.0, 0.0, 1.0], -0.1),
+    ];
+    tracing::subscriber::with_default(Counting(counter.clone()), || {
+        karcher_mean(&rotations).unwrap();
+    });
+    assert_eq!(counter.spans.load(Ordering::SeqCst), 1);
+    assert!(counter.events.load(Ordering::SeqCst) >= 2);
+}
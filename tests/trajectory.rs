@@ -0,0 +1,283 @@
+use mathroborust::trajectory::{BlendedTrajectory, MotionLimits, TimedPose, Trajectory, Waypoint};
+use mathroborust::{Error, RustSe3, RustSo3};
+
+const TOL: f64 = 1e-9;
+
+fn straight_line(speed: f64, count: usize) -> Trajectory {
+    let times: Vec<f64> = (0..count).map(|i| i as f64 * 0.1).collect();
+    let poses: Vec<RustSe3> = times
+        .iter()
+        .map(|t| RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [speed * t, 0.0, 0.0]))
+        .collect();
+    Trajectory::from_poses(&times, &poses).unwrap()
+}
+
+fn corner() -> Trajectory {
+    let poses = [
+        RustSe3::from_parts(RustSo3::from_rotation_vector([0.0; 3]), [0.0, 0.0, 0.0]),
+        RustSe3::from_parts(
+            RustSo3::from_rotation_vector([0.0, 0.0, 0.3]),
+            [0.5, 0.0, 0.0],
+        ),
+        RustSe3::from_parts(
+            RustSo3::from_rotation_vector([0.0, 0.0, 0.6]),
+            [0.5, 0.5, 0.0],
+        ),
+        RustSe3::from_parts(
+            RustSo3::from_rotation_vector([0.2, 0.0, 0.6]),
+            [0.5, 0.5, 0.4],
+        ),
+        RustSe3::from_parts(
+            RustSo3::from_rotation_vector([0.2, 0.0, 0.6]),
+            [0.6, 0.5, 0.4],
+        ),
+    ];
+    Trajectory::from_poses(&[0.0, 0.2, 0.4, 0.6, 0.8], &poses).unwrap()
+}
+
+fn within(values: &[[f64; 6]], limits: &[f64; 6]) -> bool {
+    values.iter().all(|v| {
+        v.iter()
+            .zip(limits)
+            .all(|(x, l)| x.abs() <= l * (1.0 + TOL))
+    })
+}
+
+#[test]
+fn trajectory_rejects_invalid_timestamps() {
+    let pose = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.0, [0.0; 3]);
+    let err = Trajectory::from_poses(&[0.0, 0.0], &[pose.clone(), pose.clone()]).unwrap_err();
+    assert!(matches!(err, Error::InvalidTrajectory(_)));
+    assert_eq!(err.code(), 9);
+    assert!(Trajectory::new(Vec::new()).is_err());
+    assert!(Trajectory::new(vec![TimedPose::new(f64::NAN, pose)]).is_err());
+}
+
+#[test]
+fn trajectory_sample_interpolates_rotation_and_translation() {
+    let a = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.2, [1.0, 0.0, 0.0]);
+    let b = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.6, [2.0, 2.0, 0.0]);
+    let trajectory = Trajectory::from_poses(&[1.0, 3.0], &[a.clone(), b.clone()]).unwrap();
+
+    let mid = trajectory.sample(2.0);
+    let expected = RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], 0.4, [1.5, 1.0, 0.0]);
+    for (x, y) in mid
+        .to_row_major_flat()
+        .iter()
+        .zip(expected.to_row_major_flat())
+    {
+        assert!((x - y).abs() < TOL);
+    }
+    assert_eq!(trajectory.sample(0.0), a);
+    assert_eq!(trajectory.sample(5.0), b);
+    assert_eq!(trajectory.duration(), 2.0);
+}
+
+#[test]
+fn time_scale_slows_segments_to_velocity_limit() {
+    let trajectory = straight_line(2.0, 6);
+    let limits = MotionLimits {
+        velocity: [f64::INFINITY, f64::INFINITY, f64::INFINITY, 0.5, 1.0, 1.0],
+        acceleration: [f64::INFINITY; 6],
+    };
+    let scaled = trajectory.time_scale(&limits).unwrap();
+
+    assert_eq!(scaled.scale.len(), 5);
+    for scale in &scaled.scale {
+        assert!((scale - 4.0).abs() < TOL);
+    }
+    assert!((scaled.trajectory.duration() - 2.0).abs() < TOL);
+    for (old, new) in trajectory.samples().iter().zip(scaled.trajectory.samples()) {
+        assert_eq!(old.pose, new.pose);
+    }
+
+    // Already feasible trajectories keep their timing.
+    let unchanged = scaled.trajectory.time_scale(&limits).unwrap();
+    assert_eq!(unchanged.trajectory, scaled.trajectory);
+    assert!(unchanged.scale.iter().all(|s| *s == 1.0));
+}
+
+#[test]
+fn time_scale_respects_velocity_and_acceleration_limits() {
+    let trajectory = corner();
+    let limits = MotionLimits {
+        velocity: [0.5, 0.5, 0.5, 1.0, 1.0, 1.0],
+        acceleration: [2.0, 2.0, 2.0, 3.0, 3.0, 3.0],
+    };
+    assert!(!within(&trajectory.segment_velocities(), &limits.velocity));
+
+    let scaled = trajectory.time_scale(&limits).unwrap();
+    assert!(within(
+        &scaled.trajectory.segment_velocities(),
+        &limits.velocity
+    ));
+    assert!(within(
+        &scaled.trajectory.knot_accelerations(),
+        &limits.acceleration
+    ));
+    assert!(scaled.scale.iter().all(|s| *s >= 1.0));
+
+    // The path is preserved: each sample keeps its pose.
+    for (old, new) in trajectory.samples().iter().zip(scaled.trajectory.samples()) {
+        assert_eq!(old.pose, new.pose);
+    }
+}
+
+#[test]
+fn time_scale_rejects_non_positive_limits() {
+    let trajectory = corner();
+    for bad in [0.0, -1.0, f64::NAN, f64::NEG_INFINITY] {
+        let mut limits = MotionLimits {
+            velocity: [1.0; 6],
+            acceleration: [1.0; 6],
+        };
+        limits.velocity[3] = bad;
+        assert_eq!(
+            trajectory.time_scale(&limits).unwrap_err().code(),
+            17,
+            "{bad}"
+        );
+        limits.velocity[3] = 1.0;
+        limits.acceleration[0] = bad;
+        assert_eq!(
+            trajectory.time_scale(&limits).unwrap_err().code(),
+            17,
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn trajectory_serde_round_trip_validates() {
+    let trajectory = corner();
+    let json = serde_json::to_string(&trajectory).unwrap();
+    let back: Trajectory = serde_json::from_str(&json).unwrap();
+    for (a, b) in back.samples().iter().zip(trajectory.samples()) {
+        assert_eq!(a.time, b.time);
+        let (a, b) = (a.pose.to_row_major_flat(), b.pose.to_row_major_flat());
+        assert!(a.iter().zip(&b).all(|(x, y)| (x - y).abs() < 1e-15));
+    }
+
+    let reversed = json.replacen("\"time\":0.0", "\"time\":5.0", 1);
+    assert!(serde_json::from_str::<Trajectory>(&reversed).is_err());
+}
+
+fn square_waypoints(radius: f64, angle: f64) -> Vec<Waypoint> {
+    [
+        ([0.0, 0.0, 0.0], 0.0),
+        ([1.0, 0.0, 0.0], 0.5),
+        ([1.0, 1.0, 0.0], 1.0),
+        ([0.0, 1.0, 0.5], 1.0),
+    ]
+    .into_iter()
+    .map(|(translation, yaw)| {
+        Waypoint::new(
+            RustSe3::from_axis_angle_translation([0.0, 0.0, 1.0], yaw, translation),
+            radius,
+            angle,
+        )
+    })
+    .collect()
+}
+
+fn pose_distance(a: &RustSe3, b: &RustSe3) -> f64 {
+    a.to_row_major_flat()
+        .iter()
+        .zip(b.to_row_major_flat())
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f64::max)
+}
+
+/// Central-difference world-frame velocity of the flattened pose matrix.
+fn matrix_rate(trajectory: &BlendedTrajectory, time: f64, h: f64) -> [f64; 16] {
+    let before = trajectory.sample(time - h).to_row_major_flat();
+    let after = trajectory.sample(time + h).to_row_major_flat();
+    std::array::from_fn(|k| (after[k] - before[k]) / (2.0 * h))
+}
+
+#[test]
+fn blended_trajectory_starts_and_ends_at_rest_on_the_endpoints() {
+    let waypoints = square_waypoints(0.2, 0.2);
+    let trajectory = BlendedTrajectory::build(&waypoints, 0.5, 1.0).unwrap();
+
+    assert!(pose_distance(&trajectory.sample(0.0), &waypoints[0].pose) < TOL);
+    let end = trajectory.duration();
+    assert!(pose_distance(&trajectory.sample(end), &waypoints[3].pose) < TOL);
+    assert!(
+        matrix_rate(&trajectory, 1e-6, 1e-7)
+            .iter()
+            .all(|v| v.abs() < 1e-4)
+    );
+    assert!(
+        matrix_rate(&trajectory, end - 1e-6, 1e-7)
+            .iter()
+            .all(|v| v.abs() < 1e-4)
+    );
+}
+
+#[test]
+fn blended_trajectory_is_c1_and_stays_within_blend_radius() {
+    let radius = 0.2;
+    let waypoints = square_waypoints(radius, 0.2);
+    let trajectory = BlendedTrajectory::build(&waypoints, 0.5, 1.0).unwrap();
+
+    // Velocity is continuous at every blend boundary.
+    for (&time, &tau) in trajectory
+        .waypoint_times()
+        .iter()
+        .zip(trajectory.blend_durations())
+    {
+        for boundary in [time - tau, time + tau] {
+            if boundary <= 0.0 || boundary >= trajectory.duration() {
+                continue;
+            }
+            let left = matrix_rate(&trajectory, boundary - 1e-5, 1e-6);
+            let right = matrix_rate(&trajectory, boundary + 1e-5, 1e-6);
+            assert!(left.iter().zip(&right).all(|(a, b)| (a - b).abs() < 1e-3));
+        }
+    }
+
+    // The corner is cut, but only within the blend radius.
+    let corner = &waypoints[1].pose;
+    let closest = (0..=2000)
+        .map(|k| trajectory.sample(k as f64 * trajectory.duration() / 2000.0))
+        .map(|pose| {
+            let [x, y, z] = pose.translation();
+            let [cx, cy, cz] = corner.translation();
+            ((x - cx).powi(2) + (y - cy).powi(2) + (z - cz).powi(2)).sqrt()
+        })
+        .fold(f64::INFINITY, f64::min);
+    assert!(closest > 1e-3 && closest < radius);
+}
+
+#[test]
+fn blended_trajectory_without_blends_passes_through_waypoints() {
+    let waypoints = square_waypoints(0.0, 0.0);
+    let trajectory = BlendedTrajectory::build(&waypoints, 0.5, 1.0).unwrap();
+    for (waypoint, &time) in waypoints
+        .iter()
+        .zip(trajectory.waypoint_times())
+        .skip(1)
+        .take(2)
+    {
+        assert!(pose_distance(&trajectory.sample(time), &waypoint.pose) < TOL);
+    }
+
+    let sampled = trajectory.to_trajectory(0.05).unwrap();
+    assert!((sampled.duration() - trajectory.duration()).abs() < TOL);
+}
+
+#[test]
+fn blended_trajectory_rejects_degenerate_input() {
+    let waypoints = square_waypoints(0.1, 0.1);
+    assert!(BlendedTrajectory::build(&waypoints[..1], 0.5, 1.0).is_err());
+    assert!(BlendedTrajectory::build(&waypoints, 0.0, 1.0).is_err());
+    let repeated = vec![waypoints[0].clone(), waypoints[0].clone()];
+    let err = BlendedTrajectory::build(&repeated, 0.5, 1.0).unwrap_err();
+    assert_eq!(err.code(), 9);
+
+    let trajectory = BlendedTrajectory::build(&waypoints, 0.5, 1.0).unwrap();
+    for dt in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+        assert_eq!(trajectory.to_trajectory(dt).unwrap_err().code(), 9, "{dt}");
+    }
+}
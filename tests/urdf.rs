@@ -0,0 +1,153 @@
+use mathroborust::error::Error;
+use mathroborust::robot::{Inertial, Joint, JointKind, JointLimits, Link, RobotDescription};
+use mathroborust::se3::Se3;
+use mathroborust::so3::So3;
+use mathroborust::urdf::{self, Urdf};
+use mathroborust::{mjcf, sdf};
+
+fn arm() -> RobotDescription {
+    let inertial = Inertial {
+        mass: 1.25,
+        center_of_mass: [0.1, 0.0, -0.02],
+        inertia: [[0.01, 0.001, 0.0], [0.001, 0.02, 0.0], [0.0, 0.0, 0.03]],
+    };
+    let links = vec![
+        Link::new("base", None),
+        Link::new("upper", Some(inertial)),
+        Link::new("tool <tip>", None),
+        Link::new("rail", None),
+    ];
+    let joints = vec![
+        Joint::new(
+            "shoulder",
+            "base",
+            "upper",
+            Se3::from_parts(So3::from_euler_angles(0.1, -0.4, 2.0), [0.0, 0.0, 0.3]),
+            JointKind::Revolute {
+                axis: [0.0, 0.0, 1.0],
+            },
+        )
+        .with_limits(JointLimits {
+            lower: -1.5,
+            upper: 1.5,
+            velocity: Some(2.0),
+            effort: None,
+        }),
+        Joint::new(
+            "tip",
+            "upper",
+            "tool <tip>",
+            Se3::from_parts(So3::from_euler_angles(0.0, 0.0, 0.0), [0.4, 0.0, 0.0]),
+            JointKind::Fixed,
+        ),
+        Joint::new(
+            "slide",
+            "base",
+            "rail",
+            Se3::from_parts(So3::from_euler_angles(0.0, 0.0, 0.0), [0.0; 3]),
+            JointKind::Prismatic {
+                axis: [1.0, 0.0, 0.0],
+            },
+        ),
+    ];
+    RobotDescription::new("arm & co", links, joints).unwrap()
+}
+
+fn assert_equivalent(a: &RobotDescription, b: &RobotDescription) {
+    assert_eq!(a.name(), b.name());
+    assert_eq!(a.links(), b.links());
+    assert_eq!(a.joints().len(), b.joints().len());
+    for (x, y) in a.joints().iter().zip(b.joints()) {
+        assert_eq!(
+            (&x.name, &x.parent, &x.child, &x.kind),
+            (&y.name, &y.parent, &y.child, &y.kind)
+        );
+        let difference = x.origin.inverse().compose(&y.origin);
+        assert!(difference.translation().iter().all(|v| v.abs() < 1e-12));
+        assert!(
+            difference
+                .rotation()
+                .to_rotation_vector()
+                .iter()
+                .all(|v| v.abs() < 1e-12)
+        );
+    }
+}
+
+#[test]
+fn exported_description_round_trips() {
+    let robot = arm();
+    let text = urdf::to_urdf(&robot);
+    assert!(text.contains(r#"<robot name="arm &amp; co">"#));
+    assert!(text.contains(r#"<link name="tool &lt;tip&gt;"/>"#));
+    assert!(text.contains(r#"<limit lower="-1.5" upper="1.5" effort="0" velocity="2"/>"#));
+
+    let parsed = RobotDescription::parse::<Urdf>(&text).unwrap();
+    assert_equivalent(&parsed, &robot);
+    assert_eq!(
+        parsed.joint("shoulder").unwrap().limits,
+        robot.joint("shoulder").unwrap().limits
+    );
+    // URDF needs bounds on prismatic joints, so unlimited ones are written
+    // with wide limits that read back as none.
+    assert!(text.contains(r#"<limit lower="-10000000000000000" upper="10000000000000000""#));
+    assert_eq!(parsed.joint("slide").unwrap().limits, None);
+}
+
+#[test]
+fn imported_models_survive_export() {
+    let mjcf = r#"
+<mujoco model="pendulum">
+  <worldbody>
+    <body name="bob" pos="0 0 1" euler="10 20 30">
+      <joint name="swing" axis="0 1 0" range="-45 45"/>
+      <inertial pos="0 0 -0.5" mass="2" fullinertia="0.1 0.2 0.3 0.01 0.02 0.03"/>
+    </body>
+  </worldbody>
+</mujoco>"#;
+    let robot = mjcf::parse(mjcf).unwrap();
+    assert_equivalent(&urdf::parse(&urdf::to_urdf(&robot)).unwrap(), &robot);
+
+    let sdf = r#"
+<sdf version="1.7">
+  <model name="wheel">
+    <link name="hub"/>
+    <link name="tyre"><pose>0.2 0 0 1.5707963267948966 0 0</pose></link>
+    <joint name="spin" type="continuous">
+      <parent>hub</parent><child>tyre</child><axis><xyz>0 0 1</xyz></axis>
+    </joint>
+  </model>
+</sdf>"#;
+    let robot = sdf::parse(sdf).unwrap();
+    assert_equivalent(&urdf::parse(&urdf::to_urdf(&robot)).unwrap(), &robot);
+}
+
+#[test]
+fn unsupported_or_malformed_urdf_is_rejected() {
+    let floating = r#"<robot name="r"><link name="a"/><link name="b"/>
+        <joint name="j" type="floating"><parent link="a"/><child link="b"/></joint></robot>"#;
+    let err = urdf::parse(floating).unwrap_err();
+    assert!(matches!(err, Error::InvalidDescription(_)));
+    assert_eq!(err.code(), 13);
+
+    assert!(urdf::parse("<robot>").is_err());
+    assert!(urdf::parse("<sdf/>").is_err());
+    let missing_parent = r#"<robot name="r"><link name="a"/>
+        <joint name="j" type="fixed"><child link="a"/></joint></robot>"#;
+    assert!(matches!(
+        urdf::parse(missing_parent),
+        Err(Error::InvalidDescription(_))
+    ));
+    // A NaN axis would otherwise slip past the zero-length check.
+    for value in ["nan", "inf", "-infinity"] {
+        let axis = format!(
+            r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="revolute"><parent link="a"/><child link="b"/>
+            <axis xyz="{value} 0 1"/></joint></robot>"#
+        );
+        assert!(
+            matches!(urdf::parse(&axis), Err(Error::InvalidDescription(_))),
+            "{value}"
+        );
+    }
+}